@@ -9,6 +9,8 @@
 // From this library
 
 fn main() {
+    println!("cargo:rustc-check-cfg=cfg(v2_39)");
+
     if let Ok(version_hex) = env::var("DEP_FDISK_VERSION_NUMBER") {
         let version = u64::from_str_radix(&version_hex, 16).unwrap();
 
@@ -0,0 +1,653 @@
+// Copyright (c) 2023 Nick Piaddo
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Interactive partitioning shell, mimicking the single-letter menus of `fdisk`.
+//!
+//! Every question asked by `libfdisk` (partition number, first/last sector, partition kind, etc.)
+//! goes through the [`Prompt`] handler installed below, showing that all dialogs are reachable
+//! through the safe API.
+//!
+//! ```text
+//! cargo run --example fdisk_repl -- /dev/vda
+//! ```
+
+// From dependency library
+
+// From standard library
+use std::error::Error;
+use std::io;
+use std::io::Write;
+use std::process::ExitCode;
+
+// From this library
+use rsfdisk::core::errors::PromptError;
+use rsfdisk::core::partition::BitFlag;
+use rsfdisk::core::partition::DOSFlag;
+use rsfdisk::core::partition::GPTFlag;
+use rsfdisk::core::partition::GptAttributes;
+use rsfdisk::core::partition::Partition;
+use rsfdisk::core::partition::SGIFlag;
+use rsfdisk::core::partition_table::InputType;
+use rsfdisk::core::partition_table::PartitionTableKind;
+use rsfdisk::core::prompt::Prompt;
+use rsfdisk::core::prompt::PromptKind;
+use rsfdisk::fdisk::Fdisk;
+use rsfdisk::fdisk::FdiskBSDExt;
+use rsfdisk::fdisk::FdiskDOSExt;
+use rsfdisk::fdisk::FdiskGPTExt;
+use rsfdisk::fdisk::FdiskSGIExt;
+use rsfdisk::fdisk::FdiskSUNExt;
+use rsfdisk::fdisk::SizeFormat;
+use rsfdisk::fdisk::TableRenderer;
+
+/// Type of result returned by menu commands.
+type ReplResult<T> = Result<T, Box<dyn Error>>;
+
+/// Menus of the shell.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Menu {
+    Expert,
+    Main,
+}
+
+/// What to do once a command completes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Action {
+    Continue,
+    Enter(Menu),
+    Quit,
+}
+
+/// Prints `prompt`, then reads a line on the standard input. Returns `None` at the end of the
+/// input.
+fn read_line(prompt: &str) -> Option<String> {
+    print!("{}", prompt);
+    io::stdout().flush().ok()?;
+
+    let mut line = String::new();
+    match io::stdin().read_line(&mut line) {
+        Ok(0) | Err(_) => None,
+        Ok(_) => Some(line.trim().to_owned()),
+    }
+}
+
+/// Error returned when the standard input is closed while a question is pending.
+fn no_answer() -> PromptError {
+    PromptError::Selection("no answer provided".to_owned())
+}
+
+/// Parses a size with an optional unit suffix (e.g. `100`, `512K`, `2GiB`, `1MB`). Returns the
+/// value, and whether it is expressed in bytes.
+fn parse_size(input: &str) -> Option<(u64, bool)> {
+    let split = input
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(input.len());
+    let (digits, suffix) = input.split_at(split);
+    let value: u64 = digits.parse().ok()?;
+
+    let (power, base) = match suffix.to_ascii_uppercase().as_str() {
+        "" => return Some((value, false)),
+        "K" | "KIB" => (1, 1024),
+        "M" | "MIB" => (2, 1024),
+        "G" | "GIB" => (3, 1024),
+        "T" | "TIB" => (4, 1024),
+        "KB" => (1, 1000),
+        "MB" => (2, 1000),
+        "GB" => (3, 1000),
+        "TB" => (4, 1000),
+        _ => return None,
+    };
+
+    value
+        .checked_mul(u64::pow(base, power))
+        .map(|bytes| (bytes, true))
+}
+
+/// Converts the answer to a [`PromptKind::Offset`] question into a sector number. Like `fdisk`,
+/// accepts absolute values, `+size` relative to the reference point, and `-size` relative to the
+/// upper bound.
+fn parse_offset(prompt: &mut Prompt, input: &str) -> Option<u64> {
+    let (sign, size) = match input.strip_prefix('+') {
+        Some(size) => (Some('+'), size),
+        None => match input.strip_prefix('-') {
+            Some(size) => (Some('-'), size),
+            None => (None, input),
+        },
+    };
+
+    let (value, in_bytes) = parse_size(size)?;
+    let value = if in_bytes {
+        value / prompt.number_bytes_per_unit().max(1)
+    } else {
+        value
+    };
+
+    match sign {
+        Some('+') => {
+            prompt.number_enable_relative().ok()?;
+            prompt.number_reference_point().checked_add(value)
+        }
+        Some(_) => {
+            prompt.number_enable_relative().ok()?;
+            prompt.number_upper_bound().checked_sub(value)
+        }
+        None => Some(value),
+    }
+}
+
+/// Answers the questions asked by `libfdisk`, and prints its messages.
+fn answer(prompt: &mut Prompt) -> Result<(), PromptError> {
+    let query = prompt.query().unwrap_or_default().to_owned();
+
+    match prompt.kind() {
+        PromptKind::Info => {
+            println!("{}", prompt.error_message().unwrap_or_default());
+
+            Ok(())
+        }
+        PromptKind::Warn => {
+            let message = prompt.error_message().unwrap_or_default();
+            let error = io::Error::from_raw_os_error(prompt.error_number());
+            eprintln!("{}: {}", message, error);
+
+            Ok(())
+        }
+        PromptKind::WarnX => {
+            eprintln!("{}", prompt.error_message().unwrap_or_default());
+
+            Ok(())
+        }
+        kind @ (PromptKind::Number | PromptKind::Offset) => loop {
+            let (low, high) = (prompt.number_lower_bound(), prompt.number_upper_bound());
+            let default = prompt.number_default();
+            let range = prompt
+                .number_range()
+                .map(str::to_owned)
+                .unwrap_or_else(|| format!("{}-{}", low, high));
+
+            let line = read_line(&format!("{} ({}, default {}): ", query, range, default))
+                .ok_or_else(no_answer)?;
+            if line.is_empty() {
+                return prompt.number_set_answer(default);
+            }
+
+            let value = if kind == PromptKind::Offset {
+                parse_offset(prompt, &line)
+            } else {
+                line.parse().ok()
+            };
+
+            match value {
+                Some(value) if (low..=high).contains(&value) => {
+                    return prompt.number_set_answer(value)
+                }
+                _ => println!("Value out of range."),
+            }
+        },
+        PromptKind::YesNo => loop {
+            let line = read_line(&format!("{} [Y]es/[N]o: ", query)).ok_or_else(no_answer)?;
+
+            match line.to_ascii_lowercase().as_str() {
+                "y" | "yes" => return prompt.yes_no_set_answer(true),
+                "n" | "no" => return prompt.yes_no_set_answer(false),
+                _ => continue,
+            }
+        },
+        PromptKind::String => {
+            let line = read_line(&format!("{}: ", query)).ok_or_else(no_answer)?;
+
+            prompt.string_set_answer(line)
+        }
+        PromptKind::Menu => {
+            let items: Vec<_> = (0..prompt.menu_count_items())
+                .filter_map(|nth| prompt.menu_nth_item(nth))
+                .collect();
+
+            println!("{}", query);
+            for item in items.iter() {
+                let key = char::from_u32(item.key_code() as u32).unwrap_or('?');
+                println!("   {}   {} ({})", key, item.name(), item.description());
+            }
+
+            let default = prompt.menu_default_key();
+            let default_key = char::from_u32(default as u32).unwrap_or('?');
+            loop {
+                let line = read_line(&format!("Select (default {}): ", default_key))
+                    .ok_or_else(no_answer)?;
+
+                let key = match line.chars().next() {
+                    None => default,
+                    Some(c) => c as i32,
+                };
+
+                if items.iter().any(|item| item.key_code() == key) {
+                    return prompt.menu_item_select(key);
+                }
+
+                println!("Value out of range.");
+            }
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Returns the type of the partition table on `disk`.
+fn table_kind(disk: &Fdisk) -> Option<PartitionTableKind> {
+    disk.partition_table_current().map(|table| table.kind())
+}
+
+/// Prints the disk header, then the partitions on the device.
+fn print_table(disk: &Fdisk) -> ReplResult<()> {
+    println!("{}", disk.device_report());
+
+    let partitions = match disk.list_partitions() {
+        Some(partitions) if !partitions.is_empty() => partitions,
+        _ => return Ok(()),
+    };
+
+    println!();
+    print!("{}", TableRenderer::new(disk).render(&partitions)?);
+
+    Ok(())
+}
+
+/// Prints the unallocated areas on the device.
+fn print_free_space(disk: &Fdisk) -> ReplResult<()> {
+    let spaces = disk
+        .list_empty_spaces()
+        .ok_or("failed to list free space")?;
+
+    println!("{:>12} {:>12} {:>12}", "Start", "End", "Sectors");
+    for space in spaces.iter() {
+        println!(
+            "{:>12} {:>12} {:>12}",
+            space.starting_sector().unwrap_or_default(),
+            space.ending_sector().unwrap_or_default(),
+            space.size_in_sectors().unwrap_or_default()
+        );
+    }
+
+    Ok(())
+}
+
+/// Prints the partition types supported by the current partition table, and their aliases.
+fn list_types(disk: &Fdisk) -> ReplResult<()> {
+    let table = disk
+        .partition_table_current()
+        .ok_or("no partition table defined")?;
+
+    for nth in 0..table.count_supported_partition_types() {
+        let Some(kind) = table.supported_partition_types(nth) else {
+            continue;
+        };
+        let name = kind.name().unwrap_or_default();
+
+        match (kind.code(), kind.guid()) {
+            (code, _) if table.uses_partition_type_codes() => {
+                println!("{:>2x}  {}", code.unwrap_or_default(), name)
+            }
+            (_, Some(guid)) => println!("{:>3} {:<40} {}", nth + 1, name, guid),
+            _ => println!("{:>3} {}", nth + 1, name),
+        }
+    }
+
+    if table.supports_partition_type_shortcuts() {
+        println!("\nAliases:");
+
+        let shortcuts = (0..).map_while(|nth| table.partition_type_shortcut(nth));
+        for shortcut in shortcuts.filter(|s| !s.has_alias_deprecated()) {
+            println!("   {:<16} - {}", shortcut.alias(), shortcut.shortcut());
+        }
+    }
+
+    Ok(())
+}
+
+/// Changes the type of a partition.
+fn change_type(disk: &mut Fdisk) -> ReplResult<()> {
+    let partition = disk.ask_partition_number_used()?;
+
+    let kind = loop {
+        let input = disk.ask_string_value("Partition type or alias (type L to list all)")?;
+        if input.eq_ignore_ascii_case("l") {
+            list_types(disk)?;
+            continue;
+        }
+
+        let table = disk
+            .partition_table_current()
+            .ok_or("no partition table defined")?;
+        match table.partition_type_parse(&input, [InputType::Default]) {
+            Ok(kind) if !kind.is_unknown_type() => break kind,
+            _ => println!("Failed to parse {} partition type.", input),
+        }
+    };
+
+    let name = kind.name().unwrap_or_default().to_owned();
+    disk.partition_table_set_partition_type(partition, kind)?;
+    println!("Changed type of partition {} to '{}'.", partition + 1, name);
+
+    Ok(())
+}
+
+/// Toggles a flag of a partition.
+fn toggle_flag<T>(disk: &mut Fdisk, flag: T) -> ReplResult<()>
+where
+    T: Into<BitFlag>,
+{
+    let partition = disk.ask_partition_number_used()?;
+    disk.partition_table_toggle_partition_flag(partition, flag.into())?;
+
+    Ok(())
+}
+
+/// Toggles one of the GUID specific attribute bits of a `GPT` partition.
+fn toggle_guid_specific_bit(disk: &mut Fdisk) -> ReplResult<()> {
+    let partition = disk.ask_partition_number_used()?;
+    let bit = disk.ask_number_in_range("Enter GUID specific bit", 48, 48, 63)?;
+
+    let mut attributes = disk.gpt_attributes(partition).unwrap_or_default();
+    attributes.toggle(GptAttributes::from_bits_retain(1 << bit));
+    disk.gpt_set_attributes(partition, attributes)?;
+
+    Ok(())
+}
+
+/// Overrides the name or UUID of a partition.
+fn set_partition_identifier(disk: &mut Fdisk, uuid: bool) -> ReplResult<()> {
+    let partition = disk.ask_partition_number_used()?;
+
+    let template = if uuid {
+        let value = disk.ask_string_value("New UUID (in 8-4-4-4-12 format)")?;
+        Partition::builder().uuid(value).build()?
+    } else {
+        let value = disk.ask_string_value("New name")?;
+        Partition::builder().name(value).build()?
+    };
+
+    disk.partition_override_settings(partition, &template)?;
+
+    Ok(())
+}
+
+/// Checks the partition table. `libfdisk` reports each issue it finds through the prompt handler.
+fn verify(disk: &Fdisk) -> ReplResult<()> {
+    let findings = disk.partition_table_verify()?;
+
+    if !findings.is_empty() {
+        println!("{} issue(s) detected.", findings.len());
+    }
+
+    Ok(())
+}
+
+/// Writes the partition table to disk.
+fn write(disk: &mut Fdisk) -> ReplResult<()> {
+    disk.partition_table_write_to_disk()?;
+    println!("The partition table has been altered.");
+
+    if !disk.device_is_image_file() && disk.reread_partition_entries().is_err() {
+        eprintln!("Re-reading the partition table failed, the kernel still uses the old table.");
+    }
+
+    disk.close_device()?;
+
+    Ok(())
+}
+
+/// Prints the commands of the main menu.
+fn main_menu_help() {
+    println!(
+        "\
+Help:
+
+  Generic
+   d   delete a partition
+   F   list free unpartitioned space
+   l   list known partition types
+   n   add a new partition
+   p   print the partition table
+   t   change a partition type
+   v   verify the partition table
+
+  DOS/SGI
+   a   toggle a bootable flag
+
+  Misc
+   m   print this menu
+   x   extra functionality (experts only)
+
+  Save & Exit
+   w   write table to disk and exit
+   q   quit without saving changes
+
+  Create a new label
+   g   create a new empty GPT partition table
+   G   create a new empty SGI (IRIX) partition table
+   o   create a new empty DOS partition table
+   s   create a new empty Sun partition table
+"
+    );
+}
+
+/// Prints the commands of the expert menu matching the current partition table.
+fn expert_menu_help(kind: Option<PartitionTableKind>) {
+    println!("Help (expert commands):\n");
+
+    let label_commands = match kind {
+        Some(PartitionTableKind::DOS) => {
+            "\
+  DOS (MBR)
+   b   move beginning of data in a partition
+   f   fix partitions order
+   i   change the disk identifier
+"
+        }
+        Some(PartitionTableKind::GPT) => {
+            "\
+  GPT
+   i   change disk GUID
+   n   change partition name
+   u   change partition UUID
+   l   change table length
+   A   toggle the legacy BIOS bootable flag
+   B   toggle the no block IO protocol flag
+   R   toggle the required partition flag
+   S   toggle the GUID specific bits
+"
+        }
+        Some(PartitionTableKind::SUN) => {
+            "\
+  Sun
+   a   change number of alternate cylinders
+   e   change number of extra sectors per cylinder
+   i   change interleave factor
+   o   change rotation speed (rpm)
+   y   change number of physical cylinders
+"
+        }
+        Some(PartitionTableKind::SGI) => {
+            "\
+  SGI
+   b   edit bootfile entry
+   i   create SGI info
+"
+        }
+        Some(PartitionTableKind::BSD) => {
+            "\
+  BSD
+   b   install bootstrap
+   e   edit drive data
+   x   link BSD partition to non-BSD partition
+"
+        }
+        _ => "",
+    };
+
+    println!(
+        "{}
+  Generic
+   p   print the partition table
+   v   verify the partition table
+
+  Misc
+   m   print this menu
+   r   return to main menu
+
+  Save & Exit
+   w   write table to disk and exit
+   q   quit without saving changes
+",
+        label_commands
+    );
+}
+
+/// Runs a command from the main menu.
+fn main_command(disk: &mut Fdisk, command: char) -> ReplResult<Action> {
+    match command {
+        'a' => match table_kind(disk) {
+            Some(PartitionTableKind::DOS) => toggle_flag(disk, DOSFlag::Boot)?,
+            Some(PartitionTableKind::SGI) => toggle_flag(disk, SGIFlag::Boot)?,
+            _ => return Err("bootable flag not supported by this partition table".into()),
+        },
+        'd' => {
+            let partition = disk.ask_partition_number_used()?;
+            disk.partition_delete(partition)?;
+            println!("Partition {} has been deleted.", partition + 1);
+        }
+        'F' => print_free_space(disk)?,
+        'g' => disk.partition_table_create(PartitionTableKind::GPT)?,
+        'G' => disk.partition_table_create(PartitionTableKind::SGI)?,
+        'l' => list_types(disk)?,
+        'm' => main_menu_help(),
+        'n' => {
+            disk.partition_add_interactive()?;
+        }
+        'o' => disk.partition_table_create(PartitionTableKind::DOS)?,
+        'p' => print_table(disk)?,
+        'q' => return Ok(Action::Quit),
+        's' => disk.partition_table_create(PartitionTableKind::SUN)?,
+        't' => change_type(disk)?,
+        'v' => verify(disk)?,
+        'w' => {
+            write(disk)?;
+            return Ok(Action::Quit);
+        }
+        'x' => return Ok(Action::Enter(Menu::Expert)),
+        unknown => println!("{}: unknown command", unknown),
+    }
+
+    Ok(Action::Continue)
+}
+
+/// Runs a command from the expert menu.
+fn expert_command(disk: &mut Fdisk, command: char) -> ReplResult<Action> {
+    let kind = table_kind(disk);
+
+    match (kind, command) {
+        (_, 'm') => expert_menu_help(kind),
+        (_, 'p') => print_table(disk)?,
+        (_, 'q') => return Ok(Action::Quit),
+        (_, 'r') => return Ok(Action::Enter(Menu::Main)),
+        (_, 'v') => verify(disk)?,
+        (_, 'w') => {
+            write(disk)?;
+            return Ok(Action::Quit);
+        }
+        (Some(PartitionTableKind::DOS), 'b') => {
+            let partition = disk.ask_partition_number_used()?;
+            disk.dos_relocate_partition(partition)?;
+        }
+        (Some(PartitionTableKind::DOS), 'f') => disk.partition_table_sort_partitions()?,
+        (Some(PartitionTableKind::DOS | PartitionTableKind::GPT), 'i') => {
+            disk.partition_table_set_id()?
+        }
+        (Some(PartitionTableKind::GPT), 'n') => set_partition_identifier(disk, false)?,
+        (Some(PartitionTableKind::GPT), 'u') => set_partition_identifier(disk, true)?,
+        (Some(PartitionTableKind::GPT), 'l') => {
+            let size = disk.ask_number_in_range("New maximum entries", 128, 1, u32::MAX.into())?;
+            disk.gpt_set_partition_entry_array_size(size as u32)?;
+        }
+        (Some(PartitionTableKind::GPT), 'A') => toggle_flag(disk, GPTFlag::LegacyBiosBootable)?,
+        (Some(PartitionTableKind::GPT), 'B') => toggle_flag(disk, GPTFlag::NoIoBlockProtocol)?,
+        (Some(PartitionTableKind::GPT), 'R') => toggle_flag(disk, GPTFlag::RequiredPartition)?,
+        (Some(PartitionTableKind::GPT), 'S') => toggle_guid_specific_bit(disk)?,
+        (Some(PartitionTableKind::SUN), 'a') => disk.sun_set_alternate_cylinder_count()?,
+        (Some(PartitionTableKind::SUN), 'e') => disk.sun_set_extra_sectors_per_cylinder()?,
+        (Some(PartitionTableKind::SUN), 'i') => disk.sun_set_interleave_factor()?,
+        (Some(PartitionTableKind::SUN), 'o') => disk.sun_set_rotation_per_minute()?,
+        (Some(PartitionTableKind::SUN), 'y') => disk.sun_set_physical_cylinder_count()?,
+        (Some(PartitionTableKind::SGI), 'b') => disk.sgi_set_boot_file()?,
+        (Some(PartitionTableKind::SGI), 'i') => disk.sgi_add_hint()?,
+        (Some(PartitionTableKind::BSD), 'b') => disk.bsd_install_bootstrap_file()?,
+        (Some(PartitionTableKind::BSD), 'e') => disk.bsd_edit_disk_label()?,
+        (Some(PartitionTableKind::BSD), 'x') => disk.bsd_link_to_nested_partition()?,
+        (_, unknown) => println!("{}: unknown command", unknown),
+    }
+
+    Ok(Action::Continue)
+}
+
+/// Runs the shell on `device`.
+fn run(device: &str) -> ReplResult<()> {
+    let mut disk = Fdisk::builder()
+        .assign_device(device)
+        .enable_read_write()
+        .enable_interactive()
+        .prompt_handler(answer)
+        .partition_size_format(SizeFormat::HumanReadable)
+        .build()?;
+
+    println!("Changes will remain in memory only, until you decide to write them.");
+    println!("Be careful before using the write command.\n");
+
+    if !disk.device_has_partition_table() {
+        println!("Device does not contain a recognized partition table.\n");
+    }
+
+    let mut menu = Menu::Main;
+    loop {
+        let prompt = match menu {
+            Menu::Main => "Command (m for help): ",
+            Menu::Expert => "Expert command (m for help): ",
+        };
+
+        let Some(line) = read_line(prompt) else {
+            println!();
+            return Ok(());
+        };
+        let Some(command) = line.chars().next() else {
+            continue;
+        };
+
+        let result = match menu {
+            Menu::Main => main_command(&mut disk, command),
+            Menu::Expert => expert_command(&mut disk, command),
+        };
+
+        match result {
+            Ok(Action::Continue) => {}
+            Ok(Action::Enter(next)) => menu = next,
+            Ok(Action::Quit) => return Ok(()),
+            Err(e) => eprintln!("{}", e),
+        }
+        println!();
+    }
+}
+
+fn main() -> ExitCode {
+    let Some(device) = std::env::args().nth(1) else {
+        eprintln!("Usage: fdisk_repl <device>");
+
+        return ExitCode::FAILURE;
+    };
+
+    match run(&device) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("fdisk_repl: {}", e);
+
+            ExitCode::FAILURE
+        }
+    }
+}
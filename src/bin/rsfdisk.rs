@@ -0,0 +1,589 @@
+// Copyright (c) 2023 Nick Piaddo
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! `sfdisk`-compatible command-line tool built on `rsfdisk`.
+//!
+//! Requires the `cli` feature: `cargo build --features cli --bin rsfdisk`.
+
+// From dependency library
+
+// From standard library
+use std::error::Error;
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::Write;
+use std::os::fd::AsFd;
+use std::process::ExitCode;
+
+// From this library
+use rsfdisk::core::partition::Partition;
+use rsfdisk::core::partition_table::InputType;
+use rsfdisk::core::partition_table::PartitionTableGPTExt;
+use rsfdisk::core::partition_table::PartitionTableKind;
+use rsfdisk::fdisk::Fdisk;
+use rsfdisk::fdisk::SizeFormat;
+use rsfdisk::fdisk::TableRenderer;
+
+const USAGE: &str = "\
+Usage:
+ rsfdisk [options] <dev>                  apply a script read from stdin
+ rsfdisk [options] <command>
+
+Display or manipulate a disk partition table.
+
+Commands:
+ -d, --dump <dev>                  dump partition table (usable for later input)
+ -J, --json <dev>                  dump partition table in JSON format
+ -l, --list [<dev> ...]            list partitions of each device
+     --delete <dev> [<part> ...]   delete all or specified partitions
+     --part-type <dev> <part> [<type>]   print or change partition type
+     --part-uuid <dev> <part> [<uuid>]   print or change partition uuid
+     --part-label <dev> <part> [<str>]   print or change partition label
+     --part-attrs <dev> <part> [<str>]   print or change partition attributes
+     --relocate <oper> <dev>       move partition table headers
+
+Options:
+ -n, --no-act                      do everything except write to device
+     --wipe <mode>                 wipe signatures (auto, always or never)
+ -h, --help                        display this help
+ -V, --version                     display version
+
+Partitions are numbered from 1. Available --relocate operations:
+ gpt-bak-std    move the GPT backup header to the end of the device
+ gpt-bak-mini   move the GPT backup header right after the last partition
+";
+
+/// Type of result returned by the operations of this tool.
+type CliResult<T> = Result<T, Box<dyn Error>>;
+
+/// Operations supported by this tool.
+#[derive(Debug, PartialEq)]
+enum Command {
+    Apply(String),
+    Delete(String, Vec<usize>),
+    Dump(String),
+    Help,
+    Json(String),
+    List(Vec<String>),
+    Partition(PartitionField, String, usize, Option<String>),
+    Relocate(Relocation, String),
+    Version,
+}
+
+/// Partition attributes managed by the `--part-*` commands.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum PartitionField {
+    Attributes,
+    Label,
+    Type,
+    Uuid,
+}
+
+/// Operations supported by `--relocate`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Relocation {
+    GptBackupMinimal,
+    GptBackupStandard,
+}
+
+/// When to erase existing signatures before writing a new partition table.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+enum WipeMode {
+    Always,
+    #[default]
+    Auto,
+    Never,
+}
+
+/// Parsed command line.
+#[derive(Debug, PartialEq)]
+struct Options {
+    command: Command,
+    no_act: bool,
+    wipe: WipeMode,
+}
+
+/// Converts a partition number, as given on the command line, to a `libfdisk` partition index.
+fn parse_partition_number(arg: &str) -> Result<usize, String> {
+    arg.parse::<usize>()
+        .ok()
+        .and_then(|n| n.checked_sub(1))
+        .ok_or_else(|| format!("invalid partition number: {:?}", arg))
+}
+
+/// Parses the command line arguments, excluding the program name.
+fn parse_args<I>(args: I) -> Result<Options, String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut no_act = false;
+    let mut wipe = WipeMode::default();
+    let mut mode: Option<String> = None;
+    let mut relocation: Option<Relocation> = None;
+    let mut positionals: Vec<String> = vec![];
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-n" | "--no-act" => no_act = true,
+            "--wipe" => {
+                let value = args.next().ok_or("option --wipe requires an argument")?;
+                wipe = match value.as_str() {
+                    "auto" => WipeMode::Auto,
+                    "always" => WipeMode::Always,
+                    "never" => WipeMode::Never,
+                    _ => return Err(format!("unsupported wipe mode: {:?}", value)),
+                };
+            }
+            "--relocate" => {
+                let value = args
+                    .next()
+                    .ok_or("option --relocate requires an argument")?;
+                relocation = match value.as_str() {
+                    "gpt-bak-std" => Some(Relocation::GptBackupStandard),
+                    "gpt-bak-mini" => Some(Relocation::GptBackupMinimal),
+                    _ => return Err(format!("unsupported relocation operation: {:?}", value)),
+                };
+                if let Some(previous) = mode.replace(arg.clone()) {
+                    return Err(format!("{} and {} are mutually exclusive", previous, arg));
+                }
+            }
+            "-d" | "--dump" | "-J" | "--json" | "-l" | "--list" | "--delete" | "--part-type"
+            | "--part-uuid" | "--part-label" | "--part-attrs" | "-h" | "--help" | "-V"
+            | "--version" => {
+                if let Some(previous) = mode.replace(arg.clone()) {
+                    return Err(format!("{} and {} are mutually exclusive", previous, arg));
+                }
+            }
+            "--" => positionals.extend(args.by_ref()),
+            option if option.starts_with('-') && option.len() > 1 => {
+                return Err(format!("unrecognized option: {:?}", option));
+            }
+            _ => positionals.push(arg),
+        }
+    }
+
+    let mut positionals = positionals.into_iter();
+    let no_device = "no device specified";
+
+    let command = match mode.as_deref() {
+        None => Command::Apply(positionals.next().ok_or(no_device)?),
+        Some("-h" | "--help") => Command::Help,
+        Some("-V" | "--version") => Command::Version,
+        Some("-d" | "--dump") => Command::Dump(positionals.next().ok_or(no_device)?),
+        Some("-J" | "--json") => Command::Json(positionals.next().ok_or(no_device)?),
+        Some("-l" | "--list") => Command::List(positionals.by_ref().collect()),
+        Some("--relocate") => {
+            Command::Relocate(relocation.unwrap(), positionals.next().ok_or(no_device)?)
+        }
+        Some("--delete") => {
+            let device = positionals.next().ok_or(no_device)?;
+            let partitions = positionals
+                .by_ref()
+                .map(|arg| parse_partition_number(&arg))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Command::Delete(device, partitions)
+        }
+        Some(option) => {
+            let field = match option {
+                "--part-type" => PartitionField::Type,
+                "--part-uuid" => PartitionField::Uuid,
+                "--part-label" => PartitionField::Label,
+                _ => PartitionField::Attributes,
+            };
+            let device = positionals.next().ok_or(no_device)?;
+            let partition = positionals
+                .next()
+                .ok_or_else(|| "no partition number specified".to_owned())
+                .and_then(|arg| parse_partition_number(&arg))?;
+
+            Command::Partition(field, device, partition, positionals.next())
+        }
+    };
+
+    match positionals.next() {
+        Some(unexpected) => Err(format!("unexpected argument: {:?}", unexpected)),
+        None => Ok(Options {
+            command,
+            no_act,
+            wipe,
+        }),
+    }
+}
+
+/// Assigns `device` to a new `Fdisk`.
+fn open_device<'a>(device: &str, read_write: bool, wipe: bool) -> rsfdisk::Result<Fdisk<'a>> {
+    let builder = Fdisk::builder().assign_device(device);
+
+    let disk = match (read_write, wipe) {
+        (false, _) => builder.build()?,
+        (true, false) => builder.enable_read_write().build()?,
+        (true, true) => builder.enable_read_write().wipe_device_metadata().build()?,
+    };
+
+    Ok(disk)
+}
+
+/// Writes the in-memory partition table to disk, unless `no_act` is set.
+fn write_changes(disk: &mut Fdisk, no_act: bool) -> rsfdisk::Result<()> {
+    if no_act {
+        println!("The partition table is unchanged (--no-act).");
+
+        return Ok(());
+    }
+
+    disk.partition_table_write_to_disk()?;
+    println!("The partition table has been altered.");
+
+    if !disk.device_is_image_file() && disk.reread_partition_entries().is_err() {
+        eprintln!("rsfdisk: failed to inform the kernel of the changes, the new table will be used at the next reboot");
+    }
+
+    disk.close_device()?;
+
+    Ok(())
+}
+
+/// Returns a handle to the standard output usable by `libfdisk`.
+fn stdout_file() -> io::Result<File> {
+    let fd = io::stdout().as_fd().try_clone_to_owned()?;
+
+    Ok(File::from(fd))
+}
+
+/// Prints the partition table on `device` as an `sfdisk` script.
+fn dump(device: &str, json: bool) -> CliResult<()> {
+    let disk = open_device(device, false, false)?;
+
+    if !disk.device_has_partition_table() {
+        return Err(format!("{}: does not contain a recognized partition table", device).into());
+    }
+
+    let script = disk.script_new()?;
+    if json {
+        script.enable_json_output()?;
+    }
+    script.compose_script()?;
+
+    io::stdout().flush()?;
+    script.export_to_stream(&mut stdout_file()?)?;
+
+    Ok(())
+}
+
+/// Prints the disk header and partitions of `disk`.
+fn print_partitions(disk: &Fdisk) -> CliResult<()> {
+    println!("{}", disk.device_report());
+
+    let partitions = match disk.list_partitions() {
+        Some(partitions) if !partitions.is_empty() => partitions,
+        _ => return Ok(()),
+    };
+
+    println!();
+    print!("{}", TableRenderer::new(disk).render(&partitions)?);
+
+    Ok(())
+}
+
+/// Returns the paths of all non-empty block devices on the system.
+fn system_devices() -> io::Result<Vec<String>> {
+    let mut devices = vec![];
+
+    for entry in fs::read_dir("/sys/block")? {
+        let entry = entry?;
+        let size = fs::read_to_string(entry.path().join("size")).unwrap_or_default();
+
+        if size.trim().parse::<u64>().unwrap_or(0) > 0 {
+            devices.push(format!("/dev/{}", entry.file_name().to_string_lossy()));
+        }
+    }
+
+    devices.sort();
+
+    Ok(devices)
+}
+
+/// Lists the partitions on each device.
+fn list(devices: Vec<String>) -> CliResult<()> {
+    let devices = if devices.is_empty() {
+        system_devices()?
+    } else {
+        devices
+    };
+
+    for (i, device) in devices.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+
+        let disk = Fdisk::builder()
+            .assign_device(device)
+            .partition_size_format(SizeFormat::HumanReadable)
+            .build()?;
+        print_partitions(&disk)?;
+    }
+
+    Ok(())
+}
+
+/// Creates a new partition table on `device` from a script read on the standard input.
+fn apply(device: &str, no_act: bool, wipe: WipeMode) -> CliResult<()> {
+    // The script is parsed by a read-only `Fdisk`, to leave the one applying it free to be
+    // modified.
+    let reader = open_device(device, false, false)?;
+    let script = reader.script_new_from_file("/dev/stdin")?;
+
+    let collision = reader.device_describe_collisions().map(str::to_owned);
+    let wipe = match wipe {
+        WipeMode::Always => true,
+        WipeMode::Auto => collision.is_some(),
+        WipeMode::Never => false,
+    };
+
+    let mut disk = open_device(device, !no_act, wipe)?;
+    if let Some(signature) = collision {
+        if wipe {
+            println!(
+                "The {} signature will be removed by a write command.",
+                signature
+            );
+        } else {
+            eprintln!(
+                "rsfdisk: the old {} signature may remain on the device",
+                signature
+            );
+        }
+    }
+
+    disk.script_apply(script)?;
+
+    println!("New situation:");
+    print_partitions(&disk)?;
+    println!();
+
+    write_changes(&mut disk, no_act)?;
+
+    Ok(())
+}
+
+/// Returns an error if `partition` is not in use on `disk`.
+fn check_partition_exists(disk: &Fdisk, device: &str, partition: usize) -> CliResult<()> {
+    if disk.partition_is_number_in_use(partition) {
+        Ok(())
+    } else {
+        Err(format!("{}: partition {} does not exist", device, partition + 1).into())
+    }
+}
+
+/// Deletes the given partitions on `device`, or all of them if none is specified.
+fn delete(device: &str, partitions: Vec<usize>, no_act: bool) -> CliResult<()> {
+    let mut disk = open_device(device, !no_act, false)?;
+
+    if partitions.is_empty() {
+        disk.partition_delete_all()?;
+    }
+
+    for partition in partitions {
+        check_partition_exists(&disk, device, partition)?;
+        disk.partition_delete(partition)?;
+    }
+
+    write_changes(&mut disk, no_act)?;
+
+    Ok(())
+}
+
+/// Prints or sets a partition attribute.
+fn partition_field(
+    field: PartitionField,
+    device: &str,
+    partition: usize,
+    value: Option<String>,
+    no_act: bool,
+) -> CliResult<()> {
+    let read_write = value.is_some() && !no_act;
+    let mut disk = open_device(device, read_write, false)?;
+    check_partition_exists(&disk, device, partition)?;
+
+    let Some(value) = value else {
+        // Safe to unwrap, the partition exists.
+        let entry = disk.partition_by_number(partition).unwrap();
+        let content = match field {
+            PartitionField::Attributes => entry
+                .attribute_bits()
+                .map(|bits| String::from_utf8_lossy(&bits).into_owned()),
+            PartitionField::Label => entry.name().map(str::to_owned),
+            PartitionField::Type => entry.partition_type().and_then(|kind| {
+                kind.guid()
+                    .map(str::to_owned)
+                    .or_else(|| kind.code().map(|code| format!("{:x}", code)))
+            }),
+            PartitionField::Uuid => entry.uuid().map(str::to_owned),
+        };
+        println!("{}", content.unwrap_or_default());
+
+        return Ok(());
+    };
+
+    match field {
+        PartitionField::Type => {
+            let table = disk.partition_table_current().ok_or_else(|| {
+                format!("{}: does not contain a recognized partition table", device)
+            })?;
+            let kind = table.partition_type_parse(&value, [InputType::Default])?;
+            disk.partition_table_set_partition_type(partition, kind)?;
+        }
+        PartitionField::Attributes => {
+            let template = Partition::builder().attribute_bits(&value).build()?;
+            disk.partition_override_settings(partition, &template)?;
+        }
+        PartitionField::Label => {
+            let template = Partition::builder().name(value).build()?;
+            disk.partition_override_settings(partition, &template)?;
+        }
+        PartitionField::Uuid => {
+            let template = Partition::builder().uuid(value).build()?;
+            disk.partition_override_settings(partition, &template)?;
+        }
+    }
+
+    write_changes(&mut disk, no_act)?;
+
+    Ok(())
+}
+
+/// Moves the headers of the partition table on `device`.
+fn relocate(relocation: Relocation, device: &str, no_act: bool) -> CliResult<()> {
+    let mut disk = open_device(device, !no_act, false)?;
+
+    if !disk.partition_table_is_of_type(PartitionTableKind::GPT) {
+        return Err(format!(
+            "{}: relocation only supported for GPT partition tables",
+            device
+        )
+        .into());
+    }
+
+    // Safe to unwrap, the device has a `GPT` partition table.
+    let table = disk.partition_table_current_mut().unwrap();
+    match relocation {
+        Relocation::GptBackupStandard => {
+            table.gpt_enable_backup_header_relocation();
+            table.gpt_disable_minimize_footprint();
+        }
+        Relocation::GptBackupMinimal => table.gpt_enable_minimize_footprint(),
+    }
+
+    // Headers are placed while probing the device.
+    disk.discard_changes()?;
+    disk.partition_table_current_mut()
+        .unwrap()
+        .mark_as_changed();
+
+    write_changes(&mut disk, no_act)?;
+
+    Ok(())
+}
+
+/// Runs a command.
+fn run(options: Options) -> CliResult<()> {
+    let no_act = options.no_act;
+
+    match options.command {
+        Command::Apply(device) => apply(&device, no_act, options.wipe),
+        Command::Delete(device, partitions) => delete(&device, partitions, no_act),
+        Command::Dump(device) => dump(&device, false),
+        Command::Help => {
+            print!("{}", USAGE);
+            Ok(())
+        }
+        Command::Json(device) => dump(&device, true),
+        Command::List(devices) => list(devices),
+        Command::Partition(field, device, partition, value) => {
+            partition_field(field, &device, partition, value, no_act)
+        }
+        Command::Relocate(relocation, device) => relocate(relocation, &device, no_act),
+        Command::Version => {
+            println!("rsfdisk {}", env!("CARGO_PKG_VERSION"));
+            Ok(())
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(err_msg) => {
+            eprintln!("rsfdisk: {}", err_msg);
+            eprintln!("Try 'rsfdisk --help' for more information.");
+
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match run(options) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("rsfdisk: {}", e);
+
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::*;
+    use pretty_assertions::{assert_eq, assert_ne};
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_owned).collect()
+    }
+
+    #[test]
+    fn rsfdisk_parses_a_partition_attribute_command() {
+        let actual = parse_args(args("--no-act --part-type /dev/sda 2 linux"));
+        let expected = Ok(Options {
+            command: Command::Partition(
+                PartitionField::Type,
+                "/dev/sda".to_owned(),
+                1,
+                Some("linux".to_owned()),
+            ),
+            no_act: true,
+            wipe: WipeMode::Auto,
+        });
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn rsfdisk_parses_a_script_application_command() {
+        let actual = parse_args(args("--wipe always /dev/sda"));
+        let expected = Ok(Options {
+            command: Command::Apply("/dev/sda".to_owned()),
+            no_act: false,
+            wipe: WipeMode::Always,
+        });
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn rsfdisk_rejects_invalid_command_lines() {
+        for line in [
+            "",
+            "--dump",
+            "--dump /dev/sda --list",
+            "--delete /dev/sda 0",
+            "--relocate gpt-bak-foo /dev/sda",
+            "--dump /dev/sda /dev/sdb",
+            "--part-uuid /dev/sda",
+        ] {
+            let actual = parse_args(args(line));
+            assert!(actual.is_err(), "{:?} should be rejected", line);
+        }
+    }
+}
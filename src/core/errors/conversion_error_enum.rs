@@ -20,6 +20,20 @@ pub enum ConversionError {
     #[error("{0}")]
     Code(String),
 
+    /// Error while converting a value to a field of [`GptAttributes`](crate::core::partition::GptAttributes).
+    #[error("{0}")]
+    GptAttributes(String),
+
+    /// Error while converting a [`PartitionData`](crate::core::partition::PartitionData) into a
+    /// [`Partition`](crate::core::partition::Partition).
+    #[error("{0}")]
+    Partition(String),
+
+    /// Error while converting a [`PartitionKindData`](crate::core::partition::PartitionKindData)
+    /// into a [`PartitionKind`](crate::core::partition::PartitionKind).
+    #[error("{0}")]
+    PartitionKind(String),
+
     /// Error while converting a value to a [`MaxColWidth`](crate::core::partition_table::MaxColWidth).
     #[error("{0}")]
     MaxColWidth(String),
@@ -0,0 +1,29 @@
+// Copyright (c) 2023 Nick Piaddo
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// From dependency library
+use thiserror::Error;
+
+// From standard library
+
+// From this library
+use crate::core::errors::PartitionBuilderError;
+
+/// [`Layout`](crate::core::layout::Layout) runtime errors.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum LayoutError {
+    /// Error while configuring a [`LayoutPartition`](crate::core::layout::LayoutPartition).
+    #[error("{0}")]
+    Config(String),
+
+    /// Error when the device is too small to hold all partitions in a
+    /// [`Layout`](crate::core::layout::Layout).
+    #[error("{0}")]
+    InsufficientSpace(String),
+
+    /// Error while converting a [`PlannedPartition`](crate::core::layout::PlannedPartition) to a
+    /// [`Partition`](crate::core::partition::Partition).
+    #[error(transparent)]
+    Partition(#[from] PartitionBuilderError),
+}
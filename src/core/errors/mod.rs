@@ -11,6 +11,7 @@
 pub use conversion_error_enum::ConversionError;
 pub use gen_iterator_error_enum::GenIteratorError;
 pub use header_entry_content_error_enum::HeaderEntryContentError;
+pub use layout_error_enum::LayoutError;
 pub use parser_error_enum::ParserError;
 pub use partition_builder_error_enum::PartitionBuilderError;
 pub use partition_error_enum::PartitionError;
@@ -20,11 +21,13 @@
 pub use partition_list_error_enum::PartitionListError;
 pub use partition_table_error_enum::PartitionTableError;
 pub use prompt_error_enum::PromptError;
+pub use script_document_error_enum::ScriptDocumentError;
 pub use script_error_enum::ScriptError;
 
 mod conversion_error_enum;
 mod gen_iterator_error_enum;
 mod header_entry_content_error_enum;
+mod layout_error_enum;
 mod parser_error_enum;
 mod partition_builder_error_enum;
 mod partition_error_enum;
@@ -34,4 +37,5 @@
 mod partition_list_error_enum;
 mod partition_table_error_enum;
 mod prompt_error_enum;
+mod script_document_error_enum;
 mod script_error_enum;
@@ -19,4 +19,8 @@ pub enum ParserError {
     /// Error while parsing a string into a [`Code`](crate::core::partition::Code).
     #[error("{0}")]
     Code(String),
+
+    /// Error while parsing a string into a [`PartitionTableKind`](crate::core::partition_table::PartitionTableKind).
+    #[error("{0}")]
+    PartitionTableKind(String),
 }
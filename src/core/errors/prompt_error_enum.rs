@@ -28,4 +28,8 @@ pub enum PromptError {
     /// Error while selecting a [`MenuItem`](crate::core::prompt::MenuItem).
     #[error("{0}")]
     Selection(String),
+
+    /// Error when receiving a [`Prompt`](crate::core::prompt::Prompt) that was not expected.
+    #[error("{0}")]
+    Unexpected(String),
 }
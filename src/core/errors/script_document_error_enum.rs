@@ -0,0 +1,26 @@
+// Copyright (c) 2023 Nick Piaddo
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// From dependency library
+use thiserror::Error;
+
+// From standard library
+
+// From this library
+
+/// [`ScriptDocument`](crate::core::script::ScriptDocument) runtime errors.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum ScriptDocumentError {
+    /// Error while converting a value to/from a [`ScriptDocument`](crate::core::script::ScriptDocument).
+    #[error("{0}")]
+    Conversion(String),
+
+    /// Error while parsing a JSON description of a partition table.
+    #[error("{0}")]
+    Parse(String),
+
+    /// Error while validating the content of a [`ScriptDocument`](crate::core::script::ScriptDocument).
+    #[error("{0}")]
+    Validation(String),
+}
@@ -8,6 +8,7 @@
 use std::ffi::NulError;
 
 // From this library
+use crate::core::errors::ScriptDocumentError;
 
 /// [`Script`](crate::core::script::Script) runtime errors.
 #[derive(Debug, Error)]
@@ -21,6 +22,10 @@ pub enum ScriptError {
     #[error("{0}")]
     Compose(String),
 
+    /// Error while processing a [`ScriptDocument`](crate::core::script::ScriptDocument).
+    #[error(transparent)]
+    Document(#[from] ScriptDocumentError),
+
     /// Error while converting a value to [`CString`](std::ffi::CString).
     #[error("failed to convert value to `CString`: {}", .0)]
     CStringConversion(#[from] NulError),
@@ -0,0 +1,148 @@
+// Copyright (c) 2023 Nick Piaddo
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// From dependency library
+use typed_builder::TypedBuilder;
+
+// From standard library
+
+// From this library
+use crate::core::errors::LayoutError;
+use crate::core::layout::LayoutPartition;
+use crate::core::layout::LayoutSize;
+use crate::core::partition::PartitionKind;
+
+#[derive(Debug, TypedBuilder)]
+#[builder(
+    builder_type(
+        name = LayoutPartitionBuilder,
+        vis = "pub",
+        doc ="Configure and instantiate a [`LayoutPartition`].\n\nFor usage, see [`LayoutPartitionBuilder::build`]."),
+    build_method(vis = "", name = __make))]
+pub(crate) struct LayoutPartBuilder {
+    #[builder(
+        default,
+        setter(
+            strip_option,
+            doc = "Set the partition's maximum size in bytes. Applies to sizes expressed as a
+percentage or as a share of the remaining space."
+        )
+    )]
+    max_size_in_bytes: Option<u64>,
+
+    #[builder(
+        default,
+        setter(
+            strip_option,
+            doc = "Set the partition's minimum size in bytes. Applies to sizes expressed as a
+percentage or as a share of the remaining space."
+        )
+    )]
+    min_size_in_bytes: Option<u64>,
+
+    #[builder(default, setter(into, strip_option, doc = "Set the partition's name."))]
+    name: Option<String>,
+
+    #[builder(
+        default,
+        setter(
+            strip_option,
+            doc = "Set the partition's type. By default, uses the partition table's default
+partition type."
+        )
+    )]
+    partition_type: Option<PartitionKind>,
+
+    #[builder(
+        default = LayoutSize::Remaining,
+        setter(doc = "Set the partition's size. By default, set to [`LayoutSize::Remaining`].")
+    )]
+    size: LayoutSize,
+}
+
+#[allow(non_camel_case_types)]
+impl<
+        __max_size_in_bytes: ::typed_builder::Optional<Option<u64>>,
+        __min_size_in_bytes: ::typed_builder::Optional<Option<u64>>,
+        __name: ::typed_builder::Optional<Option<String>>,
+        __partition_type: ::typed_builder::Optional<Option<PartitionKind>>,
+        __size: ::typed_builder::Optional<LayoutSize>,
+    >
+    LayoutPartitionBuilder<(
+        __max_size_in_bytes,
+        __min_size_in_bytes,
+        __name,
+        __partition_type,
+        __size,
+    )>
+{
+    /// Completes a [`LayoutPartition`]'s configuration process, and creates a new instance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rsfdisk::core::layout::LayoutPartition;
+    /// use rsfdisk::core::layout::LayoutSize;
+    /// use rsfdisk::core::partition::Guid;
+    /// use rsfdisk::core::partition::PartitionKind;
+    ///
+    /// fn main() -> rsfdisk::Result<()> {
+    ///     let partition_type = PartitionKind::builder()
+    ///         .guid(Guid::LinuxHome)
+    ///         .build()?;
+    ///
+    ///     // A partition taking 40% of the device, but no less than 1 GiB, and no more than
+    ///     // 100 GiB.
+    ///     let home = LayoutPartition::builder()
+    ///         .partition_type(partition_type)
+    ///         .name("Home")
+    ///         .size(LayoutSize::Percentage(40))
+    ///         .min_size_in_bytes(1 << 30)
+    ///         .max_size_in_bytes(100 << 30)
+    ///         .build()?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn build(self) -> Result<LayoutPartition, LayoutError> {
+        let builder = self.__make();
+
+        match builder.size {
+            LayoutSize::Bytes(0) => {
+                let err_msg = "partition size must be greater than 0 bytes".to_owned();
+
+                return Err(LayoutError::Config(err_msg));
+            }
+            LayoutSize::Percentage(percent) if percent == 0 || percent > 100 => {
+                let err_msg = format!(
+                    "partition size percentage must be between 1 and 100, got: {}",
+                    percent
+                );
+
+                return Err(LayoutError::Config(err_msg));
+            }
+            _ => {}
+        }
+
+        if let (Some(min), Some(max)) = (builder.min_size_in_bytes, builder.max_size_in_bytes) {
+            if min > max {
+                let err_msg = format!(
+                    "partition minimum size ({} bytes) is greater than its maximum size ({} bytes)",
+                    min, max
+                );
+
+                return Err(LayoutError::Config(err_msg));
+            }
+        }
+
+        let partition = LayoutPartition::new(
+            builder.partition_type,
+            builder.name,
+            builder.size,
+            builder.min_size_in_bytes,
+            builder.max_size_in_bytes,
+        );
+
+        Ok(partition)
+    }
+}
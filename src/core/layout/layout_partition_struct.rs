@@ -0,0 +1,78 @@
+// Copyright (c) 2023 Nick Piaddo
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// From dependency library
+
+// From standard library
+
+// From this library
+use crate::core::layout::LayoutPartBuilder;
+use crate::core::layout::LayoutPartitionBuilder;
+use crate::core::layout::LayoutSize;
+use crate::core::partition::PartitionKind;
+
+/// Description of a partition in a [`Layout`](crate::core::layout::Layout).
+#[derive(Clone, Debug)]
+pub struct LayoutPartition {
+    partition_type: Option<PartitionKind>,
+    name: Option<String>,
+    size: LayoutSize,
+    min_size_in_bytes: Option<u64>,
+    max_size_in_bytes: Option<u64>,
+}
+
+impl LayoutPartition {
+    #[doc(hidden)]
+    /// Creates a new `LayoutPartition`.
+    pub(crate) fn new(
+        partition_type: Option<PartitionKind>,
+        name: Option<String>,
+        size: LayoutSize,
+        min_size_in_bytes: Option<u64>,
+        max_size_in_bytes: Option<u64>,
+    ) -> LayoutPartition {
+        Self {
+            partition_type,
+            name,
+            size,
+            min_size_in_bytes,
+            max_size_in_bytes,
+        }
+    }
+
+    /// Creates a [`LayoutPartitionBuilder`] to configure and construct a new `LayoutPartition`
+    /// instance.
+    ///
+    /// Call the [`LayoutPartitionBuilder`]'s
+    /// [`build()`](crate::core::layout::LayoutPartitionBuilder::build) method to instantiate a new
+    /// `LayoutPartition`.
+    pub fn builder() -> LayoutPartitionBuilder {
+        log::debug!("LayoutPartition::builder creating new `LayoutPartitionBuilder` instance");
+        LayoutPartBuilder::builder()
+    }
+
+    /// Returns the partition's type.
+    pub fn partition_type(&self) -> Option<&PartitionKind> {
+        self.partition_type.as_ref()
+    }
+
+    /// Returns the partition's name.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the partition's size.
+    pub fn size(&self) -> LayoutSize {
+        self.size
+    }
+
+    /// Returns the partition's minimum size in bytes.
+    pub fn min_size_in_bytes(&self) -> Option<u64> {
+        self.min_size_in_bytes
+    }
+
+    /// Returns the partition's maximum size in bytes.
+    pub fn max_size_in_bytes(&self) -> Option<u64> {
+        self.max_size_in_bytes
+    }
+}
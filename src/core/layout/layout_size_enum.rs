@@ -0,0 +1,33 @@
+// Copyright (c) 2023 Nick Piaddo
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// From dependency library
+
+// From standard library
+use std::fmt;
+
+// From this library
+
+/// Size of a partition in a [`Layout`](crate::core::layout::Layout).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LayoutSize {
+    /// Absolute size in bytes, rounded up to a whole number of sectors.
+    Bytes(u64),
+
+    /// Percentage (between `1` and `100`) of the device's total size.
+    Percentage(u8),
+
+    /// Share of the space left after sizing all [`LayoutSize::Bytes`] and
+    /// [`LayoutSize::Percentage`] partitions, split evenly between all partitions of this kind.
+    Remaining,
+}
+
+impl fmt::Display for LayoutSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bytes(bytes) => write!(f, "{} bytes", bytes),
+            Self::Percentage(percent) => write!(f, "{}%", percent),
+            Self::Remaining => write!(f, "remaining space"),
+        }
+    }
+}
@@ -0,0 +1,407 @@
+// Copyright (c) 2023 Nick Piaddo
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// From dependency library
+
+// From standard library
+
+// From this library
+use crate::core::errors::LayoutError;
+use crate::core::layout::LayoutPartition;
+use crate::core::layout::LayoutSize;
+use crate::core::layout::PlannedPartition;
+use crate::fdisk::Fdisk;
+
+/// Ordered list of partitions to create on a device.
+///
+/// Partitions are laid out one after the other, in insertion order, starting from the first
+/// usable sector of the device. Each partition begins on a sector aligned on the device's grain
+/// size.
+///
+/// # Examples
+///
+/// ```ignore
+/// use rsfdisk::core::layout::Layout;
+/// use rsfdisk::core::layout::LayoutPartition;
+/// use rsfdisk::core::layout::LayoutSize;
+/// use rsfdisk::core::partition::Guid;
+/// use rsfdisk::core::partition::PartitionKind;
+/// use rsfdisk::core::partition_table::PartitionTableKind;
+/// use rsfdisk::fdisk::Fdisk;
+///
+/// fn main() -> rsfdisk::Result<()> {
+///     let mut disk = Fdisk::builder()
+///         .assign_device("/dev/vda")
+///         .enable_read_write()
+///         .wipe_device_metadata()
+///         .build()?;
+///
+///     disk.partition_table_create(PartitionTableKind::GPT)?;
+///
+///     let esp = LayoutPartition::builder()
+///         .partition_type(PartitionKind::builder().guid(Guid::EfiSystem).build()?)
+///         .name("EFI System")
+///         .size(LayoutSize::Bytes(512 << 20))
+///         .build()?;
+///
+///     let swap = LayoutPartition::builder()
+///         .partition_type(PartitionKind::builder().guid(Guid::LinuxSwap).build()?)
+///         .name("Swap")
+///         .size(LayoutSize::Percentage(10))
+///         .max_size_in_bytes(8 << 30)
+///         .build()?;
+///
+///     let root = LayoutPartition::builder()
+///         .partition_type(PartitionKind::builder().guid(Guid::LinuxRootx86_64).build()?)
+///         .name("System")
+///         .size(LayoutSize::Remaining)
+///         .build()?;
+///
+///     let layout = Layout::new().partition(esp).partition(swap).partition(root);
+///
+///     // Create all partitions at once.
+///     disk.partitions_apply_layout(&layout)?;
+///
+///     disk.partition_table_write_to_disk()?;
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Layout {
+    partitions: Vec<LayoutPartition>,
+}
+
+impl Layout {
+    /// Creates a new empty `Layout`.
+    pub fn new() -> Layout {
+        log::debug!("Layout::new creating a new `Layout` instance");
+
+        Self::default()
+    }
+
+    /// Adds a partition at the end of this `Layout`, and returns the updated `Layout`.
+    pub fn partition(mut self, partition: LayoutPartition) -> Layout {
+        self.push(partition);
+
+        self
+    }
+
+    /// Adds a partition at the end of this `Layout`.
+    pub fn push(&mut self, partition: LayoutPartition) {
+        log::debug!("Layout::push adding partition: {:?}", partition);
+
+        self.partitions.push(partition);
+    }
+
+    /// Returns the partitions in this `Layout`.
+    pub fn partitions(&self) -> &[LayoutPartition] {
+        &self.partitions
+    }
+
+    /// Returns the number of partitions in this `Layout`.
+    pub fn len(&self) -> usize {
+        self.partitions.len()
+    }
+
+    /// Returns `true` if this `Layout` has no partition.
+    pub fn is_empty(&self) -> bool {
+        self.partitions.is_empty()
+    }
+
+    #[doc(hidden)]
+    /// Converts a size in bytes to a number of sectors, rounding up.
+    fn to_sectors(bytes: u64, sector_size: u64) -> Result<u64, LayoutError> {
+        bytes
+            .checked_add(sector_size - 1)
+            .map(|bytes| bytes / sector_size)
+            .ok_or_else(|| Self::overflow(bytes, "bytes"))
+    }
+
+    #[doc(hidden)]
+    /// Rounds `sector` up to the next multiple of `grain`.
+    fn align_up(sector: u64, grain: u64) -> Result<u64, LayoutError> {
+        sector
+            .checked_add(grain - 1)
+            .and_then(|sector| (sector / grain).checked_mul(grain))
+            .ok_or_else(|| Self::overflow(sector, "sectors"))
+    }
+
+    #[doc(hidden)]
+    /// Returns an error for a `value`, expressed in `unit`, too large to lay out.
+    fn overflow(value: u64, unit: &str) -> LayoutError {
+        let err_msg = format!("size overflow: can not lay out {} {}", value, unit);
+        log::debug!("Layout::plan {}", err_msg);
+
+        LayoutError::InsufficientSpace(err_msg)
+    }
+
+    #[doc(hidden)]
+    /// Applies a partition's minimum/maximum size constraints to `size` (in sectors).
+    fn constrain(
+        partition: &LayoutPartition,
+        size: u64,
+        sector_size: u64,
+    ) -> Result<u64, LayoutError> {
+        let size = match partition.min_size_in_bytes() {
+            Some(min) => size.max(Self::to_sectors(min, sector_size)?),
+            None => size,
+        };
+
+        let size = match partition.max_size_in_bytes() {
+            // Round down to avoid going over the limit.
+            Some(max) => size.min(max / sector_size),
+            None => size,
+        };
+
+        Ok(size)
+    }
+
+    #[doc(hidden)]
+    /// Computes the starting sector and size (in sectors) of each partition.
+    fn plan(
+        &self,
+        first_lba: u64,
+        last_lba: u64,
+        device_size_in_sectors: u64,
+        sector_size: u64,
+        grain_size: u64,
+    ) -> Result<Vec<(u64, u64)>, LayoutError> {
+        let sector_size = sector_size.max(1);
+        let grain = (grain_size / sector_size).max(1);
+        let start = Self::align_up(first_lba, grain)?;
+
+        if start > last_lba {
+            let err_msg = format!(
+                "no usable sector on device: first aligned sector {} is past the last usable sector {}",
+                start, last_lba
+            );
+
+            return Err(LayoutError::InsufficientSpace(err_msg));
+        }
+
+        let available = last_lba - start + 1;
+
+        // Sizes of partitions with a fixed size, `None` for partitions sharing the remaining space.
+        let fixed: Vec<Option<u64>> = self
+            .partitions
+            .iter()
+            .map(|partition| {
+                let size = match partition.size() {
+                    LayoutSize::Bytes(bytes) => Self::to_sectors(bytes, sector_size)?,
+                    LayoutSize::Percentage(percent) => device_size_in_sectors
+                        .checked_mul(u64::from(percent))
+                        .map(|size| size / 100)
+                        .ok_or_else(|| Self::overflow(device_size_in_sectors, "sectors"))?,
+                    LayoutSize::Remaining => return Ok(None),
+                };
+
+                Self::constrain(partition, size, sector_size).map(Some)
+            })
+            .collect::<Result<_, _>>()?;
+
+        let reserved = fixed.iter().flatten().try_fold(0u64, |reserved, &size| {
+            Self::align_up(size, grain)?
+                .checked_add(reserved)
+                .ok_or_else(|| Self::overflow(size, "sectors"))
+        })?;
+        let count_remaining = fixed.iter().filter(|size| size.is_none()).count() as u64;
+
+        let share = match available
+            .saturating_sub(reserved)
+            .checked_div(count_remaining)
+        {
+            // Keep partitions sharing the remaining space aligned, unless they are smaller than
+            // the grain size.
+            Some(share) if share >= grain => (share / grain) * grain,
+            Some(share) => share,
+            None => 0,
+        };
+
+        let mut planned = Vec::with_capacity(self.partitions.len());
+        let mut cursor = start;
+
+        for (i, (partition, size)) in self.partitions.iter().zip(fixed).enumerate() {
+            let is_last = i + 1 == self.partitions.len();
+
+            let size = match size {
+                Some(size) => size,
+                // The last partition takes everything up to the last usable sector.
+                None if is_last && cursor <= last_lba => {
+                    Self::constrain(partition, last_lba - cursor + 1, sector_size)?
+                }
+                None => Self::constrain(partition, share, sector_size)?,
+            };
+
+            if size == 0 || cursor > last_lba || size > last_lba - cursor + 1 {
+                let err_msg = format!(
+                    "not enough space on device for partition {} ({}): {} sectors requested, {} available",
+                    i,
+                    partition.size(),
+                    size,
+                    (last_lba + 1).saturating_sub(cursor)
+                );
+
+                return Err(LayoutError::InsufficientSpace(err_msg));
+            }
+
+            planned.push((cursor, size));
+            cursor = cursor
+                .checked_add(size)
+                .ok_or_else(|| Self::overflow(size, "sectors"))
+                .and_then(|end| Self::align_up(end, grain))?;
+        }
+
+        Ok(planned)
+    }
+
+    /// Computes the position and size of each partition in this `Layout` from the geometry of
+    /// the device assigned to `disk`.
+    pub fn resolve(&self, disk: &Fdisk) -> Result<Vec<PlannedPartition>, LayoutError> {
+        log::debug!("Layout::resolve resolving partition layout");
+
+        let plan = self.plan(
+            disk.device_first_lba(),
+            disk.device_last_lba(),
+            disk.device_size_in_sectors(),
+            disk.device_bytes_per_logical_sector(),
+            disk.device_grain_size(),
+        )?;
+
+        let planned: Vec<_> = self
+            .partitions
+            .iter()
+            .zip(plan)
+            .map(|(partition, (starting_sector, size_in_sectors))| {
+                PlannedPartition::new(
+                    partition.partition_type().cloned(),
+                    partition.name().map(String::from),
+                    starting_sector,
+                    size_in_sectors,
+                )
+            })
+            .collect();
+
+        log::debug!("Layout::resolve resolved partition layout: {:?}", planned);
+
+        Ok(planned)
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::*;
+    use pretty_assertions::{assert_eq, assert_ne};
+
+    fn partition(size: LayoutSize) -> LayoutPartition {
+        LayoutPartition::builder().size(size).build().unwrap()
+    }
+
+    #[test]
+    fn layout_partition_percentage_must_be_between_1_and_100() {
+        let actual = LayoutPartition::builder()
+            .size(LayoutSize::Percentage(0))
+            .build();
+        assert!(actual.is_err());
+
+        let actual = LayoutPartition::builder()
+            .size(LayoutSize::Percentage(101))
+            .build();
+        assert!(actual.is_err());
+    }
+
+    #[test]
+    fn layout_partition_min_size_can_not_be_greater_than_max_size() {
+        let actual = LayoutPartition::builder()
+            .min_size_in_bytes(2048)
+            .max_size_in_bytes(1024)
+            .build();
+
+        assert!(actual.is_err());
+    }
+
+    #[test]
+    fn layout_can_plan_partitions_of_fixed_and_remaining_sizes() -> crate::Result<()> {
+        // 100 MiB device, 512 bytes per sector, 1 MiB grain.
+        let layout = Layout::new()
+            .partition(partition(LayoutSize::Bytes(10 << 20)))
+            .partition(partition(LayoutSize::Percentage(25)))
+            .partition(partition(LayoutSize::Remaining));
+
+        let actual = layout.plan(34, 204_766, 204_800, 512, 1 << 20)?;
+        let expected = vec![(2048, 20_480), (22_528, 51_200), (73_728, 131_039)];
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn layout_can_split_remaining_space_evenly() -> crate::Result<()> {
+        let layout = Layout::new()
+            .partition(partition(LayoutSize::Remaining))
+            .partition(partition(LayoutSize::Remaining));
+
+        let actual = layout.plan(2048, 206_847, 206_848, 512, 1 << 20)?;
+        let expected = vec![(2048, 102_400), (104_448, 102_400)];
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn layout_can_apply_min_and_max_size_constraints() -> crate::Result<()> {
+        let small = LayoutPartition::builder()
+            .size(LayoutSize::Percentage(1))
+            .min_size_in_bytes(4 << 20)
+            .build()?;
+        let capped = LayoutPartition::builder()
+            .size(LayoutSize::Remaining)
+            .max_size_in_bytes(8 << 20)
+            .build()?;
+        let layout = Layout::new().partition(small).partition(capped);
+
+        let actual = layout.plan(2048, 206_847, 206_848, 512, 1 << 20)?;
+        let expected = vec![(2048, 8192), (10_240, 16_384)];
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn layout_can_not_plan_partitions_larger_than_the_device() {
+        let layout = Layout::new()
+            .partition(partition(LayoutSize::Percentage(60)))
+            .partition(partition(LayoutSize::Percentage(60)));
+
+        let actual = layout.plan(2048, 206_847, 206_848, 512, 1 << 20);
+        assert!(matches!(actual, Err(LayoutError::InsufficientSpace(_))));
+    }
+
+    #[test]
+    fn layout_can_not_plan_a_remaining_partition_smaller_than_its_minimum_size() {
+        let fixed = partition(LayoutSize::Percentage(90));
+        let remaining = LayoutPartition::builder()
+            .size(LayoutSize::Remaining)
+            .min_size_in_bytes(50 << 20)
+            .build()
+            .unwrap();
+        let layout = Layout::new().partition(fixed).partition(remaining);
+
+        let actual = layout.plan(2048, 206_847, 206_848, 512, 1 << 20);
+        assert!(matches!(actual, Err(LayoutError::InsufficientSpace(_))));
+    }
+
+    #[test]
+    fn layout_can_not_plan_partitions_too_large_to_count_in_sectors() {
+        let layout = Layout::new().partition(partition(LayoutSize::Bytes(u64::MAX)));
+
+        let actual = layout.plan(2048, 206_847, 206_848, 512, 1 << 20);
+        assert!(matches!(actual, Err(LayoutError::InsufficientSpace(_))));
+
+        let layout = Layout::new().partition(partition(LayoutSize::Percentage(100)));
+
+        let actual = layout.plan(2048, u64::MAX - 1, u64::MAX, 512, 1 << 20);
+        assert!(matches!(actual, Err(LayoutError::InsufficientSpace(_))));
+    }
+}
@@ -0,0 +1,30 @@
+// Copyright (c) 2023 Nick Piaddo
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Module for describing partition layouts declaratively.
+//!
+//! A [`Layout`] is an ordered list of [`LayoutPartition`]s, each with a type, an optional name,
+//! and a [`LayoutSize`] expressed in bytes, as a percentage of the device's size, or as a share of
+//! the space left over by the other partitions. [`Fdisk::partitions_apply_layout`] resolves a
+//! `Layout` against the geometry of the assigned device, then adds the resulting partitions to its
+//! partition table.
+//!
+//! [`Fdisk::partitions_apply_layout`]: crate::fdisk::Fdisk::partitions_apply_layout
+
+// From dependency library
+
+// From standard library
+
+// From this library
+pub(crate) use layout_partition_builder_struct::LayoutPartBuilder;
+pub use layout_partition_builder_struct::LayoutPartitionBuilder;
+pub use layout_partition_struct::LayoutPartition;
+pub use layout_size_enum::LayoutSize;
+pub use layout_struct::Layout;
+pub use planned_partition_struct::PlannedPartition;
+
+mod layout_partition_builder_struct;
+mod layout_partition_struct;
+mod layout_size_enum;
+mod layout_struct;
+mod planned_partition_struct;
@@ -0,0 +1,85 @@
+// Copyright (c) 2023 Nick Piaddo
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// From dependency library
+
+// From standard library
+
+// From this library
+use crate::core::errors::PartitionBuilderError;
+use crate::core::partition::Partition;
+use crate::core::partition::PartitionKind;
+
+/// Position and size of a partition, computed by resolving a
+/// [`Layout`](crate::core::layout::Layout) against a device's geometry.
+#[derive(Clone, Debug)]
+pub struct PlannedPartition {
+    partition_type: Option<PartitionKind>,
+    name: Option<String>,
+    starting_sector: u64,
+    size_in_sectors: u64,
+}
+
+impl PlannedPartition {
+    #[doc(hidden)]
+    /// Creates a new `PlannedPartition`.
+    pub(crate) fn new(
+        partition_type: Option<PartitionKind>,
+        name: Option<String>,
+        starting_sector: u64,
+        size_in_sectors: u64,
+    ) -> PlannedPartition {
+        Self {
+            partition_type,
+            name,
+            starting_sector,
+            size_in_sectors,
+        }
+    }
+
+    #[doc(hidden)]
+    /// Converts this `PlannedPartition` to a [`Partition`] template.
+    pub(crate) fn to_partition(&self) -> Result<Partition, PartitionBuilderError> {
+        // Sizes are already computed, prevent `libfdisk` from aligning them again.
+        let mut partition = Partition::builder()
+            .ask_size_interactive()
+            .starting_sector(self.starting_sector)
+            .size_in_sectors(self.size_in_sectors)
+            .build()?;
+
+        if let Some(partition_type) = self.partition_type.clone() {
+            partition.set_partition_type(partition_type)?;
+        }
+
+        if let Some(name) = self.name.clone() {
+            partition.set_name(name)?;
+        }
+
+        Ok(partition)
+    }
+
+    /// Returns the partition's type.
+    pub fn partition_type(&self) -> Option<&PartitionKind> {
+        self.partition_type.as_ref()
+    }
+
+    /// Returns the partition's name.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the partition's first sector.
+    pub fn starting_sector(&self) -> u64 {
+        self.starting_sector
+    }
+
+    /// Returns the partition's last sector.
+    pub fn ending_sector(&self) -> u64 {
+        self.starting_sector + self.size_in_sectors - 1
+    }
+
+    /// Returns the partition's size in sectors.
+    pub fn size_in_sectors(&self) -> u64 {
+        self.size_in_sectors
+    }
+}
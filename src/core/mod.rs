@@ -11,6 +11,7 @@
 
 pub mod errors;
 pub mod iter;
+pub mod layout;
 pub(crate) mod macros;
 pub mod partition;
 pub mod partition_table;
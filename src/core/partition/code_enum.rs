@@ -4,6 +4,8 @@
 // From dependency library
 use enum_iterator::Sequence;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 // From standard library
 use std::fmt;
@@ -12,6 +14,7 @@
 // From this library
 use crate::core::errors::ConversionError;
 use crate::core::errors::ParserError;
+use crate::core::partition::Guid;
 
 /// Supported `MBR` partitions.
 #[derive(
@@ -436,6 +439,58 @@ pub fn as_str(&self) -> &str {
     pub fn to_u32(&self) -> u32 {
         *self as u8 as u32
     }
+
+    /// Returns the `GPT` partition type matching this `MBR` partition type, when converting a
+    /// `DOS` partition table to `GPT`.
+    ///
+    /// `MBR` types without a `GPT` counterpart are mapped to [`Guid::LinuxData`]. Returns `None`
+    /// for [`Code::EmptyPartition`], extended partitions, and protective partitions, none of
+    /// which hold data.
+    pub fn to_guid(&self) -> Option<Guid> {
+        match self {
+            Self::EmptyPartition
+            | Self::ExtendedPartition
+            | Self::W95ExtendedLBA
+            | Self::LinuxExtended
+            | Self::GPTProtectiveMBR
+            | Self::EBBRProtective => None,
+            Self::FAT12
+            | Self::FAT16
+            | Self::FAT16B
+            | Self::HPFSNTFSExfat
+            | Self::W95FAT32
+            | Self::W95FAT32LBA
+            | Self::W95FAT16LBA
+            | Self::HiddenFAT12
+            | Self::HiddenFAT16
+            | Self::HiddenFAT16B
+            | Self::HiddenHPFSNTFSExFat
+            | Self::HiddenW95FAT32
+            | Self::HiddenW95FAT32LBA
+            | Self::HiddenW95FAT16LBA => Some(Guid::WindowsBasicData),
+            Self::HiddenNTFSRescue => Some(Guid::WindowsRecovery),
+            Self::PPCPrepBoot => Some(Guid::PPCPrePBoot),
+            Self::OldMinix | Self::MinixOldLinux => Some(Guid::Minix),
+            Self::LinuxSwap => Some(Guid::LinuxSwap),
+            Self::LinuxLVM => Some(Guid::LinuxLVM),
+            Self::LinuxRaidAuto => Some(Guid::LinuxRaid),
+            Self::FreeBSD => Some(Guid::FreeBSDDisklabel),
+            Self::OpenBSD => Some(Guid::OpenBSDData),
+            Self::NetBSD => Some(Guid::NetBSDFFS),
+            Self::DarwinUFS => Some(Guid::MacOSUFS),
+            Self::DarwinBoot => Some(Guid::MacOSBootRecovery),
+            Self::HFSHFSPlus => Some(Guid::MacOSHFSPlus),
+            Self::SolarisBoot => Some(Guid::SolarisBoot),
+            Self::Solaris => Some(Guid::SolarisRoot),
+            Self::BeOSBFS => Some(Guid::HaikuBFS),
+            Self::Plan9 => Some(Guid::Plan9),
+            Self::FreedesktopBoot => Some(Guid::LinuxXBOOTLDR),
+            Self::EfiSystem => Some(Guid::EfiSystem),
+            Self::VMWareVMFS => Some(Guid::VMWareVMFS),
+            Self::VMWareVMKCORE => Some(Guid::VMWareVMKCORE),
+            _ => Some(Guid::LinuxData),
+        }
+    }
 }
 
 impl AsRef<Code> for Code {
@@ -527,6 +582,28 @@ fn from_str(s: &str) -> Result<Self, Self::Err> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl Serialize for Code {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Code {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+
+        Code::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
 #[cfg(test)]
 #[allow(unused_imports)]
 mod tests {
@@ -1096,4 +1173,19 @@ fn code_can_parse_a_valid_device_code() -> crate::Result<()> {
 
         Ok(())
     }
+
+    #[test]
+    fn code_can_map_mbr_types_to_gpt_types() {
+        let actual = Code::W95FAT32LBA.to_guid();
+        let expected = Some(Guid::WindowsBasicData);
+        assert_eq!(actual, expected);
+
+        let actual = Code::Linux.to_guid();
+        let expected = Some(Guid::LinuxData);
+        assert_eq!(actual, expected);
+
+        let actual = Code::W95ExtendedLBA.to_guid();
+        let expected = None;
+        assert_eq!(actual, expected);
+    }
 }
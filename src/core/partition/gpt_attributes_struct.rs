@@ -0,0 +1,308 @@
+// Copyright (c) 2023 Nick Piaddo
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// From dependency library
+use bitflags::bitflags;
+
+// From standard library
+
+// From this library
+use crate::core::errors::ConversionError;
+
+bitflags! {
+    /// `GPT` Partition Entry attributes.
+    ///
+    /// | Bits       | Name                 |
+    /// |----        |----                  |
+    /// | Bit 0      | Required Partition   |
+    /// | Bit 1      | No Block IO Protocol |
+    /// | Bit 2      | Legacy BIOS Bootable |
+    /// | Bits 3-47  | Reserved, must be zero |
+    /// | Bits 48-63 | Reserved for GUID specific use |
+    ///
+    /// The meaning of bits 48 to 63 depends on the partition type. `GptAttributes` provides
+    /// helper methods for well-known uses, like the boot priority of ChromeOS kernel partitions,
+    /// or the read-only flag of Microsoft basic data partitions.
+    ///
+    /// Source: UEFI 2.10 specs, <cite>[Defined GPT Partition Entry - Attributes](https://uefi.org/specs/UEFI/2.10/05_GUID_Partition_Table_Format.html#defined-gpt-partition-entry-attributes)</cite>
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use rsfdisk::core::partition::GptAttributes;
+    ///
+    /// fn main() -> rsfdisk::Result<()> {
+    ///     let mut attributes = GptAttributes::REQUIRED_PARTITION;
+    ///     attributes.set_chromeos_priority(15)?;
+    ///     attributes.set_chromeos_successful(true);
+    ///
+    ///     let actual = attributes.bits();
+    ///     let expected = 0x010F_0000_0000_0001;
+    ///     assert_eq!(actual, expected);
+    ///
+    ///     let actual = attributes.chromeos_priority();
+    ///     let expected = 15;
+    ///     assert_eq!(actual, expected);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+    pub struct GptAttributes: u64 {
+        /// `Bit 0`: the partition is required for the platform to function.
+        const REQUIRED_PARTITION = 1 << 0;
+
+        /// `Bit 1`: the firmware must not produce an `EFI_BLOCK_IO_PROTOCOL` device for this
+        /// partition.
+        const NO_BLOCK_IO_PROTOCOL = 1 << 1;
+
+        /// `Bit 2`: the partition may be bootable by legacy BIOS firmware.
+        const LEGACY_BIOS_BOOTABLE = 1 << 2;
+
+        /// `Bit 48`, reserved for GUID specific use.
+        const GUID_BIT_48 = 1 << 48;
+        /// `Bit 49`, reserved for GUID specific use.
+        const GUID_BIT_49 = 1 << 49;
+        /// `Bit 50`, reserved for GUID specific use.
+        const GUID_BIT_50 = 1 << 50;
+        /// `Bit 51`, reserved for GUID specific use.
+        const GUID_BIT_51 = 1 << 51;
+        /// `Bit 52`, reserved for GUID specific use.
+        const GUID_BIT_52 = 1 << 52;
+        /// `Bit 53`, reserved for GUID specific use.
+        const GUID_BIT_53 = 1 << 53;
+        /// `Bit 54`, reserved for GUID specific use.
+        const GUID_BIT_54 = 1 << 54;
+        /// `Bit 55`, reserved for GUID specific use.
+        const GUID_BIT_55 = 1 << 55;
+        /// `Bit 56`, reserved for GUID specific use.
+        const GUID_BIT_56 = 1 << 56;
+        /// `Bit 57`, reserved for GUID specific use.
+        const GUID_BIT_57 = 1 << 57;
+        /// `Bit 58`, reserved for GUID specific use.
+        const GUID_BIT_58 = 1 << 58;
+        /// `Bit 59`, reserved for GUID specific use.
+        const GUID_BIT_59 = 1 << 59;
+        /// `Bit 60`, reserved for GUID specific use.
+        const GUID_BIT_60 = 1 << 60;
+        /// `Bit 61`, reserved for GUID specific use.
+        const GUID_BIT_61 = 1 << 61;
+        /// `Bit 62`, reserved for GUID specific use.
+        const GUID_BIT_62 = 1 << 62;
+        /// `Bit 63`, reserved for GUID specific use.
+        const GUID_BIT_63 = 1 << 63;
+    }
+}
+
+impl GptAttributes {
+    /// Bits 3 to 47, reserved for future versions of the UEFI specification.
+    pub const RESERVED_BITS: u64 = 0x0000_FFFF_FFFF_FFF8;
+
+    /// Position of the first GUID specific bit.
+    const GUID_SPECIFIC_SHIFT: u32 = 48;
+
+    /// Position of the ChromeOS kernel partition priority (4 bits).
+    const CHROMEOS_PRIORITY_SHIFT: u32 = 48;
+
+    /// Position of the ChromeOS kernel partition remaining boot tries counter (4 bits).
+    const CHROMEOS_TRIES_SHIFT: u32 = 52;
+
+    /// Returns `true` if any of the bits reserved for future versions of the UEFI
+    /// specification is set.
+    pub fn has_reserved_bits(&self) -> bool {
+        self.bits() & Self::RESERVED_BITS != 0
+    }
+
+    /// Returns the value of the GUID specific bits 48 to 63.
+    pub fn guid_specific_bits(&self) -> u16 {
+        (self.bits() >> Self::GUID_SPECIFIC_SHIFT) as u16
+    }
+
+    /// Replaces the GUID specific bits 48 to 63 by `bits`.
+    pub fn set_guid_specific_bits(&mut self, bits: u16) {
+        self.set_field(Self::GUID_SPECIFIC_SHIFT, 0xFFFF, u64::from(bits));
+    }
+
+    #[doc(hidden)]
+    /// Returns the value of a `mask` wide field starting at bit `shift`.
+    fn field(&self, shift: u32, mask: u64) -> u64 {
+        (self.bits() >> shift) & mask
+    }
+
+    #[doc(hidden)]
+    /// Replaces the content of a `mask` wide field starting at bit `shift`.
+    fn set_field(&mut self, shift: u32, mask: u64, value: u64) {
+        let bits = (self.bits() & !(mask << shift)) | ((value & mask) << shift);
+        *self = Self::from_bits_retain(bits);
+    }
+
+    #[doc(hidden)]
+    /// Replaces the content of a 4-bit field starting at bit `shift`.
+    fn set_nibble(
+        &mut self,
+        field_name: &str,
+        shift: u32,
+        value: u8,
+    ) -> Result<(), ConversionError> {
+        if value > 0xF {
+            let err_msg = format!("{} must be between 0 and 15, got: {}", field_name, value);
+
+            return Err(ConversionError::GptAttributes(err_msg));
+        }
+
+        self.set_field(shift, 0xF, u64::from(value));
+
+        Ok(())
+    }
+
+    /// Returns the boot priority of a ChromeOS kernel partition (bits 48-51), from 0 (not
+    /// bootable) to 15 (highest priority).
+    pub fn chromeos_priority(&self) -> u8 {
+        self.field(Self::CHROMEOS_PRIORITY_SHIFT, 0xF) as u8
+    }
+
+    /// Sets the boot priority of a ChromeOS kernel partition (bits 48-51). `priority` must be
+    /// between 0 and 15.
+    pub fn set_chromeos_priority(&mut self, priority: u8) -> Result<(), ConversionError> {
+        self.set_nibble("ChromeOS priority", Self::CHROMEOS_PRIORITY_SHIFT, priority)
+    }
+
+    /// Returns the number of boot attempts left for a ChromeOS kernel partition (bits 52-55).
+    pub fn chromeos_tries(&self) -> u8 {
+        self.field(Self::CHROMEOS_TRIES_SHIFT, 0xF) as u8
+    }
+
+    /// Sets the number of boot attempts left for a ChromeOS kernel partition (bits 52-55).
+    /// `tries` must be between 0 and 15.
+    pub fn set_chromeos_tries(&mut self, tries: u8) -> Result<(), ConversionError> {
+        self.set_nibble("ChromeOS tries", Self::CHROMEOS_TRIES_SHIFT, tries)
+    }
+
+    /// Returns `true` if a ChromeOS kernel partition booted successfully (bit 56).
+    pub fn chromeos_successful(&self) -> bool {
+        self.contains(Self::GUID_BIT_56)
+    }
+
+    /// Marks a ChromeOS kernel partition as having booted successfully (bit 56).
+    pub fn set_chromeos_successful(&mut self, successful: bool) {
+        self.set(Self::GUID_BIT_56, successful)
+    }
+
+    /// Returns `true` if a Microsoft basic data partition is read-only (bit 60).
+    pub fn microsoft_read_only(&self) -> bool {
+        self.contains(Self::GUID_BIT_60)
+    }
+
+    /// Sets the read-only flag of a Microsoft basic data partition (bit 60).
+    pub fn set_microsoft_read_only(&mut self, read_only: bool) {
+        self.set(Self::GUID_BIT_60, read_only)
+    }
+
+    /// Returns `true` if a Microsoft basic data partition is a shadow copy of another partition
+    /// (bit 61).
+    pub fn microsoft_shadow_copy(&self) -> bool {
+        self.contains(Self::GUID_BIT_61)
+    }
+
+    /// Sets the shadow copy flag of a Microsoft basic data partition (bit 61).
+    pub fn set_microsoft_shadow_copy(&mut self, shadow_copy: bool) {
+        self.set(Self::GUID_BIT_61, shadow_copy)
+    }
+
+    /// Returns `true` if a Microsoft basic data partition is hidden (bit 62).
+    pub fn microsoft_hidden(&self) -> bool {
+        self.contains(Self::GUID_BIT_62)
+    }
+
+    /// Sets the hidden flag of a Microsoft basic data partition (bit 62).
+    pub fn set_microsoft_hidden(&mut self, hidden: bool) {
+        self.set(Self::GUID_BIT_62, hidden)
+    }
+
+    /// Returns `true` if a Microsoft basic data partition should not get a drive letter
+    /// assigned automatically (bit 63).
+    pub fn microsoft_no_automount(&self) -> bool {
+        self.contains(Self::GUID_BIT_63)
+    }
+
+    /// Sets the no automount flag of a Microsoft basic data partition (bit 63).
+    pub fn set_microsoft_no_automount(&mut self, no_automount: bool) {
+        self.set(Self::GUID_BIT_63, no_automount)
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::*;
+    use pretty_assertions::{assert_eq, assert_ne};
+
+    #[test]
+    fn gpt_attributes_can_set_chromeos_fields() -> crate::Result<()> {
+        let mut attributes = GptAttributes::empty();
+        attributes.set_chromeos_priority(2)?;
+        attributes.set_chromeos_tries(5)?;
+        attributes.set_chromeos_successful(true);
+
+        let actual = attributes.bits();
+        let expected = 0x0152_0000_0000_0000;
+        assert_eq!(actual, expected);
+
+        attributes.set_chromeos_priority(0)?;
+
+        let actual = attributes.chromeos_priority();
+        let expected = 0;
+        assert_eq!(actual, expected);
+
+        let actual = attributes.chromeos_tries();
+        let expected = 5;
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn gpt_attributes_can_not_set_a_chromeos_priority_greater_than_15() {
+        let mut attributes = GptAttributes::empty();
+
+        let actual = attributes.set_chromeos_priority(16);
+        assert!(actual.is_err());
+    }
+
+    #[test]
+    fn gpt_attributes_can_set_microsoft_flags() {
+        let mut attributes = GptAttributes::empty();
+        attributes.set_microsoft_read_only(true);
+        attributes.set_microsoft_hidden(true);
+        attributes.set_microsoft_no_automount(true);
+
+        let actual = attributes;
+        let expected =
+            GptAttributes::GUID_BIT_60 | GptAttributes::GUID_BIT_62 | GptAttributes::GUID_BIT_63;
+        assert_eq!(actual, expected);
+
+        let actual = attributes.guid_specific_bits();
+        let expected = 0xD000;
+        assert_eq!(actual, expected);
+
+        attributes.set_microsoft_hidden(false);
+
+        let actual = attributes.microsoft_hidden();
+        let expected = false;
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn gpt_attributes_can_detect_reserved_bits() {
+        let attributes = GptAttributes::from_bits_retain(1 << 3);
+
+        let actual = attributes.has_reserved_bits();
+        let expected = true;
+        assert_eq!(actual, expected);
+
+        let actual = GptAttributes::all().has_reserved_bits();
+        let expected = false;
+        assert_eq!(actual, expected);
+    }
+}
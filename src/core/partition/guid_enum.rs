@@ -3,6 +3,8 @@
 
 // From dependency library
 use enum_iterator::Sequence;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 // From standard library
 use std::ffi::CString;
@@ -12,6 +14,7 @@
 // From this library
 use crate::core::errors::ConversionError;
 use crate::core::errors::ParserError;
+use crate::core::partition::Code;
 
 /// Supported `GPT` partitions.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Sequence)]
@@ -974,6 +977,57 @@ pub fn to_c_string(&self) -> CString {
         // unwrap the new CString.
         CString::new(self.as_str()).unwrap()
     }
+
+    /// Returns the `MBR` partition type matching this `GPT` partition type, when converting a
+    /// `GPT` partition table to `DOS`.
+    ///
+    /// `GPT` types without an `MBR` counterpart are mapped to [`Code::Linux`]. Returns `None` for
+    /// [`Guid::EmptyPartition`].
+    pub fn to_code(&self) -> Option<Code> {
+        match self {
+            Self::EmptyPartition => None,
+            Self::EfiSystem => Some(Code::EfiSystem),
+            Self::MicrosoftReserved | Self::WindowsBasicData => Some(Code::HPFSNTFSExfat),
+            Self::WindowsLDM | Self::WindowsLDMData => Some(Code::Sfs),
+            Self::WindowsRecovery => Some(Code::HiddenNTFSRescue),
+            Self::LinuxSwap => Some(Code::LinuxSwap),
+            Self::LinuxLVM => Some(Code::LinuxLVM),
+            Self::LinuxRaid => Some(Code::LinuxRaidAuto),
+            Self::LinuxXBOOTLDR => Some(Code::FreedesktopBoot),
+            Self::FreeBSDBoot
+            | Self::FreeBSDDisklabel
+            | Self::FreeBSDSwap
+            | Self::FreeBSDUFS
+            | Self::FreeBSDVinum
+            | Self::FreeBSDZFS
+            | Self::FreeBSDnandfs => Some(Code::FreeBSD),
+            Self::OpenBSDData => Some(Code::OpenBSD),
+            Self::NetBSDSwap
+            | Self::NetBSDFFS
+            | Self::NetBSDLFS
+            | Self::NetBSDRAID
+            | Self::NetBSDConcatenated
+            | Self::NetBSDEncrypted => Some(Code::NetBSD),
+            Self::MacOSHFSPlus | Self::MacOSAPFS => Some(Code::HFSHFSPlus),
+            Self::MacOSUFS => Some(Code::DarwinUFS),
+            Self::MacOSBootRecovery => Some(Code::DarwinBoot),
+            Self::SolarisBoot => Some(Code::SolarisBoot),
+            Self::SolarisRoot
+            | Self::SolarisSwap
+            | Self::SolarisBackup
+            | Self::SolarisUsr
+            | Self::SolarisVar
+            | Self::SolarisHome
+            | Self::SolarisAlternateSector => Some(Code::Solaris),
+            Self::HaikuBFS => Some(Code::BeOSBFS),
+            Self::Plan9 => Some(Code::Plan9),
+            Self::PPCPrePBoot => Some(Code::PPCPrepBoot),
+            Self::Minix => Some(Code::MinixOldLinux),
+            Self::VMWareVMFS => Some(Code::VMWareVMFS),
+            Self::VMWareVMKCORE => Some(Code::VMWareVMKCORE),
+            _ => Some(Code::Linux),
+        }
+    }
 }
 
 impl AsRef<Guid> for Guid {
@@ -1355,6 +1409,28 @@ fn from_str(s: &str) -> Result<Self, Self::Err> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl Serialize for Guid {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Guid {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+
+        Guid::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
 #[cfg(test)]
 #[allow(unused_imports)]
 mod tests {
@@ -2886,4 +2962,19 @@ fn guid_can_parse_a_valid_guid() -> crate::Result<()> {
 
         Ok(())
     }
+
+    #[test]
+    fn guid_can_map_gpt_types_to_mbr_types() {
+        let actual = Guid::WindowsBasicData.to_code();
+        let expected = Some(Code::HPFSNTFSExfat);
+        assert_eq!(actual, expected);
+
+        let actual = Guid::LinuxRootx86_64.to_code();
+        let expected = Some(Code::Linux);
+        assert_eq!(actual, expected);
+
+        let actual = Guid::EmptyPartition.to_code();
+        let expected = None;
+        assert_eq!(actual, expected);
+    }
 }
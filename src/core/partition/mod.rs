@@ -11,14 +11,17 @@
 pub use bit_flag_enum::BitFlag;
 pub use code_enum::Code;
 pub use dos_flag_enum::DOSFlag;
+pub use gpt_attributes_struct::GptAttributes;
 pub use gpt_flag_enum::GPTFlag;
 pub use guid_enum::Guid;
 pub(crate) use partition_builder_struct::PartBuilder;
 pub use partition_builder_struct::PartitionBuilder;
+pub use partition_data_struct::PartitionData;
 pub use partition_iter_mut_struct::PartitionIterMut;
 pub use partition_iter_struct::PartitionIter;
 pub(crate) use partition_kind_builder_struct::PartTypeBuilder;
 pub use partition_kind_builder_struct::PartitionKindBuilder;
+pub use partition_kind_data_struct::PartitionKindData;
 pub use partition_kind_struct::PartitionKind;
 pub use partition_list_struct::PartitionList;
 pub use partition_struct::Partition;
@@ -27,12 +30,15 @@
 mod bit_flag_enum;
 mod code_enum;
 mod dos_flag_enum;
+mod gpt_attributes_struct;
 mod gpt_flag_enum;
 mod guid_enum;
 mod partition_builder_struct;
+mod partition_data_struct;
 mod partition_iter_mut_struct;
 mod partition_iter_struct;
 mod partition_kind_builder_struct;
+mod partition_kind_data_struct;
 mod partition_kind_struct;
 mod partition_list_struct;
 mod partition_struct;
@@ -0,0 +1,161 @@
+// Copyright (c) 2023 Nick Piaddo
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// From dependency library
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// From standard library
+
+// From this library
+use crate::core::errors::ConversionError;
+use crate::core::partition::Partition;
+use crate::core::partition::PartitionKind;
+use crate::core::partition::PartitionKindData;
+
+/// Owned, plain-data view of a [`Partition`].
+///
+/// When converting a `PartitionData` to a [`Partition`], unset `number`, `starting_sector`, and
+/// `size_in_sectors` fields respectively default to the first free partition number, the first
+/// free sector, and all the free space up to the last free sector.
+///
+/// # Examples
+///
+/// ```
+/// # use pretty_assertions::assert_eq;
+/// use rsfdisk::core::partition::Partition;
+/// use rsfdisk::core::partition::PartitionData;
+/// use rsfdisk::core::partition::PartitionKindData;
+///
+/// fn main() -> rsfdisk::Result<()> {
+///     let data = PartitionData {
+///         number: Some(0),
+///         starting_sector: Some(2048),
+///         size_in_sectors: Some(4096),
+///         partition_type: Some(PartitionKindData {
+///             guid: Some("0FC63DAF-8483-4772-8E79-3D69D8477DE4".to_owned()),
+///             ..Default::default()
+///         }),
+///         name: Some("Data".to_owned()),
+///         ..Default::default()
+///     };
+///
+///     let partition = Partition::try_from(&data)?;
+///
+///     let actual = partition.starting_sector();
+///     let expected = Some(2048);
+///     assert_eq!(actual, expected);
+///
+///     let actual = partition.name();
+///     let expected = Some("Data");
+///     assert_eq!(actual, expected);
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default))]
+pub struct PartitionData {
+    /// Partition identification number.
+    pub number: Option<usize>,
+
+    /// Address of the partition's first sector.
+    pub starting_sector: Option<u64>,
+
+    /// Size of the partition in sectors.
+    pub size_in_sectors: Option<u64>,
+
+    /// Partition type.
+    pub partition_type: Option<PartitionKindData>,
+
+    /// Partition name.
+    pub name: Option<String>,
+
+    /// Partition UUID.
+    pub uuid: Option<String>,
+
+    /// Partition attributes (e.g. `RequiredPartition GUID:63` for a GPT partition).
+    pub attributes: Option<String>,
+
+    /// Bootable flag (read-only, ignored when converting to a [`Partition`]).
+    pub bootable: bool,
+}
+
+impl From<&Partition> for PartitionData {
+    fn from(partition: &Partition) -> PartitionData {
+        let attributes = partition
+            .attribute_bits()
+            .map(|bits| String::from_utf8_lossy(&bits).into_owned())
+            .filter(|attrs| !attrs.is_empty());
+
+        Self {
+            number: partition.number(),
+            starting_sector: partition.starting_sector(),
+            size_in_sectors: partition.size_in_sectors(),
+            partition_type: partition
+                .partition_type()
+                .map(|kind| PartitionKindData::from(&kind)),
+            name: partition.name().map(String::from),
+            uuid: partition.uuid().map(String::from),
+            attributes,
+            bootable: partition.is_bootable(),
+        }
+    }
+}
+
+impl TryFrom<&PartitionData> for Partition {
+    type Error = ConversionError;
+
+    fn try_from(data: &PartitionData) -> Result<Self, Self::Error> {
+        let to_conversion_error = |e: crate::core::errors::PartitionError| {
+            let err_msg = format!("failed to create partition. {}", e);
+
+            ConversionError::Partition(err_msg)
+        };
+
+        let mut partition = Partition::new().map_err(to_conversion_error)?;
+
+        if let Some(data_type) = data.partition_type.as_ref() {
+            let partition_type = PartitionKind::try_from(data_type)?;
+            partition
+                .set_partition_type(partition_type)
+                .map_err(to_conversion_error)?;
+        }
+
+        match data.number {
+            Some(number) => partition.set_partition_number(number),
+            None => partition.use_first_free_partition_number(true),
+        }
+        .map_err(to_conversion_error)?;
+
+        match data.starting_sector {
+            Some(starting_sector) => partition.set_starting_sector(starting_sector),
+            None => partition.use_first_free_starting_sector(true),
+        }
+        .map_err(to_conversion_error)?;
+
+        match data.size_in_sectors {
+            Some(size) => partition
+                .use_last_free_ending_sector(false)
+                .and_then(|_| partition.set_size_in_sectors(size)),
+            None => partition.use_last_free_ending_sector(true),
+        }
+        .map_err(to_conversion_error)?;
+
+        if let Some(name) = data.name.clone() {
+            partition.set_name(name).map_err(to_conversion_error)?;
+        }
+
+        if let Some(uuid) = data.uuid.clone() {
+            partition.set_uuid(uuid).map_err(to_conversion_error)?;
+        }
+
+        if let Some(attributes) = data.attributes.clone() {
+            partition
+                .set_attribute_bits(attributes.into_bytes())
+                .map_err(to_conversion_error)?;
+        }
+
+        Ok(partition)
+    }
+}
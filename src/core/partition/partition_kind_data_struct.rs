@@ -0,0 +1,81 @@
+// Copyright (c) 2023 Nick Piaddo
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// From dependency library
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// From standard library
+use std::str::FromStr;
+
+// From this library
+use crate::core::errors::ConversionError;
+use crate::core::partition::Code;
+use crate::core::partition::Guid;
+use crate::core::partition::PartitionKind;
+
+/// Owned, plain-data view of a [`PartitionKind`].
+///
+/// Exactly one of `code` or `guid` is expected to be set, respectively for `MBR` and `GPT`
+/// partition types.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default))]
+pub struct PartitionKindData {
+    /// `MBR` partition type code.
+    pub code: Option<u32>,
+
+    /// `GPT` partition type GUID.
+    pub guid: Option<String>,
+
+    /// Human readable name of the partition type.
+    pub name: Option<String>,
+}
+
+impl From<&PartitionKind> for PartitionKindData {
+    fn from(kind: &PartitionKind) -> PartitionKindData {
+        let guid = kind.guid().map(String::from);
+        // `libfdisk` sets the code of GPT partition types to 0.
+        let code = if guid.is_some() { None } else { kind.code() };
+
+        Self {
+            code,
+            guid,
+            name: kind.name().map(String::from),
+        }
+    }
+}
+
+impl TryFrom<&PartitionKindData> for PartitionKind {
+    type Error = ConversionError;
+
+    fn try_from(data: &PartitionKindData) -> Result<Self, Self::Error> {
+        let name = data.name.clone().unwrap_or_default();
+
+        let result = match (data.code, data.guid.as_deref()) {
+            (Some(code), None) => match u8::try_from(code).ok().map(Code::try_from) {
+                Some(Ok(code)) => PartitionKind::builder().code(code).build(),
+                _ => PartitionKind::builder().unknown_kind(code, name).build(),
+            },
+            (None, Some(guid)) => match Guid::from_str(guid) {
+                Ok(guid) => PartitionKind::builder().guid(guid).build(),
+                Err(_) => PartitionKind::builder().unknown_kind(0, guid).build(),
+            },
+            (None, None) => {
+                let err_msg = "missing partition type code or GUID".to_owned();
+
+                return Err(ConversionError::PartitionKind(err_msg));
+            }
+            (Some(_), Some(_)) => {
+                let err_msg = "partition type code and GUID are mutually exclusive".to_owned();
+
+                return Err(ConversionError::PartitionKind(err_msg));
+            }
+        };
+
+        result.map_err(|e| {
+            let err_msg = format!("failed to create partition type. {}", e);
+
+            ConversionError::PartitionKind(err_msg)
+        })
+    }
+}
@@ -2,6 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 // From dependency library
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 // From standard library
 use std::mem::MaybeUninit;
@@ -12,6 +14,8 @@
 use crate::core::partition::Guid;
 use crate::core::partition::PartTypeBuilder;
 use crate::core::partition::PartitionKindBuilder;
+#[cfg(feature = "serde")]
+use crate::core::partition::PartitionKindData;
 
 use crate::ffi_utils;
 
@@ -420,6 +424,28 @@ fn drop(&mut self) {
     }
 }
 
+#[cfg(feature = "serde")]
+impl Serialize for PartitionKind {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        PartitionKindData::from(self).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for PartitionKind {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let data = PartitionKindData::deserialize(deserializer)?;
+
+        PartitionKind::try_from(&data).map_err(serde::de::Error::custom)
+    }
+}
+
 #[cfg(test)]
 #[allow(unused_imports)]
 mod tests {
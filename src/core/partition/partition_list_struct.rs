@@ -2,6 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 // From dependency library
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 // From standard library
 use std::mem::MaybeUninit;
@@ -11,6 +13,8 @@
 // From this library
 use crate::core::errors::PartitionListError;
 use crate::core::partition::Partition;
+#[cfg(feature = "serde")]
+use crate::core::partition::PartitionData;
 use crate::core::partition::PartitionIter;
 use crate::core::partition::PartitionIterMut;
 use crate::owning_mut_from_ptr;
@@ -528,6 +532,34 @@ fn drop(&mut self) {
     }
 }
 
+#[cfg(feature = "serde")]
+impl Serialize for PartitionList {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(self.iter().map(PartitionData::from))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for PartitionList {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let entries = Vec::<PartitionData>::deserialize(deserializer)?;
+        let mut list = PartitionList::new().map_err(serde::de::Error::custom)?;
+
+        for data in entries.iter() {
+            let partition = Partition::try_from(data).map_err(serde::de::Error::custom)?;
+            list.push(partition).map_err(serde::de::Error::custom)?;
+        }
+
+        Ok(list)
+    }
+}
+
 #[cfg(test)]
 #[allow(unused_imports)]
 mod tests {
@@ -1256,4 +1288,42 @@ fn partition_list_can_mutably_index_into_a_list() -> crate::Result<()> {
 
         Ok(())
     }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn partition_list_can_be_serialized_to_json_and_back() -> crate::Result<()> {
+        let partition_kind = PartitionKind::builder().guid(Guid::EfiSystem).build()?;
+        let partition1 = Partition::builder()
+            .partition_type(partition_kind)
+            .name("EFI System")
+            .number(1)
+            .starting_sector(2048)
+            .size_in_sectors(1024)
+            .build()?;
+        let partition2 = Partition::builder()
+            .number(2)
+            .starting_sector(4096)
+            .build()?;
+
+        let mut list = PartitionList::new()?;
+        list.push(partition1)?;
+        list.push(partition2)?;
+
+        let json = serde_json::to_string(&list).unwrap();
+        let copy: PartitionList = serde_json::from_str(&json).unwrap();
+
+        let actual = copy.len();
+        let expected = 2;
+        assert_eq!(actual, expected);
+
+        let actual = copy.get(0).and_then(|p| p.name().map(String::from));
+        let expected = Some(String::from("EFI System"));
+        assert_eq!(actual, expected);
+
+        let actual = copy.get(1).and_then(|p| p.starting_sector());
+        let expected = Some(4096);
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
 }
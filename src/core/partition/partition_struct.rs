@@ -2,6 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 // From dependency library
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 // From standard library
 use std::cmp::Ordering;
@@ -9,6 +11,8 @@
 
 // From this library
 use crate::core::errors::PartitionError;
+#[cfg(feature = "serde")]
+use crate::core::partition::PartitionData;
 
 use crate::core::partition::PartBuilder;
 use crate::core::partition::PartitionBuilder;
@@ -981,11 +985,35 @@ fn drop(&mut self) {
     }
 }
 
+#[cfg(feature = "serde")]
+impl Serialize for Partition {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        PartitionData::from(self).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Partition {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let data = PartitionData::deserialize(deserializer)?;
+
+        Partition::try_from(&data).map_err(serde::de::Error::custom)
+    }
+}
+
 #[cfg(test)]
 #[allow(unused_imports)]
 mod tests {
     use super::*;
+    use crate::core::partition::Code;
     use crate::core::partition::Guid;
+    use crate::core::partition::PartitionData;
     use crate::core::partition::PartitionKind;
     use pretty_assertions::{assert_eq, assert_ne};
     use std::cmp::Ordering;
@@ -1237,4 +1265,62 @@ fn partition_can_unset_a_partition_starting_sector() -> crate::Result<()> {
 
         Ok(())
     }
+
+    #[test]
+    fn partition_can_convert_to_and_from_partition_data() -> crate::Result<()> {
+        let partition_kind = PartitionKind::builder().guid(Guid::LinuxData).build()?;
+        let partition = Partition::builder()
+            .partition_type(partition_kind)
+            .name("Data")
+            .number(2)
+            .starting_sector(2048)
+            .size_in_sectors(4096)
+            .build()?;
+
+        let data = PartitionData::from(&partition);
+
+        let actual = data
+            .partition_type
+            .as_ref()
+            .and_then(|kind| kind.guid.clone());
+        let expected = Some(Guid::LinuxData.to_string());
+        assert_eq!(actual, expected);
+
+        let actual = data.partition_type.as_ref().and_then(|kind| kind.code);
+        let expected = None;
+        assert_eq!(actual, expected);
+
+        let copy = Partition::try_from(&data)?;
+
+        let actual = PartitionData::from(&copy);
+        let expected = data;
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn partition_can_be_serialized_to_json_and_back() -> crate::Result<()> {
+        let partition_kind = PartitionKind::builder().code(Code::Linux).build()?;
+        let partition = Partition::builder()
+            .partition_type(partition_kind)
+            .number(0)
+            .starting_sector(2048)
+            .size_in_sectors(4096)
+            .build()?;
+
+        let json = serde_json::to_string(&partition).unwrap();
+        let copy: Partition = serde_json::from_str(&json).unwrap();
+
+        let actual = PartitionData::from(&copy);
+        let expected = PartitionData::from(&partition);
+        assert_eq!(actual, expected);
+
+        let actual = copy.partition_type().and_then(|kind| kind.code());
+        let expected = Some(0x83);
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,179 @@
+// Copyright (c) 2023 Nick Piaddo
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// From dependency library
+
+// From standard library
+
+// From this library
+use crate::core::partition_table::BsdDriveType;
+use crate::core::prompt::Answer;
+use crate::core::prompt::AnswerQueue;
+use crate::core::prompt::PromptKind;
+use crate::core::prompt::QueryMatcher;
+
+/// New values for the editable fields of a `BSD disklabel`.
+///
+/// Fields left unset keep their current value. Once applied with
+/// [`FdiskBSDExt::bsd_set_disk_label_fields`](crate::fdisk::FdiskBSDExt::bsd_set_disk_label_fields),
+/// their values can be read back with the matching `HeaderEntry::Bsd*`
+/// [`HeaderEntry`](crate::core::partition_table::HeaderEntry).
+///
+/// **Note:** `libfdisk` does not provide a way to change the drive type, pack name, or sector size
+/// of a `BSD disklabel`. When one of them is set, the disklabel is written to the assigned device,
+/// then patched in place (see
+/// [`FdiskBSDExt::bsd_set_disk_label_fields`](crate::fdisk::FdiskBSDExt::bsd_set_disk_label_fields)).
+///
+/// # Examples
+///
+/// ```
+/// use rsfdisk::core::partition_table::BsdDiskLabelFields;
+/// use rsfdisk::core::partition_table::BsdDriveType;
+///
+/// let fields = BsdDiskLabelFields::new()
+///     .drive_type(BsdDriveType::SCSI)
+///     .pack_name("data")
+///     .rpm(7200)
+///     .interleave(1)
+///     .track_skew(2)
+///     .cylinder_skew(4);
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BsdDiskLabelFields {
+    pub(crate) drive_type: Option<BsdDriveType>,
+    pub(crate) pack_name: Option<String>,
+    pub(crate) sector_size: Option<u32>,
+    sectors_per_cylinder: Option<u32>,
+    rpm: Option<u16>,
+    interleave: Option<u16>,
+    track_skew: Option<u16>,
+    cylinder_skew: Option<u16>,
+    head_switch: Option<u32>,
+    track_to_track_seek: Option<u32>,
+}
+
+impl BsdDiskLabelFields {
+    /// Creates a new `BsdDiskLabelFields`, leaving all fields unchanged.
+    pub fn new() -> BsdDiskLabelFields {
+        log::debug!("BsdDiskLabelFields::new creating a new `BsdDiskLabelFields` instance");
+
+        Self::default()
+    }
+
+    /// Sets the type of drive.
+    pub fn drive_type(mut self, drive_type: BsdDriveType) -> BsdDiskLabelFields {
+        self.drive_type = Some(drive_type);
+        self
+    }
+
+    /// Sets the pack identifier, a string of at most 16 printable ASCII characters. An empty
+    /// string clears the pack identifier.
+    pub fn pack_name<T>(mut self, name: T) -> BsdDiskLabelFields
+    where
+        T: AsRef<str>,
+    {
+        self.pack_name = Some(name.as_ref().to_owned());
+        self
+    }
+
+    /// Sets the size of a sector in bytes, a power of 2 greater than or equal to 512.
+    pub fn sector_size(mut self, size: u32) -> BsdDiskLabelFields {
+        self.sector_size = Some(size);
+        self
+    }
+
+    /// Sets the number of sectors per cylinder, which can not exceed the number of sectors per
+    /// track times the number of tracks per cylinder.
+    pub fn sectors_per_cylinder(mut self, sectors: u32) -> BsdDiskLabelFields {
+        self.sectors_per_cylinder = Some(sectors);
+        self
+    }
+
+    /// Sets the rotational speed of the disk, in revolutions per minute.
+    pub fn rpm(mut self, rpm: u16) -> BsdDiskLabelFields {
+        self.rpm = Some(rpm);
+        self
+    }
+
+    /// Sets the hardware sector interleave factor.
+    pub fn interleave(mut self, interleave: u16) -> BsdDiskLabelFields {
+        self.interleave = Some(interleave);
+        self
+    }
+
+    /// Sets the number of sectors to skip when switching to the next track (track skew).
+    pub fn track_skew(mut self, skew: u16) -> BsdDiskLabelFields {
+        self.track_skew = Some(skew);
+        self
+    }
+
+    /// Sets the number of sectors to skip when switching to the next cylinder (cylinder skew).
+    pub fn cylinder_skew(mut self, skew: u16) -> BsdDiskLabelFields {
+        self.cylinder_skew = Some(skew);
+        self
+    }
+
+    /// Sets the head switch time, in microseconds.
+    pub fn head_switch(mut self, microseconds: u32) -> BsdDiskLabelFields {
+        self.head_switch = Some(microseconds);
+        self
+    }
+
+    /// Sets the track-to-track seek time, in microseconds.
+    pub fn track_to_track_seek(mut self, microseconds: u32) -> BsdDiskLabelFields {
+        self.track_to_track_seek = Some(microseconds);
+        self
+    }
+
+    #[doc(hidden)]
+    /// Returns `true` if one of the fields `libfdisk` can not change is set.
+    pub(crate) fn patches_disk_label(&self) -> bool {
+        self.drive_type.is_some() || self.pack_name.is_some() || self.sector_size.is_some()
+    }
+
+    #[doc(hidden)]
+    /// Converts these fields into answers to the questions asked by
+    /// `libfdisk::fdisk_bsd_edit_disklabel`, in the order they are asked.
+    pub(crate) fn to_answer_queue(&self) -> AnswerQueue {
+        let answers: [(&str, Option<u64>); 7] = [
+            ("sectors/cylinder", self.sectors_per_cylinder.map(u64::from)),
+            ("rpm", self.rpm.map(u64::from)),
+            ("interleave", self.interleave.map(u64::from)),
+            ("trackskew", self.track_skew.map(u64::from)),
+            ("cylinderskew", self.cylinder_skew.map(u64::from)),
+            ("headswitch", self.head_switch.map(u64::from)),
+            (
+                "track-to-track seek",
+                self.track_to_track_seek.map(u64::from),
+            ),
+        ];
+
+        answers
+            .into_iter()
+            .fold(AnswerQueue::new(), |queue, (query, value)| {
+                let answer = value.map(Answer::Number).unwrap_or(Answer::Default);
+
+                queue.expect(
+                    PromptKind::Number,
+                    QueryMatcher::Exact(query.to_owned()),
+                    answer,
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::*;
+    use pretty_assertions::{assert_eq, assert_ne};
+
+    #[test]
+    fn bsd_disk_label_fields_answer_every_question() {
+        let fields = BsdDiskLabelFields::new().rpm(7200);
+
+        let actual = fields.to_answer_queue().remaining();
+        let expected = 7;
+        assert_eq!(actual, expected);
+    }
+}
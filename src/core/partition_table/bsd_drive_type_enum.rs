@@ -0,0 +1,37 @@
+// Copyright (c) 2023 Nick Piaddo
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// From dependency library
+
+// From standard library
+
+// From this library
+
+/// Type of drive described by a `BSD disklabel`.
+///
+/// The string in parentheses is the name `libfdisk` gives to each drive type in the
+/// [`HeaderEntry::BsdType`](crate::core::partition_table::HeaderEntry::BsdType) header entry.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u16)]
+pub enum BsdDriveType {
+    /// Unknown drive type (`unknown`).
+    Unknown = 0,
+    /// Storage Module Device (`SMD`).
+    SMD = 1,
+    /// Mass Storage Control Protocol (`MSCP`).
+    MSCP = 2,
+    /// Old DEC drive (`old DEC`).
+    DEC = 3,
+    /// Small Computer System Interface (`SCSI`).
+    SCSI = 4,
+    /// Enhanced Small Disk Interface (`ESDI`).
+    ESDI = 5,
+    /// ST-506 interface (`ST506`).
+    ST506 = 6,
+    /// Hewlett-Packard Interface Bus (`HP-IB`).
+    HPIB = 7,
+    /// Hewlett-Packard Fiber-Link (`HP-FL`).
+    HPFL = 8,
+    /// Floppy disk (`floppy`).
+    Floppy = 10,
+}
@@ -0,0 +1,146 @@
+// Copyright (c) 2023 Nick Piaddo
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// From dependency library
+
+// From standard library
+use std::fmt;
+
+// From this library
+use crate::core::partition_table::PartitionTableKind;
+
+/// Issue preventing the conversion of a partition table to another type (see
+/// [`Fdisk::partition_table_check_gpt_conversion`](crate::fdisk::Fdisk::partition_table_check_gpt_conversion)
+/// and
+/// [`Fdisk::partition_table_check_dos_conversion`](crate::fdisk::Fdisk::partition_table_check_dos_conversion)).
+///
+/// Partition numbers are zero-based, as in the rest of this library's API.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ConversionBlocker {
+    /// A partition ends after the last sector a `DOS` partition table can address (i.e. beyond
+    /// 2 TiB on a device with 512-byte sectors).
+    BeyondDosLimit {
+        partition_number: usize,
+        ending_sector: u64,
+        last_addressable_lba: u64,
+    },
+
+    /// A partition starts before the first sector usable once the primary `GPT` header and
+    /// Partition Entry Array are written at the beginning of the device.
+    NoRoomForPrimaryGpt {
+        partition_number: usize,
+        starting_sector: u64,
+        first_usable_lba: u64,
+    },
+
+    /// A partition ends after the last sector usable once the backup `GPT` header and Partition
+    /// Entry Array are written at the end of the device.
+    NoRoomForBackupGpt {
+        partition_number: usize,
+        ending_sector: u64,
+        last_usable_lba: u64,
+    },
+
+    /// A partition that would become a logical partition is not preceded by a free sector to
+    /// hold its Extended Boot Record (EBR).
+    NoRoomForEbr {
+        partition_number: usize,
+        starting_sector: u64,
+    },
+
+    /// A partition is a protective partition, left by a `GPT` partition table.
+    ProtectivePartition { partition_number: usize },
+
+    /// The partition table has more partitions than the target partition table can hold.
+    TooManyPartitions { count: usize, max: usize },
+
+    /// The partition table to convert is not of the expected type.
+    UnsupportedPartitionTable {
+        expected: PartitionTableKind,
+        found: Option<PartitionTableKind>,
+    },
+}
+
+impl ConversionBlocker {
+    /// Returns the number of the partition preventing the conversion, if the issue concerns a
+    /// specific partition.
+    pub fn partition_number(&self) -> Option<usize> {
+        match self {
+            Self::BeyondDosLimit {
+                partition_number, ..
+            }
+            | Self::NoRoomForPrimaryGpt {
+                partition_number, ..
+            }
+            | Self::NoRoomForBackupGpt {
+                partition_number, ..
+            }
+            | Self::NoRoomForEbr {
+                partition_number, ..
+            }
+            | Self::ProtectivePartition { partition_number } => Some(*partition_number),
+            Self::TooManyPartitions { .. } | Self::UnsupportedPartitionTable { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for ConversionBlocker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BeyondDosLimit {
+                partition_number,
+                ending_sector,
+                last_addressable_lba,
+            } => write!(
+                f,
+                "partition {} ends at sector {}, beyond the last sector addressable by a DOS partition table {}",
+                partition_number, ending_sector, last_addressable_lba
+            ),
+            Self::NoRoomForPrimaryGpt {
+                partition_number,
+                starting_sector,
+                first_usable_lba,
+            } => write!(
+                f,
+                "partition {} starts at sector {}, before the first usable GPT sector {}",
+                partition_number, starting_sector, first_usable_lba
+            ),
+            Self::NoRoomForBackupGpt {
+                partition_number,
+                ending_sector,
+                last_usable_lba,
+            } => write!(
+                f,
+                "partition {} ends at sector {}, beyond the last usable GPT sector {}",
+                partition_number, ending_sector, last_usable_lba
+            ),
+            Self::NoRoomForEbr {
+                partition_number,
+                starting_sector,
+            } => write!(
+                f,
+                "partition {} starting at sector {} is not preceded by a free sector for its extended boot record",
+                partition_number, starting_sector
+            ),
+            Self::ProtectivePartition { partition_number } => write!(
+                f,
+                "partition {} is a GPT protective partition",
+                partition_number
+            ),
+            Self::TooManyPartitions { count, max } => write!(
+                f,
+                "{} partitions to convert, the target partition table can hold at most {}",
+                count, max
+            ),
+            Self::UnsupportedPartitionTable { expected, found } => match found {
+                Some(found) => write!(
+                    f,
+                    "expected a {} partition table, found a {} partition table",
+                    expected, found
+                ),
+                None => write!(f, "expected a {} partition table, found none", expected),
+            },
+        }
+    }
+}
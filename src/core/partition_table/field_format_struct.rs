@@ -68,7 +68,8 @@ pub fn width(&self) -> Option<MaxColWidth> {
 
     /// Returns `true` if the [`Field`] contains a numerical value.
     pub fn is_numeric(&self) -> bool {
-        let state = unsafe { libfdisk::fdisk_field_is_number(self.inner) == 1 };
+        // `libfdisk` returns the value of the `FDISK_FIELDFL_NUMBER` flag, not a boolean.
+        let state = unsafe { libfdisk::fdisk_field_is_number(self.inner) != 0 };
         log::debug!("FieldFormat::is_numeric value: {:?}", state);
 
         state
@@ -0,0 +1,47 @@
+// Copyright (c) 2023 Nick Piaddo
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// From dependency library
+
+// From standard library
+use std::fmt;
+
+// From this library
+
+/// Fix applied to the `GPT` headers of a device by
+/// [`FdiskGPTExt::gpt_repair`](crate::fdisk::FdiskGPTExt::gpt_repair).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum GptRepair {
+    /// The primary header, or its Partition Entry Array, was missing or corrupt. Both were
+    /// rebuilt from the backup header.
+    PrimaryHeaderRebuilt,
+
+    /// The backup header at `location`, or its Partition Entry Array, was missing or corrupt.
+    /// Both were rebuilt from the primary header.
+    BackupHeaderRebuilt { location: u64 },
+
+    /// The backup header was not on the last sector of the device (e.g. after copying a disk
+    /// image to a larger device), and was moved there.
+    BackupHeaderRelocated { from: u64, to: u64 },
+}
+
+impl fmt::Display for GptRepair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PrimaryHeaderRebuilt => {
+                write!(f, "rebuilt primary GPT header from backup header")
+            }
+            Self::BackupHeaderRebuilt { location } => write!(
+                f,
+                "rebuilt backup GPT header at sector {} from primary header",
+                location
+            ),
+            Self::BackupHeaderRelocated { from, to } => write!(
+                f,
+                "moved backup GPT header from sector {} to sector {}",
+                from, to
+            ),
+        }
+    }
+}
@@ -0,0 +1,73 @@
+// Copyright (c) 2023 Nick Piaddo
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// From dependency library
+
+// From standard library
+
+// From this library
+use crate::core::partition::Code;
+
+/// A `GPT` partition to mirror in a hybrid `MBR`.
+///
+/// # Examples
+///
+/// ```
+/// use rsfdisk::core::partition::Code;
+/// use rsfdisk::core::partition_table::HybridMbrEntry;
+///
+/// // Mirror the second GPT partition as a bootable FAT32 partition.
+/// let entry = HybridMbrEntry::new(1).code(Code::W95FAT32LBA).bootable();
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct HybridMbrEntry {
+    partition_number: usize,
+    code: Option<Code>,
+    bootable: bool,
+}
+
+impl HybridMbrEntry {
+    /// Creates a new `HybridMbrEntry` mirroring the `GPT` partition `partition_number`.
+    ///
+    /// Unless set with [`HybridMbrEntry::code`], the `MBR` partition type is deduced from the
+    /// `GPT` partition type (see [`Guid::to_code`](crate::core::partition::Guid::to_code)).
+    pub fn new(partition_number: usize) -> HybridMbrEntry {
+        log::debug!(
+            "HybridMbrEntry::new creating a new `HybridMbrEntry` for partition {:?}",
+            partition_number
+        );
+
+        Self {
+            partition_number,
+            code: None,
+            bootable: false,
+        }
+    }
+
+    /// Sets the `MBR` partition type of the mirrored partition.
+    pub fn code(mut self, code: Code) -> HybridMbrEntry {
+        self.code = Some(code);
+        self
+    }
+
+    /// Sets the boot indicator flag of the mirrored partition.
+    pub fn bootable(mut self) -> HybridMbrEntry {
+        self.bootable = true;
+        self
+    }
+
+    /// Returns the number of the mirrored `GPT` partition.
+    pub fn partition_number(&self) -> usize {
+        self.partition_number
+    }
+
+    /// Returns the `MBR` partition type set with [`HybridMbrEntry::code`], if any.
+    pub fn partition_code(&self) -> Option<Code> {
+        self.code
+    }
+
+    /// Returns `true` when the mirrored partition is flagged as bootable.
+    pub fn is_bootable(&self) -> bool {
+        self.bootable
+    }
+}
@@ -0,0 +1,71 @@
+// Copyright (c) 2023 Nick Piaddo
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// From dependency library
+
+// From standard library
+
+// From this library
+use crate::core::partition_table::HybridMbrEntry;
+use crate::core::partition_table::ProtectiveEntryPosition;
+
+/// Layout of a hybrid `MBR`, written by
+/// [`FdiskGPTExt::gpt_write_hybrid_mbr`](crate::fdisk::FdiskGPTExt::gpt_write_hybrid_mbr).
+///
+/// A hybrid `MBR` mirrors up to three `GPT` partitions, for the benefit of operating systems or
+/// boot loaders that do not understand `GPT`. Its fourth entry is a `0xEE` protective partition,
+/// covering the sectors from the `GPT` header up to the first mirrored partition.
+///
+/// # Examples
+///
+/// ```
+/// use rsfdisk::core::partition::Code;
+/// use rsfdisk::core::partition_table::HybridMbr;
+/// use rsfdisk::core::partition_table::HybridMbrEntry;
+/// use rsfdisk::core::partition_table::ProtectiveEntryPosition;
+///
+/// let layout = HybridMbr::new()
+///     .entry(HybridMbrEntry::new(0).code(Code::EfiSystem))
+///     .entry(HybridMbrEntry::new(2).bootable())
+///     .protective_entry_position(ProtectiveEntryPosition::Last);
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct HybridMbr {
+    entries: Vec<HybridMbrEntry>,
+    protective_entry_position: ProtectiveEntryPosition,
+}
+
+impl HybridMbr {
+    /// Maximum number of `GPT` partitions a hybrid `MBR` can mirror.
+    pub const MAX_ENTRIES: usize = 3;
+
+    /// Creates a new, empty, `HybridMbr` with its protective entry in the first slot.
+    pub fn new() -> HybridMbr {
+        log::debug!("HybridMbr::new creating a new `HybridMbr` instance");
+
+        Self::default()
+    }
+
+    /// Adds a `GPT` partition to mirror. Entries are stored in the `MBR` in the order they are
+    /// added.
+    pub fn entry(mut self, entry: HybridMbrEntry) -> HybridMbr {
+        self.entries.push(entry);
+        self
+    }
+
+    /// Sets the slot of the `0xEE` protective entry.
+    pub fn protective_entry_position(mut self, position: ProtectiveEntryPosition) -> HybridMbr {
+        self.protective_entry_position = position;
+        self
+    }
+
+    /// Returns the `GPT` partitions to mirror.
+    pub fn entries(&self) -> &[HybridMbrEntry] {
+        &self.entries
+    }
+
+    /// Returns the slot of the `0xEE` protective entry.
+    pub fn protective_position(&self) -> ProtectiveEntryPosition {
+        self.protective_entry_position
+    }
+}
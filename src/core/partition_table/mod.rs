@@ -9,32 +9,56 @@
 
 // From this library
 
+pub use bsd_disk_label_fields_struct::BsdDiskLabelFields;
+pub use bsd_drive_type_enum::BsdDriveType;
+pub use conversion_blocker_enum::ConversionBlocker;
 pub use field_enum::Field;
 pub use field_format_struct::FieldFormat;
+pub use gpt_repair_enum::GptRepair;
 pub use header_entry_content_struct::HeaderEntryContent;
 pub use header_entry_enum::HeaderEntry;
+pub use hybrid_mbr_entry_struct::HybridMbrEntry;
+pub use hybrid_mbr_struct::HybridMbr;
 pub use input_type_enum::InputType;
 pub use max_col_width_enum::MaxColWidth;
+pub use partition_table_backup_struct::PartitionTableBackup;
+pub use partition_table_change_enum::PartitionTableChange;
+pub use partition_table_diff_struct::PartitionTableDiff;
 pub use partition_table_dos_ext_trait::PartitionTableDOSExt;
 pub use partition_table_gpt_ext_trait::PartitionTableGPTExt;
 pub use partition_table_kind_enum::PartitionTableKind;
 pub use partition_table_struct::PartitionTable;
+pub use protective_entry_position_enum::ProtectiveEntryPosition;
 pub use range_struct::Range;
+pub use sgi_volume_entry_struct::SgiVolumeEntry;
 pub use shortcut_struct::Shortcut;
 pub use table_section_struct::TableSection;
+pub use verification_finding_enum::VerificationFinding;
 pub use verification_status_enum::VerificationStatus;
 
+mod bsd_disk_label_fields_struct;
+mod bsd_drive_type_enum;
+mod conversion_blocker_enum;
 mod field_enum;
 mod field_format_struct;
+mod gpt_repair_enum;
 mod header_entry_content_struct;
 mod header_entry_enum;
+mod hybrid_mbr_entry_struct;
+mod hybrid_mbr_struct;
 mod input_type_enum;
 mod max_col_width_enum;
+mod partition_table_backup_struct;
+mod partition_table_change_enum;
+mod partition_table_diff_struct;
 mod partition_table_dos_ext_trait;
 mod partition_table_gpt_ext_trait;
 mod partition_table_kind_enum;
 mod partition_table_struct;
+mod protective_entry_position_enum;
 mod range_struct;
+mod sgi_volume_entry_struct;
 mod shortcut_struct;
 mod table_section_struct;
+mod verification_finding_enum;
 mod verification_status_enum;
@@ -0,0 +1,225 @@
+// Copyright (c) 2023 Nick Piaddo
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// From dependency library
+
+// From standard library
+
+// From this library
+use crate::core::partition_table::PartitionTableKind;
+use crate::core::partition_table::TableSection;
+use crate::core::utils::misc::crc32;
+
+/// Raw copy of the sectors holding a partition table, created by
+/// [`Fdisk::partition_table_backup`](crate::fdisk::Fdisk::partition_table_backup).
+///
+/// A backup file is self-describing. It records the geometry of the device it was taken from,
+/// the type of partition table, and for each [`TableSection`] its name, its location on the
+/// device, and its content. A CRC32 checksum protects the whole file.
+///
+/// | Field                  | Encoding                               |
+/// |----                    |----                                    |
+/// | Signature              | `RSFDBKP` followed by a version byte   |
+/// | Sector size in bytes   | `u64`                                  |
+/// | Device size in sectors | `u64`                                  |
+/// | Partition table type   | string                                 |
+/// | Number of sections     | `u32`                                  |
+/// | Sections               | name (string), offset in bytes (`u64`), size in bytes (`u64`), content |
+/// | Checksum               | `u32`, CRC32 of all the preceding bytes |
+///
+/// Integers are stored in little-endian byte order, strings are prefixed by their length in bytes
+/// as a `u32`.
+#[derive(Debug)]
+pub struct PartitionTableBackup {
+    sector_size: u64,
+    device_size: u64,
+    partition_table: PartitionTableKind,
+    sections: Vec<(TableSection, Vec<u8>)>,
+}
+
+impl PartitionTableBackup {
+    /// Signature at the start of a backup file, including the format version.
+    const SIGNATURE: [u8; 8] = *b"RSFDBKP\x01";
+
+    #[doc(hidden)]
+    /// Creates a new, empty, `PartitionTableBackup`.
+    pub(crate) fn new(
+        sector_size: u64,
+        device_size: u64,
+        partition_table: PartitionTableKind,
+    ) -> PartitionTableBackup {
+        log::debug!("PartitionTableBackup::new creating a new `PartitionTableBackup` instance");
+
+        Self {
+            sector_size,
+            device_size,
+            partition_table,
+            sections: Vec::new(),
+        }
+    }
+
+    #[doc(hidden)]
+    /// Adds a copy of a partition table section.
+    pub(crate) fn push_section(&mut self, section: TableSection, content: Vec<u8>) {
+        self.sections.push((section, content));
+    }
+
+    /// Returns the size in bytes of a sector on the backed up device.
+    pub fn sector_size(&self) -> u64 {
+        self.sector_size
+    }
+
+    /// Returns the size in sectors of the backed up device.
+    pub fn device_size_in_sectors(&self) -> u64 {
+        self.device_size
+    }
+
+    /// Returns the type of the backed up partition table.
+    pub fn partition_table_kind(&self) -> PartitionTableKind {
+        self.partition_table
+    }
+
+    /// Returns an iterator over the backed up [`TableSection`]s, and their content.
+    pub fn sections(&self) -> impl Iterator<Item = (&TableSection, &[u8])> {
+        self.sections
+            .iter()
+            .map(|(section, content)| (section, content.as_slice()))
+    }
+
+    #[doc(hidden)]
+    /// Serializes this `PartitionTableBackup`.
+    pub(crate) fn to_bytes(&self) -> Vec<u8> {
+        fn push_str(bytes: &mut Vec<u8>, s: &str) {
+            bytes.extend_from_slice(&(s.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(s.as_bytes());
+        }
+
+        let mut bytes = Self::SIGNATURE.to_vec();
+        bytes.extend_from_slice(&self.sector_size.to_le_bytes());
+        bytes.extend_from_slice(&self.device_size.to_le_bytes());
+        push_str(&mut bytes, self.partition_table.as_str());
+        bytes.extend_from_slice(&(self.sections.len() as u32).to_le_bytes());
+
+        for (section, content) in self.sections.iter() {
+            push_str(&mut bytes, section.name());
+            bytes.extend_from_slice(&section.starting_offset().to_le_bytes());
+            bytes.extend_from_slice(&(content.len() as u64).to_le_bytes());
+            bytes.extend_from_slice(content);
+        }
+
+        let checksum = crc32(&bytes);
+        bytes.extend_from_slice(&checksum.to_le_bytes());
+
+        bytes
+    }
+
+    #[doc(hidden)]
+    /// Deserializes a `PartitionTableBackup`, returns `None` if `bytes` are not a valid backup.
+    pub(crate) fn from_bytes(bytes: &[u8]) -> Option<PartitionTableBackup> {
+        let (content, checksum) = bytes.split_at(bytes.len().checked_sub(4)?);
+
+        if crc32(content).to_le_bytes() != checksum {
+            log::debug!("PartitionTableBackup::from_bytes checksum mismatch");
+
+            return None;
+        }
+
+        let mut reader = Reader(content);
+
+        if reader.take(Self::SIGNATURE.len())? != Self::SIGNATURE {
+            log::debug!("PartitionTableBackup::from_bytes unknown signature");
+
+            return None;
+        }
+
+        let sector_size = reader.u64()?;
+        let device_size = reader.u64()?;
+        let partition_table = reader.string()?.parse().ok()?;
+        let mut backup = Self::new(sector_size, device_size, partition_table);
+
+        for _ in 0..reader.u32()? {
+            let name = reader.string()?;
+            let offset = reader.u64()?;
+            let size = usize::try_from(reader.u64()?).ok()?;
+            let content = reader.take(size)?.to_vec();
+
+            backup.push_section(TableSection::new(name, offset, size), content);
+        }
+
+        // Trailing bytes are not part of the format.
+        reader.0.is_empty().then_some(backup)
+    }
+}
+
+#[doc(hidden)]
+/// Reads values sequentially from a byte slice.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    #[doc(hidden)]
+    /// Consumes the next `len` bytes.
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if len > self.0.len() {
+            return None;
+        }
+
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+
+        Some(head)
+    }
+
+    #[doc(hidden)]
+    /// Consumes a little-endian `u32`.
+    fn u32(&mut self) -> Option<u32> {
+        self.take(4)?.try_into().ok().map(u32::from_le_bytes)
+    }
+
+    #[doc(hidden)]
+    /// Consumes a little-endian `u64`.
+    fn u64(&mut self) -> Option<u64> {
+        self.take(8)?.try_into().ok().map(u64::from_le_bytes)
+    }
+
+    #[doc(hidden)]
+    /// Consumes a string prefixed by its length.
+    fn string(&mut self) -> Option<String> {
+        let len = self.u32()? as usize;
+
+        String::from_utf8(self.take(len)?.to_vec()).ok()
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::*;
+    use pretty_assertions::{assert_eq, assert_ne};
+
+    #[test]
+    fn partition_table_backup_can_be_serialized_and_deserialized() {
+        let mut backup = PartitionTableBackup::new(512, 20480, PartitionTableKind::DOS);
+        backup.push_section(TableSection::new("MBR".to_owned(), 0, 512), vec![0xAA; 512]);
+
+        let bytes = backup.to_bytes();
+        let restored = PartitionTableBackup::from_bytes(&bytes).unwrap();
+
+        let actual = restored.device_size_in_sectors();
+        let expected = 20480;
+        assert_eq!(actual, expected);
+
+        let actual: Vec<_> = restored
+            .sections()
+            .map(|(section, content)| (section.name(), section.starting_offset(), content.len()))
+            .collect();
+        let expected = vec![("MBR", 0, 512)];
+        assert_eq!(actual, expected);
+
+        // A single altered byte invalidates the backup.
+        let mut corrupt = bytes.clone();
+        corrupt[20] ^= 0xFF;
+
+        let actual = PartitionTableBackup::from_bytes(&corrupt);
+        assert!(actual.is_none());
+    }
+}
@@ -0,0 +1,233 @@
+// Copyright (c) 2023 Nick Piaddo
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// From dependency library
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// From standard library
+use std::fmt;
+
+// From this library
+use crate::core::partition_table::PartitionTableKind;
+
+/// Difference between the partition table on disk and the one in memory (see
+/// [`Fdisk::partition_table_diff`](crate::fdisk::Fdisk::partition_table_diff)).
+///
+/// Partition types are identified by their GUID for GPT partitions, or by their hexadecimal code
+/// for MBR partitions (e.g. `0x83`).
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(tag = "change", rename_all = "snake_case")
+)]
+#[non_exhaustive]
+pub enum PartitionTableChange {
+    /// The type of partition table changed.
+    KindChanged {
+        before: Option<PartitionTableKind>,
+        after: Option<PartitionTableKind>,
+    },
+
+    /// The disk identifier (e.g. disk GUID, MBR disk identifier) changed.
+    IdChanged {
+        before: Option<String>,
+        after: Option<String>,
+    },
+
+    /// A new partition will be created.
+    PartitionAdded {
+        partition_number: usize,
+        starting_sector: u64,
+        size_in_sectors: u64,
+        partition_type: Option<String>,
+        name: Option<String>,
+    },
+
+    /// A partition will be deleted.
+    PartitionDeleted {
+        partition_number: usize,
+        starting_sector: u64,
+        size_in_sectors: u64,
+        partition_type: Option<String>,
+        name: Option<String>,
+    },
+
+    /// The first sector of a partition changed.
+    PartitionMoved {
+        partition_number: usize,
+        before: u64,
+        after: u64,
+    },
+
+    /// The size in sectors of a partition changed.
+    PartitionResized {
+        partition_number: usize,
+        before: u64,
+        after: u64,
+    },
+
+    /// The type of a partition changed.
+    PartitionRetyped {
+        partition_number: usize,
+        before: Option<String>,
+        after: Option<String>,
+    },
+
+    /// The name of a partition changed.
+    PartitionRenamed {
+        partition_number: usize,
+        before: Option<String>,
+        after: Option<String>,
+    },
+}
+
+impl PartitionTableChange {
+    /// Returns the number of the partition affected by this change, if it concerns a specific
+    /// partition.
+    pub fn partition_number(&self) -> Option<usize> {
+        match self {
+            Self::PartitionAdded {
+                partition_number, ..
+            }
+            | Self::PartitionDeleted {
+                partition_number, ..
+            }
+            | Self::PartitionMoved {
+                partition_number, ..
+            }
+            | Self::PartitionResized {
+                partition_number, ..
+            }
+            | Self::PartitionRetyped {
+                partition_number, ..
+            }
+            | Self::PartitionRenamed {
+                partition_number, ..
+            } => Some(*partition_number),
+            Self::KindChanged { .. } | Self::IdChanged { .. } => None,
+        }
+    }
+
+    #[doc(hidden)]
+    /// Formats an optional value, or `-` if it is absent.
+    fn or_dash<T>(value: &Option<T>) -> String
+    where
+        T: fmt::Display,
+    {
+        value
+            .as_ref()
+            .map(|v| v.to_string())
+            .unwrap_or_else(|| "-".to_owned())
+    }
+
+    #[doc(hidden)]
+    /// Formats a partition's characteristics.
+    fn describe(
+        f: &mut fmt::Formatter<'_>,
+        starting_sector: u64,
+        size_in_sectors: u64,
+        partition_type: &Option<String>,
+        name: &Option<String>,
+    ) -> fmt::Result {
+        write!(
+            f,
+            "sectors {}-{} ({} sectors), type {}",
+            starting_sector,
+            starting_sector + size_in_sectors.saturating_sub(1),
+            size_in_sectors,
+            Self::or_dash(partition_type)
+        )?;
+
+        match name {
+            Some(name) => write!(f, ", name {:?}", name),
+            None => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for PartitionTableChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::KindChanged { before, after } => write!(
+                f,
+                "~ partition table type: {} -> {}",
+                Self::or_dash(before),
+                Self::or_dash(after)
+            ),
+            Self::IdChanged { before, after } => write!(
+                f,
+                "~ disk identifier: {} -> {}",
+                Self::or_dash(before),
+                Self::or_dash(after)
+            ),
+            Self::PartitionAdded {
+                partition_number,
+                starting_sector,
+                size_in_sectors,
+                partition_type,
+                name,
+            } => {
+                write!(f, "+ partition {}: ", partition_number)?;
+                Self::describe(f, *starting_sector, *size_in_sectors, partition_type, name)
+            }
+            Self::PartitionDeleted {
+                partition_number,
+                starting_sector,
+                size_in_sectors,
+                partition_type,
+                name,
+            } => {
+                write!(f, "- partition {}: ", partition_number)?;
+                Self::describe(f, *starting_sector, *size_in_sectors, partition_type, name)
+            }
+            Self::PartitionMoved {
+                partition_number,
+                before,
+                after,
+            } => write!(
+                f,
+                "~ partition {} moved: starting sector {} -> {}",
+                partition_number, before, after
+            ),
+            Self::PartitionResized {
+                partition_number,
+                before,
+                after,
+            } => write!(
+                f,
+                "~ partition {} resized: {} -> {} sectors",
+                partition_number, before, after
+            ),
+            Self::PartitionRetyped {
+                partition_number,
+                before,
+                after,
+            } => write!(
+                f,
+                "~ partition {} type: {} -> {}",
+                partition_number,
+                Self::or_dash(before),
+                Self::or_dash(after)
+            ),
+            Self::PartitionRenamed {
+                partition_number,
+                before,
+                after,
+            } => write!(
+                f,
+                "~ partition {} name: {} -> {}",
+                partition_number,
+                before
+                    .as_ref()
+                    .map(|name| format!("{:?}", name))
+                    .unwrap_or_else(|| "-".to_owned()),
+                after
+                    .as_ref()
+                    .map(|name| format!("{:?}", name))
+                    .unwrap_or_else(|| "-".to_owned()),
+            ),
+        }
+    }
+}
@@ -0,0 +1,271 @@
+// Copyright (c) 2023 Nick Piaddo
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// From dependency library
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// From standard library
+use std::collections::BTreeMap;
+use std::fmt;
+
+// From this library
+use crate::core::partition::Partition;
+use crate::core::partition_table::PartitionTableChange;
+use crate::core::partition_table::PartitionTableKind;
+use crate::fdisk::Fdisk;
+
+/// Characteristics of a partition compared by [`PartitionTableDiff`].
+#[derive(Debug)]
+struct PartitionState {
+    starting_sector: u64,
+    size_in_sectors: u64,
+    partition_type: Option<String>,
+    name: Option<String>,
+}
+
+impl PartitionState {
+    #[doc(hidden)]
+    /// Extracts the characteristics of a `Partition`.
+    fn from_partition(partition: &Partition) -> Option<(usize, PartitionState)> {
+        let partition_number = partition.number()?;
+        let partition_type = partition.partition_type().and_then(|kind| {
+            kind.guid()
+                .map(String::from)
+                .or_else(|| kind.code().map(|code| format!("{:#04x}", code)))
+        });
+
+        let state = Self {
+            starting_sector: partition.starting_sector()?,
+            size_in_sectors: partition.size_in_sectors()?,
+            partition_type,
+            name: partition.name().map(String::from),
+        };
+
+        Some((partition_number, state))
+    }
+}
+
+/// Summary of the modifications an [`Fdisk`] will apply when writing its in-memory partition
+/// table to disk (see [`Fdisk::partition_table_diff`]).
+///
+/// Changes are ordered: partition table type and disk identifier first, then partition changes by
+/// increasing partition number.
+///
+/// # Examples
+///
+/// ```ignore
+/// use rsfdisk::fdisk::Fdisk;
+///
+/// fn main() -> rsfdisk::Result<()> {
+///     let mut disk = Fdisk::builder()
+///         .assign_device("/dev/vda")
+///         .enable_read_write()
+///         .build()?;
+///
+///     disk.partition_delete(1)?;
+///
+///     let diff = disk.partition_table_diff()?;
+///     println!("{}", diff);
+///
+///     // Example output
+///     //
+///     // - partition 1: sectors 7680-16383 (8704 sectors), type 0xa5
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PartitionTableDiff {
+    changes: Vec<PartitionTableChange>,
+}
+
+impl PartitionTableDiff {
+    #[doc(hidden)]
+    /// Collects the partitions of the partition table held by an `Fdisk`, excluding free space.
+    fn collect_partitions(disk: &Fdisk) -> BTreeMap<usize, PartitionState> {
+        disk.list_partitions()
+            .map(|partitions| {
+                partitions
+                    .iter()
+                    .filter(|p| !p.is_free_space())
+                    .filter_map(PartitionState::from_partition)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    #[doc(hidden)]
+    /// Returns the type of partition table held by an `Fdisk`.
+    fn table_kind(disk: &Fdisk) -> Option<PartitionTableKind> {
+        if disk.device_has_partition_table() {
+            disk.partition_table_current().map(|table| table.kind())
+        } else {
+            None
+        }
+    }
+
+    #[doc(hidden)]
+    /// Computes the changes needed to go from the partition table in `before` to the one in
+    /// `after`.
+    pub(crate) fn compute(before: &Fdisk, after: &Fdisk) -> PartitionTableDiff {
+        let mut changes = Vec::new();
+
+        let kind_before = Self::table_kind(before);
+        let kind_after = Self::table_kind(after);
+        let same_kind = kind_before == kind_after;
+
+        if !same_kind {
+            changes.push(PartitionTableChange::KindChanged {
+                before: kind_before,
+                after: kind_after,
+            });
+        }
+
+        let id_before = kind_before.and_then(|_| before.partition_table_id());
+        let id_after = kind_after.and_then(|_| after.partition_table_id());
+
+        if id_before != id_after {
+            changes.push(PartitionTableChange::IdChanged {
+                before: id_before,
+                after: id_after,
+            });
+        }
+
+        let mut partitions_before = Self::collect_partitions(before);
+        let mut partitions_after = Self::collect_partitions(after);
+
+        // Partitions in a different type of partition table are unrelated.
+        if !same_kind {
+            for (partition_number, state) in std::mem::take(&mut partitions_before) {
+                changes.push(Self::deleted(partition_number, state));
+            }
+
+            for (partition_number, state) in std::mem::take(&mut partitions_after) {
+                changes.push(Self::added(partition_number, state));
+            }
+        }
+
+        let mut numbers: Vec<usize> = partitions_before
+            .keys()
+            .chain(partitions_after.keys())
+            .copied()
+            .collect();
+        numbers.sort_unstable();
+        numbers.dedup();
+
+        for partition_number in numbers {
+            match (
+                partitions_before.remove(&partition_number),
+                partitions_after.remove(&partition_number),
+            ) {
+                (Some(old), None) => changes.push(Self::deleted(partition_number, old)),
+                (None, Some(new)) => changes.push(Self::added(partition_number, new)),
+                (Some(old), Some(new)) => {
+                    if old.starting_sector != new.starting_sector {
+                        changes.push(PartitionTableChange::PartitionMoved {
+                            partition_number,
+                            before: old.starting_sector,
+                            after: new.starting_sector,
+                        });
+                    }
+
+                    if old.size_in_sectors != new.size_in_sectors {
+                        changes.push(PartitionTableChange::PartitionResized {
+                            partition_number,
+                            before: old.size_in_sectors,
+                            after: new.size_in_sectors,
+                        });
+                    }
+
+                    if old.partition_type != new.partition_type {
+                        changes.push(PartitionTableChange::PartitionRetyped {
+                            partition_number,
+                            before: old.partition_type,
+                            after: new.partition_type,
+                        });
+                    }
+
+                    if old.name != new.name {
+                        changes.push(PartitionTableChange::PartitionRenamed {
+                            partition_number,
+                            before: old.name,
+                            after: new.name,
+                        });
+                    }
+                }
+                (None, None) => {}
+            }
+        }
+
+        log::debug!("PartitionTableDiff::compute changes: {:?}", changes);
+
+        Self { changes }
+    }
+
+    #[doc(hidden)]
+    /// Creates a `PartitionAdded` change.
+    fn added(partition_number: usize, state: PartitionState) -> PartitionTableChange {
+        PartitionTableChange::PartitionAdded {
+            partition_number,
+            starting_sector: state.starting_sector,
+            size_in_sectors: state.size_in_sectors,
+            partition_type: state.partition_type,
+            name: state.name,
+        }
+    }
+
+    #[doc(hidden)]
+    /// Creates a `PartitionDeleted` change.
+    fn deleted(partition_number: usize, state: PartitionState) -> PartitionTableChange {
+        PartitionTableChange::PartitionDeleted {
+            partition_number,
+            starting_sector: state.starting_sector,
+            size_in_sectors: state.size_in_sectors,
+            partition_type: state.partition_type,
+            name: state.name,
+        }
+    }
+
+    /// Returns the list of changes.
+    pub fn changes(&self) -> &[PartitionTableChange] {
+        &self.changes
+    }
+
+    /// Returns an iterator over the list of changes.
+    pub fn iter(&self) -> std::slice::Iter<'_, PartitionTableChange> {
+        self.changes.iter()
+    }
+
+    /// Returns the number of changes.
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// Returns `true` if the partition tables in memory and on disk are identical.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl<'a> IntoIterator for &'a PartitionTableDiff {
+    type Item = &'a PartitionTableChange;
+    type IntoIter = std::slice::Iter<'a, PartitionTableChange>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl fmt::Display for PartitionTableDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.changes.is_empty() {
+            return write!(f, "no changes");
+        }
+
+        let lines: Vec<String> = self.changes.iter().map(|c| c.to_string()).collect();
+
+        write!(f, "{}", lines.join("\n"))
+    }
+}
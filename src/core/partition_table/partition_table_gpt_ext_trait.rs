@@ -29,7 +29,8 @@ pub trait PartitionTableGPTExt: Sealed {
 }
 
 fn set_relocation(ptr: *mut libfdisk::fdisk_label, enable: bool) {
-    let op = if enable { 1 } else { 0 };
+    // `libfdisk::fdisk_gpt_disable_relocation` disables relocation when given a non-zero value.
+    let op = if enable { 0 } else { 1 };
     let op_str = if enable {
         "enable".to_owned()
     } else {
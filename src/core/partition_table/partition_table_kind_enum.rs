@@ -3,15 +3,24 @@
 
 // From dependency library
 use num_enum::{IntoPrimitive, TryFromPrimitive};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 // From standard library
 use std::ffi::CString;
 use std::fmt;
+use std::str::FromStr;
 
 // From this library
+use crate::core::errors::ParserError;
 
 /// Supported types of partition tables.
 #[derive(Clone, Copy, Debug, Eq, IntoPrimitive, PartialEq, TryFromPrimitive)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "lowercase")
+)]
 #[repr(u32)]
 #[non_exhaustive]
 pub enum PartitionTableKind {
@@ -44,3 +53,22 @@ fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.as_str())
     }
 }
+
+impl FromStr for PartitionTableKind {
+    type Err = ParserError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "bsd" => Ok(Self::BSD),
+            "dos" => Ok(Self::DOS),
+            "gpt" => Ok(Self::GPT),
+            "sgi" => Ok(Self::SGI),
+            "sun" => Ok(Self::SUN),
+            _ => {
+                let err_msg = format!("unsupported partition table type: {:?}", s);
+
+                Err(ParserError::PartitionTableKind(err_msg))
+            }
+        }
+    }
+}
@@ -0,0 +1,18 @@
+// Copyright (c) 2023 Nick Piaddo
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// From dependency library
+
+// From standard library
+
+// From this library
+
+/// Slot of the `0xEE` protective entry in a hybrid `MBR` Partition Entry Array.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ProtectiveEntryPosition {
+    /// Put the protective entry in the first slot, before the mirrored `GPT` partitions.
+    #[default]
+    First,
+    /// Put the protective entry in the slot following the mirrored `GPT` partitions.
+    Last,
+}
@@ -0,0 +1,176 @@
+// Copyright (c) 2023 Nick Piaddo
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// From dependency library
+
+// From standard library
+
+// From this library
+
+/// An entry in the volume directory of an `SGI` volume header.
+///
+/// The volume directory lists up to 15 files (e.g. a standalone shell `sash`, or the `sgilabel`
+/// hint added by [`FdiskSGIExt::sgi_add_hint`](crate::fdisk::FdiskSGIExt::sgi_add_hint)) stored
+/// in the volume header partition. Each file is identified by a name of at most 8 ASCII
+/// characters, and is located by the number of its first 512-byte block and its size in bytes.
+///
+/// # Examples
+///
+/// ```
+/// # use pretty_assertions::assert_eq;
+/// use rsfdisk::core::partition_table::SgiVolumeEntry;
+///
+/// let entry = SgiVolumeEntry::new("sash", 4, 302_592);
+///
+/// let actual = entry.name();
+/// let expected = "sash";
+/// assert_eq!(actual, expected);
+///
+/// let actual = entry.size_in_blocks();
+/// let expected = 591;
+/// assert_eq!(actual, expected);
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SgiVolumeEntry {
+    name: String,
+    first_block: u32,
+    size_in_bytes: u32,
+}
+
+impl SgiVolumeEntry {
+    /// Maximum length of an entry's name.
+    pub const NAME_MAX_LEN: usize = 8;
+
+    /// Size of a block in the volume header.
+    pub const BLOCK_SIZE: u32 = 512;
+
+    /// Creates a new `SgiVolumeEntry` for a file named `name`, starting at block `first_block`
+    /// of the volume header, and `size_in_bytes` bytes long.
+    pub fn new<T>(name: T, first_block: u32, size_in_bytes: u32) -> SgiVolumeEntry
+    where
+        T: AsRef<str>,
+    {
+        let name = name.as_ref().to_owned();
+        log::debug!(
+            "SgiVolumeEntry::new created a new `SgiVolumeEntry` instance with name: {:?}, first block: {}, size: {}",
+            name,
+            first_block,
+            size_in_bytes
+        );
+
+        Self {
+            name,
+            first_block,
+            size_in_bytes,
+        }
+    }
+
+    /// Returns the name of the file.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the number of the file's first block in the volume header.
+    pub fn first_block(&self) -> u32 {
+        self.first_block
+    }
+
+    /// Returns the size of the file in bytes.
+    pub fn size_in_bytes(&self) -> u32 {
+        self.size_in_bytes
+    }
+
+    /// Returns the number of blocks occupied by the file.
+    pub fn size_in_blocks(&self) -> u32 {
+        let blocks = (u64::from(self.size_in_bytes) + u64::from(Self::BLOCK_SIZE) - 1)
+            / u64::from(Self::BLOCK_SIZE);
+
+        blocks as u32
+    }
+
+    #[doc(hidden)]
+    /// Returns `true` if this entry's name is a non-empty string of at most 8 printable ASCII
+    /// characters.
+    pub(crate) fn has_valid_name(&self) -> bool {
+        !self.name.is_empty()
+            && self.name.len() <= Self::NAME_MAX_LEN
+            && self.name.bytes().all(|b| b.is_ascii_graphic())
+    }
+
+    #[doc(hidden)]
+    /// Returns `true` if the blocks occupied by this entry and `other` overlap.
+    pub(crate) fn overlaps(&self, other: &SgiVolumeEntry) -> bool {
+        let start = u64::from(self.first_block);
+        let end = start + u64::from(self.size_in_blocks());
+        let other_start = u64::from(other.first_block);
+        let other_end = other_start + u64::from(other.size_in_blocks());
+
+        start < other_end && other_start < end
+    }
+
+    #[doc(hidden)]
+    /// Converts a raw 16-byte volume directory entry into an `SgiVolumeEntry`. Returns `None`
+    /// for unused entries.
+    pub(crate) fn from_raw(raw: &[u8]) -> Option<SgiVolumeEntry> {
+        let name_len = raw[..Self::NAME_MAX_LEN]
+            .iter()
+            .position(|&b| b == 0)
+            .unwrap_or(Self::NAME_MAX_LEN);
+
+        if name_len == 0 {
+            return None;
+        }
+
+        let name = String::from_utf8_lossy(&raw[..name_len]);
+        let first_block = u32::from_be_bytes(raw[8..12].try_into().ok()?);
+        let size_in_bytes = u32::from_be_bytes(raw[12..16].try_into().ok()?);
+
+        Some(Self::new(name, first_block, size_in_bytes))
+    }
+
+    #[doc(hidden)]
+    /// Converts this entry into a raw 16-byte volume directory entry.
+    pub(crate) fn to_raw(&self) -> [u8; 16] {
+        let mut raw = [0u8; 16];
+        let name = &self.name.as_bytes()[..self.name.len().min(Self::NAME_MAX_LEN)];
+        raw[..name.len()].copy_from_slice(name);
+        raw[8..12].copy_from_slice(&self.first_block.to_be_bytes());
+        raw[12..16].copy_from_slice(&self.size_in_bytes.to_be_bytes());
+
+        raw
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::*;
+    use pretty_assertions::{assert_eq, assert_ne};
+
+    #[test]
+    fn sgi_volume_entry_can_round_trip_through_its_raw_form() {
+        let entry = SgiVolumeEntry::new("sgilabel", 2, 512);
+        let raw = entry.to_raw();
+
+        let actual = SgiVolumeEntry::from_raw(&raw);
+        let expected = Some(entry);
+        assert_eq!(actual, expected);
+
+        let actual = SgiVolumeEntry::from_raw(&[0u8; 16]);
+        let expected = None;
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn sgi_volume_entry_can_detect_overlapping_entries() {
+        let entry = SgiVolumeEntry::new("sash", 4, 1024);
+
+        let actual = entry.overlaps(&SgiVolumeEntry::new("ide", 5, 512));
+        let expected = true;
+        assert_eq!(actual, expected);
+
+        let actual = entry.overlaps(&SgiVolumeEntry::new("ide", 6, 512));
+        let expected = false;
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,117 @@
+// Copyright (c) 2023 Nick Piaddo
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// From dependency library
+
+// From standard library
+use std::fmt;
+
+// From this library
+
+/// Issue detected while verifying a partition table (see
+/// [`Fdisk::partition_table_verify`](crate::fdisk::Fdisk::partition_table_verify)).
+///
+/// Partition numbers are zero-based, as in the rest of this library's API.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum VerificationFinding {
+    /// The primary and backup GPT headers differ, or the backup header is missing/invalid.
+    BackupHeaderMismatch { message: String },
+
+    /// A GPT header or partition entry array has an invalid CRC32 checksum.
+    BadChecksum { message: String },
+
+    /// A partition extends past the last usable sector of the device.
+    BeyondLastLba {
+        partition_number: usize,
+        ending_sector: u64,
+        last_lba: u64,
+    },
+
+    /// Inconsistent Cylinder-Head-Sector addresses in a DOS partition entry.
+    ChsInconsistency {
+        partition_number: Option<usize>,
+        message: String,
+    },
+
+    /// A partition's first sector is not aligned on the device's grain size.
+    MisalignedStart {
+        partition_number: usize,
+        starting_sector: u64,
+        grain_size: u64,
+    },
+
+    /// Two partitions share some sectors.
+    Overlap {
+        partition_number: usize,
+        other_partition_number: usize,
+    },
+
+    /// Any other issue, e.g. an unreadable primary GPT header.
+    Other {
+        partition_number: Option<usize>,
+        message: String,
+    },
+}
+
+impl VerificationFinding {
+    /// Returns the number of the partition affected by this issue, if it concerns a specific
+    /// partition.
+    pub fn partition_number(&self) -> Option<usize> {
+        match self {
+            Self::BeyondLastLba {
+                partition_number, ..
+            }
+            | Self::MisalignedStart {
+                partition_number, ..
+            }
+            | Self::Overlap {
+                partition_number, ..
+            } => Some(*partition_number),
+            Self::ChsInconsistency {
+                partition_number, ..
+            }
+            | Self::Other {
+                partition_number, ..
+            } => *partition_number,
+            Self::BackupHeaderMismatch { .. } | Self::BadChecksum { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for VerificationFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BackupHeaderMismatch { message }
+            | Self::BadChecksum { message }
+            | Self::ChsInconsistency { message, .. }
+            | Self::Other { message, .. } => write!(f, "{}", message),
+            Self::BeyondLastLba {
+                partition_number,
+                ending_sector,
+                last_lba,
+            } => write!(
+                f,
+                "partition {} ends at sector {}, beyond the last usable sector {}",
+                partition_number, ending_sector, last_lba
+            ),
+            Self::MisalignedStart {
+                partition_number,
+                starting_sector,
+                grain_size,
+            } => write!(
+                f,
+                "partition {} starting sector {} is not aligned on a {} bytes boundary",
+                partition_number, starting_sector, grain_size
+            ),
+            Self::Overlap {
+                partition_number,
+                other_partition_number,
+            } => write!(
+                f,
+                "partition {} overlaps partition {}",
+                partition_number, other_partition_number
+            ),
+        }
+    }
+}
@@ -0,0 +1,32 @@
+// Copyright (c) 2023 Nick Piaddo
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// From dependency library
+
+// From standard library
+
+// From this library
+
+/// Pre-recorded answer to a [`Prompt`](crate::core::prompt::Prompt), for use with an
+/// [`AnswerQueue`](crate::core::prompt::AnswerQueue).
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Answer {
+    /// Accept the default value proposed by a
+    /// [`PromptKind::Number`](crate::core::prompt::PromptKind::Number),
+    /// [`PromptKind::Offset`](crate::core::prompt::PromptKind::Offset), or
+    /// [`PromptKind::Menu`](crate::core::prompt::PromptKind::Menu) prompt.
+    Default,
+
+    /// Select the menu item with the given key code.
+    MenuItem(i32),
+
+    /// Provide a numerical value.
+    Number(u64),
+
+    /// Provide a string value.
+    String(String),
+
+    /// Answer a yes/no question.
+    YesNo(bool),
+}
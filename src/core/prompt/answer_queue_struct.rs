@@ -0,0 +1,282 @@
+// Copyright (c) 2023 Nick Piaddo
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// From dependency library
+
+// From standard library
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+// From this library
+use crate::core::errors::PromptError;
+use crate::core::prompt::Answer;
+use crate::core::prompt::Prompt;
+use crate::core::prompt::PromptHandler;
+use crate::core::prompt::PromptKind;
+use crate::core::prompt::QueryMatcher;
+use crate::fdisk::FdiskError;
+
+#[derive(Debug)]
+struct ExpectedPrompt {
+    kind: PromptKind,
+    query: QueryMatcher,
+    answer: Answer,
+}
+
+#[derive(Debug, Default)]
+struct QueueState {
+    expected: VecDeque<ExpectedPrompt>,
+    failure: Option<String>,
+}
+
+/// Ordered list of pre-recorded answers for unattended dialog-driven partitioning.
+///
+/// An `AnswerQueue` is a [`PromptHandler`] that expects to receive questions in a predetermined
+/// order. Each question is matched by [`PromptKind`] and text (see [`QueryMatcher`]), then answered
+/// with the corresponding [`Answer`]. Information and warning messages
+/// ([`PromptKind::Info`], [`PromptKind::Warn`], [`PromptKind::WarnX`]) are acknowledged without
+/// consuming an entry of the queue.
+///
+/// When a question arrives out of order, or after the queue is exhausted, the `AnswerQueue`
+/// refuses to answer it, which aborts the operation in progress. It also refuses any further
+/// question until the end of its lifetime.
+///
+/// Cloning an `AnswerQueue` returns a handle to the same queue. Keep one before handing a copy to
+/// [`FdiskBuilder::prompt_handler`](crate::fdisk::FdiskBuilder::prompt_handler), then call
+/// [`AnswerQueue::verify`] once the operation is over to check that every question was expected
+/// and answered.
+///
+/// # Examples
+///
+/// ```ignore
+/// use rsfdisk::core::prompt::Answer;
+/// use rsfdisk::core::prompt::AnswerQueue;
+/// use rsfdisk::core::prompt::PromptKind;
+/// use rsfdisk::core::prompt::QueryMatcher;
+/// use rsfdisk::fdisk::Fdisk;
+///
+/// fn main() -> rsfdisk::Result<()> {
+///     let answers = AnswerQueue::new()
+///         .expect(
+///             PromptKind::Number,
+///             QueryMatcher::Contains("Partition number".to_owned()),
+///             Answer::Number(1),
+///         )
+///         .expect(
+///             PromptKind::Number,
+///             QueryMatcher::Contains("First sector".to_owned()),
+///             Answer::Default,
+///         )
+///         .expect(
+///             PromptKind::Offset,
+///             QueryMatcher::Contains("Last sector".to_owned()),
+///             Answer::Default,
+///         );
+///
+///     let mut disk = Fdisk::builder()
+///         .assign_device("/dev/vda")
+///         .enable_read_write()
+///         .enable_interactive()
+///         .prompt_handler(answers.clone())
+///         .build()?;
+///
+///     let result = disk.partition_add_interactive();
+///
+///     // Reports any unexpected, or unanswered question.
+///     answers.verify()?;
+///     result?;
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct AnswerQueue {
+    state: Rc<RefCell<QueueState>>,
+}
+
+impl AnswerQueue {
+    /// Creates an empty `AnswerQueue`.
+    pub fn new() -> AnswerQueue {
+        log::debug!("AnswerQueue::new creating a new `AnswerQueue` instance");
+
+        Self::default()
+    }
+
+    /// Appends to the queue a question of the given `kind`, with a text satisfying `query`, and the
+    /// `answer` to give it.
+    pub fn expect(self, kind: PromptKind, query: QueryMatcher, answer: Answer) -> AnswerQueue {
+        log::debug!(
+            "AnswerQueue::expect expecting {:?} prompt with {}, answer: {:?}",
+            kind,
+            query,
+            answer
+        );
+
+        self.state.borrow_mut().expected.push_back(ExpectedPrompt {
+            kind,
+            query,
+            answer,
+        });
+
+        self
+    }
+
+    /// Returns the number of questions left to answer.
+    pub fn remaining(&self) -> usize {
+        self.state.borrow().expected.len()
+    }
+
+    /// Returns `true` if every expected question was answered.
+    pub fn is_empty(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    /// Checks that no unexpected question was received, and that all expected questions were
+    /// answered.
+    pub fn verify(&self) -> Result<(), FdiskError> {
+        let state = self.state.borrow();
+
+        match (&state.failure, state.expected.front()) {
+            (Some(failure), _) => {
+                let err_msg = failure.clone();
+                log::debug!("AnswerQueue::verify {}", err_msg);
+
+                Err(FdiskError::Prompt(err_msg))
+            }
+            (None, Some(next)) => {
+                let err_msg = format!(
+                    "{} question(s) left unanswered, next: {:?} prompt with {}",
+                    state.expected.len(),
+                    next.kind,
+                    next.query
+                );
+                log::debug!("AnswerQueue::verify {}", err_msg);
+
+                Err(FdiskError::Prompt(err_msg))
+            }
+            (None, None) => {
+                log::debug!("AnswerQueue::verify all questions answered");
+
+                Ok(())
+            }
+        }
+    }
+
+    #[doc(hidden)]
+    /// Answers `prompt` with `answer`.
+    fn apply(prompt: &mut Prompt, answer: &Answer) -> Result<(), PromptError> {
+        match (prompt.kind(), answer) {
+            (PromptKind::Number | PromptKind::Offset, Answer::Default) => {
+                let value = prompt.number_default();
+                prompt.number_set_answer(value)
+            }
+            (PromptKind::Number | PromptKind::Offset, Answer::Number(value)) => {
+                let lower = prompt.number_lower_bound();
+                let upper = prompt.number_upper_bound();
+
+                // `libfdisk` stores any value, without checking it is within range.
+                if upper > 0 && !(lower..=upper).contains(value) {
+                    let err_msg = format!(
+                        "value {} out of range, must be between {} and {}",
+                        value, lower, upper
+                    );
+
+                    return Err(PromptError::Config(err_msg));
+                }
+
+                prompt.number_set_answer(*value)
+            }
+            (PromptKind::Menu, Answer::Default) => {
+                let key = prompt.menu_default_key();
+                prompt.menu_item_select(key)
+            }
+            (PromptKind::Menu, Answer::MenuItem(key)) => prompt.menu_item_select(*key),
+            (PromptKind::String, Answer::String(value)) => prompt.string_set_answer(value),
+            (PromptKind::YesNo, Answer::YesNo(value)) => prompt.yes_no_set_answer(*value),
+            (kind, answer) => {
+                let err_msg = format!("can not answer {:?} prompt with {:?}", kind, answer);
+
+                Err(PromptError::Config(err_msg))
+            }
+        }
+    }
+
+    #[doc(hidden)]
+    /// Records the first failure, and returns an error describing it.
+    fn fail(state: &mut QueueState, err_msg: String) -> PromptError {
+        log::debug!("AnswerQueue::answer {}", err_msg);
+
+        if state.failure.is_none() {
+            state.failure = Some(err_msg.clone());
+        }
+
+        PromptError::Unexpected(err_msg)
+    }
+}
+
+impl PromptHandler for AnswerQueue {
+    fn answer(&mut self, prompt: &mut Prompt) -> Result<(), PromptError> {
+        let kind = prompt.kind();
+
+        if matches!(
+            kind,
+            PromptKind::Info | PromptKind::Warn | PromptKind::WarnX | PromptKind::None
+        ) {
+            log::debug!("AnswerQueue::answer acknowledged {:?} message", kind);
+
+            return Ok(());
+        }
+
+        let mut state = self.state.borrow_mut();
+        let query = prompt.query().map(str::to_owned);
+
+        if let Some(failure) = &state.failure {
+            let err_msg = format!(
+                "refusing {:?} prompt {:?} after previous failure: {}",
+                kind, query, failure
+            );
+            log::debug!("AnswerQueue::answer {}", err_msg);
+
+            return Err(PromptError::Unexpected(err_msg));
+        }
+
+        match state.expected.pop_front() {
+            None => {
+                let err_msg = format!(
+                    "unexpected {:?} prompt {:?}: no answer left in queue",
+                    kind, query
+                );
+
+                Err(Self::fail(&mut state, err_msg))
+            }
+            Some(expected)
+                if expected.kind != kind || !expected.query.is_match(query.as_deref()) =>
+            {
+                let err_msg = format!(
+                    "unexpected {:?} prompt {:?}: expected {:?} prompt with {}",
+                    kind, query, expected.kind, expected.query
+                );
+
+                Err(Self::fail(&mut state, err_msg))
+            }
+            Some(expected) => match Self::apply(prompt, &expected.answer) {
+                Ok(_) => {
+                    log::debug!(
+                        "AnswerQueue::answer answered {:?} prompt {:?} with {:?}",
+                        kind,
+                        query,
+                        expected.answer
+                    );
+
+                    Ok(())
+                }
+                Err(e) => {
+                    let err_msg = format!("failed to answer {:?} prompt {:?}: {}", kind, query, e);
+
+                    Err(Self::fail(&mut state, err_msg))
+                }
+            },
+        }
+    }
+}
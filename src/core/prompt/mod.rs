@@ -9,10 +9,18 @@
 
 // From this library
 
+pub use answer_enum::Answer;
+pub use answer_queue_struct::AnswerQueue;
 pub use menu_item_struct::MenuItem;
+pub use prompt_handler_trait::PromptHandler;
 pub use prompt_kind_enum::PromptKind;
 pub use prompt_struct::Prompt;
+pub use query_matcher_enum::QueryMatcher;
 
+mod answer_enum;
+mod answer_queue_struct;
 mod menu_item_struct;
+mod prompt_handler_trait;
 mod prompt_kind_enum;
 mod prompt_struct;
+mod query_matcher_enum;
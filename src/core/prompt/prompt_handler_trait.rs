@@ -0,0 +1,81 @@
+// Copyright (c) 2023 Nick Piaddo
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// From dependency library
+
+// From standard library
+use std::fmt;
+
+// From this library
+use crate::core::errors::PromptError;
+use crate::core::prompt::Prompt;
+
+/// Answers the [`Prompt`]s emitted by an [`Fdisk`](crate::fdisk::Fdisk) during dialog-driven
+/// partitioning.
+///
+/// A `PromptHandler` is installed with
+/// [`FdiskBuilder::prompt_handler`](crate::fdisk::FdiskBuilder::prompt_handler). It receives every
+/// question (e.g. [`PromptKind::Number`](crate::core::prompt::PromptKind::Number),
+/// [`PromptKind::YesNo`](crate::core::prompt::PromptKind::YesNo), etc.), as well as every
+/// information and warning message (e.g. [`PromptKind::Info`](crate::core::prompt::PromptKind::Info)).
+///
+/// Questions are answered by calling one of [`Prompt::number_set_answer`],
+/// [`Prompt::string_set_answer`], [`Prompt::yes_no_set_answer`], or [`Prompt::menu_item_select`]
+/// depending on the value returned by [`Prompt::kind`]. Returning an error aborts the operation
+/// that issued the `Prompt`.
+///
+/// Any closure with a signature matching `FnMut(&mut Prompt) -> Result<(), PromptError>`
+/// implements this trait.
+///
+/// # Examples
+///
+/// ```ignore
+/// use rsfdisk::core::prompt::Prompt;
+/// use rsfdisk::core::prompt::PromptKind;
+/// use rsfdisk::fdisk::Fdisk;
+///
+/// fn main() -> rsfdisk::Result<()> {
+///     let mut disk = Fdisk::builder()
+///         .assign_device("/dev/vda")
+///         .enable_read_write()
+///         .enable_interactive()
+///         // Accept all default values.
+///         .prompt_handler(|prompt: &mut Prompt| match prompt.kind() {
+///             PromptKind::Number | PromptKind::Offset => {
+///                 let default = prompt.number_default();
+///                 prompt.number_set_answer(default)
+///             }
+///             PromptKind::YesNo => prompt.yes_no_set_answer(true),
+///             PromptKind::Menu => {
+///                 let key = prompt.menu_default_key();
+///                 prompt.menu_item_select(key)
+///             }
+///             _ => Ok(()),
+///         })
+///         .build()?;
+///
+///     disk.partition_add_interactive()?;
+///     disk.partition_table_write_to_disk()?;
+///
+///     Ok(())
+/// }
+/// ```
+pub trait PromptHandler {
+    /// Answers the `prompt`, or acknowledges it when it is an information/warning message.
+    fn answer(&mut self, prompt: &mut Prompt) -> Result<(), PromptError>;
+}
+
+impl<F> PromptHandler for F
+where
+    F: FnMut(&mut Prompt) -> Result<(), PromptError>,
+{
+    fn answer(&mut self, prompt: &mut Prompt) -> Result<(), PromptError> {
+        self(prompt)
+    }
+}
+
+impl fmt::Debug for dyn PromptHandler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PromptHandler")
+    }
+}
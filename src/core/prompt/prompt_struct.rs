@@ -21,6 +21,24 @@ pub struct Prompt {
 }
 
 impl Prompt {
+    #[doc(hidden)]
+    /// Increments the `Prompt`'s reference counter.
+    pub(crate) fn incr_ref_counter(&mut self) {
+        unsafe { libfdisk::fdisk_ref_ask(self.inner) }
+    }
+
+    #[doc(hidden)]
+    /// Borrows a `Prompt` instance.
+    pub(crate) fn borrow_ptr(ptr: *mut libfdisk::fdisk_ask) -> Prompt {
+        let mut prompt = Self { inner: ptr };
+        // We are virtually ceding ownership of this prompt which will be automatically
+        // deallocated once it is out of scope, incrementing its reference counter protects it from
+        // being freed prematurely.
+        prompt.incr_ref_counter();
+
+        prompt
+    }
+
     /// Returns the type of this `Prompt`.
     pub fn kind(&self) -> PromptKind {
         let code = unsafe { libfdisk::fdisk_ask_get_type(self.inner) };
@@ -488,7 +506,7 @@ pub fn accepts_negative_numbers(&self) -> bool {
 
     /// Returns `true` if this `Prompt` matches any of the [`PromptKind`] variants.
     pub fn is_of_kind(&self, kind: PromptKind) -> bool {
-        let state = unsafe { libfdisk::fdisk_ask_get_type(self.inner) as u32 == kind.into() };
+        let state = unsafe { libfdisk::fdisk_ask_get_type(self.inner) as u32 == u32::from(kind) };
         log::debug!(
             "Prompt::is_of_kind is `Prompt` of kind {:?}: {:?}",
             kind,
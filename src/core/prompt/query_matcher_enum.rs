@@ -0,0 +1,101 @@
+// Copyright (c) 2023 Nick Piaddo
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// From dependency library
+use regex::Regex;
+
+// From standard library
+use std::fmt;
+
+// From this library
+
+/// Pattern to match against the text of a [`Prompt`](crate::core::prompt::Prompt) (see
+/// [`Prompt::query`](crate::core::prompt::Prompt::query)).
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum QueryMatcher {
+    /// Match any text, including a missing one.
+    Any,
+
+    /// Match a text containing the given sub-string.
+    Contains(String),
+
+    /// Match a text identical to the given string.
+    Exact(String),
+
+    /// Match a text satisfying the given regular expression.
+    Regex(Regex),
+}
+
+impl QueryMatcher {
+    /// Returns `true` if the `query` text satisfies this `QueryMatcher`.
+    pub fn is_match(&self, query: Option<&str>) -> bool {
+        match (self, query) {
+            (Self::Any, _) => true,
+            (Self::Contains(pattern), Some(text)) => text.contains(pattern.as_str()),
+            (Self::Exact(pattern), Some(text)) => text == pattern,
+            (Self::Regex(regex), Some(text)) => regex.is_match(text),
+            (_, None) => false,
+        }
+    }
+}
+
+impl fmt::Display for QueryMatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Any => write!(f, "any text"),
+            Self::Contains(pattern) => write!(f, "text containing {:?}", pattern),
+            Self::Exact(pattern) => write!(f, "text {:?}", pattern),
+            Self::Regex(regex) => write!(f, "text matching /{}/", regex.as_str()),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::*;
+    use pretty_assertions::{assert_eq, assert_ne};
+
+    #[test]
+    fn query_matcher_any_matches_everything() {
+        let matcher = QueryMatcher::Any;
+
+        assert!(matcher.is_match(None));
+        assert!(matcher.is_match(Some("")));
+        assert!(matcher.is_match(Some("Partition number")));
+    }
+
+    #[test]
+    fn query_matcher_can_match_a_sub_string() {
+        let matcher = QueryMatcher::Contains("sector".to_owned());
+
+        assert!(matcher.is_match(Some("First sector")));
+        assert!(!matcher.is_match(Some("Partition number")));
+        assert!(!matcher.is_match(None));
+    }
+
+    #[test]
+    fn query_matcher_can_match_an_exact_string() {
+        let matcher = QueryMatcher::Exact("First sector".to_owned());
+
+        assert!(matcher.is_match(Some("First sector")));
+        assert!(!matcher.is_match(Some("First sector (2048-20446, default 2048)")));
+    }
+
+    #[test]
+    fn query_matcher_can_match_a_regular_expression() {
+        let matcher = QueryMatcher::Regex(Regex::new(r"^Last sector").unwrap());
+
+        assert!(matcher.is_match(Some("Last sector, +/-sectors or +/-size{K,M,G,T,P}")));
+        assert!(!matcher.is_match(Some("First sector")));
+    }
+
+    #[test]
+    fn query_matcher_has_a_readable_description() {
+        let matcher = QueryMatcher::Regex(Regex::new(r"^Last sector").unwrap());
+        let actual = matcher.to_string();
+        let expected = "text matching /^Last sector/";
+        assert_eq!(actual, expected);
+    }
+}
@@ -9,6 +9,8 @@
 
 // From this library
 
+pub use script_document_struct::ScriptDocument;
 pub use script_struct::Script;
 
+mod script_document_struct;
 mod script_struct;
@@ -0,0 +1,828 @@
+// Copyright (c) 2023 Nick Piaddo
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// From dependency library
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// From standard library
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+// From this library
+use crate::core::errors::ScriptDocumentError;
+use crate::core::partition::PartitionData;
+use crate::core::partition::PartitionKindData;
+use crate::core::partition_table::PartitionTableKind;
+use crate::core::script::Script;
+use crate::core::utils::misc::partition_name;
+
+#[cfg(feature = "serde")]
+/// Number of device nodes checked when numbering the partitions of a JSON document without a
+/// `table-length` header.
+const DEFAULT_MAX_PARTITIONS: usize = 128;
+
+/// Owned, typed representation of an `sfdisk`-compatible script.
+///
+/// A `ScriptDocument` can be built field by field, checked with [`ScriptDocument::validate`],
+/// converted to and from a [`Script`], and printed in the `sfdisk` dump format without accessing
+/// a device.
+///
+/// Only sectors are supported as unit, so the `unit` header is always set to `sectors`.
+///
+/// # Examples
+///
+/// ```
+/// # use pretty_assertions::assert_eq;
+/// use rsfdisk::core::partition::PartitionData;
+/// use rsfdisk::core::partition::PartitionKindData;
+/// use rsfdisk::core::partition_table::PartitionTableKind;
+/// use rsfdisk::core::script::ScriptDocument;
+///
+/// fn main() -> rsfdisk::Result<()> {
+///     let mut document = ScriptDocument::new();
+///     document.label = Some(PartitionTableKind::DOS);
+///     document.label_id = Some("0x12345678".to_owned());
+///     document.device = Some("/dev/vda".to_owned());
+///     document.entries.push(PartitionData {
+///         number: Some(0),
+///         starting_sector: Some(2048),
+///         size_in_sectors: Some(204800),
+///         partition_type: Some(PartitionKindData {
+///             code: Some(0x83),
+///             ..Default::default()
+///         }),
+///         bootable: true,
+///         ..Default::default()
+///     });
+///
+///     document.validate()?;
+///
+///     let actual = document.to_string();
+///     let expected = r#"label: dos
+/// label-id: 0x12345678
+/// device: /dev/vda
+/// unit: sectors
+///
+/// /dev/vda1 : start=        2048, size=      204800, type=83, bootable
+/// "#;
+///     assert_eq!(actual, expected);
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default))]
+pub struct ScriptDocument {
+    /// Partition table type (header `label`).
+    pub label: Option<PartitionTableKind>,
+
+    /// Disk identifier (header `label-id`).
+    pub label_id: Option<String>,
+
+    /// Path to the device (header `device`).
+    pub device: Option<String>,
+
+    /// First usable sector of a `GPT` partition table (header `first-lba`).
+    pub first_lba: Option<u64>,
+
+    /// Last usable sector of a `GPT` partition table (header `last-lba`).
+    pub last_lba: Option<u64>,
+
+    /// Maximum number of entries in a `GPT` partition table (header `table-length`).
+    pub table_length: Option<u64>,
+
+    /// Alignment size in bytes for partitions (header `grain`).
+    pub grain: Option<u64>,
+
+    /// Size of a sector in bytes (header `sector-size`).
+    pub sector_size: Option<u64>,
+
+    /// Partition table entries.
+    pub entries: Vec<PartitionData>,
+}
+
+impl ScriptDocument {
+    /// Creates a new empty `ScriptDocument`.
+    pub fn new() -> ScriptDocument {
+        log::debug!("ScriptDocument::new creating a new `ScriptDocument` instance");
+
+        Self::default()
+    }
+
+    #[doc(hidden)]
+    /// Returns an error if `value` contains a line break or one of the `forbidden` characters.
+    fn check_characters(
+        field: &str,
+        value: &str,
+        forbidden: &[char],
+    ) -> Result<(), ScriptDocumentError> {
+        Script::check_dump_value(field, value, forbidden)
+            .map_err(|e| ScriptDocumentError::Validation(e.to_string()))
+    }
+
+    #[doc(hidden)]
+    /// Checks the characteristics of a partition entry.
+    fn validate_entry(
+        &self,
+        index: usize,
+        entry: &PartitionData,
+    ) -> Result<(), ScriptDocumentError> {
+        let invalid = |reason: String| {
+            let err_msg = format!("invalid partition entry {}: {}", index, reason);
+
+            Err(ScriptDocumentError::Validation(err_msg))
+        };
+
+        if entry.size_in_sectors == Some(0) {
+            return invalid("size can not be zero".to_owned());
+        }
+
+        if let (Some(start), Some(first_lba)) = (entry.starting_sector, self.first_lba) {
+            if start < first_lba {
+                return invalid(format!(
+                    "starting sector {} is before the first usable sector {}",
+                    start, first_lba
+                ));
+            }
+        }
+
+        if let (Some(start), Some(size), Some(last_lba)) =
+            (entry.starting_sector, entry.size_in_sectors, self.last_lba)
+        {
+            if start.saturating_add(size - 1) > last_lba {
+                return invalid(format!(
+                    "ending sector {} is past the last usable sector {}",
+                    start.saturating_add(size - 1),
+                    last_lba
+                ));
+            }
+        }
+
+        if let (Some(number), Some(table_length)) = (entry.number, self.table_length) {
+            if number as u64 >= table_length {
+                return invalid(format!(
+                    "partition number {} exceeds the partition table length {}",
+                    number, table_length
+                ));
+            }
+        }
+
+        if let Some(kind) = entry.partition_type.as_ref() {
+            match (self.label, kind.code, kind.guid.as_ref()) {
+                (_, None, None) => return invalid("partition type has no code or GUID".to_owned()),
+                (Some(PartitionTableKind::GPT), _, None) => {
+                    return invalid("GPT partition type has no GUID".to_owned())
+                }
+                (Some(label), _, Some(_)) if label != PartitionTableKind::GPT => {
+                    return invalid(format!("{} partition type can not have a GUID", label))
+                }
+                _ => {}
+            }
+
+            if let Some(guid) = kind.guid.as_ref() {
+                Self::check_characters("partition type", guid, &[',', '"', ' ', '\t'])?;
+            }
+        }
+
+        if let Some(uuid) = entry.uuid.as_ref() {
+            Self::check_characters("partition UUID", uuid, &[',', '"', ' ', '\t'])?;
+        }
+
+        if let Some(attributes) = entry.attributes.as_ref() {
+            Self::check_characters("partition attributes", attributes, &['"'])?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the content of this `ScriptDocument` is consistent.
+    ///
+    /// This method verifies, among other things, that:
+    /// - the sector size is a power of 2, greater than or equal to 512 bytes,
+    /// - the first usable sector is before the last,
+    /// - `GPT`-specific headers are only set on `GPT` partition tables,
+    /// - partition numbers are unique,
+    /// - partitions are within the usable area, and do not overlap.
+    pub fn validate(&self) -> Result<(), ScriptDocumentError> {
+        log::debug!("ScriptDocument::validate validating script document");
+
+        let invalid = |reason: String| Err(ScriptDocumentError::Validation(reason));
+
+        if let Some(label_id) = self.label_id.as_ref() {
+            Self::check_characters("label-id", label_id, &[])?;
+        }
+
+        if let Some(device) = self.device.as_ref() {
+            Self::check_characters("device", device, &[':', '=', ','])?;
+        }
+
+        if let Some(sector_size) = self.sector_size {
+            if sector_size < 512 || !sector_size.is_power_of_two() {
+                return invalid(format!(
+                    "sector size {} is not a power of 2 greater than or equal to 512",
+                    sector_size
+                ));
+            }
+        }
+
+        if let (Some(first_lba), Some(last_lba)) = (self.first_lba, self.last_lba) {
+            if first_lba > last_lba {
+                return invalid(format!(
+                    "first usable sector {} is after the last usable sector {}",
+                    first_lba, last_lba
+                ));
+            }
+        }
+
+        if let Some(label) = self.label.filter(|label| *label != PartitionTableKind::GPT) {
+            let gpt_only_header = [
+                ("first-lba", self.first_lba.is_some()),
+                ("last-lba", self.last_lba.is_some()),
+                ("table-length", self.table_length.is_some()),
+            ]
+            .into_iter()
+            .find(|(_, is_set)| *is_set);
+
+            if let Some((header, _)) = gpt_only_header {
+                return invalid(format!("header {} is not supported by {}", header, label));
+            }
+        }
+
+        match self.table_length {
+            Some(0) => return invalid("table length can not be zero".to_owned()),
+            Some(table_length) if self.entries.len() as u64 > table_length => {
+                return invalid(format!(
+                    "{} partition entries exceed the partition table length {}",
+                    self.entries.len(),
+                    table_length
+                ));
+            }
+            _ => {}
+        }
+
+        match (self.grain, self.sector_size) {
+            (Some(0), _) => return invalid("grain size can not be zero".to_owned()),
+            (Some(grain), Some(sector_size)) if grain % sector_size != 0 => {
+                return invalid(format!(
+                    "grain size {} is not a multiple of the sector size {}",
+                    grain, sector_size
+                ));
+            }
+            _ => {}
+        }
+
+        let mut numbers = HashSet::new();
+        for (index, entry) in self.entries.iter().enumerate() {
+            self.validate_entry(index, entry)?;
+
+            if let Some(number) = entry.number {
+                if !numbers.insert(number) {
+                    return invalid(format!("duplicate partition number {}", number));
+                }
+            }
+        }
+
+        // Check overlaps between partitions with a known location.
+        let mut extents: Vec<(u64, u64, usize)> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| {
+                let start = entry.starting_sector?;
+                let size = entry.size_in_sectors?;
+
+                Some((start, start.saturating_add(size - 1), index))
+            })
+            .collect();
+        extents.sort_unstable();
+
+        for pair in extents.windows(2) {
+            let (_, previous_end, previous) = pair[0];
+            let (start, _, current) = pair[1];
+
+            if start <= previous_end {
+                return invalid(format!(
+                    "partition entries {} and {} overlap",
+                    previous, current
+                ));
+            }
+        }
+
+        log::debug!("ScriptDocument::validate script document is valid");
+
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[doc(hidden)]
+    /// Returns the number (starting from 0) of the partition with device node `node`, at position
+    /// `index` in a JSON document.
+    ///
+    /// Without a `device` header or a device node, partitions are numbered in order of appearance.
+    fn entry_number(&self, node: Option<&str>, index: usize) -> Option<usize> {
+        match (self.device.as_ref(), node) {
+            (Some(device), Some(node)) => {
+                let count = self
+                    .table_length
+                    .and_then(|length| usize::try_from(length).ok())
+                    .unwrap_or(DEFAULT_MAX_PARTITIONS)
+                    .max(index + 1);
+
+                (1..=count)
+                    .find(|&number| partition_name(device, number).as_deref() == Some(node))
+                    .map(|number| number - 1)
+            }
+            _ => Some(index),
+        }
+    }
+
+    #[doc(hidden)]
+    /// Returns the textual representation of a partition type.
+    fn partition_type_str(kind: &PartitionKindData) -> Option<String> {
+        kind.guid
+            .clone()
+            .or_else(|| kind.code.map(|code| format!("{:x}", code)))
+    }
+
+    #[cfg(feature = "serde")]
+    #[doc(hidden)]
+    /// Parses the textual representation of a partition type (GUID or hexadecimal code).
+    fn parse_partition_type(value: &str) -> Result<PartitionKindData, ScriptDocumentError> {
+        if value.contains('-') {
+            return Ok(PartitionKindData {
+                guid: Some(value.to_owned()),
+                ..Default::default()
+            });
+        }
+
+        let hex = value
+            .strip_prefix("0x")
+            .or_else(|| value.strip_prefix("0X"))
+            .unwrap_or(value);
+
+        u32::from_str_radix(hex, 16)
+            .map(|code| PartitionKindData {
+                code: Some(code),
+                ..Default::default()
+            })
+            .map_err(|_| {
+                let err_msg = format!("invalid partition type: {:?}", value);
+
+                ScriptDocumentError::Conversion(err_msg)
+            })
+    }
+
+    #[doc(hidden)]
+    /// Parses the value of a numeric header or field.
+    fn parse_number(field: &str, value: &str) -> Result<u64, ScriptDocumentError> {
+        value.trim().parse::<u64>().map_err(|_| {
+            let err_msg = format!("invalid value for {}: {:?}", field, value);
+
+            ScriptDocumentError::Conversion(err_msg)
+        })
+    }
+
+    #[doc(hidden)]
+    /// Sets the header `name` to `value`.
+    fn set_header(&mut self, name: &str, value: &str) -> Result<(), ScriptDocumentError> {
+        match name {
+            "label" => {
+                let label = PartitionTableKind::from_str(value)
+                    .map_err(|e| ScriptDocumentError::Conversion(e.to_string()))?;
+                self.label = Some(label);
+            }
+            "label-id" => self.label_id = Some(value.to_owned()),
+            "device" => self.device = Some(value.to_owned()),
+            "unit" if value == "sectors" => {}
+            "unit" => {
+                let err_msg = format!("unsupported unit: {:?}", value);
+
+                return Err(ScriptDocumentError::Conversion(err_msg));
+            }
+            "first-lba" => self.first_lba = Some(Self::parse_number(name, value)?),
+            "last-lba" => self.last_lba = Some(Self::parse_number(name, value)?),
+            "table-length" => self.table_length = Some(Self::parse_number(name, value)?),
+            "grain" => self.grain = Some(Self::parse_number(name, value)?),
+            "sector-size" => self.sector_size = Some(Self::parse_number(name, value)?),
+            unknown => {
+                let err_msg = format!("unsupported header: {:?}", unknown);
+
+                return Err(ScriptDocumentError::Conversion(err_msg));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[doc(hidden)]
+    /// Converts a partition entry in a JSON document to a `PartitionData`.
+    fn entry_from_json(
+        members: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<PartitionData, ScriptDocumentError> {
+        let mut entry = PartitionData::default();
+
+        for (key, value) in members {
+            let scalar = || {
+                Script::json_scalar(key, value)
+                    .map_err(|e| ScriptDocumentError::Parse(e.to_string()))
+            };
+
+            match key.as_str() {
+                // Partition numbers are computed by `ScriptDocument::entry_number`.
+                "node" => {}
+                "start" => entry.starting_sector = Some(Self::parse_number(key, &scalar()?)?),
+                "size" => entry.size_in_sectors = Some(Self::parse_number(key, &scalar()?)?),
+                "type" => entry.partition_type = Some(Self::parse_partition_type(&scalar()?)?),
+                "uuid" => entry.uuid = Some(scalar()?),
+                "name" => entry.name = Some(scalar()?),
+                "attrs" => entry.attributes = Some(scalar()?),
+                "bootable" => match value.as_bool() {
+                    Some(bootable) => entry.bootable = bootable,
+                    None => {
+                        let err_msg = "invalid value for partition \"bootable\"".to_owned();
+
+                        return Err(ScriptDocumentError::Parse(err_msg));
+                    }
+                },
+                unknown => {
+                    log::debug!(
+                        "ScriptDocument::entry_from_json ignoring unknown partition field: {:?}",
+                        unknown
+                    );
+                }
+            }
+        }
+
+        Ok(entry)
+    }
+
+    /// Creates a `ScriptDocument` from a partition table description in the JSON format
+    /// produced by `sfdisk --json`. Requires the `serde` feature.
+    ///
+    /// Returns an error if the description has a header without a matching `ScriptDocument`
+    /// field, or a unit other than `sectors`. Use [`Script::import_json`] to pass all headers on
+    /// to `libfdisk`.
+    #[cfg(feature = "serde")]
+    pub fn from_json<T>(content: T) -> Result<ScriptDocument, ScriptDocumentError>
+    where
+        T: AsRef<str>,
+    {
+        log::debug!("ScriptDocument::from_json parsing JSON document");
+
+        let root: serde_json::Value = serde_json::from_str(content.as_ref())
+            .map_err(|e| ScriptDocumentError::Parse(e.to_string()))?;
+        let table = Script::json_partition_table(&root)
+            .map_err(|e| ScriptDocumentError::Parse(e.to_string()))?;
+
+        let mut document = Self::new();
+
+        for (key, value) in table.iter().filter(|(key, _)| *key != "partitions") {
+            let value = Script::json_scalar(key, value)
+                .map_err(|e| ScriptDocumentError::Parse(e.to_string()))?;
+
+            document.set_header(Script::json_header_name(key), &value)?;
+        }
+
+        if let Some(partitions) = table.get("partitions") {
+            let partitions = partitions.as_array().ok_or_else(|| {
+                ScriptDocumentError::Parse("`partitions` is not a JSON array".to_owned())
+            })?;
+
+            for (index, partition) in partitions.iter().enumerate() {
+                let members = partition.as_object().ok_or_else(|| {
+                    ScriptDocumentError::Parse("partition entry is not a JSON object".to_owned())
+                })?;
+
+                let mut entry = Self::entry_from_json(members)?;
+                let node = members.get("node").and_then(|node| node.as_str());
+                entry.number = document.entry_number(node, index);
+
+                document.entries.push(entry);
+            }
+        }
+
+        log::debug!("ScriptDocument::from_json parsed document: {:?}", document);
+
+        Ok(document)
+    }
+
+    #[doc(hidden)]
+    /// Formats a partition entry in the `sfdisk` dump format.
+    fn format_entry(&self, entry: &PartitionData) -> String {
+        let mut fields = Vec::new();
+
+        if let Some(start) = entry.starting_sector {
+            fields.push(format!("start={:>12}", start));
+        }
+
+        if let Some(size) = entry.size_in_sectors {
+            fields.push(format!("size={:>12}", size));
+        }
+
+        if let Some(kind) = entry
+            .partition_type
+            .as_ref()
+            .and_then(Self::partition_type_str)
+        {
+            fields.push(format!("type={}", kind));
+        }
+
+        if let Some(uuid) = entry.uuid.as_ref() {
+            fields.push(format!("uuid={}", uuid));
+        }
+
+        if let Some(name) = entry.name.as_ref().filter(|name| !name.is_empty()) {
+            fields.push(format!("name={}", Script::quote_dump_string(name)));
+        }
+
+        // On MBR partition tables, attributes only mark bootable partitions.
+        if let Some(attributes) = entry.attributes.as_ref() {
+            if self.label != Some(PartitionTableKind::DOS) {
+                fields.push(format!("attrs=\"{}\"", attributes));
+            }
+        }
+
+        if entry.bootable {
+            fields.push("bootable".to_owned());
+        }
+
+        let fields = fields.join(", ");
+
+        match entry.number {
+            Some(number) => {
+                let node = self
+                    .device
+                    .as_ref()
+                    .and_then(|device| partition_name(device, number + 1))
+                    .unwrap_or_else(|| (number + 1).to_string());
+
+                format!("{} : {}", node, fields)
+            }
+            None => fields,
+        }
+    }
+}
+
+impl fmt::Display for ScriptDocument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(label) = self.label {
+            writeln!(f, "label: {}", label)?;
+        }
+
+        if let Some(label_id) = self.label_id.as_ref() {
+            writeln!(f, "label-id: {}", label_id)?;
+        }
+
+        if let Some(device) = self.device.as_ref() {
+            writeln!(f, "device: {}", device)?;
+        }
+
+        writeln!(f, "unit: sectors")?;
+
+        let numeric_headers = [
+            ("first-lba", self.first_lba),
+            ("last-lba", self.last_lba),
+            ("table-length", self.table_length),
+            ("grain", self.grain),
+            ("sector-size", self.sector_size),
+        ];
+
+        for (name, value) in numeric_headers {
+            if let Some(value) = value {
+                writeln!(f, "{}: {}", name, value)?;
+            }
+        }
+
+        writeln!(f)?;
+
+        for entry in self.entries.iter() {
+            writeln!(f, "{}", self.format_entry(entry))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'fdisk> TryFrom<&Script<'fdisk>> for ScriptDocument {
+    type Error = ScriptDocumentError;
+
+    fn try_from(script: &Script<'fdisk>) -> Result<Self, Self::Error> {
+        let mut document = Self::new();
+
+        for name in [
+            "label",
+            "label-id",
+            "device",
+            "unit",
+            "first-lba",
+            "last-lba",
+            "table-length",
+            "grain",
+            "sector-size",
+        ] {
+            if let Some(value) = script.header_value(name) {
+                document.set_header(name, value)?;
+            }
+        }
+
+        if let Some(entries) = script.partition_table_entries() {
+            document.entries = entries.iter().map(PartitionData::from).collect();
+        }
+
+        Ok(document)
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::*;
+    use pretty_assertions::{assert_eq, assert_ne};
+
+    fn entry(number: usize, start: u64, size: u64) -> PartitionData {
+        PartitionData {
+            number: Some(number),
+            starting_sector: Some(start),
+            size_in_sectors: Some(size),
+            ..Default::default()
+        }
+    }
+
+    fn gpt_document() -> ScriptDocument {
+        ScriptDocument {
+            label: Some(PartitionTableKind::GPT),
+            label_id: Some("01234567-89AB-CDEF-0123-456789ABCDEF".to_owned()),
+            device: Some("/dev/nvme0n1".to_owned()),
+            first_lba: Some(34),
+            last_lba: Some(204766),
+            sector_size: Some(512),
+            entries: vec![
+                PartitionData {
+                    partition_type: Some(PartitionKindData {
+                        guid: Some("C12A7328-F81F-11D2-BA4B-00A0C93EC93B".to_owned()),
+                        ..Default::default()
+                    }),
+                    name: Some("EFI \"System\"".to_owned()),
+                    attributes: Some("RequiredPartition".to_owned()),
+                    ..entry(0, 2048, 20480)
+                },
+                entry(1, 22528, 182239),
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn script_document_can_print_an_sfdisk_dump() {
+        let actual = gpt_document().to_string();
+        let expected = r#"label: gpt
+label-id: 01234567-89AB-CDEF-0123-456789ABCDEF
+device: /dev/nvme0n1
+unit: sectors
+first-lba: 34
+last-lba: 204766
+sector-size: 512
+
+/dev/nvme0n1p1 : start=        2048, size=       20480, type=C12A7328-F81F-11D2-BA4B-00A0C93EC93B, name="EFI \x22System\x22", attrs="RequiredPartition"
+/dev/nvme0n1p2 : start=       22528, size=      182239
+"#;
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn script_document_can_validate_a_consistent_document() -> crate::Result<()> {
+        gpt_document().validate()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn script_document_can_not_validate_overlapping_partitions() {
+        let mut document = gpt_document();
+        document.entries[1].starting_sector = Some(20480);
+
+        let actual = document.validate();
+        assert!(matches!(actual, Err(ScriptDocumentError::Validation(_))));
+    }
+
+    #[test]
+    fn script_document_can_not_validate_duplicate_partition_numbers() {
+        let mut document = gpt_document();
+        document.entries[1].number = Some(0);
+
+        let actual = document.validate();
+        assert!(matches!(actual, Err(ScriptDocumentError::Validation(_))));
+    }
+
+    #[test]
+    fn script_document_can_not_validate_partitions_outside_the_usable_area() {
+        let mut document = gpt_document();
+        document.entries[1].size_in_sectors = Some(182240);
+
+        let actual = document.validate();
+        assert!(matches!(actual, Err(ScriptDocumentError::Validation(_))));
+
+        let mut document = gpt_document();
+        document.entries[0].starting_sector = Some(33);
+
+        let actual = document.validate();
+        assert!(matches!(actual, Err(ScriptDocumentError::Validation(_))));
+    }
+
+    #[test]
+    fn script_document_can_not_validate_gpt_headers_on_other_partition_tables() {
+        let mut document = gpt_document();
+        document.label = Some(PartitionTableKind::DOS);
+        document.entries.clear();
+
+        let actual = document.validate();
+        assert!(matches!(actual, Err(ScriptDocumentError::Validation(_))));
+    }
+
+    #[test]
+    fn script_document_can_not_validate_an_invalid_sector_size() {
+        let mut document = gpt_document();
+        document.sector_size = Some(1000);
+
+        let actual = document.validate();
+        assert!(matches!(actual, Err(ScriptDocumentError::Validation(_))));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn script_document_can_parse_a_json_document() -> crate::Result<()> {
+        let json = r#"{
+           "partitiontable": {
+              "label": "gpt",
+              "id": "01234567-89AB-CDEF-0123-456789ABCDEF",
+              "device": "/dev/nvme0n1",
+              "unit": "sectors",
+              "firstlba": 34,
+              "lastlba": 204766,
+              "sectorsize": 512,
+              "partitions": [
+                 {
+                    "node": "/dev/nvme0n1p1",
+                    "start": 2048,
+                    "size": 20480,
+                    "type": "C12A7328-F81F-11D2-BA4B-00A0C93EC93B",
+                    "name": "EFI \"System\"",
+                    "attrs": "RequiredPartition"
+                 },
+                 {"node": "/dev/nvme0n1p2", "start": 22528, "size": 182239}
+              ]
+           }
+        }"#;
+
+        let actual = ScriptDocument::from_json(json)?;
+        let expected = gpt_document();
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn script_document_can_not_parse_an_unsupported_unit() {
+        let json = r#"{"partitiontable": {"label": "dos", "unit": "cylinders"}}"#;
+
+        let actual = ScriptDocument::from_json(json);
+        assert!(matches!(actual, Err(ScriptDocumentError::Conversion(_))));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn script_document_can_not_parse_an_unknown_header() {
+        let json = r#"{"partitiontable": {"label": "dos", "unknown": "value"}}"#;
+
+        let actual = ScriptDocument::from_json(json);
+        assert!(matches!(actual, Err(ScriptDocumentError::Conversion(_))));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn script_document_can_number_partitions_from_their_device_nodes() -> crate::Result<()> {
+        let json = r#"{
+           "partitiontable": {
+              "label": "dos",
+              "device": "/dev/sda",
+              "unit": "sectors",
+              "partitions": [
+                 {"node": "/dev/sda1", "start": 2048, "size": 8192, "type": "5"},
+                 {"node": "/dev/sda5", "start": 4096, "size": 2048, "type": "83"}
+              ]
+           }
+        }"#;
+
+        let document = ScriptDocument::from_json(json)?;
+
+        let actual: Vec<_> = document.entries.iter().map(|entry| entry.number).collect();
+        let expected = vec![Some(0), Some(4)];
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+}
@@ -4,9 +4,14 @@
 // From dependency library
 
 // From standard library
+use std::ffi::CString;
+#[cfg(feature = "serde")]
+use std::fs;
 use std::fs::File;
 use std::fs::OpenOptions;
 use std::io;
+#[cfg(feature = "serde")]
+use std::io::Read;
 use std::marker::PhantomData;
 use std::mem::MaybeUninit;
 use std::os::fd::{FromRawFd, IntoRawFd};
@@ -15,6 +20,7 @@
 // From this library
 use crate::core::errors::ScriptError;
 use crate::core::partition::PartitionList;
+use crate::core::script::ScriptDocument;
 use crate::fdisk::Fdisk;
 
 use crate::ffi_utils;
@@ -67,9 +73,11 @@ pub(crate) unsafe fn mut_from_boxed_ptr<'a>(
     }
 
     #[doc(hidden)]
-    /// Creates a new `Script` instance.
-    pub(crate) fn new(_: &'fdisk Fdisk, inner: *mut libfdisk::fdisk_script) -> Script<'fdisk> {
-        log::debug!("Script::new creating a new `Script` instance");
+    /// Wraps a raw `libfdisk::fdisk_script` pointer, without tying the new `Script` to the
+    /// lifetime of an [`Fdisk`]. `libfdisk` scripts hold a reference to the context they were
+    /// created from, keeping it alive as long as they are.
+    pub(crate) fn from_ptr(inner: *mut libfdisk::fdisk_script) -> Script<'fdisk> {
+        log::debug!("Script::from_ptr creating a new `Script` instance");
 
         Self {
             inner,
@@ -123,6 +131,314 @@ pub fn import_stream(&mut self, file: &mut File) -> Result<(), ScriptError> {
         Self::read_file(self, file)
     }
 
+    #[doc(hidden)]
+    /// Reads and parses a script's content in the `sfdisk` dump format from memory.
+    fn read_dump(ptr: &mut Self, dump: String) -> Result<(), ScriptError> {
+        let mut dump = dump.into_bytes();
+        let mode = CString::new("r")?;
+
+        let mut file_stream = MaybeUninit::<*mut libc::FILE>::zeroed();
+        unsafe {
+            file_stream.write(libc::fmemopen(
+                dump.as_mut_ptr() as *mut _,
+                dump.len(),
+                mode.as_ptr(),
+            ));
+        }
+
+        let file_stream = match unsafe { file_stream.assume_init() } {
+            ptr if ptr.is_null() => {
+                let err = io::Error::last_os_error();
+                log::debug!(
+                    "Script::read_dump failed to open in-memory stream. libc::fmemopen returned a NULL pointer: {:?}",
+                    err
+                );
+
+                return Err(ScriptError::IoError(err));
+            }
+            ptr => ptr,
+        };
+
+        let result = unsafe { libfdisk::fdisk_script_read_file(ptr.inner, file_stream as *mut _) };
+
+        unsafe {
+            libc::fclose(file_stream);
+        }
+
+        match result {
+            0 => {
+                log::debug!("Script::read_dump dump read");
+
+                Ok(())
+            }
+            code => {
+                let err_msg = "failed to read script dump".to_owned();
+                log::debug!("Script::read_dump {}. libfdisk::fdisk_script_read_file returned error code: {:?}", err_msg, code);
+
+                Err(ScriptError::Read(err_msg))
+            }
+        }
+    }
+
+    #[doc(hidden)]
+    /// Returns an error if `value` contains characters that would alter the structure of an
+    /// `sfdisk` dump.
+    pub(crate) fn check_dump_value(
+        key: &str,
+        value: &str,
+        separators: &[char],
+    ) -> Result<(), ScriptError> {
+        match value.find(|c: char| c == '\n' || c == '\r' || separators.contains(&c)) {
+            Some(_) => {
+                let err_msg = format!("unsupported character in value of {:?}: {:?}", key, value);
+
+                Err(ScriptError::Read(err_msg))
+            }
+            None => Ok(()),
+        }
+    }
+
+    #[doc(hidden)]
+    /// Quotes a string, escaping characters the same way `sfdisk` does when writing a dump.
+    pub(crate) fn quote_dump_string(value: &str) -> String {
+        let mut quoted = String::from("\"");
+
+        for byte in value.bytes() {
+            match byte {
+                b'"' | b'\\' | b'`' | b'$' => quoted.push_str(&format!("\\x{:02x}", byte)),
+                byte if byte.is_ascii_graphic() || byte == b' ' => quoted.push(byte as char),
+                byte => quoted.push_str(&format!("\\x{:02x}", byte)),
+            }
+        }
+
+        quoted.push('"');
+
+        quoted
+    }
+
+    #[cfg(feature = "serde")]
+    #[doc(hidden)]
+    /// Returns the `partitiontable` object of a partition table description in JSON.
+    pub(crate) fn json_partition_table(
+        root: &serde_json::Value,
+    ) -> Result<&serde_json::Map<String, serde_json::Value>, ScriptError> {
+        root.get("partitiontable")
+            .and_then(|table| table.as_object())
+            .ok_or_else(|| {
+                ScriptError::Read("missing `partitiontable` object in JSON document".to_owned())
+            })
+    }
+
+    #[cfg(feature = "serde")]
+    #[doc(hidden)]
+    /// Converts the name of a header in a JSON dump to its name in an `sfdisk` dump.
+    pub(crate) fn json_header_name(key: &str) -> &str {
+        match key {
+            "id" => "label-id",
+            "firstlba" => "first-lba",
+            "lastlba" => "last-lba",
+            "sectorsize" => "sector-size",
+            name => name,
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[doc(hidden)]
+    /// Returns the string, number, or boolean value of a header or partition field as a string.
+    pub(crate) fn json_scalar(key: &str, value: &serde_json::Value) -> Result<String, ScriptError> {
+        match value {
+            serde_json::Value::String(value) => Ok(value.clone()),
+            serde_json::Value::Number(value) => Ok(value.to_string()),
+            serde_json::Value::Bool(value) => Ok(value.to_string()),
+            _ => Err(ScriptError::Read(format!(
+                "unsupported value for {:?}",
+                key
+            ))),
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[doc(hidden)]
+    /// Converts a partition table description in JSON to the `sfdisk` dump format.
+    fn json_to_dump(content: &str) -> Result<String, ScriptError> {
+        let root: serde_json::Value =
+            serde_json::from_str(content).map_err(|e| ScriptError::Read(e.to_string()))?;
+        let table = Self::json_partition_table(&root)?;
+
+        let mut dump = String::new();
+
+        // Headers
+        for (key, value) in table.iter().filter(|(key, _)| *key != "partitions") {
+            let value = Self::json_scalar(key, value)?;
+            Self::check_dump_value(key, &value, &[])?;
+
+            dump.push_str(&format!("{}: {}\n", Self::json_header_name(key), value));
+        }
+
+        dump.push('\n');
+
+        // Partitions
+        let partitions = match table.get("partitions") {
+            Some(value) => value
+                .as_array()
+                .ok_or_else(|| ScriptError::Read("`partitions` is not a JSON array".to_owned()))?
+                .as_slice(),
+            None => &[],
+        };
+
+        for partition in partitions {
+            let members = partition.as_object().ok_or_else(|| {
+                ScriptError::Read("partition entry is not a JSON object".to_owned())
+            })?;
+
+            let mut node = None;
+            let mut fields = Vec::new();
+
+            for (key, value) in members {
+                match key.as_str() {
+                    "node" => {
+                        let value = Self::json_scalar(key, value)?;
+                        Self::check_dump_value(key, &value, &[':', '=', ','])?;
+                        node = Some(value);
+                    }
+                    "start" | "size" => {
+                        let sectors = value.as_u64().ok_or_else(|| {
+                            ScriptError::Read(format!(
+                                "invalid value for partition {:?}: {}",
+                                key, value
+                            ))
+                        })?;
+                        fields.push(format!("{}={}", key, sectors));
+                    }
+                    "type" | "uuid" => {
+                        let value = Self::json_scalar(key, value)?;
+                        Self::check_dump_value(key, &value, &[',', '"', ' ', '\t'])?;
+                        fields.push(format!("{}={}", key, value));
+                    }
+                    "name" => {
+                        let value = Self::json_scalar(key, value)?;
+                        fields.push(format!("name={}", Self::quote_dump_string(&value)));
+                    }
+                    "attrs" => {
+                        let value = Self::json_scalar(key, value)?;
+                        Self::check_dump_value(key, &value, &['"'])?;
+                        fields.push(format!("attrs=\"{}\"", value));
+                    }
+                    "bootable" => match value.as_bool() {
+                        Some(true) => fields.push("bootable".to_owned()),
+                        Some(false) => {}
+                        None => {
+                            let err_msg = "invalid value for partition \"bootable\"".to_owned();
+
+                            return Err(ScriptError::Read(err_msg));
+                        }
+                    },
+                    unknown => {
+                        log::debug!(
+                            "Script::json_to_dump ignoring unknown partition field: {:?}",
+                            unknown
+                        );
+                    }
+                }
+            }
+
+            match node {
+                Some(node) => dump.push_str(&format!("{} : {}\n", node, fields.join(", "))),
+                None => dump.push_str(&format!("{}\n", fields.join(", "))),
+            }
+        }
+
+        log::debug!("Script::json_to_dump converted JSON to dump:\n{}", dump);
+
+        Ok(dump)
+    }
+
+    #[cfg(feature = "serde")]
+    #[doc(hidden)]
+    /// Reads and parses a partition table description in JSON.
+    fn read_json(ptr: &mut Self, content: &str) -> Result<(), ScriptError> {
+        let dump = Self::json_to_dump(content)?;
+
+        Self::read_dump(ptr, dump)
+    }
+
+    /// Imports the content of a [`ScriptDocument`].
+    pub fn import_document(&mut self, document: &ScriptDocument) -> Result<(), ScriptError> {
+        log::debug!("Script::import_document importing document: {:?}", document);
+
+        document.validate()?;
+
+        Self::read_dump(self, document.to_string())
+    }
+
+    #[cfg(feature = "serde")]
+    /// Imports a partition table description in the JSON format produced by `sfdisk --json`, or
+    /// by exporting a `Script` after calling [`Script::enable_json_output`].
+    ///
+    /// Headers are passed on to `libfdisk` as they are, while unknown partition fields are
+    /// ignored. Requires the `serde` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use rsfdisk::fdisk::Fdisk;
+    ///
+    /// fn main() -> rsfdisk::Result<()> {
+    ///     let disk = Fdisk::builder()
+    ///         .assign_device("/dev/vda")
+    ///         .build()?;
+    ///
+    ///     let script = disk.script_new()?;
+    ///     script.import_json(r#"{
+    ///         "partitiontable": {
+    ///             "label": "gpt",
+    ///             "unit": "sectors",
+    ///             "partitions": [
+    ///                 {"start": 2048, "size": 204800, "type": "C12A7328-F81F-11D2-BA4B-00A0C93EC93B", "name": "EFI System"},
+    ///                 {"start": 206848, "type": "0FC63DAF-8483-4772-8E79-3D69D8477DE4"}
+    ///             ]
+    ///         }
+    ///     }"#)?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn import_json<T>(&mut self, content: T) -> Result<(), ScriptError>
+    where
+        T: AsRef<str>,
+    {
+        log::debug!("Script::import_json importing JSON document");
+
+        Self::read_json(self, content.as_ref())
+    }
+
+    #[cfg(feature = "serde")]
+    /// Imports a partition table description in JSON from the file at `file_path` (see
+    /// [`Script::import_json`]).
+    pub fn import_json_file<T>(&mut self, file_path: T) -> Result<(), ScriptError>
+    where
+        T: AsRef<Path>,
+    {
+        let file_path = file_path.as_ref();
+        log::debug!("Script::import_json_file importing file: {:?}", file_path);
+
+        let content = fs::read_to_string(file_path)?;
+
+        Self::read_json(self, &content)
+    }
+
+    #[cfg(feature = "serde")]
+    /// Imports a partition table description in JSON from an open [`File`] (see
+    /// [`Script::import_json`]).
+    pub fn import_json_stream(&mut self, file: &mut File) -> Result<(), ScriptError> {
+        log::debug!("Script::import_json_stream importing from file");
+
+        let mut content = String::new();
+        file.read_to_string(&mut content)?;
+
+        Self::read_json(self, &content)
+    }
+
     /// Sets a callback function (`fn_read_line`) for reading characters from a [`File`] stream, and storing them in
     /// a buffer. Reading should stop if a newline character is found (in which case the buffer
     /// will contain that newline character), or if we reach the end of the file.
@@ -394,6 +710,11 @@ fn write_file(ptr: &mut Self, file: &mut File) -> Result<(), ScriptError> {
 
         let result = unsafe { libfdisk::fdisk_script_write_file(ptr.inner, file_stream as *mut _) };
 
+        // Push buffered data to the file, so that it is visible to readers right away.
+        unsafe {
+            libc::fflush(file_stream);
+        }
+
         match result {
             0 => {
                 log::debug!("Script::write_file wrote script to file");
@@ -57,3 +57,18 @@ pub fn partition_name<T>(device_name: T, partition_number: usize) -> Option<Stri
         }
     }
 }
+
+#[doc(hidden)]
+/// Computes the CRC32 (IEEE 802.3) checksum of `bytes`, as used by `GPT` headers and Partition
+/// Entry Arrays.
+pub(crate) fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0u32, |crc, &byte| {
+        (0..8).fold(crc ^ u32::from(byte), |crc, _| {
+            if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            }
+        })
+    })
+}
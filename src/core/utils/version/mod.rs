@@ -19,13 +19,13 @@
 pub static VERSION_STRING: Lazy<&str> = Lazy::new(|| libfdisk::LIBFDISK_VERSION.to_str().unwrap());
 
 /// Semantic version major number e.g. `2`.
-pub static VERSION_NUMBER_MAJOR: u32 = libfdisk::LIBFDISK_MAJOR_VERSION as u32;
+pub static VERSION_NUMBER_MAJOR: u32 = libfdisk::LIBFDISK_MAJOR_VERSION;
 
 /// Semantic version minor number e.g. `39`.
-pub static VERSION_NUMBER_MINOR: u32 = libfdisk::LIBFDISK_MINOR_VERSION as u32;
+pub static VERSION_NUMBER_MINOR: u32 = libfdisk::LIBFDISK_MINOR_VERSION;
 
 /// Semantic version patch number e.g. `4`.
-pub static VERSION_NUMBER_PATCH: u32 = libfdisk::LIBFDISK_PATCH_VERSION as u32;
+pub static VERSION_NUMBER_PATCH: u32 = libfdisk::LIBFDISK_PATCH_VERSION;
 
 /// Converts a version string to the corresponding release code.
 ///
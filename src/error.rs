@@ -12,6 +12,7 @@
 use crate::core::errors::ConversionError;
 use crate::core::errors::GenIteratorError;
 use crate::core::errors::HeaderEntryContentError;
+use crate::core::errors::LayoutError;
 use crate::core::errors::ParserError;
 use crate::core::errors::PartitionBuilderError;
 use crate::core::errors::PartitionError;
@@ -21,6 +22,7 @@
 use crate::core::errors::PartitionListError;
 use crate::core::errors::PartitionTableError;
 use crate::core::errors::PromptError;
+use crate::core::errors::ScriptDocumentError;
 use crate::core::errors::ScriptError;
 
 use crate::fdisk::FdiskBuilderError;
@@ -67,6 +69,9 @@ pub enum RsFdiskError {
     #[error(transparent)]
     HeaderEntryContent(#[from] HeaderEntryContentError),
 
+    #[error(transparent)]
+    Layout(#[from] LayoutError),
+
     #[error(transparent)]
     Parser(#[from] ParserError),
 
@@ -97,6 +102,9 @@ pub enum RsFdiskError {
     #[error(transparent)]
     Script(#[from] ScriptError),
 
+    #[error(transparent)]
+    ScriptDocument(#[from] ScriptDocumentError),
+
     #[error(transparent)]
     Version(#[from] VersionError),
 }
@@ -0,0 +1,279 @@
+// Copyright (c) 2023 Nick Piaddo
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// From dependency library
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// From standard library
+use std::fmt;
+use std::path::Path;
+use std::path::PathBuf;
+
+// From this library
+use crate::core::partition_table::PartitionTableKind;
+use crate::fdisk::Fdisk;
+
+/// Summary of the characteristics of the device assigned to an [`Fdisk`] (see
+/// [`Fdisk::device_report`]).
+///
+/// Its `Display` implementation mimics the header printed by `fdisk -l`.
+///
+/// # Examples
+///
+/// ```ignore
+/// use rsfdisk::fdisk::Fdisk;
+///
+/// fn main() -> rsfdisk::Result<()> {
+///     let disk = Fdisk::builder().assign_device("/dev/sda").build()?;
+///
+///     println!("{}", disk.device_report());
+///
+///     // Example output
+///     //
+///     // Disk /dev/sda: 465.76 GiB, 500107862016 bytes, 976773168 sectors
+///     // Disk model: Samsung SSD 870
+///     // Units: sectors of 1 * 512 = 512 bytes
+///     // Sector size (logical/physical): 512 bytes / 512 bytes
+///     // I/O size (minimum/optimal): 512 bytes / 512 bytes
+///     // Disklabel type: gpt
+///     // Disk identifier: DD27F98D-7519-4C9E-8041-F2BFA7B1EF61
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DeviceReport {
+    name: Option<PathBuf>,
+    model: Option<String>,
+    size_in_bytes: u64,
+    size_in_sectors: u64,
+    bytes_per_logical_sector: u64,
+    bytes_per_physical_sector: u64,
+    minimum_io_size: u64,
+    optimal_io_size: u64,
+    alignment_offset: u64,
+    cylinders: u64,
+    heads: u64,
+    sectors_per_track: u64,
+    unit: String,
+    sectors_per_unit: u64,
+    partition_table_kind: Option<PartitionTableKind>,
+    partition_table_id: Option<String>,
+}
+
+impl DeviceReport {
+    #[doc(hidden)]
+    /// Collects the characteristics of the device assigned to `disk`.
+    pub(crate) fn new(disk: &Fdisk) -> DeviceReport {
+        let partition_table_kind = if disk.device_has_partition_table() {
+            disk.partition_table_current().map(|table| table.kind())
+        } else {
+            None
+        };
+        let partition_table_id = partition_table_kind.and_then(|_| disk.partition_table_id());
+
+        Self {
+            name: disk.device_name().map(Path::to_path_buf),
+            model: disk.device_model().map(String::from),
+            size_in_bytes: disk.device_size_in_bytes(),
+            size_in_sectors: disk.device_size_in_sectors(),
+            bytes_per_logical_sector: disk.device_bytes_per_logical_sector(),
+            bytes_per_physical_sector: disk.device_bytes_per_physical_sector(),
+            minimum_io_size: disk.device_minimum_io_size(),
+            optimal_io_size: disk.device_optimal_io_size(),
+            alignment_offset: disk.device_alignment_offset(),
+            cylinders: disk.device_count_cylinders(),
+            heads: disk.device_count_heads(),
+            sectors_per_track: disk.device_count_sectors(),
+            unit: disk.displayed_unit_plural().to_owned(),
+            sectors_per_unit: disk.sectors_per_cylinder(),
+            partition_table_kind,
+            partition_table_id,
+        }
+    }
+
+    /// Returns the device's name.
+    pub fn name(&self) -> Option<&Path> {
+        self.name.as_deref()
+    }
+
+    /// Returns the device's model.
+    pub fn model(&self) -> Option<&str> {
+        self.model.as_deref()
+    }
+
+    /// Returns the device's size in bytes.
+    pub fn size_in_bytes(&self) -> u64 {
+        self.size_in_bytes
+    }
+
+    /// Returns the device's size in logical sectors.
+    pub fn size_in_sectors(&self) -> u64 {
+        self.size_in_sectors
+    }
+
+    /// Returns the number of bytes per logical sector.
+    pub fn bytes_per_logical_sector(&self) -> u64 {
+        self.bytes_per_logical_sector
+    }
+
+    /// Returns the number of bytes per physical sector.
+    pub fn bytes_per_physical_sector(&self) -> u64 {
+        self.bytes_per_physical_sector
+    }
+
+    /// Returns the device's minimum I/O size in bytes.
+    pub fn minimum_io_size(&self) -> u64 {
+        self.minimum_io_size
+    }
+
+    /// Returns the device's optimal I/O size in bytes.
+    pub fn optimal_io_size(&self) -> u64 {
+        self.optimal_io_size
+    }
+
+    /// Returns the offset in bytes between logical and physical sectors.
+    pub fn alignment_offset(&self) -> u64 {
+        self.alignment_offset
+    }
+
+    /// Returns the number of cylinders of the device's geometry.
+    pub fn cylinders(&self) -> u64 {
+        self.cylinders
+    }
+
+    /// Returns the number of heads of the device's geometry.
+    pub fn heads(&self) -> u64 {
+        self.heads
+    }
+
+    /// Returns the number of sectors per track of the device's geometry.
+    pub fn sectors_per_track(&self) -> u64 {
+        self.sectors_per_track
+    }
+
+    /// Returns the unit, in plural form, in which numerical metadata is displayed.
+    pub fn unit(&self) -> &str {
+        &self.unit
+    }
+
+    /// Returns the number of sectors in a [`DeviceReport::unit`].
+    pub fn sectors_per_unit(&self) -> u64 {
+        self.sectors_per_unit
+    }
+
+    /// Returns the type of partition table on the device, if any.
+    pub fn partition_table_kind(&self) -> Option<PartitionTableKind> {
+        self.partition_table_kind
+    }
+
+    /// Returns the identifier of the partition table on the device, if any.
+    pub fn partition_table_id(&self) -> Option<&str> {
+        self.partition_table_id.as_deref()
+    }
+}
+
+impl fmt::Display for DeviceReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = self
+            .name
+            .as_ref()
+            .map(|name| name.display().to_string())
+            .unwrap_or_default();
+
+        writeln!(
+            f,
+            "Disk {}: {}, {} bytes, {} sectors",
+            name,
+            human_size(self.size_in_bytes),
+            self.size_in_bytes,
+            self.size_in_sectors
+        )?;
+
+        if let Some(model) = self.model.as_ref() {
+            writeln!(f, "Disk model: {}", model)?;
+        }
+
+        writeln!(
+            f,
+            "Units: {} of {} * {} = {} bytes",
+            self.unit,
+            self.sectors_per_unit,
+            self.bytes_per_logical_sector,
+            self.sectors_per_unit * self.bytes_per_logical_sector
+        )?;
+        writeln!(
+            f,
+            "Sector size (logical/physical): {} bytes / {} bytes",
+            self.bytes_per_logical_sector, self.bytes_per_physical_sector
+        )?;
+        write!(
+            f,
+            "I/O size (minimum/optimal): {} bytes / {} bytes",
+            self.minimum_io_size, self.optimal_io_size
+        )?;
+
+        if self.alignment_offset != 0 {
+            write!(f, "\nAlignment offset: {} bytes", self.alignment_offset)?;
+        }
+
+        if let Some(kind) = self.partition_table_kind {
+            write!(f, "\nDisklabel type: {}", kind)?;
+        }
+
+        if let Some(id) = self.partition_table_id.as_ref() {
+            write!(f, "\nDisk identifier: {}", id)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[doc(hidden)]
+/// Converts a size in bytes to a string with a binary unit suffix, rounded to two decimal places
+/// (e.g. `465.76 GiB`).
+fn human_size(bytes: u64) -> String {
+    const UNITS: [&str; 7] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+
+    let exponent = (0..UNITS.len())
+        .rev()
+        .find(|&exp| bytes >> (10 * exp) > 0)
+        .unwrap_or(0);
+
+    if exponent == 0 {
+        return format!("{} {}", bytes, UNITS[0]);
+    }
+
+    let value = format!("{:.2}", bytes as f64 / (1u64 << (10 * exponent)) as f64);
+    let value = value.trim_end_matches('0').trim_end_matches('.');
+
+    format!("{} {}", value, UNITS[exponent])
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::*;
+    use pretty_assertions::{assert_eq, assert_ne};
+
+    #[test]
+    fn device_report_displays_sizes_in_binary_units() {
+        let actual = human_size(512);
+        let expected = "512 B";
+        assert_eq!(actual, expected);
+
+        let actual = human_size(10 * 1024 * 1024);
+        let expected = "10 MiB";
+        assert_eq!(actual, expected);
+
+        let actual = human_size(1536);
+        let expected = "1.5 KiB";
+        assert_eq!(actual, expected);
+
+        let actual = human_size(500_107_862_016);
+        let expected = "465.76 GiB";
+        assert_eq!(actual, expected);
+    }
+}
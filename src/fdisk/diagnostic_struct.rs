@@ -0,0 +1,67 @@
+// Copyright (c) 2023 Nick Piaddo
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// From dependency library
+
+// From standard library
+use std::fmt;
+
+// From this library
+use crate::core::prompt::PromptKind;
+
+/// Information or warning message emitted by an [`Fdisk`](crate::fdisk::Fdisk).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Diagnostic {
+    kind: PromptKind,
+    message: String,
+    error_number: i32,
+}
+
+impl Diagnostic {
+    #[doc(hidden)]
+    /// Creates a new `Diagnostic`.
+    pub(crate) fn new(kind: PromptKind, message: String, error_number: i32) -> Diagnostic {
+        Self {
+            kind,
+            message,
+            error_number,
+        }
+    }
+
+    /// Returns the kind of message, one of [`PromptKind::Info`], [`PromptKind::Warn`], or
+    /// [`PromptKind::WarnX`].
+    pub fn kind(&self) -> PromptKind {
+        self.kind
+    }
+
+    /// Returns the content of the message.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the error number (`errno`) attached to a [`PromptKind::Warn`] message, `0`
+    /// otherwise.
+    pub fn error_number(&self) -> i32 {
+        self.error_number
+    }
+
+    /// Returns `true` if this `Diagnostic` is a warning.
+    pub fn is_warning(&self) -> bool {
+        matches!(self.kind, PromptKind::Warn | PromptKind::WarnX)
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            PromptKind::Warn if self.error_number != 0 => write!(
+                f,
+                "warning: {}: {}",
+                self.message,
+                std::io::Error::from_raw_os_error(self.error_number)
+            ),
+            PromptKind::Warn | PromptKind::WarnX => write!(f, "warning: {}", self.message),
+            _ => write!(f, "{}", self.message),
+        }
+    }
+}
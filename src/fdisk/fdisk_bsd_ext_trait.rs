@@ -4,12 +4,67 @@
 // From dependency library
 
 // From standard library
+use std::fs::OpenOptions;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
 
 // From this library
+use crate::core::partition_table::BsdDiskLabelFields;
+use crate::core::partition_table::PartitionTableKind;
 use crate::core::private::Sealed;
+use crate::core::prompt::Answer;
+use crate::core::prompt::AnswerQueue;
+use crate::core::prompt::PromptKind;
+use crate::core::prompt::QueryMatcher;
 use crate::fdisk::Fdisk;
 use crate::fdisk::FdiskError;
 
+use crate::ffi_utils;
+
+/// Offset of a `BSD disklabel` from the start of its partition, or of the device if it is not
+/// nested within a `DOS` partition.
+const BSD_LABEL_OFFSET: u64 = 512;
+
+/// Size of a `BSD disklabel` with the maximum number of partitions, in bytes.
+const BSD_LABEL_SIZE: usize = 148 + 16 * BSD_MAX_PARTITIONS;
+
+/// Maximum number of partitions in a `BSD disklabel`.
+const BSD_MAX_PARTITIONS: usize = 16;
+
+/// `BSD disklabel` magic number, stored at the beginning of the disklabel and after the spare
+/// fields.
+const BSD_LABEL_MAGIC: u32 = 0x82564557;
+
+/// Offset of the drive type in a `BSD disklabel`.
+const BSD_TYPE_OFFSET: usize = 4;
+
+/// Offset of the pack name in a `BSD disklabel`.
+const BSD_PACK_NAME_OFFSET: usize = 24;
+
+/// Maximum length of a `BSD` pack name.
+const BSD_PACK_NAME_MAX_LEN: usize = 16;
+
+/// Offset of the sector size in a `BSD disklabel`.
+const BSD_SECTOR_SIZE_OFFSET: usize = 40;
+
+/// Offset of the second magic number in a `BSD disklabel`.
+const BSD_MAGIC2_OFFSET: usize = 132;
+
+/// Offset of the checksum in a `BSD disklabel`.
+const BSD_CHECKSUM_OFFSET: usize = 136;
+
+/// Offset of the number of partitions in a `BSD disklabel`.
+const BSD_NPARTITIONS_OFFSET: usize = 138;
+
+/// Offset of the partition entries in a `BSD disklabel`.
+const BSD_PARTITIONS_OFFSET: usize = 148;
+
+/// `DOS` partition types of partitions holding a nested `BSD disklabel` (FreeBSD, OpenBSD,
+/// NetBSD).
+const BSD_DOS_PARTITION_TYPES: [u32; 3] = [0xa5, 0xa6, 0xa9];
+
 /// `BSD` specific functions for a [`Fdisk`].
 ///
 /// This trait is sealed and can not be implemented for types outside of `rsfdisk`.
@@ -22,6 +77,226 @@ pub trait FdiskBSDExt: Sealed {
 
     /// Installs a `BSD` bootstrap file on the assigned device.
     fn bsd_install_bootstrap_file(&mut self) -> Result<(), FdiskError>;
+
+    /// Sets the fields of a `BSD disklabel` without going through interactive dialogs. Fields
+    /// not set in `fields` keep their current value.
+    ///
+    /// `libfdisk` does not provide a way to change the drive type, pack name, or sector size of a
+    /// disklabel. If one of them is set, this method writes the disklabel, with all pending
+    /// changes, to the assigned device, updates these fields and the disklabel's checksum in
+    /// place, then reloads the disklabel. Pending changes to the parent `DOS` partition table, if
+    /// any, are discarded.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use rsfdisk::core::partition_table::BsdDiskLabelFields;
+    /// use rsfdisk::fdisk::Fdisk;
+    /// use rsfdisk::fdisk::FdiskBSDExt;
+    ///
+    /// fn main() -> rsfdisk::Result<()> {
+    ///     let mut disk = Fdisk::builder()
+    ///         .assign_device("/dev/vda")
+    ///         .enable_read_write()
+    ///         .build()?;
+    ///
+    ///     let mut bsd = disk.create_nested_partitioner_with_name("bsd")?;
+    ///
+    ///     let fields = BsdDiskLabelFields::new().rpm(7200).interleave(1);
+    ///     bsd.bsd_set_disk_label_fields(fields)?;
+    ///     bsd.partition_table_write_to_disk()?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    fn bsd_set_disk_label_fields(&mut self, fields: BsdDiskLabelFields) -> Result<(), FdiskError>;
+
+    /// Makes the partition `bsd_partition` (`0` for `a`, `1` for `b`, etc.) of a nested `BSD
+    /// disklabel` cover the same sectors as the partition `dos_partition` of its parent `DOS`
+    /// partition table, without going through interactive dialogs.
+    fn bsd_link_dos_partition(
+        &mut self,
+        dos_partition: usize,
+        bsd_partition: usize,
+    ) -> Result<(), FdiskError>;
+
+    /// Installs the `BSD` bootstrap programs `/usr/ucb/mdec/<name>boot` and
+    /// `/usr/ucb/mdec/boot<name>` on the assigned device, without going through interactive
+    /// dialogs (e.g. `"sd"` for `/usr/ucb/mdec/sdboot` and `/usr/ucb/mdec/bootsd`).
+    ///
+    /// **Note:** the directory holding bootstrap programs is set when `libfdisk` is compiled.
+    fn bsd_install_bootstrap<T>(&mut self, name: T) -> Result<(), FdiskError>
+    where
+        T: AsRef<str>;
+}
+
+#[doc(hidden)]
+/// Returns an error if the drive type, pack name, or sector size in `fields` can not be written to
+/// a `BSD disklabel`.
+fn bsd_check_disk_label_patch(fields: &BsdDiskLabelFields) -> Result<(), FdiskError> {
+    if let Some(name) = fields.pack_name.as_deref() {
+        if name.len() > BSD_PACK_NAME_MAX_LEN
+            || !name.bytes().all(|b| b.is_ascii_graphic() || b == b' ')
+        {
+            let err_msg = format!(
+                "invalid BSD pack name: {:?}, expected at most {} printable ASCII characters",
+                name, BSD_PACK_NAME_MAX_LEN
+            );
+            log::debug!("Fdisk::bsd_check_disk_label_patch {}", err_msg);
+
+            return Err(FdiskError::Config(err_msg));
+        }
+    }
+
+    if let Some(size) = fields.sector_size {
+        if size < 512 || !size.is_power_of_two() {
+            let err_msg = format!(
+                "invalid BSD sector size: {:?}, expected a power of 2 greater than or equal to 512",
+                size
+            );
+            log::debug!("Fdisk::bsd_check_disk_label_patch {}", err_msg);
+
+            return Err(FdiskError::Config(err_msg));
+        }
+    }
+
+    Ok(())
+}
+
+#[doc(hidden)]
+/// Writes the `BSD disklabel` of `disk` to its assigned device, sets the drive type, pack name,
+/// and sector size in `fields`, updates its checksum, then reloads it.
+fn bsd_patch_disk_label(disk: &mut Fdisk, fields: &BsdDiskLabelFields) -> Result<(), FdiskError> {
+    if !disk.partition_table_is_of_type(PartitionTableKind::BSD) {
+        let err_msg = "no BSD disklabel on assigned device".to_owned();
+        log::debug!("Fdisk::bsd_patch_disk_label {}", err_msg);
+
+        return Err(FdiskError::Config(err_msg));
+    }
+
+    // `libfdisk` looks for a nested disklabel in the first primary partition with a BSD type.
+    let (device, offset) = match disk.parent_partitioner() {
+        Some(parent) => {
+            let partition = parent.list_partitions().and_then(|partitions| {
+                partitions.iter().find_map(|partition| {
+                    let number = partition.number()?;
+                    let code = partition.partition_type()?.code()?;
+
+                    if number < 4 && BSD_DOS_PARTITION_TYPES.contains(&code) {
+                        partition.starting_sector()
+                    } else {
+                        None
+                    }
+                })
+            });
+
+            match (parent.device_name(), partition) {
+                (Some(device), Some(start)) => (
+                    device.to_path_buf(),
+                    start * parent.device_bytes_per_logical_sector() + BSD_LABEL_OFFSET,
+                ),
+                _ => {
+                    let err_msg = "no BSD partition found in parent partition table".to_owned();
+                    log::debug!("Fdisk::bsd_patch_disk_label {}", err_msg);
+
+                    return Err(FdiskError::Config(err_msg));
+                }
+            }
+        }
+        None => match disk.device_name() {
+            Some(device) => (device.to_path_buf(), BSD_LABEL_OFFSET),
+            None => {
+                let err_msg = "no device assigned".to_owned();
+                log::debug!("Fdisk::bsd_patch_disk_label {}", err_msg);
+
+                return Err(FdiskError::Config(err_msg));
+            }
+        },
+    };
+
+    disk.partition_table_write_to_disk()?;
+
+    let mut file = OpenOptions::new().read(true).write(true).open(&device)?;
+
+    let mut label = [0u8; BSD_LABEL_SIZE];
+    file.seek(SeekFrom::Start(offset))?;
+    file.read_exact(&mut label)?;
+
+    // `libfdisk` stores disklabel fields in native byte order.
+    let read_u32 = |label: &[u8], at: usize| {
+        u32::from_ne_bytes([label[at], label[at + 1], label[at + 2], label[at + 3]])
+    };
+    let partition_count = u16::from_ne_bytes([
+        label[BSD_NPARTITIONS_OFFSET],
+        label[BSD_NPARTITIONS_OFFSET + 1],
+    ]) as usize;
+
+    if read_u32(&label, 0) != BSD_LABEL_MAGIC
+        || read_u32(&label, BSD_MAGIC2_OFFSET) != BSD_LABEL_MAGIC
+        || partition_count > BSD_MAX_PARTITIONS
+    {
+        let err_msg = format!(
+            "no BSD disklabel found on device: {:?} at offset: {:?}",
+            device, offset
+        );
+        log::debug!("Fdisk::bsd_patch_disk_label {}", err_msg);
+
+        return Err(FdiskError::Config(err_msg));
+    }
+
+    if let Some(drive_type) = fields.drive_type {
+        label[BSD_TYPE_OFFSET..BSD_TYPE_OFFSET + 2]
+            .copy_from_slice(&(drive_type as u16).to_ne_bytes());
+    }
+
+    if let Some(name) = fields.pack_name.as_deref() {
+        let field = &mut label[BSD_PACK_NAME_OFFSET..BSD_PACK_NAME_OFFSET + BSD_PACK_NAME_MAX_LEN];
+        field.fill(0);
+        field[..name.len()].copy_from_slice(name.as_bytes());
+    }
+
+    if let Some(size) = fields.sector_size {
+        label[BSD_SECTOR_SIZE_OFFSET..BSD_SECTOR_SIZE_OFFSET + 4]
+            .copy_from_slice(&size.to_ne_bytes());
+    }
+
+    // The checksum is the XOR of all 16-bit words from the beginning of the disklabel to the end
+    // of its last partition entry, computed with the checksum set to zero.
+    label[BSD_CHECKSUM_OFFSET..BSD_CHECKSUM_OFFSET + 2].fill(0);
+    let mut checksum = [0u8; 2];
+    for (i, byte) in label[..BSD_PARTITIONS_OFFSET + 16 * partition_count]
+        .iter()
+        .enumerate()
+    {
+        checksum[i % 2] ^= byte;
+    }
+    label[BSD_CHECKSUM_OFFSET..BSD_CHECKSUM_OFFSET + 2].copy_from_slice(&checksum);
+
+    file.seek(SeekFrom::Start(offset))?;
+    file.write_all(&label)?;
+    file.sync_all()?;
+
+    // `libfdisk` redirects device assignments on a nested context to its parent, then probes the
+    // nested disklabel again.
+    let device_path = ffi_utils::as_ref_path_to_c_string(&device)?;
+    let read_only = disk.device_is_read_only();
+    let result = unsafe {
+        libfdisk::fdisk_assign_device(disk.inner, device_path.as_ptr(), read_only as i32)
+    };
+
+    match result {
+        0 => {
+            log::debug!("Fdisk::bsd_patch_disk_label reloaded BSD disklabel");
+
+            Ok(())
+        }
+        code => {
+            let err_msg = format!("failed to reload BSD disklabel from device: {:?}", device);
+            log::debug!("Fdisk::bsd_patch_disk_label {}. libfdisk::fdisk_assign_device returned error code: {:?}", err_msg, code);
+
+            Err(FdiskError::Config(err_msg))
+        }
+    }
 }
 
 impl<'a> FdiskBSDExt for Fdisk<'a> {
@@ -88,4 +363,133 @@ fn bsd_install_bootstrap_file(&mut self) -> Result<(), FdiskError> {
             }
         }
     }
+
+    fn bsd_set_disk_label_fields(&mut self, fields: BsdDiskLabelFields) -> Result<(), FdiskError> {
+        log::debug!(
+            "Fdisk::bsd_set_disk_label_fields setting BSD disklabel fields: {:?}",
+            fields
+        );
+
+        bsd_check_disk_label_patch(&fields)?;
+
+        self.answer_dialogs_with(fields.to_answer_queue(), |disk| disk.bsd_edit_disk_label())?;
+
+        if fields.patches_disk_label() {
+            bsd_patch_disk_label(self, &fields)?;
+        }
+
+        log::debug!("Fdisk::bsd_set_disk_label_fields set BSD disklabel fields");
+
+        Ok(())
+    }
+
+    fn bsd_link_dos_partition(
+        &mut self,
+        dos_partition: usize,
+        bsd_partition: usize,
+    ) -> Result<(), FdiskError> {
+        log::debug!(
+            "Fdisk::bsd_link_dos_partition linking BSD partition {:?} to DOS partition {:?}",
+            bsd_partition,
+            dos_partition
+        );
+
+        let max_partitions = self.partition_table_max_partitions();
+        if bsd_partition >= max_partitions {
+            let err_msg = format!(
+                "BSD partition number out of range: {:?}, must be less than {:?}",
+                bsd_partition, max_partitions
+            );
+            log::debug!("Fdisk::bsd_link_dos_partition {}", err_msg);
+
+            return Err(FdiskError::Config(err_msg));
+        }
+
+        let dos_partitions = match self.parent_partitioner() {
+            Some(parent) => parent.list_partitions(),
+            None => {
+                let err_msg = "BSD disklabel is not nested within a DOS partition".to_owned();
+                log::debug!("Fdisk::bsd_link_dos_partition {}", err_msg);
+
+                return Err(FdiskError::Config(err_msg));
+            }
+        };
+
+        // Partition numbers are 1-based in dialogs.
+        let partition_number = || QueryMatcher::Exact("Partition number".to_owned());
+        let mut answers = AnswerQueue::new();
+
+        // `libfdisk` selects the only partition of the parent partition table without asking.
+        match dos_partitions {
+            Some(partitions) if partitions.len() == 1 => {
+                let selected = partitions[0].number();
+                if selected != Some(dos_partition) {
+                    let err_msg = format!(
+                        "DOS partition {:?} not found, only partition: {:?}",
+                        dos_partition, selected
+                    );
+                    log::debug!("Fdisk::bsd_link_dos_partition {}", err_msg);
+
+                    return Err(FdiskError::Config(err_msg));
+                }
+            }
+            _ => {
+                answers = answers.expect(
+                    PromptKind::Number,
+                    partition_number(),
+                    Answer::Number(dos_partition as u64 + 1),
+                );
+            }
+        }
+
+        let answers = answers.expect(
+            PromptKind::Number,
+            partition_number(),
+            Answer::Number(bsd_partition as u64 + 1),
+        );
+
+        self.answer_dialogs_with(answers, |disk| disk.bsd_link_to_nested_partition())?;
+
+        log::debug!(
+            "Fdisk::bsd_link_dos_partition linked BSD partition {:?} to DOS partition {:?}",
+            bsd_partition,
+            dos_partition
+        );
+
+        Ok(())
+    }
+
+    fn bsd_install_bootstrap<T>(&mut self, name: T) -> Result<(), FdiskError>
+    where
+        T: AsRef<str>,
+    {
+        let name = name.as_ref();
+        log::debug!(
+            "Fdisk::bsd_install_bootstrap installing BSD bootstrap programs: {:?}",
+            name
+        );
+
+        // An empty answer selects libfdisk's default bootstrap programs.
+        if name.is_empty() {
+            let err_msg = "missing BSD bootstrap program name".to_owned();
+            log::debug!("Fdisk::bsd_install_bootstrap {}", err_msg);
+
+            return Err(FdiskError::Config(err_msg));
+        }
+
+        let answers = AnswerQueue::new().expect(
+            PromptKind::String,
+            QueryMatcher::Contains("Bootstrap:".to_owned()),
+            Answer::String(name.to_owned()),
+        );
+
+        self.answer_dialogs_with(answers, |disk| disk.bsd_install_bootstrap_file())?;
+
+        log::debug!(
+            "Fdisk::bsd_install_bootstrap installed BSD bootstrap programs: {:?}",
+            name
+        );
+
+        Ok(())
+    }
 }
@@ -9,6 +9,7 @@
 use std::path::{Path, PathBuf};
 
 // From this library
+use crate::core::prompt::PromptHandler;
 use crate::fdisk::DeviceAddressing;
 use crate::fdisk::Fdisk;
 use crate::fdisk::FdiskBuilderError;
@@ -105,6 +106,25 @@ pub(crate) struct CtxBuilder {
     ))]
     enable_interactive: bool,
 
+    #[builder(
+        default,
+        setter(
+        transform = |handler: impl PromptHandler + 'static| Some(Box::new(handler) as Box<dyn PromptHandler>),
+        doc = "Set the [`PromptHandler`] answering the [`Prompt`](crate::core::prompt::Prompt)s
+issued during dialog-driven partitioning (see [`FdiskBuilder::enable_interactive`]). The handler
+also receives all information and warning messages.\n\n
+Without a prompt handler, all questions are left unanswered and the operations requiring them
+fail."))]
+    prompt_handler: Option<Box<dyn PromptHandler>>,
+
+    #[builder(setter(
+        strip_bool,
+        doc = "Forward the information and warning messages emitted by an [`Fdisk`] to the `log`
+crate, at `info` and `warn` level respectively. Disabled by default.\n\n
+Messages are collected whether or not this option is set (see [`Fdisk::diagnostics`])."
+    ))]
+    log_diagnostics: bool,
+
     #[builder(setter(
         strip_bool,
         doc = "Show only a list of partitions when printing on the console (shows NO detailed metadata). (default)"
@@ -158,6 +178,8 @@ impl<
         __device_sector_sizes: ::typed_builder::Optional<Option<(u32, u32)>>,
         __device_addressing: ::typed_builder::Optional<Option<DeviceAddressing>>,
         __enable_interactive: ::typed_builder::Optional<bool>,
+        __prompt_handler: ::typed_builder::Optional<Option<Box<dyn PromptHandler>>>,
+        __log_diagnostics: ::typed_builder::Optional<bool>,
         __display_partition_list_only: ::typed_builder::Optional<bool>,
         __display_partition_details: ::typed_builder::Optional<bool>,
         __erase_master_boot_record: ::typed_builder::Optional<bool>,
@@ -173,6 +195,8 @@ impl<
         __device_sector_sizes,
         __device_addressing,
         __enable_interactive,
+        __prompt_handler,
+        __log_diagnostics,
         __display_partition_list_only,
         __display_partition_details,
         __erase_master_boot_record,
@@ -189,6 +213,13 @@ pub fn build(self) -> Result<Fdisk<'a>, FdiskBuilderError> {
 
         let mut context = Fdisk::new()?;
 
+        // Set up prompt handling first, to receive messages issued while assigning a device.
+        if let Some(handler) = builder.prompt_handler {
+            context.set_prompt_handler(handler)?;
+        }
+
+        context.set_log_diagnostics(builder.log_diagnostics)?;
+
         match (
             builder.enable_read_write,
             builder.assign_device,
@@ -4,8 +4,12 @@
 // From dependency library
 
 // From standard library
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
 
 // From this library
+use crate::core::partition_table::VerificationFinding;
 use crate::core::private::Sealed;
 use crate::fdisk::Fdisk;
 use crate::fdisk::FdiskError;
@@ -24,6 +28,120 @@ pub trait FdiskDOSExt: Sealed {
     fn dos_relocate_partition(&mut self, partition_number: usize) -> Result<(), FdiskError>;
 }
 
+/// Offset of the first partition entry in an `MBR`, or an `EBR`.
+const DOS_PARTITION_ENTRIES_OFFSET: usize = 446;
+
+/// Size in bytes of a `DOS` partition entry.
+const DOS_PARTITION_ENTRY_SIZE: usize = 16;
+
+#[doc(hidden)]
+/// Returns the Cylinder-Head-Sector address of `lba`, as written in a partition entry.
+fn dos_chs_from_lba(lba: u64, heads: u64, sectors: u64) -> (u64, u64, u64) {
+    // Addresses past the 1024th cylinder are written as the last addressable sector.
+    let lba = lba.min(1024 * heads * sectors - 1);
+
+    (
+        lba / (heads * sectors),
+        (lba / sectors) % heads,
+        lba % sectors + 1,
+    )
+}
+
+#[doc(hidden)]
+/// Decodes the 3-byte Cylinder-Head-Sector address `chs` of a partition entry.
+fn dos_decode_chs(chs: &[u8]) -> (u64, u64, u64) {
+    let cylinder = (u64::from(chs[1] & 0xc0) << 2) | u64::from(chs[2]);
+
+    (cylinder, u64::from(chs[0]), u64::from(chs[1] & 0x3f))
+}
+
+#[doc(hidden)]
+/// Checks that the Cylinder-Head-Sector addresses in the partition entries on `device` match
+/// their LBA addresses, for a disk of `heads` heads and `sectors` sectors per track. `ebr_offsets`
+/// are the locations, in bytes, of the `EBR`s describing logical partitions, in order.
+pub(crate) fn dos_verify_chs(
+    device: &Path,
+    sector_size: u64,
+    heads: u64,
+    sectors: u64,
+    ebr_offsets: &[u64],
+) -> Result<Vec<VerificationFinding>, FdiskError> {
+    let mut findings = Vec::new();
+
+    // Geometries a CHS address can not represent have no meaningful CHS address to check.
+    if !(1..=255).contains(&heads) || !(1..=63).contains(&sectors) {
+        return Ok(findings);
+    }
+
+    let mut file = File::open(device)?;
+
+    // (partition number, location in bytes of the table, number of entries to check)
+    let tables = std::iter::once((0, 0, 4)).chain(
+        ebr_offsets
+            .iter()
+            .enumerate()
+            .map(|(nth, &offset)| (4 + nth, offset, 1)),
+    );
+
+    for (first_number, table_offset, count) in tables {
+        let mut sector = vec![0u8; sector_size as usize];
+        file.seek(SeekFrom::Start(table_offset))?;
+        file.read_exact(&mut sector)?;
+
+        // Entries in an EBR locate their partition relative to the EBR itself.
+        let base_lba = table_offset / sector_size;
+
+        for nth in 0..count {
+            let entry_offset = DOS_PARTITION_ENTRIES_OFFSET + nth * DOS_PARTITION_ENTRY_SIZE;
+            let entry = &sector[entry_offset..entry_offset + DOS_PARTITION_ENTRY_SIZE];
+
+            let mut buffer = [0u8; 4];
+            buffer.copy_from_slice(&entry[8..12]);
+            let start = u64::from(u32::from_le_bytes(buffer));
+            buffer.copy_from_slice(&entry[12..16]);
+            let size = u64::from(u32::from_le_bytes(buffer));
+
+            // Unused entry.
+            if entry[4] == 0 || size == 0 {
+                continue;
+            }
+
+            let partition_number = first_number + nth;
+            let start = base_lba + start;
+            let end = start + size - 1;
+
+            for (boundary, chs, lba) in
+                [("first", &entry[1..4], start), ("last", &entry[5..8], end)]
+            {
+                let actual = dos_decode_chs(chs);
+                let expected = dos_chs_from_lba(lba, heads, sectors);
+
+                if actual != expected {
+                    let message = format!(
+                        "partition {}: CHS address {}/{}/{} of its {} sector {} should be {}/{}/{}",
+                        partition_number,
+                        actual.0,
+                        actual.1,
+                        actual.2,
+                        boundary,
+                        lba,
+                        expected.0,
+                        expected.1,
+                        expected.2
+                    );
+
+                    findings.push(VerificationFinding::ChsInconsistency {
+                        partition_number: Some(partition_number),
+                        message,
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(findings)
+}
+
 impl<'a> FdiskDOSExt for Fdisk<'a> {
     #[cfg(v2_39)]
     fn dos_fix_chs_values(&mut self) -> usize {
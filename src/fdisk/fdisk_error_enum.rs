@@ -49,6 +49,10 @@ pub enum FdiskError {
     #[error(transparent)]
     IoError(#[from] std::io::Error),
 
+    /// [`Layout`](crate::core::layout::Layout) runtime error.
+    #[error("{0}")]
+    Layout(String),
+
     /// Error while printing log messages.
     #[error("{0}")]
     Log(String),
@@ -73,6 +77,13 @@ pub enum FdiskError {
     #[error("{0}")]
     Restore(String),
 
+    /// Error while rolling back a failed transaction, carrying the error that made it fail.
+    #[error("{source}. Failed to roll back changes: {rollback}")]
+    Rollback {
+        source: Box<dyn std::error::Error + Send + Sync>,
+        rollback: Box<FdiskError>,
+    },
+
     /// Error if returned value is out of range.
     #[error("{0}")]
     ResultOutOfRange(String),
@@ -87,4 +98,8 @@ pub enum FdiskError {
 
     #[error("{0}")]
     Unexpected(String),
+
+    /// Error while verifying a partition table.
+    #[error("{0}")]
+    Verification(String),
 }
@@ -4,10 +4,26 @@
 // From dependency library
 
 // From standard library
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
 use std::mem::MaybeUninit;
+use std::path::Path;
 
 // From this library
+use crate::core::partition::BitFlag;
+use crate::core::partition::Code;
+use crate::core::partition::DOSFlag;
+use crate::core::partition::GptAttributes;
+use crate::core::partition::Guid;
+use crate::core::partition::PartitionKind;
+use crate::core::partition_table::GptRepair;
+use crate::core::partition_table::HybridMbr;
+use crate::core::partition_table::PartitionTableGPTExt;
+use crate::core::partition_table::PartitionTableKind;
+use crate::core::partition_table::ProtectiveEntryPosition;
+use crate::core::partition_table::VerificationFinding;
 use crate::core::private::Sealed;
+use crate::core::utils::misc::crc32;
 use crate::fdisk::Fdisk;
 use crate::fdisk::FdiskError;
 
@@ -44,8 +60,401 @@ fn gpt_set_attribute_bits<T>(
         attribute_bits: u64,
     ) -> Result<(), FdiskError>;
 
+    /// Returns a partition's attributes, or `None` if an error occurred.
+    fn gpt_attributes(&self, partition_number: usize) -> Option<GptAttributes>;
+
+    /// Sets a partition's attributes.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use rsfdisk::core::partition::GptAttributes;
+    /// use rsfdisk::fdisk::Fdisk;
+    /// use rsfdisk::fdisk::FdiskGPTExt;
+    ///
+    /// fn main() -> rsfdisk::Result<()> {
+    ///     let mut disk = Fdisk::builder()
+    ///         .assign_device("/dev/vda")
+    ///         .enable_read_write()
+    ///         .build()?;
+    ///
+    ///     // Hide the first partition from Windows.
+    ///     let mut attributes = disk.gpt_attributes(0).unwrap_or_default();
+    ///     attributes.set_microsoft_hidden(true);
+    ///     attributes.set_microsoft_no_automount(true);
+    ///
+    ///     disk.gpt_set_attributes(0, attributes)?;
+    ///     disk.partition_table_write_to_disk()?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    fn gpt_set_attributes(
+        &mut self,
+        partition_number: usize,
+        attributes: GptAttributes,
+    ) -> Result<(), FdiskError>;
+
     /// Sets the maximum number of elements in the Partition Entry Array for a `GPT` partition table.
     fn gpt_set_partition_entry_array_size(&mut self, size: u32) -> Result<(), FdiskError>;
+
+    /// Replaces the Protective MBR (`PMBR`) on the assigned device by a hybrid `MBR`, mirroring
+    /// the `GPT` partitions listed in `layout`.
+    ///
+    /// Mirrored partitions keep their starting and ending sectors, and get the `MBR` partition
+    /// type set in their [`HybridMbrEntry`](crate::core::partition_table::HybridMbrEntry), or one
+    /// deduced from their `GPT` partition type. The `0xEE` protective entry covers the sectors
+    /// from the `GPT` header up to the first mirrored partition.
+    ///
+    /// **Note:** the hybrid `MBR` is written to disk immediately, through a nested `Fdisk` (see
+    /// [`Fdisk::create_nested_partitioner`]). The `GPT` partition table itself is left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use rsfdisk::core::partition::Code;
+    /// use rsfdisk::core::partition_table::HybridMbr;
+    /// use rsfdisk::core::partition_table::HybridMbrEntry;
+    /// use rsfdisk::fdisk::Fdisk;
+    /// use rsfdisk::fdisk::FdiskGPTExt;
+    ///
+    /// fn main() -> rsfdisk::Result<()> {
+    ///     let mut disk = Fdisk::builder()
+    ///         .assign_device("/dev/vda")
+    ///         .enable_read_write()
+    ///         .build()?;
+    ///
+    ///     // Make the second partition visible, and bootable, to legacy BIOS firmware.
+    ///     let layout = HybridMbr::new()
+    ///         .entry(HybridMbrEntry::new(1).code(Code::Linux).bootable());
+    ///
+    ///     disk.gpt_write_hybrid_mbr(&layout)?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    fn gpt_write_hybrid_mbr(&mut self, layout: &HybridMbr) -> Result<(), FdiskError>;
+
+    /// Repairs the `GPT` headers on the assigned device, and returns the list of fixes applied.
+    /// An empty list means both headers were valid, and in place.
+    ///
+    /// A missing or corrupt header is rebuilt from the other one, and the backup header is moved
+    /// to the last sector of the device if it is elsewhere (e.g. after copying a disk image to a
+    /// larger device).
+    ///
+    /// **Note:** the repaired partition table is written to disk immediately. Any in-memory
+    /// change not yet written to disk is discarded.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use rsfdisk::fdisk::Fdisk;
+    /// use rsfdisk::fdisk::FdiskGPTExt;
+    ///
+    /// fn main() -> rsfdisk::Result<()> {
+    ///     let mut disk = Fdisk::builder()
+    ///         .assign_device("/dev/vda")
+    ///         .enable_read_write()
+    ///         .build()?;
+    ///
+    ///     for repair in disk.gpt_repair()? {
+    ///         println!("{}", repair);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    fn gpt_repair(&mut self) -> Result<Vec<GptRepair>, FdiskError>;
+}
+
+/// Signature of a `GPT` header.
+const GPT_HEADER_SIGNATURE: &[u8; 8] = b"EFI PART";
+
+/// Minimum size in bytes of a `GPT` header.
+const GPT_HEADER_MIN_SIZE: usize = 92;
+
+/// Location of the primary `GPT` header.
+const GPT_PRIMARY_HEADER_LBA: u64 = 1;
+
+/// Maximum size in bytes of a `GPT` Partition Entry Array this library will check.
+const GPT_ENTRY_ARRAY_MAX_SIZE: u64 = 1 << 20;
+
+#[doc(hidden)]
+/// Reads a little-endian `u32` at `offset` in `bytes`.
+fn le_u32(bytes: &[u8], offset: usize) -> u32 {
+    let mut buffer = [0u8; 4];
+    buffer.copy_from_slice(&bytes[offset..offset + 4]);
+
+    u32::from_le_bytes(buffer)
+}
+
+#[doc(hidden)]
+/// Reads a little-endian `u64` at `offset` in `bytes`.
+fn le_u64(bytes: &[u8], offset: usize) -> u64 {
+    let mut buffer = [0u8; 8];
+    buffer.copy_from_slice(&bytes[offset..offset + 8]);
+
+    u64::from_le_bytes(buffer)
+}
+
+#[doc(hidden)]
+/// Issue found in a `GPT` header on disk.
+#[derive(Debug)]
+enum GptHeaderIssue {
+    /// The header, or its Partition Entry Array, is missing or unreadable.
+    Invalid(String),
+    /// The header, or its Partition Entry Array, does not match its CRC32 checksum.
+    BadChecksum(String),
+}
+
+#[doc(hidden)]
+/// Reads the `GPT` header at `lba`, and returns its content if both the header and its Partition
+/// Entry Array are valid.
+fn gpt_check_header(
+    file: &mut File,
+    lba: u64,
+    sector_size: u64,
+) -> Result<Vec<u8>, GptHeaderIssue> {
+    let invalid =
+        |reason: &str| GptHeaderIssue::Invalid(format!("GPT header at sector {}: {}", lba, reason));
+
+    // A location beyond the addressable range is as invalid as a bad signature.
+    let header_offset = lba
+        .checked_mul(sector_size)
+        .ok_or_else(|| invalid("location out of range"))?;
+    let mut header = vec![0u8; sector_size as usize];
+    file.seek(SeekFrom::Start(header_offset))
+        .and_then(|_| file.read_exact(&mut header))
+        .map_err(|_| invalid("unreadable"))?;
+
+    let header_size = le_u32(&header, 12) as usize;
+
+    if &header[..GPT_HEADER_SIGNATURE.len()] != GPT_HEADER_SIGNATURE
+        || !(GPT_HEADER_MIN_SIZE..=header.len()).contains(&header_size)
+        || le_u64(&header, 24) != lba
+    {
+        return Err(invalid("missing or malformed"));
+    }
+
+    // The header checksum is computed with its own field set to zero.
+    let header_crc = le_u32(&header, 16);
+    header[16..20].fill(0);
+
+    if crc32(&header[..header_size]) != header_crc {
+        let message = format!("GPT header at sector {}: invalid CRC32 checksum", lba);
+
+        return Err(GptHeaderIssue::BadChecksum(message));
+    }
+
+    header[16..20].copy_from_slice(&header_crc.to_le_bytes());
+
+    let entry_array_lba = le_u64(&header, 72);
+    let entry_array_size = u64::from(le_u32(&header, 80)) * u64::from(le_u32(&header, 84));
+
+    if entry_array_size > GPT_ENTRY_ARRAY_MAX_SIZE {
+        return Err(invalid("Partition Entry Array too large"));
+    }
+
+    let entry_array_offset = entry_array_lba
+        .checked_mul(sector_size)
+        .ok_or_else(|| invalid("Partition Entry Array location out of range"))?;
+    let mut entries = vec![0u8; entry_array_size as usize];
+    file.seek(SeekFrom::Start(entry_array_offset))
+        .and_then(|_| file.read_exact(&mut entries))
+        .map_err(|_| invalid("unreadable Partition Entry Array"))?;
+
+    if crc32(&entries) != le_u32(&header, 88) {
+        let message = format!(
+            "Partition Entry Array of GPT header at sector {}: invalid CRC32 checksum",
+            lba
+        );
+
+        return Err(GptHeaderIssue::BadChecksum(message));
+    }
+
+    Ok(header)
+}
+
+#[doc(hidden)]
+/// Reads the `GPT` header at `lba`, and returns the location of its alternate header if both the
+/// header and its Partition Entry Array are valid.
+fn gpt_read_header(file: &mut File, lba: u64, sector_size: u64) -> Option<u64> {
+    gpt_check_header(file, lba, sector_size)
+        .ok()
+        .map(|header| le_u64(&header, 32))
+}
+
+#[doc(hidden)]
+/// Returns `true` if the sector at `lba` holds a `GPT` header, valid or not, describing the disk
+/// with GUID `disk_guid`.
+fn gpt_header_describes(file: &mut File, lba: u64, sector_size: u64, disk_guid: &str) -> bool {
+    let mut header = vec![0u8; sector_size as usize];
+    let is_readable = lba
+        .checked_mul(sector_size)
+        .and_then(|offset| file.seek(SeekFrom::Start(offset)).ok())
+        .and_then(|_| file.read_exact(&mut header).ok())
+        .is_some();
+
+    if !is_readable || &header[..GPT_HEADER_SIGNATURE.len()] != GPT_HEADER_SIGNATURE {
+        return false;
+    }
+
+    // The first three fields of a GUID are stored in little-endian order.
+    let guid = &header[56..72];
+    let guid = format!(
+        "{:08X}-{:04X}-{:04X}-{:02X}{:02X}-{:02X}{:02X}{:02X}{:02X}{:02X}{:02X}",
+        le_u32(guid, 0),
+        u16::from_le_bytes([guid[4], guid[5]]),
+        u16::from_le_bytes([guid[6], guid[7]]),
+        guid[8],
+        guid[9],
+        guid[10],
+        guid[11],
+        guid[12],
+        guid[13],
+        guid[14],
+        guid[15]
+    );
+
+    guid.eq_ignore_ascii_case(disk_guid)
+}
+
+#[doc(hidden)]
+/// Checks the checksums of the `GPT` headers on `device`, and that the backup header is on the
+/// device's last sector, and agrees with the primary header.
+///
+/// Headers on disk are only checked if one of them describes the disk with GUID `disk_guid`, i.e.
+/// if the partition table in memory was read from `device`.
+pub(crate) fn gpt_verify_headers(
+    device: &Path,
+    sector_size: u64,
+    last_lba: u64,
+    disk_guid: &str,
+) -> Result<Vec<VerificationFinding>, FdiskError> {
+    let mut file = File::open(device)?;
+    let mut findings = Vec::new();
+
+    if !gpt_header_describes(&mut file, GPT_PRIMARY_HEADER_LBA, sector_size, disk_guid)
+        && !gpt_header_describes(&mut file, last_lba, sector_size, disk_guid)
+    {
+        return Ok(findings);
+    }
+
+    let primary = match gpt_check_header(&mut file, GPT_PRIMARY_HEADER_LBA, sector_size) {
+        Ok(header) => Some(header),
+        Err(GptHeaderIssue::BadChecksum(message)) => {
+            findings.push(VerificationFinding::BadChecksum { message });
+
+            None
+        }
+        Err(GptHeaderIssue::Invalid(message)) => {
+            findings.push(VerificationFinding::Other {
+                partition_number: None,
+                message,
+            });
+
+            None
+        }
+    };
+
+    let backup_lba = primary
+        .as_ref()
+        .map(|header| le_u64(header, 32))
+        .unwrap_or(last_lba);
+
+    match gpt_check_header(&mut file, backup_lba, sector_size) {
+        Ok(backup) => {
+            // Both headers describe the same disk GUID, usable sectors, and partition entries.
+            let agrees = |primary: &[u8]| {
+                le_u64(&backup, 32) == GPT_PRIMARY_HEADER_LBA
+                    && primary[40..72] == backup[40..72]
+                    && primary[80..92] == backup[80..92]
+            };
+
+            if !primary.as_deref().map(agrees).unwrap_or(true) {
+                let message = format!(
+                    "backup GPT header at sector {} does not match the primary header",
+                    backup_lba
+                );
+                findings.push(VerificationFinding::BackupHeaderMismatch { message });
+            }
+        }
+        Err(GptHeaderIssue::BadChecksum(message)) => {
+            findings.push(VerificationFinding::BadChecksum { message });
+        }
+        Err(GptHeaderIssue::Invalid(message)) => {
+            findings.push(VerificationFinding::BackupHeaderMismatch { message });
+        }
+    }
+
+    if backup_lba != last_lba {
+        let message = format!(
+            "backup GPT header at sector {}, instead of the last sector {}",
+            backup_lba, last_lba
+        );
+        findings.push(VerificationFinding::BackupHeaderMismatch { message });
+    }
+
+    Ok(findings)
+}
+
+#[doc(hidden)]
+/// Lists the fixes the `GPT` headers on `device` need.
+fn gpt_inspect_headers(
+    device: &Path,
+    sector_size: u64,
+    last_lba: u64,
+) -> Result<Vec<GptRepair>, FdiskError> {
+    let mut file = File::open(device)?;
+    let mut repairs = Vec::new();
+
+    match gpt_read_header(&mut file, GPT_PRIMARY_HEADER_LBA, sector_size) {
+        Some(backup_lba) => {
+            if gpt_read_header(&mut file, backup_lba, sector_size).is_none() {
+                repairs.push(GptRepair::BackupHeaderRebuilt {
+                    location: backup_lba,
+                });
+            }
+
+            if backup_lba != last_lba {
+                repairs.push(GptRepair::BackupHeaderRelocated {
+                    from: backup_lba,
+                    to: last_lba,
+                });
+            }
+        }
+        None => {
+            if gpt_read_header(&mut file, last_lba, sector_size).is_none() {
+                let err_msg = format!(
+                    "both primary and backup GPT headers are corrupt on device: {:?}",
+                    device
+                );
+                log::debug!("Fdisk::gpt_inspect_headers {}", err_msg);
+
+                return Err(FdiskError::Config(err_msg));
+            }
+
+            repairs.push(GptRepair::PrimaryHeaderRebuilt);
+        }
+    }
+
+    Ok(repairs)
+}
+
+#[doc(hidden)]
+/// Reads the `GPT` partition table of `disk` again from its device, rebuilding a corrupt header
+/// from the other one, and moving the backup header to the end of the device.
+///
+/// In-memory changes not yet written to disk are discarded.
+pub(crate) fn gpt_reread_with_relocation(disk: &mut Fdisk) -> Result<(), FdiskError> {
+    // `libfdisk` rebuilds a corrupt header from the other one when it reads a partition
+    // table, and relocates the backup header to the end of the device if allowed to.
+    if let Some(table) = disk.partition_table_current_mut() {
+        table.gpt_enable_backup_header_relocation();
+        table.gpt_disable_minimize_footprint();
+    }
+
+    disk.discard_changes()
 }
 
 impl<'a> FdiskGPTExt for Fdisk<'a> {
@@ -104,7 +513,7 @@ fn gpt_set_attribute_bits<T>(
 
         // Check that bits 3 to 47, reserved for expansion by future versions of the UEFI
         // specification, are not set.
-        let mut mask = 1u64 << 4;
+        let mut mask = 1u64 << 3;
         for i in 3..=47 {
             if attribute_bits & mask != 0 {
                 let err_msg = format!(
@@ -143,6 +552,31 @@ fn gpt_set_attribute_bits<T>(
         }
     }
 
+    fn gpt_attributes(&self, partition_number: usize) -> Option<GptAttributes> {
+        log::debug!(
+            "Fdisk::gpt_attributes getting attributes of partition {:?}",
+            partition_number
+        );
+
+        self.gpt_attribute_bits(partition_number)
+            .map(GptAttributes::from_bits_retain)
+    }
+
+    fn gpt_set_attributes(
+        &mut self,
+        partition_number: usize,
+        attributes: GptAttributes,
+    ) -> Result<(), FdiskError> {
+        log::debug!(
+            "Fdisk::gpt_set_attributes setting attributes {:?} for partition {:?}",
+            attributes,
+            partition_number
+        );
+
+        // The type parameter of `gpt_set_attribute_bits` is unused.
+        self.gpt_set_attribute_bits::<()>(partition_number, attributes.bits())
+    }
+
     fn gpt_set_partition_entry_array_size(&mut self, size: u32) -> Result<(), FdiskError> {
         log::debug!("Fdisk::gpt_set_partition_entry_array_size setting GPT partition entry array size to: {:?}", size);
 
@@ -165,4 +599,206 @@ fn gpt_set_partition_entry_array_size(&mut self, size: u32) -> Result<(), FdiskE
             }
         }
     }
+
+    fn gpt_write_hybrid_mbr(&mut self, layout: &HybridMbr) -> Result<(), FdiskError> {
+        log::debug!(
+            "Fdisk::gpt_write_hybrid_mbr writing hybrid MBR: {:?}",
+            layout
+        );
+
+        if !self.partition_table_is_of_type(PartitionTableKind::GPT) {
+            let err_msg =
+                "can not write a hybrid MBR on a device without a GPT partition table".to_owned();
+            log::debug!("Fdisk::gpt_write_hybrid_mbr {}", err_msg);
+
+            return Err(FdiskError::Config(err_msg));
+        }
+
+        let entries = layout.entries();
+
+        if entries.is_empty() || entries.len() > HybridMbr::MAX_ENTRIES {
+            let err_msg = format!(
+                "a hybrid MBR mirrors between 1 and {} GPT partitions, got: {}",
+                HybridMbr::MAX_ENTRIES,
+                entries.len()
+            );
+            log::debug!("Fdisk::gpt_write_hybrid_mbr {}", err_msg);
+
+            return Err(FdiskError::Config(err_msg));
+        }
+
+        // (partition number, starting sector, ending sector, MBR type, is bootable)
+        let mut mirrored = Vec::with_capacity(entries.len());
+
+        for (i, entry) in entries.iter().enumerate() {
+            let number = entry.partition_number();
+
+            if entries[..i].iter().any(|e| e.partition_number() == number) {
+                let err_msg = format!("partition {:?} is mirrored more than once", number);
+                log::debug!("Fdisk::gpt_write_hybrid_mbr {}", err_msg);
+
+                return Err(FdiskError::Config(err_msg));
+            }
+
+            let (start, end, guid) = self
+                .partition_by_number(number)
+                .filter(|p| p.points_to_used_area())
+                .and_then(|p| {
+                    let guid = p
+                        .partition_type()
+                        .and_then(|kind| kind.guid().and_then(|guid| guid.parse::<Guid>().ok()));
+
+                    Some((p.starting_sector()?, p.ending_sector()?, guid))
+                })
+                .ok_or_else(|| {
+                    let err_msg = format!("no GPT partition with number: {:?}", number);
+                    log::debug!("Fdisk::gpt_write_hybrid_mbr {}", err_msg);
+
+                    FdiskError::Config(err_msg)
+                })?;
+
+            // Unknown GPT types are mirrored as Linux partitions.
+            let code = entry
+                .partition_code()
+                .or_else(|| guid.and_then(|guid| guid.to_code()))
+                .unwrap_or(Code::Linux);
+
+            if matches!(
+                code,
+                Code::EmptyPartition
+                    | Code::ExtendedPartition
+                    | Code::W95ExtendedLBA
+                    | Code::LinuxExtended
+                    | Code::GPTProtectiveMBR
+            ) {
+                let err_msg = format!(
+                    "can not mirror partition {:?} with MBR partition type: {:?}",
+                    number, code
+                );
+                log::debug!("Fdisk::gpt_write_hybrid_mbr {}", err_msg);
+
+                return Err(FdiskError::Config(err_msg));
+            }
+
+            if end > u64::from(u32::MAX) {
+                let err_msg = format!(
+                    "can not mirror partition {:?}. Its ending sector {} is beyond the last sector addressable by an MBR: {}",
+                    number,
+                    end,
+                    u32::MAX
+                );
+                log::debug!("Fdisk::gpt_write_hybrid_mbr {}", err_msg);
+
+                return Err(FdiskError::Config(err_msg));
+            }
+
+            mirrored.push((number, start, end, code, entry.is_bootable()));
+        }
+
+        // The protective entry starts at the GPT header, and stops right before the first
+        // mirrored partition.
+        let protective_end = mirrored
+            .iter()
+            .map(|&(_, start, ..)| start - 1)
+            .min()
+            .unwrap_or(1);
+
+        let (protective_number, first_mirrored_number) = match layout.protective_position() {
+            ProtectiveEntryPosition::First => (0, 1),
+            ProtectiveEntryPosition::Last => (mirrored.len(), 0),
+        };
+
+        let mut mbr = self.create_nested_partitioner_with_name("dos")?;
+        mbr.partition_delete_all()?;
+        // Allow the protective entry to start at the GPT header.
+        mbr.device_set_first_lba(1)?;
+
+        let entry = |number: usize, start: u64, end: u64, code: Code| {
+            Fdisk::converted_partition(
+                number,
+                start,
+                end,
+                PartitionKind::builder().code(code).build(),
+            )
+        };
+
+        mbr.partition_add(entry(
+            protective_number,
+            1,
+            protective_end,
+            Code::GPTProtectiveMBR,
+        )?)?;
+
+        for (i, &(number, start, end, code, bootable)) in mirrored.iter().enumerate() {
+            let mbr_number =
+                mbr.partition_add(entry(first_mirrored_number + i, start, end, code)?)?;
+
+            if bootable {
+                mbr.partition_table_toggle_partition_flag(mbr_number, BitFlag::DOS(DOSFlag::Boot))?;
+            }
+
+            log::debug!(
+                "Fdisk::gpt_write_hybrid_mbr mirrored GPT partition {:?} as MBR entry {:?}",
+                number,
+                mbr_number
+            );
+        }
+
+        mbr.partition_table_write_to_disk()?;
+
+        log::debug!("Fdisk::gpt_write_hybrid_mbr wrote hybrid MBR");
+
+        Ok(())
+    }
+
+    fn gpt_repair(&mut self) -> Result<Vec<GptRepair>, FdiskError> {
+        log::debug!("Fdisk::gpt_repair repairing GPT headers");
+
+        if !self.partition_table_is_of_type(PartitionTableKind::GPT) {
+            let err_msg = "no GPT partition table on assigned device".to_owned();
+            log::debug!("Fdisk::gpt_repair {}", err_msg);
+
+            return Err(FdiskError::Config(err_msg));
+        }
+
+        let device = self
+            .device_name()
+            .map(|device| device.to_path_buf())
+            .ok_or_else(|| {
+                let err_msg = "no device assigned".to_owned();
+                log::debug!("Fdisk::gpt_repair {}", err_msg);
+
+                FdiskError::Config(err_msg)
+            })?;
+        let sector_size = self.device_bytes_per_logical_sector();
+        let last_lba = self.device_size_in_sectors().saturating_sub(1);
+
+        let repairs = gpt_inspect_headers(&device, sector_size, last_lba)?;
+
+        if repairs.is_empty() {
+            log::debug!("Fdisk::gpt_repair GPT headers are valid, nothing to repair");
+
+            return Ok(repairs);
+        }
+
+        gpt_reread_with_relocation(self)?;
+        self.partition_table_write_to_disk()?;
+
+        let remaining = gpt_inspect_headers(&device, sector_size, last_lba)?;
+
+        if !remaining.is_empty() {
+            let reasons: Vec<String> = remaining.iter().map(|r| r.to_string()).collect();
+            let err_msg = format!(
+                "failed to repair GPT headers. Still needed: {}",
+                reasons.join(", ")
+            );
+            log::debug!("Fdisk::gpt_repair {}", err_msg);
+
+            return Err(FdiskError::Config(err_msg));
+        }
+
+        log::debug!("Fdisk::gpt_repair applied repairs: {:?}", repairs);
+
+        Ok(repairs)
+    }
 }
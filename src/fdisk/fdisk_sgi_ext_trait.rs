@@ -4,12 +4,49 @@
 // From dependency library
 
 // From standard library
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
 
 // From this library
+use crate::core::partition_table::HeaderEntry;
+use crate::core::partition_table::PartitionTableKind;
+use crate::core::partition_table::SgiVolumeEntry;
 use crate::core::private::Sealed;
+use crate::core::prompt::Answer;
+use crate::core::prompt::AnswerQueue;
+use crate::core::prompt::PromptKind;
+use crate::core::prompt::QueryMatcher;
 use crate::fdisk::Fdisk;
 use crate::fdisk::FdiskError;
 
+/// Size of a `SGI` volume header.
+const SGI_LABEL_SIZE: usize = 512;
+
+/// `SGI` volume header magic number.
+const SGI_LABEL_MAGIC: [u8; 4] = [0x0b, 0xe5, 0xa9, 0x41];
+
+/// Offset of the volume directory in a `SGI` volume header.
+const SGI_VOLUME_DIRECTORY_OFFSET: usize = 72;
+
+/// Size of an entry in the volume directory.
+const SGI_VOLUME_ENTRY_SIZE: usize = 16;
+
+/// Maximum number of entries in the volume directory.
+const SGI_VOLUME_DIRECTORY_LEN: usize = 15;
+
+/// Offset of the checksum in a `SGI` volume header.
+const SGI_CHECKSUM_OFFSET: usize = 504;
+
+/// Maximum length of a boot file name.
+const SGI_BOOT_FILE_MAX_LEN: usize = 16;
+
+/// Identification number of the volume header partition.
+const SGI_VOLUME_HEADER_PARTITION: usize = 8;
+
 /// `SGI` specific functions for a [`Fdisk`].
 ///
 /// This trait is sealed and can not be implemented for types outside of `rsfdisk`.
@@ -20,6 +57,179 @@ pub trait FdiskSGIExt: Sealed {
 
     /// Sets the SGI boot file by [`Prompt`](crate::core::prompt::Prompt)ing the user for a value.
     fn sgi_set_boot_file(&mut self) -> Result<(), FdiskError>;
+
+    /// Sets the SGI boot file to `path`, an absolute path between 3 and 16 ASCII characters long
+    /// (e.g. `"/unix"`).
+    fn sgi_set_boot_file_to<T>(&mut self, path: T) -> Result<(), FdiskError>
+    where
+        T: AsRef<str>;
+
+    /// Returns the name of the SGI boot file.
+    fn sgi_boot_file(&self) -> Option<String>;
+
+    /// Returns the hardware sector interleave factor.
+    fn sgi_interleave(&self) -> Option<u16>;
+
+    /// Returns the number of physical cylinders.
+    fn sgi_physical_cylinder_count(&self) -> Option<u16>;
+
+    /// Returns the number of spare sectors per cylinder.
+    fn sgi_spare_sectors_per_cylinder(&self) -> Option<u16>;
+
+    /// Returns the entries in the volume directory of the SGI volume header saved on the
+    /// assigned device.
+    fn sgi_volume_directory(&self) -> Result<Vec<SgiVolumeEntry>, FdiskError>;
+
+    /// Adds an entry to the volume directory of the SGI volume header.
+    ///
+    /// The entry's name must be unique, and at most 8 printable ASCII characters long. The blocks
+    /// it occupies must fit in the volume header partition, without overlapping the volume header
+    /// itself (block 0) or the blocks occupied by other entries. Only the directory is updated;
+    /// copying the file's content to the blocks it points to is up to the caller.
+    ///
+    /// **Note:** `libfdisk` does not provide a way to edit the volume directory. This method
+    /// writes any pending changes to the partition table on the assigned device, edits the saved
+    /// volume header in place, then reloads it.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use rsfdisk::core::partition_table::SgiVolumeEntry;
+    /// use rsfdisk::fdisk::{Fdisk, FdiskSGIExt};
+    ///
+    /// fn main() -> rsfdisk::Result<()> {
+    ///     let mut disk = Fdisk::builder()
+    ///         .assign_device("/dev/vda")
+    ///         .enable_read_write()
+    ///         .build()?;
+    ///
+    ///     disk.sgi_set_boot_file_to("/unix")?;
+    ///     disk.sgi_add_volume_entry(SgiVolumeEntry::new("sash", 4, 302_592))?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    fn sgi_add_volume_entry(&mut self, entry: SgiVolumeEntry) -> Result<(), FdiskError>;
+
+    /// Removes the entry named `name` from the volume directory of the SGI volume header.
+    ///
+    /// **Note:** like [`FdiskSGIExt::sgi_add_volume_entry`], this method writes any pending
+    /// changes to the partition table on the assigned device before editing the saved volume
+    /// header.
+    fn sgi_remove_volume_entry<T>(&mut self, name: T) -> Result<(), FdiskError>
+    where
+        T: AsRef<str>;
+}
+
+#[doc(hidden)]
+/// Returns the device assigned to `disk`, after checking it holds an `SGI` volume header.
+fn sgi_device(disk: &Fdisk, caller: &str) -> Result<std::path::PathBuf, FdiskError> {
+    if !disk.partition_table_is_of_type(PartitionTableKind::SGI) {
+        let err_msg = "no SGI volume header on assigned device".to_owned();
+        log::debug!("Fdisk::{} {}", caller, err_msg);
+
+        return Err(FdiskError::Config(err_msg));
+    }
+
+    match disk.device_name() {
+        Some(device) => Ok(device.to_path_buf()),
+        None => {
+            let err_msg = "no device assigned".to_owned();
+            log::debug!("Fdisk::{} {}", caller, err_msg);
+
+            Err(FdiskError::Config(err_msg))
+        }
+    }
+}
+
+#[doc(hidden)]
+/// Reads the `SGI` volume header at the start of `file`.
+fn sgi_read_volume_header(
+    file: &mut File,
+    device: &std::path::Path,
+) -> Result<[u8; SGI_LABEL_SIZE], FdiskError> {
+    let mut label = [0u8; SGI_LABEL_SIZE];
+    file.read_exact(&mut label)?;
+
+    if label[..SGI_LABEL_MAGIC.len()] != SGI_LABEL_MAGIC {
+        let err_msg = format!("no SGI volume header found on device: {:?}", device);
+        log::debug!("Fdisk::sgi_read_volume_header {}", err_msg);
+
+        return Err(FdiskError::Config(err_msg));
+    }
+
+    Ok(label)
+}
+
+#[doc(hidden)]
+/// Parses the volume directory of a `SGI` volume header.
+fn sgi_parse_volume_directory(label: &[u8; SGI_LABEL_SIZE]) -> Vec<SgiVolumeEntry> {
+    label[SGI_VOLUME_DIRECTORY_OFFSET
+        ..SGI_VOLUME_DIRECTORY_OFFSET + SGI_VOLUME_DIRECTORY_LEN * SGI_VOLUME_ENTRY_SIZE]
+        .chunks_exact(SGI_VOLUME_ENTRY_SIZE)
+        .filter_map(SgiVolumeEntry::from_raw)
+        .collect()
+}
+
+#[doc(hidden)]
+/// Writes the `SGI` volume header of `disk` to its assigned device, modifies its volume directory
+/// in place with `patch`, updates its checksum, then reloads it.
+///
+/// `patch` is first applied to the volume directory currently on the device, so that nothing is
+/// written if it rejects the modification.
+fn sgi_patch_volume_directory<F>(disk: &mut Fdisk, patch: F) -> Result<(), FdiskError>
+where
+    F: Fn(&mut Vec<SgiVolumeEntry>) -> Result<(), FdiskError>,
+{
+    let device = sgi_device(disk, "sgi_patch_volume_directory")?;
+
+    let mut file = OpenOptions::new().read(true).open(&device)?;
+    let label = sgi_read_volume_header(&mut file, &device)?;
+    patch(&mut sgi_parse_volume_directory(&label))?;
+
+    // Writing the volume header may update its volume directory (e.g. to store `SGI`
+    // information), so the patch is applied again to what was written.
+    disk.partition_table_write_to_disk()?;
+
+    let mut file = OpenOptions::new().read(true).write(true).open(&device)?;
+    let mut label = sgi_read_volume_header(&mut file, &device)?;
+
+    let mut entries = sgi_parse_volume_directory(&label);
+    patch(&mut entries)?;
+
+    let directory = &mut label[SGI_VOLUME_DIRECTORY_OFFSET
+        ..SGI_VOLUME_DIRECTORY_OFFSET + SGI_VOLUME_DIRECTORY_LEN * SGI_VOLUME_ENTRY_SIZE];
+    directory.fill(0);
+    for (raw, entry) in directory
+        .chunks_exact_mut(SGI_VOLUME_ENTRY_SIZE)
+        .zip(entries.iter())
+    {
+        raw.copy_from_slice(&entry.to_raw());
+    }
+
+    // The sum of all big-endian 32-bit words in a valid volume header, checksum included, is
+    // zero.
+    label[SGI_CHECKSUM_OFFSET..SGI_CHECKSUM_OFFSET + 4].fill(0);
+    let sum = label
+        .chunks_exact(4)
+        .map(|word| u32::from_be_bytes([word[0], word[1], word[2], word[3]]))
+        .fold(0u32, |sum, word| sum.wrapping_add(word));
+    label[SGI_CHECKSUM_OFFSET..SGI_CHECKSUM_OFFSET + 4]
+        .copy_from_slice(&sum.wrapping_neg().to_be_bytes());
+
+    file.seek(SeekFrom::Start(0))?;
+    file.write_all(&label)?;
+    file.sync_all()?;
+
+    disk.discard_changes()
+}
+
+#[doc(hidden)]
+/// Returns the value of a numeric entry in the `SGI` volume header.
+fn sgi_header_u16(disk: &Fdisk, entry: HeaderEntry) -> Option<u16> {
+    disk.partition_table_header_entry(entry)
+        .and_then(|content| content.data_u64())
+        .and_then(|value| u16::try_from(value).ok())
 }
 
 impl<'a> FdiskSGIExt for Fdisk<'a> {
@@ -62,4 +272,200 @@ fn sgi_set_boot_file(&mut self) -> Result<(), FdiskError> {
             }
         }
     }
+
+    fn sgi_set_boot_file_to<T>(&mut self, path: T) -> Result<(), FdiskError>
+    where
+        T: AsRef<str>,
+    {
+        let path = path.as_ref();
+        log::debug!(
+            "Fdisk::sgi_set_boot_file_to setting SGI boot file to: {:?}",
+            path
+        );
+
+        if !(3..=SGI_BOOT_FILE_MAX_LEN).contains(&path.len())
+            || !path.starts_with('/')
+            || !path.bytes().all(|b| b.is_ascii_graphic())
+        {
+            let err_msg = format!(
+                "invalid SGI boot file: {:?}, expected an absolute path between 3 and {} printable ASCII characters long",
+                path, SGI_BOOT_FILE_MAX_LEN
+            );
+            log::debug!("Fdisk::sgi_set_boot_file_to {}", err_msg);
+
+            return Err(FdiskError::Config(err_msg));
+        }
+
+        // libfdisk reports an error when asked to set the current boot file.
+        if self.sgi_boot_file().as_deref() == Some(path) {
+            log::debug!(
+                "Fdisk::sgi_set_boot_file_to SGI boot file already set to: {:?}",
+                path
+            );
+
+            return Ok(());
+        }
+
+        let answers = AnswerQueue::new().expect(
+            PromptKind::String,
+            QueryMatcher::Contains("boot file".to_owned()),
+            Answer::String(path.to_owned()),
+        );
+
+        self.answer_dialogs_with(answers, |disk| disk.sgi_set_boot_file())?;
+
+        log::debug!(
+            "Fdisk::sgi_set_boot_file_to set SGI boot file to: {:?}",
+            path
+        );
+
+        Ok(())
+    }
+
+    fn sgi_boot_file(&self) -> Option<String> {
+        self.partition_table_header_entry(HeaderEntry::SgiBootfile)
+            .and_then(|content| content.data_string().map(|s| s.to_owned()))
+    }
+
+    fn sgi_interleave(&self) -> Option<u16> {
+        sgi_header_u16(self, HeaderEntry::SgiInterleave)
+    }
+
+    fn sgi_physical_cylinder_count(&self) -> Option<u16> {
+        sgi_header_u16(self, HeaderEntry::SgiPhysicalCylindersCount)
+    }
+
+    fn sgi_spare_sectors_per_cylinder(&self) -> Option<u16> {
+        sgi_header_u16(self, HeaderEntry::SgiSpareSectorsPerCylinder)
+    }
+
+    fn sgi_volume_directory(&self) -> Result<Vec<SgiVolumeEntry>, FdiskError> {
+        log::debug!("Fdisk::sgi_volume_directory reading SGI volume directory");
+
+        let device = sgi_device(self, "sgi_volume_directory")?;
+        let mut file = File::open(&device)?;
+        let label = sgi_read_volume_header(&mut file, &device)?;
+
+        let entries = sgi_parse_volume_directory(&label);
+        log::debug!(
+            "Fdisk::sgi_volume_directory read {} SGI volume directory entries",
+            entries.len()
+        );
+
+        Ok(entries)
+    }
+
+    fn sgi_add_volume_entry(&mut self, entry: SgiVolumeEntry) -> Result<(), FdiskError> {
+        log::debug!(
+            "Fdisk::sgi_add_volume_entry adding SGI volume directory entry: {:?}",
+            entry
+        );
+
+        if !entry.has_valid_name() {
+            let err_msg = format!(
+                "invalid SGI volume directory entry name: {:?}, expected at most {} printable ASCII characters",
+                entry.name(),
+                SgiVolumeEntry::NAME_MAX_LEN
+            );
+            log::debug!("Fdisk::sgi_add_volume_entry {}", err_msg);
+
+            return Err(FdiskError::Config(err_msg));
+        }
+
+        // Entries must fit in the volume header partition, or the whole device if there is none.
+        let last_block = self
+            .partition_by_number(SGI_VOLUME_HEADER_PARTITION)
+            .and_then(|partition| partition.size_in_sectors())
+            .unwrap_or_else(|| self.device_size_in_sectors());
+        let end = u64::from(entry.first_block()) + u64::from(entry.size_in_blocks());
+
+        if entry.first_block() == 0 || end > last_block {
+            let err_msg = format!(
+                "SGI volume directory entry {:?} out of range: blocks {} to {}, must be between 1 and {}",
+                entry.name(),
+                entry.first_block(),
+                end,
+                last_block
+            );
+            log::debug!("Fdisk::sgi_add_volume_entry {}", err_msg);
+
+            return Err(FdiskError::Config(err_msg));
+        }
+
+        sgi_patch_volume_directory(self, |entries| {
+            let err_msg = if entries.iter().any(|e| e.name() == entry.name()) {
+                Some(format!(
+                    "SGI volume directory already has an entry named: {:?}",
+                    entry.name()
+                ))
+            } else if let Some(other) = entries.iter().find(|e| e.overlaps(&entry)) {
+                Some(format!(
+                    "SGI volume directory entry {:?} overlaps entry {:?}",
+                    entry.name(),
+                    other.name()
+                ))
+            } else if entries.len() >= SGI_VOLUME_DIRECTORY_LEN {
+                Some(format!(
+                    "SGI volume directory is full, it can not have more than {} entries",
+                    SGI_VOLUME_DIRECTORY_LEN
+                ))
+            } else {
+                None
+            };
+
+            match err_msg {
+                Some(err_msg) => {
+                    log::debug!("Fdisk::sgi_add_volume_entry {}", err_msg);
+
+                    Err(FdiskError::Config(err_msg))
+                }
+                None => {
+                    entries.push(entry.clone());
+
+                    Ok(())
+                }
+            }
+        })?;
+
+        log::debug!(
+            "Fdisk::sgi_add_volume_entry added SGI volume directory entry: {:?}",
+            entry
+        );
+
+        Ok(())
+    }
+
+    fn sgi_remove_volume_entry<T>(&mut self, name: T) -> Result<(), FdiskError>
+    where
+        T: AsRef<str>,
+    {
+        let name = name.as_ref();
+        log::debug!(
+            "Fdisk::sgi_remove_volume_entry removing SGI volume directory entry: {:?}",
+            name
+        );
+
+        sgi_patch_volume_directory(self, |entries| {
+            match entries.iter().position(|e| e.name() == name) {
+                Some(index) => {
+                    entries.remove(index);
+
+                    Ok(())
+                }
+                None => {
+                    let err_msg = format!("no SGI volume directory entry named: {:?}", name);
+                    log::debug!("Fdisk::sgi_remove_volume_entry {}", err_msg);
+
+                    Err(FdiskError::Config(err_msg))
+                }
+            }
+        })?;
+
+        log::debug!(
+            "Fdisk::sgi_remove_volume_entry removed SGI volume directory entry: {:?}",
+            name
+        );
+
+        Ok(())
+    }
 }
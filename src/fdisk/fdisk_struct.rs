@@ -6,36 +6,64 @@
 // From standard library
 use std::ffi::CString;
 use std::fs::File;
+use std::io::{Read, Seek, SeekFrom, Write};
 use std::mem::MaybeUninit;
 use std::os::fd::{BorrowedFd, IntoRawFd};
 use std::path::Path;
+use std::path::PathBuf;
 use std::slice;
 
 // From this library
+use crate::fdisk::fdisk_dos_ext_trait::dos_verify_chs;
+use crate::fdisk::fdisk_gpt_ext_trait::gpt_reread_with_relocation;
+use crate::fdisk::fdisk_gpt_ext_trait::gpt_verify_headers;
 use crate::fdisk::CtxBuilder;
 use crate::fdisk::DeviceAddressing;
+use crate::fdisk::DeviceReport;
+use crate::fdisk::Diagnostic;
 use crate::fdisk::FdiskBuilder;
 use crate::fdisk::FdiskError;
+use crate::fdisk::FdiskGPTExt;
 use crate::fdisk::GcItem;
 use crate::fdisk::LBAAlign;
+use crate::fdisk::PartitionGrowth;
 use crate::fdisk::PartitionTableIter;
 use crate::fdisk::PartitionTableIterMut;
+use crate::fdisk::PromptDispatcher;
+use crate::fdisk::Savepoint;
 use crate::fdisk::SizeFormat;
 
+use crate::core::errors::PartitionKindBuilderError;
+
+use crate::core::layout::Layout;
+
 use crate::core::partition::BitFlag;
+use crate::core::partition::Code;
+use crate::core::partition::DOSFlag;
+use crate::core::partition::GptAttributes;
+use crate::core::partition::Guid;
 use crate::core::partition::Partition;
 use crate::core::partition::PartitionKind;
 use crate::core::partition::PartitionList;
 
+use crate::core::partition_table::ConversionBlocker;
 use crate::core::partition_table::Field;
 use crate::core::partition_table::FieldFormat;
+use crate::core::partition_table::GptRepair;
 use crate::core::partition_table::HeaderEntry;
 use crate::core::partition_table::HeaderEntryContent;
 use crate::core::partition_table::PartitionTable;
+use crate::core::partition_table::PartitionTableBackup;
+use crate::core::partition_table::PartitionTableDiff;
 use crate::core::partition_table::PartitionTableKind;
 use crate::core::partition_table::TableSection;
+use crate::core::partition_table::VerificationFinding;
 use crate::core::partition_table::VerificationStatus;
 
+use crate::core::prompt::AnswerQueue;
+use crate::core::prompt::Prompt;
+use crate::core::prompt::PromptHandler;
+
 use crate::core::script::Script;
 
 use crate::ffi_to_string_or_empty;
@@ -43,12 +71,27 @@
 use crate::owning_mut_from_ptr;
 use crate::owning_ref_from_ptr;
 
+#[doc(hidden)]
+/// Partition number, starting sector, ending sector, type code, and bootable flag of a `DOS`
+/// partition to convert.
+type DosDataPartition = (usize, u64, u64, Option<u32>, bool);
+
+#[doc(hidden)]
+/// Partition number, starting sector, ending sector, type, and legacy BIOS bootable flag of a
+/// `GPT` partition to convert.
+type GptDataPartition = (usize, u64, u64, Option<Guid>, bool);
+
 /// Partition table reader/editor/creator.
 #[derive(Debug)]
 pub struct Fdisk<'a> {
     pub(crate) inner: *mut libfdisk::fdisk_context,
     _parent: Option<&'a Fdisk<'a>>,
     pub(crate) gc: Vec<GcItem>,
+    // Allocated by top-level instances with `Box::into_raw`, and freed when they are dropped.
+    // Nested instances share their parent's. Going through a raw pointer lets `libfdisk` and
+    // nested instances mutate the dispatcher without aliasing a `Box` owned by the parent.
+    prompt_dispatcher: *mut PromptDispatcher,
+    owns_prompt_dispatcher: bool,
 }
 
 impl<'a> Fdisk<'a> {
@@ -59,10 +102,17 @@ pub(crate) fn from_ptr(
         ptr: *mut libfdisk::fdisk_context,
         parent: Option<&'a Fdisk<'a>>,
     ) -> Fdisk<'a> {
+        // Nested contexts inherit their parent's prompt callback and data.
+        let prompt_dispatcher = parent
+            .map(|parent| parent.prompt_dispatcher)
+            .unwrap_or(std::ptr::null_mut());
+
         Self {
             inner: ptr,
             _parent: parent,
             gc: vec![],
+            prompt_dispatcher,
+            owns_prompt_dispatcher: false,
         }
     }
 
@@ -89,7 +139,8 @@ pub(crate) fn new() -> Result<Fdisk<'a>, FdiskError> {
             ptr => {
                 log::debug!("Fdisk::new created a new `Fdisk` instance");
 
-                let context = Self::from_ptr(ptr, None);
+                let mut context = Self::from_ptr(ptr, None);
+                context.install_prompt_dispatcher()?;
 
                 Ok(context)
             }
@@ -98,10 +149,10 @@ pub(crate) fn new() -> Result<Fdisk<'a>, FdiskError> {
 
     #[doc(hidden)]
     /// Creates a new nested partitioner.
-    fn make_new_nested_partitioner(
-        parent: &'a Fdisk<'a>,
+    fn make_new_nested_partitioner<'b>(
+        parent: &'b Fdisk<'a>,
         name: &str,
-    ) -> Result<Fdisk<'a>, FdiskError> {
+    ) -> Result<Fdisk<'b>, FdiskError> {
         log::debug!("Fdisk::make_new_nested_partitioner creating a new nested `Fdisk` instance");
         let name_cstr = ffi_utils::as_ref_str_to_c_string(name)?;
         let name_ptr = if name.is_empty() {
@@ -127,7 +178,7 @@ fn make_new_nested_partitioner(
                     "Fdisk::make_new_nested_partitioner created a new nested `Fdisk` instance"
                 );
 
-                let nested_partitioner = Self::from_ptr(ptr, Some(parent));
+                let nested_partitioner = Fdisk::from_ptr(ptr, Some(parent));
 
                 Ok(nested_partitioner)
             }
@@ -140,16 +191,19 @@ fn make_new_nested_partitioner(
     /// The returned `Fdisk` is initialized with data taken from its parent, sharing settings and
     /// the assigned device; changes to the nested context are propagated to its parent, but not the
     /// other way around.
-    pub fn create_nested_partitioner(&'a mut self) -> Result<Fdisk, FdiskError> {
+    ///
+    /// The parent stays mutably borrowed while the nested `Fdisk` is alive, and can be used again,
+    /// or dropped, once the nested `Fdisk` is.
+    pub fn create_nested_partitioner(&mut self) -> Result<Fdisk<'_>, FdiskError> {
         Self::make_new_nested_partitioner(self, "")
     }
 
     /// Returns a new nested `Fdisk`, acts the same as [`Fdisk::create_nested_partitioner`]
     /// with the parameter `name` added as an attribute to the partition table (e.g. `"bsd"`).
     pub fn create_nested_partitioner_with_name<T>(
-        &'a mut self,
+        &mut self,
         name: T,
-    ) -> Result<Fdisk, FdiskError>
+    ) -> Result<Fdisk<'_>, FdiskError>
     where
         T: AsRef<str>,
     {
@@ -393,6 +447,207 @@ fn display_dialogs(ptr: &mut Self, enable: bool) -> Result<(), FdiskError> {
         }
     }
 
+    #[doc(hidden)]
+    /// Forwards a `libfdisk::fdisk_ask` to the [`PromptDispatcher`] registered in `data`.
+    unsafe extern "C" fn prompt_callback(
+        _context: *mut libfdisk::fdisk_context,
+        ask: *mut libfdisk::fdisk_ask,
+        data: *mut libc::c_void,
+    ) -> libc::c_int {
+        if ask.is_null() || data.is_null() {
+            log::debug!("Fdisk::prompt_callback received a NULL pointer");
+
+            return -libc::EINVAL;
+        }
+
+        let dispatcher = unsafe { &*(data as *const PromptDispatcher) };
+        let mut prompt = Prompt::borrow_ptr(ask);
+
+        // Unwinding across an FFI boundary is undefined behaviour, we catch any panic raised by the
+        // handler, and report it as a failure to answer.
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            dispatcher.dispatch(&mut prompt)
+        }));
+
+        match result {
+            Ok(Ok(())) => {
+                log::debug!("Fdisk::prompt_callback answered prompt");
+
+                0
+            }
+            Ok(Err(e)) => {
+                log::debug!("Fdisk::prompt_callback failed to answer prompt. {:?}", e);
+
+                -libc::EINVAL
+            }
+            Err(_) => {
+                log::debug!("Fdisk::prompt_callback prompt handler panicked");
+
+                -libc::EINVAL
+            }
+        }
+    }
+
+    #[doc(hidden)]
+    /// Registers the [`PromptDispatcher`] receiving all [`Prompt`]s issued by this `Fdisk`.
+    fn install_prompt_dispatcher(&mut self) -> Result<(), FdiskError> {
+        log::debug!("Fdisk::install_prompt_dispatcher installing prompt dispatcher");
+
+        // A leaked `Box` gives us a pointer with a stable address to share with `libfdisk`,
+        // reclaimed in `Drop`.
+        let data = Box::into_raw(Box::new(PromptDispatcher::new()));
+
+        let result = unsafe {
+            libfdisk::fdisk_set_ask(
+                self.inner,
+                Some(Self::prompt_callback),
+                data as *mut libc::c_void,
+            )
+        };
+
+        match result {
+            0 => {
+                log::debug!("Fdisk::install_prompt_dispatcher installed prompt dispatcher");
+                self.prompt_dispatcher = data;
+                self.owns_prompt_dispatcher = true;
+
+                Ok(())
+            }
+            code => {
+                // `libfdisk` did not keep the pointer, we can safely reclaim the allocation.
+                drop(unsafe { Box::from_raw(data) });
+
+                let err_msg = "failed to install prompt dispatcher".to_owned();
+                log::debug!("Fdisk::install_prompt_dispatcher {}. libfdisk::fdisk_set_ask returned error code: {:?}", err_msg, code);
+
+                Err(FdiskError::Config(err_msg))
+            }
+        }
+    }
+
+    #[doc(hidden)]
+    /// Returns a mutable reference to the [`PromptDispatcher`] shared with `libfdisk`.
+    fn prompt_dispatcher_mut(&mut self) -> Option<&mut PromptDispatcher> {
+        unsafe { self.prompt_dispatcher.as_mut() }
+    }
+
+    #[doc(hidden)]
+    /// Sets the handler answering the [`Prompt`]s issued by this `Fdisk`.
+    pub(crate) fn set_prompt_handler(
+        &mut self,
+        handler: Box<dyn PromptHandler>,
+    ) -> Result<(), FdiskError> {
+        log::debug!("Fdisk::set_prompt_handler setting prompt handler");
+
+        match self.prompt_dispatcher_mut() {
+            Some(dispatcher) => {
+                dispatcher.set_handler(handler);
+                log::debug!("Fdisk::set_prompt_handler set prompt handler");
+
+                Ok(())
+            }
+            None => {
+                let err_msg = "failed to set prompt handler".to_owned();
+                log::debug!(
+                    "Fdisk::set_prompt_handler {}. no prompt dispatcher",
+                    err_msg
+                );
+
+                Err(FdiskError::Config(err_msg))
+            }
+        }
+    }
+
+    #[doc(hidden)]
+    /// Runs `operation` with dialogs enabled, answering the questions it raises with `answers`
+    /// instead of the user-provided [`PromptHandler`].
+    ///
+    /// Dialogs are temporarily enabled on this `Fdisk` and all its parents, since `libfdisk` may
+    /// ask some questions through a parent context (e.g. the `DOS` partition hosting a nested
+    /// `BSD` disklabel).
+    pub(crate) fn answer_dialogs_with<F, T>(
+        &mut self,
+        answers: AnswerQueue,
+        operation: F,
+    ) -> Result<T, FdiskError>
+    where
+        F: FnOnce(&mut Self) -> Result<T, FdiskError>,
+    {
+        log::debug!("Fdisk::answer_dialogs_with answering dialogs with pre-recorded answers");
+
+        let previous_handler = match self.prompt_dispatcher_mut() {
+            Some(dispatcher) => dispatcher.replace_handler(Some(Box::new(answers.clone()))),
+            None => {
+                let err_msg = "failed to answer dialogs".to_owned();
+                log::debug!(
+                    "Fdisk::answer_dialogs_with {}. no prompt dispatcher",
+                    err_msg
+                );
+
+                return Err(FdiskError::Config(err_msg));
+            }
+        };
+
+        // Save the dialog settings of this context and its ancestors before enabling dialogs.
+        let mut dialog_states = vec![];
+        let mut context = self.inner;
+        while !context.is_null() {
+            let has_dialogs = unsafe { libfdisk::fdisk_has_dialogs(context) == 1 };
+            dialog_states.push((context, has_dialogs));
+            unsafe {
+                libfdisk::fdisk_disable_dialogs(context, 0);
+                context = libfdisk::fdisk_get_parent(context);
+            }
+        }
+
+        let result = operation(self);
+
+        for (context, has_dialogs) in dialog_states {
+            let op = if has_dialogs { 0 } else { 1 };
+            unsafe {
+                libfdisk::fdisk_disable_dialogs(context, op);
+            }
+        }
+
+        if let Some(dispatcher) = self.prompt_dispatcher_mut() {
+            dispatcher.replace_handler(previous_handler);
+        }
+
+        // A refused answer explains why the operation failed, or may have been silently ignored.
+        answers.verify()?;
+
+        result
+    }
+
+    #[doc(hidden)]
+    /// Enables/disables forwarding information and warning messages to the `log` crate.
+    pub(crate) fn set_log_diagnostics(&mut self, enable: bool) -> Result<(), FdiskError> {
+        let op_str = if enable {
+            "enable".to_owned()
+        } else {
+            "disable".to_owned()
+        };
+        log::debug!("Fdisk::set_log_diagnostics {}d logging diagnostics", op_str);
+
+        match self.prompt_dispatcher_mut() {
+            Some(dispatcher) => {
+                dispatcher.set_log_diagnostics(enable);
+                log::debug!("Fdisk::set_log_diagnostics {}d logging diagnostics", op_str);
+
+                Ok(())
+            }
+            None => {
+                let err_msg = format!("failed to {} logging diagnostics", op_str);
+                log::debug!(
+                    "Fdisk::set_log_diagnostics {}. no prompt dispatcher",
+                    err_msg
+                );
+
+                Err(FdiskError::Config(err_msg))
+            }
+        }
+    }
+
     #[doc(hidden)]
     /// Enable disk partitioning prompts.
     pub(crate) fn enable_interactive(&mut self) -> Result<(), FdiskError> {
@@ -514,6 +769,38 @@ pub(crate) fn disable_partitions_as_list(&mut self) -> Result<(), FdiskError> {
     pub(crate) fn set_partition_size_format(
         &mut self,
         format: SizeFormat,
+    ) -> Result<(), FdiskError> {
+        Self::set_size_unit(self.inner, format)
+    }
+
+    #[doc(hidden)]
+    /// Displays partition sizes in `format` while running `f`, then restores the previous
+    /// format.
+    pub(crate) fn with_partition_size_format<F, T>(
+        &self,
+        format: SizeFormat,
+        f: F,
+    ) -> Result<T, FdiskError>
+    where
+        F: FnOnce(&Self) -> Result<T, FdiskError>,
+    {
+        let previous = self.partition_size_format();
+        if previous == format {
+            return f(self);
+        }
+
+        Self::set_size_unit(self.inner, format)?;
+        let result = f(self);
+        Self::set_size_unit(self.inner, previous)?;
+
+        result
+    }
+
+    #[doc(hidden)]
+    /// Sets the format in which to display partition sizes.
+    fn set_size_unit(
+        ptr: *mut libfdisk::fdisk_context,
+        format: SizeFormat,
     ) -> Result<(), FdiskError> {
         log::debug!(
             "Fdisk::set_partition_size_format setting partition size formatting: {:?}",
@@ -521,7 +808,7 @@ pub(crate) fn set_partition_size_format(
         );
         let c_format = format as u32 as i32;
 
-        let result = unsafe { libfdisk::fdisk_set_size_unit(self.inner, c_format) };
+        let result = unsafe { libfdisk::fdisk_set_size_unit(ptr, c_format) };
 
         match result {
             0 => {
@@ -794,6 +1081,17 @@ pub fn close_device_async(&mut self) -> Result<(), FdiskError> {
         Self::close_assigned_device(self, true)
     }
 
+    /// Removes and returns all information and warning messages collected by this `Fdisk`.
+    ///
+    /// A nested `Fdisk` shares its messages with its parent.
+    pub fn diagnostics_take(&mut self) -> Vec<Diagnostic> {
+        log::debug!("Fdisk::diagnostics_take taking collected diagnostics");
+
+        self.prompt_dispatcher_mut()
+            .map(|dispatcher| dispatcher.take_diagnostics())
+            .unwrap_or_default()
+    }
+
     /// Discards all in-memory changes to this `Fdisk`, no data is written to the assigned device.
     ///
     /// Use this method if this `Fdisk` is in an undefined state after a major adverse event.
@@ -1242,13 +1540,13 @@ fn add_partition(
     /// outlined below:
     ///
     /// - if the starting sector of `partition` is within the range reserved for the extended
-    /// partition, this method will add a logical partition to the `MBR`,
+    ///   partition, this method will add a logical partition to the `MBR`,
     /// - if the starting sector of `partition` is outside the range reserved for the extended
-    /// partition, this method will add a primary partition to the `MBR`,
+    ///   partition, this method will add a primary partition to the `MBR`,
     /// - if `partition` has a partition number < 4, this method will add a primary partition to
-    /// the `MBR`,
+    ///   the `MBR`,
     /// - if `partition` has a partition number >= 4, this method will add a logical partition to
-    /// the `MBR`.
+    ///   the `MBR`.
     ///
     /// If the template lacks essential information necessary to complete the process, it will
     /// revert to interactively asking for the missing data.
@@ -1293,6 +1591,52 @@ pub fn partitions_append(&self, partitions: PartitionList) -> Result<(), FdiskEr
         }
     }
 
+    /// Adds the partitions described by the given [`Layout`] to this `Fdisk`'s in-memory partition
+    /// table, and returns their identification numbers.
+    ///
+    /// The `layout` is resolved against the geometry of the assigned device (see
+    /// [`Layout::resolve`]): partitions are placed one after the other from the first usable
+    /// sector, each starting on a sector aligned on the device's grain size. This method is meant
+    /// to populate an empty partition table.
+    ///
+    /// Partitions are added in a [transaction](Fdisk::partition_table_transaction): if one of them
+    /// can not be added, the in-memory partition table is left as it was before the call.
+    pub fn partitions_apply_layout(&mut self, layout: &Layout) -> Result<Vec<usize>, FdiskError> {
+        log::debug!("Fdisk::partitions_apply_layout applying partition layout");
+
+        let planned = layout.resolve(self).map_err(|e| {
+            let err_msg = format!("failed to resolve partition layout. {}", e);
+            log::debug!("Fdisk::partitions_apply_layout {}", err_msg);
+
+            FdiskError::Layout(err_msg)
+        })?;
+
+        let partition_numbers = self.partition_table_transaction(|disk| {
+            let mut partition_numbers = Vec::with_capacity(planned.len());
+
+            for planned_partition in planned.iter() {
+                let partition = planned_partition.to_partition().map_err(|e| {
+                    let err_msg = format!("failed to configure partition. {}", e);
+                    log::debug!("Fdisk::partitions_apply_layout {}", err_msg);
+
+                    FdiskError::Layout(err_msg)
+                })?;
+
+                let partition_number = disk.partition_add(partition)?;
+                partition_numbers.push(partition_number);
+            }
+
+            Ok::<_, FdiskError>(partition_numbers)
+        })?;
+
+        log::debug!(
+            "Fdisk::partitions_apply_layout added partitions: {:?}",
+            partition_numbers
+        );
+
+        Ok(partition_numbers)
+    }
+
     /// Deletes a partition with the given identification number from the partition table on the
     /// device assigned to this `Fdisk`.
     pub fn partition_delete(&mut self, partition_number: usize) -> Result<(), FdiskError> {
@@ -1433,91 +1777,668 @@ pub fn partition_override_settings(
     }
 
     #[doc(hidden)]
-    /// Sets the `Partition` matching the identification number `partition_number` for metadata erasure.
-    fn wipe_partition(
-        ptr: *mut libfdisk::fdisk_context,
+    /// Returns the number of sectors the backup `GPT` header needs to move to reach the end of
+    /// the assigned device.
+    fn gpt_backup_header_offset(&self) -> u64 {
+        if !self.partition_table_is_of_type(PartitionTableKind::GPT) {
+            return 0;
+        }
+
+        let last_sector = self.device_size_in_sectors().saturating_sub(1);
+
+        self.partition_table_header_entry(HeaderEntry::GptAlternateLba)
+            .and_then(|entry| entry.data_u64())
+            .map(|alternate_lba| last_sector.saturating_sub(alternate_lba))
+            .unwrap_or(0)
+    }
+
+    #[doc(hidden)]
+    /// Returns the current size, and the size once grown, of the partition matching
+    /// `partition_number`.
+    fn grown_partition_size(
+        &self,
         partition_number: usize,
-        enable: bool,
-    ) -> Result<(), FdiskError> {
-        let op_str = if enable {
-            "enable".to_owned()
+        caller: &str,
+    ) -> Result<(u64, u64), FdiskError> {
+        let (start, end, parent) = self
+            .partition_by_number(partition_number)
+            .filter(|p| p.points_to_used_area())
+            .and_then(|p| {
+                Some((
+                    p.starting_sector()?,
+                    p.ending_sector()?,
+                    p.is_nested().then(|| p.parent_partition_number()).flatten(),
+                ))
+            })
+            .ok_or_else(|| {
+                let err_msg = format!("no partition with number: {:?}", partition_number);
+                log::debug!("Fdisk::{} {}", caller, err_msg);
+
+                FdiskError::Config(err_msg)
+            })?;
+
+        let list_error = |what: &str| {
+            let err_msg = format!(
+                "failed to list {} to grow partition {:?}",
+                what, partition_number
+            );
+            log::debug!("Fdisk::{} {}", caller, err_msg);
+
+            FdiskError::Config(err_msg)
+        };
+
+        // (partition number, starting sector, ending sector)
+        let partitions: Vec<_> = self
+            .list_partitions()
+            .ok_or_else(|| list_error("partitions"))?
+            .iter()
+            .filter(|p| !p.is_free_space())
+            .filter_map(|p| Some((p.number()?, p.starting_sector()?, p.ending_sector()?)))
+            .collect();
+
+        // A partition can grow up to the next partition, or the end of its container.
+        let next_start = partitions
+            .iter()
+            .filter(|&&(number, next_start, _)| number != partition_number && next_start > end)
+            .map(|&(_, next_start, _)| next_start - 1);
+        let container_end = parent.and_then(|parent| {
+            partitions
+                .iter()
+                .find(|&&(number, ..)| number == parent)
+                .map(|&(.., container_end)| container_end)
+        });
+
+        // It can only grow into the free space right after it, as computed by libfdisk which
+        // keeps clear of partitioning metadata (e.g. the EBR preceding a DOS logical partition,
+        // or the backup GPT header).
+        let free_end = self
+            .list_empty_spaces()
+            .ok_or_else(|| list_error("free spaces"))?
+            .iter()
+            .filter_map(|p| Some((p.starting_sector()?, p.ending_sector()?)))
+            .find(|&(free_start, _)| {
+                free_start > end && free_start <= self.aligned_lba(end + 1, LBAAlign::Up)
+            })
+            .map(|(_, free_end)| free_end)
+            .unwrap_or(end);
+
+        // Moving the backup GPT header to the end of the device makes as many sectors usable
+        // after the current last usable one.
+        let relocation_offset = self.gpt_backup_header_offset();
+        let last_usable_lba = self
+            .partition_table_header_entry(HeaderEntry::GptLastUsableLba)
+            .and_then(|entry| entry.data_u64());
+        let free_end = if relocation_offset > 0 && Some(free_end) == last_usable_lba {
+            free_end + relocation_offset
         } else {
-            "disable".to_owned()
+            free_end
         };
-        let op = if enable { 1 } else { 0 };
 
-        let result = unsafe { libfdisk::fdisk_wipe_partition(ptr, partition_number, op) };
+        let limit = next_start
+            .chain(container_end)
+            .fold(free_end, |limit, bound| limit.min(bound));
 
-        match result {
-            0 => {
-                log::debug!(
-                    "Fdisk::wipe_partition {}d wipe for partition with number: {:?}",
-                    op_str,
-                    partition_number
-                );
+        // Align the end of the partition on the device's grain.
+        let grain =
+            (self.device_grain_size() / self.device_bytes_per_logical_sector().max(1)).max(1);
+        let aligned_end = ((limit + 1) / grain * grain).saturating_sub(1);
+        let new_end = aligned_end.max(end);
 
-                Ok(())
-            }
-            code => {
-                let err_msg = format!(
-                    "failed to {} wipe for partition with number: {:?}",
-                    op_str, partition_number
-                );
-                log::debug!("Fdisk::wipe_partition {}. libfdisk::fdisk_wipe_partition returned error code: {:?}", err_msg, code);
+        log::debug!(
+            "Fdisk::{} partition {:?} can grow from sector {} to sector {}",
+            caller,
+            partition_number,
+            end,
+            new_end
+        );
 
-                Err(FdiskError::Config(err_msg))
-            }
-        }
+        Ok((end - start + 1, new_end - start + 1))
     }
 
-    /// Marks all metadata on the [`Partition`] matching the given `partition_number` for deletion.
-    pub fn partition_wipe_activate(&mut self, partition_number: usize) -> Result<(), FdiskError> {
-        log::debug!("Fdisk::partition_wipe_activate enabling partition wipe");
+    /// Returns the size, in sectors, the partition matching `partition_number` would have once
+    /// grown by [`Fdisk::partition_grow`], without modifying it (dry run).
+    ///
+    /// If the backup `GPT` header is not at the end of the device, the returned
+    /// [`PartitionGrowth`] also reports the move `partition_grow` would make to put it there.
+    pub fn partition_check_grow(
+        &self,
+        partition_number: usize,
+    ) -> Result<PartitionGrowth, FdiskError> {
+        log::debug!(
+            "Fdisk::partition_check_grow checking growth of partition {:?}",
+            partition_number
+        );
 
-        Self::wipe_partition(self.inner, partition_number, true)
-    }
+        let (size, new_size) =
+            self.grown_partition_size(partition_number, "partition_check_grow")?;
 
-    /// Marks all metadata on the [`Partition`] matching the given `partition_number` for
-    /// preservation.
-    pub fn partition_wipe_deactivate(&mut self, partition_number: usize) -> Result<(), FdiskError> {
-        log::debug!("Fdisk::partition_wipe_deactivate enabling partition wipe");
+        let relocation = match self.gpt_backup_header_offset() {
+            0 => None,
+            offset => {
+                let last_lba = self.device_size_in_sectors().saturating_sub(1);
 
-        Self::wipe_partition(self.inner, partition_number, false)
+                Some(GptRepair::BackupHeaderRelocated {
+                    from: last_lba - offset,
+                    to: last_lba,
+                })
+            }
+        };
+
+        Ok(PartitionGrowth::new(size, new_size, relocation))
     }
 
-    #[doc(hidden)]
-    /// Creates a new partition table on the associated device.
-    fn create_partition_table(
-        ptr: *mut libfdisk::fdisk_context,
-        name: *const libc::c_char,
-    ) -> Result<(), FdiskError> {
-        let result = unsafe { libfdisk::fdisk_create_disklabel(ptr, name) };
+    /// Extends the partition matching `partition_number` to fill the free space following it, up
+    /// to the last sector aligned on the device's grain (see [`Fdisk::device_grain_size`]). Returns
+    /// the partition's new size in sectors.
+    ///
+    /// The free space following a partition stops at the next partition, at the end of its
+    /// container (e.g. for a `DOS` logical partition), or at the end of the device. This method is
+    /// typically called after enlarging a virtual disk, to grow its last partition.
+    ///
+    /// If the backup `GPT` header is not at the end of the device (e.g. after copying a disk
+    /// image to a larger device with its automatic relocation disabled), this method first moves
+    /// it there the same way [`FdiskGPTExt::gpt_repair`] does, by reading the partition table
+    /// again from the device.
+    ///
+    /// **Note:** moving the backup `GPT` header discards in-memory changes not yet written to
+    /// disk. The grown partition is only modified in memory, call
+    /// [`Fdisk::partition_table_write_to_disk`] to save it.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use rsfdisk::fdisk::Fdisk;
+    ///
+    /// fn main() -> rsfdisk::Result<()> {
+    ///     let mut disk = Fdisk::builder()
+    ///         .assign_device("/dev/vda")
+    ///         .enable_read_write()
+    ///         .build()?;
+    ///
+    ///     // Preview the new size of the second partition.
+    ///     let growth = disk.partition_check_grow(1)?;
+    ///     println!("partition 1 will span {} sectors", growth.new_size());
+    ///
+    ///     disk.partition_grow(1)?;
+    ///     disk.partition_table_write_to_disk()?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn partition_grow(&mut self, partition_number: usize) -> Result<u64, FdiskError> {
+        log::debug!(
+            "Fdisk::partition_grow growing partition {:?}",
+            partition_number
+        );
 
-        match result {
-            0 => {
-                log::debug!("Fdisk::create_partition_table created partition table",);
+        // Check the partition exists before reading the partition table again.
+        self.grown_partition_size(partition_number, "partition_grow")?;
 
-                Ok(())
-            }
-            code => {
-                let err_msg = "failed to create partition table".to_owned();
-                log::debug!("Fdisk::create_partition_table {}. libfdisk::fdisk_create_disklabel returned error code: {:?}", err_msg, code);
+        if self.gpt_backup_header_offset() > 0 {
+            log::debug!("Fdisk::partition_grow moving backup GPT header to the end of the device");
 
-                Err(FdiskError::Creation(err_msg))
+            gpt_reread_with_relocation(self)?;
+
+            if self.gpt_backup_header_offset() > 0 {
+                let err_msg = format!(
+                    "failed to move backup GPT header to the end of the device to grow partition {:?}",
+                    partition_number
+                );
+                log::debug!("Fdisk::partition_grow {}", err_msg);
+
+                return Err(FdiskError::Config(err_msg));
             }
         }
-    }
 
-    /// Creates a default partition table which can be a `DOS` or `SUN` partition table depending on the systems.
-    pub fn partition_table_create_default(&mut self) -> Result<(), FdiskError> {
+        let (size, new_size) = self.grown_partition_size(partition_number, "partition_grow")?;
+
+        if new_size > size {
+            let template = Partition::builder()
+                .size_in_sectors(new_size)
+                .build()
+                .map_err(|e| {
+                    let err_msg = format!("failed to grow partition {:?}. {}", partition_number, e);
+                    log::debug!("Fdisk::partition_grow {}", err_msg);
+
+                    FdiskError::Config(err_msg)
+                })?;
+
+            self.partition_override_settings(partition_number, &template)?;
+        }
+
         log::debug!(
-            "Fdisk::partition_table_create_default creating default partition table (DOS or SUN)"
+            "Fdisk::partition_grow partition {:?} size: {} sectors",
+            partition_number,
+            new_size
         );
 
-        Self::create_partition_table(self.inner, std::ptr::null())
+        Ok(new_size)
     }
 
-    /// Creates a partition table.
+    #[doc(hidden)]
+    /// Returns `address` aligned on the device's grain in the given `direction`.
+    fn aligned_lba(&self, address: u64, direction: LBAAlign) -> u64 {
+        let aligned = unsafe { libfdisk::fdisk_align_lba(self.inner, address, direction.into()) };
+        log::debug!(
+            "Fdisk::aligned_lba address {:?} aligned {} to: {:?}",
+            address,
+            direction,
+            aligned
+        );
+
+        aligned
+    }
+
+    #[doc(hidden)]
+    /// Returns the name, first and last sectors of each area holding the partition table's
+    /// metadata, except `DOS` Extended Boot Records which move with their logical partition.
+    fn metadata_sectors(&self) -> Vec<(String, u64, u64)> {
+        let sector_size = self.device_bytes_per_logical_sector().max(1);
+
+        (0..)
+            .map_while(|nth| self.partition_table_section(nth))
+            .filter(|section| section.name() != "EBR" && section.size() > 0)
+            .map(|section| {
+                let size = section.size() as u64;
+                let first = section.starting_offset() / sector_size;
+                let last = (section.starting_offset() + size - 1) / sector_size;
+
+                (section.name().to_owned(), first, last)
+            })
+            .collect()
+    }
+
+    #[doc(hidden)]
+    /// Checks that the partition matching `partition_number` can occupy the sectors from `start`
+    /// to `end` without overlapping its neighbours, or the partition table's metadata.
+    fn check_partition_placement(
+        &self,
+        partition_number: usize,
+        start: u64,
+        end: u64,
+        caller: &str,
+    ) -> Result<(), FdiskError> {
+        // (partition number, starting sector, ending sector, parent partition number)
+        let partitions: Vec<_> = self
+            .list_partitions()
+            .map(|partitions| {
+                partitions
+                    .iter()
+                    .filter(|p| !p.is_free_space())
+                    .filter_map(|p| {
+                        Some((
+                            p.number()?,
+                            p.starting_sector()?,
+                            p.ending_sector()?,
+                            p.is_nested().then(|| p.parent_partition_number()).flatten(),
+                        ))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let (current_start, parent) = partitions
+            .iter()
+            .find(|&&(number, ..)| number == partition_number)
+            .map(|&(_, current_start, _, parent)| (current_start, parent))
+            .ok_or_else(|| {
+                let err_msg = format!("no partition with number: {:?}", partition_number);
+                log::debug!("Fdisk::{} {}", caller, err_msg);
+
+                FdiskError::Config(err_msg)
+            })?;
+
+        let reject = |reason: String| {
+            let err_msg = format!(
+                "can not place partition {:?} on sectors {} to {}: {}",
+                partition_number, start, end, reason
+            );
+            log::debug!("Fdisk::{} {}", caller, err_msg);
+
+            Err(FdiskError::Config(err_msg))
+        };
+
+        // A partition created before the current alignment settings (e.g. on sector 63 of a DOS
+        // disk) may keep starting before the first usable sector.
+        let first_usable = self.device_first_lba().min(current_start);
+        let last_usable = self.device_last_lba();
+
+        if start < first_usable || end > last_usable {
+            return reject(format!(
+                "outside of the usable sectors {} to {}",
+                first_usable, last_usable
+            ));
+        }
+
+        if let Some((name, first, last)) = self
+            .metadata_sectors()
+            .into_iter()
+            .find(|&(_, first, last)| first <= end && start <= last)
+        {
+            return reject(format!(
+                "overlaps the partition table's {} (sectors {} to {})",
+                name, first, last
+            ));
+        }
+
+        // Nested partitions (e.g. DOS logical partitions) need a sector in front of them for
+        // their Extended Boot Record.
+        let ebr = |number: usize, start: u64| {
+            let is_nested = partitions
+                .iter()
+                .any(|&(n, .., parent)| n == number && parent.is_some());
+
+            if is_nested {
+                start.saturating_sub(1)
+            } else {
+                start
+            }
+        };
+
+        if let Some(parent) = parent {
+            if let Some(&(_, container_start, container_end, _)) =
+                partitions.iter().find(|&&(number, ..)| number == parent)
+            {
+                if ebr(partition_number, start) < container_start || end > container_end {
+                    return reject(format!(
+                        "outside of its container, partition {} (sectors {} to {})",
+                        parent, container_start, container_end
+                    ));
+                }
+            }
+        }
+
+        for &(number, other_start, other_end, other_parent) in partitions.iter() {
+            if number == partition_number {
+                continue;
+            }
+
+            // A container must keep holding its nested partitions.
+            if other_parent == Some(partition_number)
+                && (ebr(number, other_start) < start || other_end > end)
+            {
+                return reject(format!(
+                    "nested partition {} (sectors {} to {}) would be left outside",
+                    number, other_start, other_end
+                ));
+            }
+
+            if other_parent == parent
+                && ebr(partition_number, start) <= other_end
+                && ebr(number, other_start) <= end
+            {
+                return reject(format!(
+                    "overlaps partition {} (sectors {} to {})",
+                    number, other_start, other_end
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[doc(hidden)]
+    /// Moves the partition matching `partition_number` to the sectors from `start` to `end`.
+    fn place_partition(
+        &mut self,
+        partition_number: usize,
+        start: u64,
+        end: u64,
+        caller: &str,
+    ) -> Result<(), FdiskError> {
+        self.check_partition_placement(partition_number, start, end, caller)?;
+
+        let template = Partition::builder()
+            .starting_sector(start)
+            .size_in_sectors(end - start + 1)
+            .build()
+            .map_err(|e| {
+                let err_msg = format!(
+                    "failed to place partition {:?} on sectors {} to {}. {}",
+                    partition_number, start, end, e
+                );
+                log::debug!("Fdisk::{} {}", caller, err_msg);
+
+                FdiskError::Config(err_msg)
+            })?;
+
+        self.partition_table_transaction(|disk| {
+            disk.partition_override_settings(partition_number, &template)?;
+
+            // Make sure libfdisk did not adjust the partition's position.
+            let position = disk
+                .partition_by_number(partition_number)
+                .map(|p| (p.starting_sector(), p.ending_sector()));
+
+            if position != Some((Some(start), Some(end))) {
+                let err_msg = format!(
+                    "failed to place partition {:?} on sectors {} to {}",
+                    partition_number, start, end
+                );
+                log::debug!("Fdisk::{} {}", caller, err_msg);
+
+                return Err(FdiskError::Config(err_msg));
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Changes the size of the partition matching `partition_number`, keeping its starting
+    /// sector. The partition's end is aligned on the device's grain (see
+    /// [`Fdisk::device_grain_size`]) in the `align` direction. Returns the partition's new size in
+    /// sectors.
+    ///
+    /// Fails if, once resized, the partition would overlap a neighbour, the partition table's
+    /// metadata, or (for nested partitions) leave its container.
+    ///
+    /// **Warning:** this method only edits the in-memory partition table, it neither moves nor
+    /// resizes the data (e.g. a file system) the partition holds. Shrink the file system first
+    /// before reducing the size of a partition.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use rsfdisk::fdisk::Fdisk;
+    /// use rsfdisk::fdisk::LBAAlign;
+    ///
+    /// fn main() -> rsfdisk::Result<()> {
+    ///     let mut disk = Fdisk::builder()
+    ///         .assign_device("/dev/vda")
+    ///         .enable_read_write()
+    ///         .build()?;
+    ///
+    ///     // Resize the first partition to (at least) 1 GiB.
+    ///     disk.partition_resize(0, 2_097_152, LBAAlign::Up)?;
+    ///     disk.partition_table_write_to_disk()?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn partition_resize(
+        &mut self,
+        partition_number: usize,
+        size: u64,
+        align: LBAAlign,
+    ) -> Result<u64, FdiskError> {
+        log::debug!(
+            "Fdisk::partition_resize resizing partition {:?} to {} sectors",
+            partition_number,
+            size
+        );
+
+        let start = self
+            .partition_by_number(partition_number)
+            .filter(|p| p.points_to_used_area())
+            .and_then(|p| p.starting_sector())
+            .ok_or_else(|| {
+                let err_msg = format!("no partition with number: {:?}", partition_number);
+                log::debug!("Fdisk::partition_resize {}", err_msg);
+
+                FdiskError::Config(err_msg)
+            })?;
+
+        let next_start = self.aligned_lba(start + size, align);
+
+        if next_start <= start {
+            let err_msg = format!(
+                "can not resize partition {:?} to {} sectors. Size is zero once aligned {}",
+                partition_number, size, align
+            );
+            log::debug!("Fdisk::partition_resize {}", err_msg);
+
+            return Err(FdiskError::Config(err_msg));
+        }
+
+        self.place_partition(partition_number, start, next_start - 1, "partition_resize")?;
+
+        let new_size = next_start - start;
+        log::debug!(
+            "Fdisk::partition_resize resized partition {:?} to {} sectors",
+            partition_number,
+            new_size
+        );
+
+        Ok(new_size)
+    }
+
+    /// Moves the partition matching `partition_number` to start at sector `start`, aligned on the
+    /// device's grain (see [`Fdisk::device_grain_size`]) in the `align` direction. The partition
+    /// keeps its size. Returns the partition's new starting sector.
+    ///
+    /// Fails if, once moved, the partition would overlap a neighbour, the partition table's
+    /// metadata, or (for nested partitions) leave its container.
+    ///
+    /// **Warning:** this method only edits the in-memory partition table, it does not move the
+    /// data the partition holds. Copy the data to its new location before writing the partition
+    /// table to disk.
+    pub fn partition_move(
+        &mut self,
+        partition_number: usize,
+        start: u64,
+        align: LBAAlign,
+    ) -> Result<u64, FdiskError> {
+        log::debug!(
+            "Fdisk::partition_move moving partition {:?} to sector {}",
+            partition_number,
+            start
+        );
+
+        let size = self
+            .partition_by_number(partition_number)
+            .filter(|p| p.points_to_used_area())
+            .and_then(|p| p.size_in_sectors())
+            .ok_or_else(|| {
+                let err_msg = format!("no partition with number: {:?}", partition_number);
+                log::debug!("Fdisk::partition_move {}", err_msg);
+
+                FdiskError::Config(err_msg)
+            })?;
+
+        let new_start = self.aligned_lba(start, align);
+
+        self.place_partition(
+            partition_number,
+            new_start,
+            new_start + size - 1,
+            "partition_move",
+        )?;
+
+        log::debug!(
+            "Fdisk::partition_move moved partition {:?} to sector {}",
+            partition_number,
+            new_start
+        );
+
+        Ok(new_start)
+    }
+
+    #[doc(hidden)]
+    /// Sets the `Partition` matching the identification number `partition_number` for metadata erasure.
+    fn wipe_partition(
+        ptr: *mut libfdisk::fdisk_context,
+        partition_number: usize,
+        enable: bool,
+    ) -> Result<(), FdiskError> {
+        let op_str = if enable {
+            "enable".to_owned()
+        } else {
+            "disable".to_owned()
+        };
+        let op = if enable { 1 } else { 0 };
+
+        let result = unsafe { libfdisk::fdisk_wipe_partition(ptr, partition_number, op) };
+
+        match result {
+            0 => {
+                log::debug!(
+                    "Fdisk::wipe_partition {}d wipe for partition with number: {:?}",
+                    op_str,
+                    partition_number
+                );
+
+                Ok(())
+            }
+            code => {
+                let err_msg = format!(
+                    "failed to {} wipe for partition with number: {:?}",
+                    op_str, partition_number
+                );
+                log::debug!("Fdisk::wipe_partition {}. libfdisk::fdisk_wipe_partition returned error code: {:?}", err_msg, code);
+
+                Err(FdiskError::Config(err_msg))
+            }
+        }
+    }
+
+    /// Marks all metadata on the [`Partition`] matching the given `partition_number` for deletion.
+    pub fn partition_wipe_activate(&mut self, partition_number: usize) -> Result<(), FdiskError> {
+        log::debug!("Fdisk::partition_wipe_activate enabling partition wipe");
+
+        Self::wipe_partition(self.inner, partition_number, true)
+    }
+
+    /// Marks all metadata on the [`Partition`] matching the given `partition_number` for
+    /// preservation.
+    pub fn partition_wipe_deactivate(&mut self, partition_number: usize) -> Result<(), FdiskError> {
+        log::debug!("Fdisk::partition_wipe_deactivate enabling partition wipe");
+
+        Self::wipe_partition(self.inner, partition_number, false)
+    }
+
+    #[doc(hidden)]
+    /// Creates a new partition table on the associated device.
+    fn create_partition_table(
+        ptr: *mut libfdisk::fdisk_context,
+        name: *const libc::c_char,
+    ) -> Result<(), FdiskError> {
+        let result = unsafe { libfdisk::fdisk_create_disklabel(ptr, name) };
+
+        match result {
+            0 => {
+                log::debug!("Fdisk::create_partition_table created partition table",);
+
+                Ok(())
+            }
+            code => {
+                let err_msg = "failed to create partition table".to_owned();
+                log::debug!("Fdisk::create_partition_table {}. libfdisk::fdisk_create_disklabel returned error code: {:?}", err_msg, code);
+
+                Err(FdiskError::Creation(err_msg))
+            }
+        }
+    }
+
+    /// Creates a default partition table which can be a `DOS` or `SUN` partition table depending on the systems.
+    pub fn partition_table_create_default(&mut self) -> Result<(), FdiskError> {
+        log::debug!(
+            "Fdisk::partition_table_create_default creating default partition table (DOS or SUN)"
+        );
+
+        Self::create_partition_table(self.inner, std::ptr::null())
+    }
+
+    /// Creates a partition table.
     pub fn partition_table_create(&mut self, kind: PartitionTableKind) -> Result<(), FdiskError> {
         log::debug!(
             "Fdisk::partition_table_create creating {:?} partition table",
@@ -1563,7 +2484,8 @@ pub fn partition_table_sort_partitions(&mut self) -> Result<(), FdiskError> {
         let result = unsafe { libfdisk::fdisk_reorder_partitions(self.inner) };
 
         match result {
-            0 => {
+            // `libfdisk` returns 1 when partitions are already in order.
+            0 | 1 => {
                 log::debug!(
                     "Fdisk::partition_table_sort_partitions sorted partition array entries"
                 );
@@ -1727,1732 +2649,5328 @@ pub fn partition_table_check(&self) -> VerificationStatus {
         }
     }
 
-    /// Writes the in-memory partition table to disk.
-    pub fn partition_table_write_to_disk(&mut self) -> Result<(), FdiskError> {
-        log::debug!("Fdisk::partition_table_write_to_disk writing partition table to disk");
+    #[doc(hidden)]
+    /// Checks the location of each partition on the device.
+    fn verify_partition_layout(&self, partitions: &PartitionList) -> Vec<VerificationFinding> {
+        let last_lba = self.device_last_lba();
+        let grain_size = self.device_grain_size();
+        let sector_size = self.device_bytes_per_logical_sector();
+        let alignment_offset = self.device_alignment_offset();
+
+        // (partition number, starting sector, ending sector, is container, is nested)
+        let layout: Vec<(usize, u64, u64, bool, bool)> = partitions
+            .iter()
+            .filter(|p| !p.is_free_space() && !p.is_whole_disk())
+            .filter_map(|p| {
+                Some((
+                    p.number()?,
+                    p.starting_sector()?,
+                    p.ending_sector()?,
+                    p.is_container(),
+                    p.is_nested(),
+                ))
+            })
+            .collect();
+
+        let mut findings = vec![];
+
+        for (i, &(number, start, end, is_container, is_nested)) in layout.iter().enumerate() {
+            if end > last_lba {
+                findings.push(VerificationFinding::BeyondLastLba {
+                    partition_number: number,
+                    ending_sector: end,
+                    last_lba,
+                });
+            }
+
+            if grain_size > 0
+                && !is_container
+                && (start * sector_size) % grain_size != alignment_offset % grain_size
+            {
+                findings.push(VerificationFinding::MisalignedStart {
+                    partition_number: number,
+                    starting_sector: start,
+                    grain_size,
+                });
+            }
+
+            for &(other_number, other_start, other_end, other_is_container, other_is_nested) in
+                layout.iter().skip(i + 1)
+            {
+                // Logical partitions are, by design, inside their extended partition.
+                let is_enclosed =
+                    (is_container && other_is_nested) || (is_nested && other_is_container);
+
+                if !is_enclosed && start <= other_end && other_start <= end {
+                    findings.push(VerificationFinding::Overlap {
+                        partition_number: number,
+                        other_partition_number: other_number,
+                    });
+                }
+            }
+        }
 
-        let result = unsafe { libfdisk::fdisk_write_disklabel(self.inner) };
+        findings
+    }
 
-        match result {
-            0 => {
-                log::debug!("Fdisk::partition_table_write_to_disk wrote partition table to disk");
+    /// Checks whether a partition table is well-formed, and returns a detailed list of the issues
+    /// found.
+    ///
+    /// In addition to the checks performed by [`Fdisk::partition_table_check`], this method
+    /// verifies that partitions do not overlap, fit on the device, and start on a sector aligned on
+    /// the device's grain size (see [`Fdisk::device_grain_size`]). When the in-memory partition
+    /// table was read from the device, it also reads its on-disk copy to check the CRC32 checksums
+    /// of the `GPT` headers and Partition Entry Arrays, that the backup `GPT` header is on the last
+    /// sector and agrees with the primary one, and, if the `DOS` partition table has no change
+    /// pending, that the CHS addresses of its partition entries match their LBA addresses.
+    ///
+    /// The messages `libfdisk` emits while checking the partition table are left in
+    /// [`Fdisk::diagnostics`] as context; they play no part in the findings.
+    ///
+    /// Returns an empty list if the partition table has no issue.
+    pub fn partition_table_verify(&self) -> Result<Vec<VerificationFinding>, FdiskError> {
+        log::debug!("Fdisk::partition_table_verify verifying partition table");
 
-                Ok(())
-            }
-            code => {
-                let err_msg = "failed to write partition table to disk".to_owned();
-                log::debug!("Fdisk::partition_table_write_to_disk {}. libfdisk::fdisk_write_disklabel returned error code: {:?}", err_msg, code);
+        if !self.device_has_partition_table() {
+            let err_msg = "no partition table to verify".to_owned();
+            log::debug!("Fdisk::partition_table_verify {}", err_msg);
 
-                Err(FdiskError::Save(err_msg))
+            return Err(FdiskError::Config(err_msg));
+        }
+
+        let result = unsafe { libfdisk::fdisk_verify_disklabel(self.inner) };
+
+        if result < 0 {
+            let err_msg = "failed to verify partition table".to_owned();
+            log::debug!("Fdisk::partition_table_verify {}. libfdisk::fdisk_verify_disklabel returned error code: {:?}", err_msg, result);
+
+            return Err(FdiskError::Verification(err_msg));
+        }
+
+        let partitions = self.list_partitions().ok_or_else(|| {
+            let err_msg = "failed to list partitions to verify".to_owned();
+            log::debug!("Fdisk::partition_table_verify {}", err_msg);
+
+            FdiskError::Verification(err_msg)
+        })?;
+
+        let mut findings = self.verify_partition_layout(&partitions);
+
+        if let Some(device) = self.device_name() {
+            let sector_size = self.device_bytes_per_logical_sector();
+
+            if self.partition_table_is_of_type(PartitionTableKind::GPT) {
+                if let Some(disk_guid) = self.partition_table_id() {
+                    let last_lba = self.device_size_in_sectors().saturating_sub(1);
+                    findings.extend(gpt_verify_headers(
+                        device,
+                        sector_size,
+                        last_lba,
+                        &disk_guid,
+                    )?);
+                }
+            } else if self.partition_table_is_of_type(PartitionTableKind::DOS) {
+                // Only check a partition table read from the device, not one created or modified
+                // in memory.
+                let is_on_disk = self
+                    .partition_table_current()
+                    .map(|table| !table.has_changes())
+                    .unwrap_or(false);
+
+                if is_on_disk {
+                    let ebr_offsets: Vec<_> = (0..)
+                        .map_while(|nth| self.partition_table_section(nth))
+                        .filter(|section| section.name() == "EBR")
+                        .map(|section| section.starting_offset())
+                        .collect();
+
+                    findings.extend(dos_verify_chs(
+                        device,
+                        sector_size,
+                        self.device_count_heads(),
+                        self.device_count_sectors(),
+                        &ebr_offsets,
+                    )?);
+                }
             }
         }
-    }
 
-    /// Returns `true` when the partition table on the assigned disk matches the given type.
-    pub fn partition_table_is_of_type(&self, kind: PartitionTableKind) -> bool {
-        let kind_u32: u32 = kind.into();
-        let state = unsafe { libfdisk::fdisk_is_labeltype(self.inner, kind_u32) == 1 };
         log::debug!(
-            "Fdisk::partition_table_is_of_type is partition table of type {:?}? {:?}",
-            kind,
-            state
+            "Fdisk::partition_table_verify found {:?} issue(s)",
+            findings.len()
         );
 
-        state
+        Ok(findings)
     }
 
-    /// Creates an `sfdisk`-compatible [`Script`] associated with this `Fdisk`.
-    pub fn script_new(&self) -> Result<&mut Script, FdiskError> {
-        log::debug!("Fdisk::script_new creating a new `Script` instance");
-
-        let mut ptr = MaybeUninit::<*mut libfdisk::fdisk_script>::zeroed();
-        unsafe {
-            ptr.write(libfdisk::fdisk_new_script(self.inner));
+    #[doc(hidden)]
+    /// Returns a blocker if the in-memory partition table is not of the `expected` type.
+    fn check_conversion_source(&self, expected: PartitionTableKind) -> Option<ConversionBlocker> {
+        if self.partition_table_is_of_type(expected) {
+            return None;
         }
 
-        match unsafe { ptr.assume_init() } {
-            ptr if ptr.is_null() => {
-                let err_msg = "failed to create a new `Script` instance".to_owned();
-                log::debug!(
-                    "Fdisk::script_new {}. libfdisk::fdisk_new_script returned a NULL pointer",
-                    err_msg
-                );
+        let found = self
+            .device_has_partition_table()
+            .then(|| self.partition_table_current().map(|table| table.kind()))
+            .flatten();
 
-                Err(FdiskError::Script(err_msg))
-            }
-            ptr => {
-                log::debug!("Fdisk::script_new created a new `Script` instance");
-                let script = owning_mut_from_ptr!(self, Script, ptr);
+        Some(ConversionBlocker::UnsupportedPartitionTable { expected, found })
+    }
 
-                Ok(script)
-            }
-        }
+    #[doc(hidden)]
+    /// Creates a template for a partition converted from another type of partition table.
+    pub(crate) fn converted_partition(
+        number: usize,
+        starting_sector: u64,
+        ending_sector: u64,
+        partition_type: Result<PartitionKind, PartitionKindBuilderError>,
+    ) -> Result<Partition, FdiskError> {
+        partition_type
+            .map_err(|e| e.to_string())
+            .and_then(|kind| {
+                // Sizes are already computed, prevent `libfdisk` from aligning them again.
+                Partition::builder()
+                    .ask_size_interactive()
+                    .number(number)
+                    .starting_sector(starting_sector)
+                    .size_in_sectors(ending_sector - starting_sector + 1)
+                    .partition_type(kind)
+                    .build()
+                    .map_err(|e| e.to_string())
+            })
+            .map_err(|e| {
+                let err_msg = format!("failed to convert partition {}. {}", number, e);
+                log::debug!("Fdisk::converted_partition {}", err_msg);
+
+                FdiskError::Conversion(err_msg)
+            })
     }
 
-    /// Creates a new `sfdisk`-compatible [`Script`] associated with this `Fdisk` importing its
-    /// content from a file.
-    pub fn script_new_from_file<T>(&self, file_path: T) -> Result<&mut Script, FdiskError>
-    where
-        T: AsRef<Path>,
-    {
-        let file_path = file_path.as_ref();
-        let path = ffi_utils::as_ref_path_to_c_string(file_path)?;
-        log::debug!(
-            "Fdisk::script_new_from_file creating a new `Script` instance from: {:?}",
-            file_path
-        );
+    #[doc(hidden)]
+    /// Returns the number of sectors occupied by a `GPT` Partition Entry Array with the default
+    /// number of entries.
+    fn gpt_entry_array_sectors(&self) -> u64 {
+        // Default number of entries, and size in bytes of an entry.
+        const GPT_ENTRY_COUNT: u64 = 128;
+        const GPT_ENTRY_SIZE: u64 = 128;
 
-        let mut ptr = MaybeUninit::<*mut libfdisk::fdisk_script>::zeroed();
-        unsafe {
-            ptr.write(libfdisk::fdisk_new_script_from_file(
-                self.inner,
-                path.as_ptr(),
-            ));
-        }
+        let sector_size = self.device_bytes_per_logical_sector().max(1);
 
-        match unsafe { ptr.assume_init() } {
-            ptr if ptr.is_null() => {
-                let err_msg = format!(
-                    "failed to create a new `Script` instance from: {:?}",
-                    file_path
-                );
-                log::debug!("Fdisk::script_new_from_file {}. libfdisk::fdisk_new_script_from_file returned a NULL pointer", err_msg);
+        (GPT_ENTRY_COUNT * GPT_ENTRY_SIZE + sector_size - 1) / sector_size
+    }
 
-                Err(FdiskError::Script(err_msg))
-            }
-            ptr => {
-                log::debug!(
-                    "Fdisk::script_new_from_file created a new `Script` instance from: {:?}",
-                    file_path
-                );
-                let script = owning_mut_from_ptr!(self, Script, ptr);
+    #[doc(hidden)]
+    /// Returns the primary and logical partitions of a `DOS` partition table, skipping extended
+    /// partitions.
+    fn dos_data_partitions(&self) -> Result<Vec<DosDataPartition>, FdiskError> {
+        let partitions = self.list_partitions().ok_or_else(|| {
+            let err_msg = "failed to list partitions to convert".to_owned();
+            log::debug!("Fdisk::dos_data_partitions {}", err_msg);
 
-                Ok(script)
-            }
-        }
+            FdiskError::Conversion(err_msg)
+        })?;
+
+        partitions
+            .iter()
+            .filter(|p| !p.is_free_space() && !p.is_container())
+            .map(
+                |p| match (p.number(), p.starting_sector(), p.ending_sector()) {
+                    (Some(number), Some(start), Some(end)) => Ok((
+                        number,
+                        start,
+                        end,
+                        p.partition_type().and_then(|kind| kind.code()),
+                        p.is_bootable(),
+                    )),
+                    _ => {
+                        let err_msg =
+                            format!("failed to get the position of partition {:?}", p.number());
+                        log::debug!("Fdisk::dos_data_partitions {}", err_msg);
+
+                        Err(FdiskError::Conversion(err_msg))
+                    }
+                },
+            )
+            .collect()
     }
 
-    /// Applies the provided [`Script`] to this `Fdisk` to create the in-memory partition
-    /// table, and partitions its content specifies.
+    /// Checks whether the in-memory `DOS` partition table can be converted to `GPT` by
+    /// [`Fdisk::partition_table_convert_to_gpt`], without modifying it (dry run).
     ///
-    /// To write the changes to disk call [`Fdisk::partition_table_write_to_disk`].
-    pub fn script_apply(&mut self, script: &Script) -> Result<(), FdiskError> {
-        log::debug!("Fdisk::script_apply applying script");
+    /// Partitions keep their starting and ending sectors when converted, so there must be enough
+    /// room before the first partition for the Protective MBR, the primary `GPT` header and its
+    /// Partition Entry Array, and enough room after the last partition for their backups.
+    ///
+    /// Returns an empty list if nothing prevents the conversion.
+    pub fn partition_table_check_gpt_conversion(
+        &self,
+    ) -> Result<Vec<ConversionBlocker>, FdiskError> {
+        log::debug!("Fdisk::partition_table_check_gpt_conversion checking conversion to GPT");
 
-        // Internally `fdisk_apply_script` calls `fdisk_apply_script_headers` on `script.inner`
-        // see https://github.com/util-linux/util-linux/blob/8aa25617467a1249669cff7240ca31973bf9a127/libfdisk/src/script.c#L1621
-        //
-        // > which in turn will call `fdisk_set_script`
-        // see https://github.com/util-linux/util-linux/blob/8aa25617467a1249669cff7240ca31973bf9a127/libfdisk/src/script.c#L1541
-        //
-        // >> `set_script` will set `script.inner` as the current associated Script
-        // https://github.com/util-linux/util-linux/blob/8aa25617467a1249669cff7240ca31973bf9a127/libfdisk/src/script.c#L1500
-        //
-        // then `fdisk_apply_script` will restore the old script as the current one by calling
-        // `set_script` which will incidentally decrement the reference counter of `script.inner'
-        // and leave us with a dangling pointer.
-        // https://github.com/util-linux/util-linux/blob/8aa25617467a1249669cff7240ca31973bf9a127/libfdisk/src/script.c#L1496`
-        //
-        // To prevent this outcome, we increment the reference counter of `script.inner` before using it as a countermeasure.
-        unsafe {
-            libfdisk::fdisk_ref_script(script.inner);
+        if let Some(blocker) = self.check_conversion_source(PartitionTableKind::DOS) {
+            log::debug!("Fdisk::partition_table_check_gpt_conversion {}", blocker);
+
+            return Ok(vec![blocker]);
         }
 
-        let result = unsafe { libfdisk::fdisk_apply_script(self.inner, script.inner) };
+        // Protective MBR and GPT header, followed by the Partition Entry Array.
+        let entry_array_sectors = self.gpt_entry_array_sectors();
+        let first_usable_lba = 2 + entry_array_sectors;
+        let last_usable_lba = self
+            .device_size_in_sectors()
+            .saturating_sub(2 + entry_array_sectors);
 
-        match result {
-            0 => {
-                log::debug!("Fdisk::script_apply applied script");
+        let mut blockers = vec![];
 
-                Ok(())
+        for (number, start, end, code, _) in self.dos_data_partitions()? {
+            if code == Some(Code::GPTProtectiveMBR.to_u32()) {
+                blockers.push(ConversionBlocker::ProtectivePartition {
+                    partition_number: number,
+                });
             }
-            code => {
-                let err_msg = "failed to apply script".to_owned();
-                log::debug!("Fdisk::script_apply {}. libfdisk::fdisk_apply_script returned error code: {:?}", err_msg, code);
 
-                Err(FdiskError::Config(err_msg))
+            if start < first_usable_lba {
+                blockers.push(ConversionBlocker::NoRoomForPrimaryGpt {
+                    partition_number: number,
+                    starting_sector: start,
+                    first_usable_lba,
+                });
+            }
+
+            if end > last_usable_lba {
+                blockers.push(ConversionBlocker::NoRoomForBackupGpt {
+                    partition_number: number,
+                    ending_sector: end,
+                    last_usable_lba,
+                });
             }
         }
-    }
 
-    // FIXME a script retains a pointer to the context it was created from
-    // https://github.com/util-linux/util-linux/blob/8aa25617467a1249669cff7240ca31973bf9a127/libfdisk/src/script.c#L103
-    // a new assignment here does not update this reference. Furthermore, calling this function
-    // can create a shared ownership of the same script between `fdisk_context` structures. How does this impact Rust ownership
-    // constraints?
-    //
-    // - Each value in Rust has an owner.
-    // - There can only be one owner at a time.
-    // - When the owner goes out of scope, the value will be dropped.
+        log::debug!(
+            "Fdisk::partition_table_check_gpt_conversion found {:?} blocker(s)",
+            blockers.len()
+        );
 
-    /// Associates a [`Script`] to this `Fdisk`, then applies it to create the in-memory partition
-    /// table it specifies.
+        Ok(blockers)
+    }
+
+    /// Replaces the in-memory `DOS` partition table by an equivalent `GPT` partition table, and
+    /// returns the identification numbers of the converted partitions.
     ///
-    /// FIXME as is, this function is unusable since there is currently no way to pass it a fully
-    /// owned [`Script`]. More information from developers upstream is needed for me to come up
-    /// with a better API design. To be clear, it is **MY** lack of knowledge about the code produced
-    /// upstream that is at fault here, **NOT** the developers of `libfdisk`. In the meantime... ¯\\\_(ツ)\_/¯
-    pub fn script_apply_headers(&mut self, mut script: Script) -> Result<(), FdiskError> {
-        log::debug!("Fdisk::script_apply_headers applying script headers");
+    /// Primary and logical partitions keep their identification numbers, as well as their
+    /// starting and ending sectors. Their `MBR` type is converted to the matching `GPT` type (see
+    /// [`Code::to_guid`]), and bootable partitions get the
+    /// [`GptAttributes::LEGACY_BIOS_BOOTABLE`] attribute. Extended partitions, which have no
+    /// equivalent in a `GPT` partition table, are dropped.
+    ///
+    /// The conversion is refused if [`Fdisk::partition_table_check_gpt_conversion`] finds any
+    /// blocker, and the in-memory partition table is left untouched if it fails midway. As with
+    /// other edits, call [`Fdisk::partition_table_write_to_disk`] to save the new partition table
+    /// to the assigned device.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use rsfdisk::fdisk::Fdisk;
+    ///
+    /// fn main() -> rsfdisk::Result<()> {
+    ///     let mut disk = Fdisk::builder()
+    ///         .assign_device("/dev/vda")
+    ///         .enable_read_write()
+    ///         .build()?;
+    ///
+    ///     let blockers = disk.partition_table_check_gpt_conversion()?;
+    ///
+    ///     if blockers.is_empty() {
+    ///         disk.partition_table_convert_to_gpt()?;
+    ///         disk.partition_table_write_to_disk()?;
+    ///     } else {
+    ///         for blocker in blockers {
+    ///             eprintln!("{}", blocker);
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn partition_table_convert_to_gpt(&mut self) -> Result<Vec<usize>, FdiskError> {
+        log::debug!("Fdisk::partition_table_convert_to_gpt converting partition table to GPT");
 
-        // We are virtually ceding ownership of this instance to the C-side of the library;
-        // instance that will be automatically deallocated once it is out of scope, incrementing
-        // its reference counter protects it from being freed prematurely.
-        script.incr_ref_counter();
+        let blockers = self.partition_table_check_gpt_conversion()?;
 
-        let result = unsafe { libfdisk::fdisk_apply_script_headers(self.inner, script.inner) };
+        if !blockers.is_empty() {
+            let reasons: Vec<String> = blockers.iter().map(|b| b.to_string()).collect();
+            let err_msg = format!(
+                "can not convert partition table to GPT: {}",
+                reasons.join(", ")
+            );
+            log::debug!("Fdisk::partition_table_convert_to_gpt {}", err_msg);
 
-        match result {
-            0 => {
-                log::debug!("Fdisk::script_apply_headers applied script headers");
+            return Err(FdiskError::Conversion(err_msg));
+        }
 
-                Ok(())
-            }
-            code => {
-                let err_msg = "failed to apply script headers".to_owned();
-                log::debug!("Fdisk::script_apply_headers {}. libfdisk::fdisk_apply_script_headers returned error code: {:?}", err_msg, code);
+        let partitions = self.dos_data_partitions()?;
 
-                Err(FdiskError::Config(err_msg))
-            }
-        }
-    }
+        let partition_numbers = self.partition_table_transaction(|disk| {
+            disk.partition_table_create(PartitionTableKind::GPT)?;
 
-    #[doc(hidden)]
-    /// Sets/unsets which [`Script`] a `Fdisk` is associated with.
-    fn set_script(ptr: &mut Self, script: *mut libfdisk::fdisk_script) -> Result<(), FdiskError> {
-        let result = unsafe { libfdisk::fdisk_set_script(ptr.inner, script) };
+            let mut partition_numbers = Vec::with_capacity(partitions.len());
 
-        match result {
-            0 => {
-                log::debug!("Fdisk::set_script script set");
+            for (number, start, end, code, bootable) in partitions {
+                // Unknown MBR types are converted to Linux data partitions.
+                let guid = match code
+                    .and_then(|code| u8::try_from(code).ok())
+                    .and_then(|code| Code::try_from(code).ok())
+                {
+                    Some(code) => code.to_guid(),
+                    None => Some(Guid::LinuxData),
+                };
 
-                Ok(())
-            }
-            code => {
-                let err_msg = "failed to set script".to_owned();
-                log::debug!(
-                    "Fdisk::set_script {}. libfdisk::fdisk_set_script returned error code: {:?}",
-                    err_msg,
-                    code
-                );
+                let Some(guid) = guid else {
+                    continue;
+                };
 
-                Err(FdiskError::Config(err_msg))
-            }
-        }
-    }
+                let partition = Self::converted_partition(
+                    number,
+                    start,
+                    end,
+                    PartitionKind::builder().guid(guid).build(),
+                )?;
 
-    /// Removes the [`Script`] currently associated with this `Fdisk`.
-    pub fn script_dissociate(&mut self) -> Result<(), FdiskError> {
-        log::debug!("Fdisk::script_dissociate dissociating script");
+                let partition_number = disk.partition_add(partition)?;
 
-        Self::set_script(self, std::ptr::null_mut())
-    }
+                if bootable {
+                    disk.gpt_set_attributes(partition_number, GptAttributes::LEGACY_BIOS_BOOTABLE)?;
+                }
 
-    //---- END mutators
+                partition_numbers.push(partition_number);
+            }
 
-    //---- BEGIN getters
+            Ok::<_, FdiskError>(partition_numbers)
+        })?;
+
+        log::debug!(
+            "Fdisk::partition_table_convert_to_gpt converted partitions: {:?}",
+            partition_numbers
+        );
+
+        Ok(partition_numbers)
+    }
 
     #[doc(hidden)]
-    /// Gets the string representation of the unit in which numerical metadata is displayed.
-    fn unit_str<'b>(context: *mut libfdisk::fdisk_context, multiplicity: i32) -> &'b str {
-        log::debug!("Fdisk::unit_str getting display unit");
+    /// Returns the partitions of a `GPT` partition table, sorted by starting sector.
+    fn gpt_data_partitions(&self) -> Result<Vec<GptDataPartition>, FdiskError> {
+        let partitions = self.list_partitions().ok_or_else(|| {
+            let err_msg = "failed to list partitions to convert".to_owned();
+            log::debug!("Fdisk::gpt_data_partitions {}", err_msg);
 
-        let mut ptr = MaybeUninit::<*const libc::c_char>::zeroed();
-        unsafe {
-            ptr.write(libfdisk::fdisk_get_unit(context, multiplicity));
-        }
+            FdiskError::Conversion(err_msg)
+        })?;
 
-        let unit_ptr = unsafe { ptr.assume_init() };
-        ffi_utils::const_char_array_to_str_ref(unit_ptr).unwrap()
-    }
+        let mut partitions = partitions
+            .iter()
+            .filter(|p| !p.is_free_space())
+            .map(
+                |p| match (p.number(), p.starting_sector(), p.ending_sector()) {
+                    (Some(number), Some(start), Some(end)) => {
+                        let guid = p
+                            .partition_type()
+                            .and_then(|kind| kind.guid().and_then(|guid| guid.parse().ok()));
+                        let bootable = self.gpt_attributes(number).is_some_and(|attributes| {
+                            attributes.contains(GptAttributes::LEGACY_BIOS_BOOTABLE)
+                        });
+
+                        Ok((number, start, end, guid, bootable))
+                    }
+                    _ => {
+                        let err_msg =
+                            format!("failed to get the position of partition {:?}", p.number());
+                        log::debug!("Fdisk::gpt_data_partitions {}", err_msg);
 
-    /// Returns the string representation in singular form of the unit in which numerical metadata
-    /// is displayed.
-    pub fn displayed_unit_singular(&self) -> &str {
-        let unit = Self::unit_str(self.inner, libfdisk::FDISK_SINGULAR);
-        log::debug!("Fdisk::displayed_unit_singular value: {:?}", unit);
+                        Err(FdiskError::Conversion(err_msg))
+                    }
+                },
+            )
+            .collect::<Result<Vec<_>, _>>()?;
 
-        unit
+        partitions.sort_by_key(|&(_, start, ..)| start);
+
+        Ok(partitions)
     }
 
-    /// Returns the string representation in plural form of the unit in which numerical metadata
-    /// is displayed.
-    pub fn displayed_unit_plural(&self) -> &str {
-        let unit = Self::unit_str(self.inner, libfdisk::FDISK_PLURAL);
-        log::debug!("Fdisk::displayed_unit_plural value: {:?}", unit);
+    #[doc(hidden)]
+    /// Restores the location of the first logical sector and the grain size saved before an edit.
+    fn restore_lba_alignment(&mut self, first_lba: u64, grain: u64) -> Result<(), FdiskError> {
+        // `libfdisk` has no setter for the grain size, resetting the alignment computes it anew.
+        self.restore_default_lba_alignment()?;
+
+        if self.device_grain_size() != grain {
+            self.save_device_grain_size_override(grain)?;
+            self.restore_default_lba_alignment()?;
+        }
 
-        unit
+        self.device_set_first_lba(first_lba)
     }
 
-    /// Returns a reference to the parent partitioner of this `Fdisk` when it is a nested
-    /// partitioner.
+    /// Checks whether the in-memory `GPT` partition table can be converted to `DOS` by
+    /// [`Fdisk::partition_table_convert_to_dos`], without modifying it (dry run).
     ///
-    /// # Panics
+    /// Partitions keep their starting and ending sectors when converted. A `DOS` partition table
+    /// can only address the first 2<sup>32</sup> sectors of a device (i.e. 2 TiB with 512-byte
+    /// sectors), and hold at most four primary partitions. When there are more than four
+    /// partitions to convert, the fourth one onwards (by starting sector) become logical
+    /// partitions inside an extended partition; each of them must then be preceded by a free
+    /// sector to hold its Extended Boot Record (EBR).
     ///
-    /// May panic if the underlying `libfdisk` parent C pointer differs from the cached parent Rust
-    /// reference.
-    pub fn parent_partitioner(&self) -> Option<&Fdisk> {
-        log::debug!("Fdisk::parent_partitioner getting reference to parent `Fdisk`");
+    /// Returns an empty list if nothing prevents the conversion.
+    pub fn partition_table_check_dos_conversion(
+        &self,
+    ) -> Result<Vec<ConversionBlocker>, FdiskError> {
+        log::debug!("Fdisk::partition_table_check_dos_conversion checking conversion to DOS");
 
-        let mut ptr = MaybeUninit::<*mut libfdisk::fdisk_context>::zeroed();
-        unsafe {
-            ptr.write(libfdisk::fdisk_get_parent(self.inner));
+        // Maximum number of partitions in a DOS partition table, extended partition included.
+        const DOS_MAX_PARTITIONS: usize = 60;
+        const DOS_MAX_PRIMARY_PARTITIONS: usize = 4;
+        const DOS_LAST_ADDRESSABLE_LBA: u64 = u32::MAX as u64;
+
+        if let Some(blocker) = self.check_conversion_source(PartitionTableKind::GPT) {
+            log::debug!("Fdisk::partition_table_check_dos_conversion {}", blocker);
+
+            return Ok(vec![blocker]);
         }
 
-        match unsafe { ptr.assume_init() } {
-            ptr if ptr.is_null() => {
-                log::debug!("Fdisk::parent_partitioner got no parent partitioner. libfdisk::fdisk_get_parent returned a NULL pointer");
-                None
+        let partitions = self.gpt_data_partitions()?;
+        let needs_extended = partitions.len() > DOS_MAX_PRIMARY_PARTITIONS;
+
+        let mut blockers = vec![];
+
+        if needs_extended && partitions.len() > DOS_MAX_PARTITIONS - 1 {
+            blockers.push(ConversionBlocker::TooManyPartitions {
+                count: partitions.len(),
+                max: DOS_MAX_PARTITIONS - 1,
+            });
+        }
+
+        let mut previous_end = 0;
+
+        for (i, &(number, start, end, _, _)) in partitions.iter().enumerate() {
+            if end > DOS_LAST_ADDRESSABLE_LBA {
+                blockers.push(ConversionBlocker::BeyondDosLimit {
+                    partition_number: number,
+                    ending_sector: end,
+                    last_addressable_lba: DOS_LAST_ADDRESSABLE_LBA,
+                });
             }
-            parent_ptr => {
-                log::debug!("Fdisk::parent_partitioner got parent partitioner");
-                self._parent.map(|parent| {
-                    if parent.inner == parent_ptr {
-                        log::debug!("Fdisk::parent_partitioner parent partitioner matches cached value");
 
-                        parent
-                    } else {
-                        log::debug!(
-                            "Fdisk::parent_partitioner parent partitioner does NOT match cached value"
-                        );
+            let is_logical = needs_extended && i >= DOS_MAX_PRIMARY_PARTITIONS - 1;
 
-                        panic!("Fdisk::parent_partitioner parent partitioner does NOT match cached value");
-                    }
-                })
+            if is_logical && start <= previous_end + 1 {
+                blockers.push(ConversionBlocker::NoRoomForEbr {
+                    partition_number: number,
+                    starting_sector: start,
+                });
             }
+
+            previous_end = end;
         }
-    }
 
-    /// Returns the number of sectors per cylinder when this `Fdisk` is set to display data in
-    /// cylinder units, or `1` if device addressing is set to [`DeviceAddressing::Sector`] (see:
-    /// [`FdiskBuilder::device_addressing`]).
-    pub fn sectors_per_cylinder(&self) -> u64 {
-        let sectors = unsafe { libfdisk::fdisk_get_units_per_sector(self.inner) };
-        log::debug!("Fdisk::sectors_per_cylinder value: {:?}", sectors);
+        log::debug!(
+            "Fdisk::partition_table_check_dos_conversion found {:?} blocker(s)",
+            blockers.len()
+        );
 
-        sectors as u64
+        Ok(blockers)
     }
 
-    /// Returns the offset in bytes between logical and physical sectors.
+    /// Replaces the in-memory `GPT` partition table by an equivalent `DOS` partition table, and
+    /// returns a list of `(GPT partition number, DOS partition number)` pairs.
     ///
-    /// For backward compatibility, the first logical sector on 4K sector devices does not have to
-    /// start on a naturally aligned physical sector boundary.
-    pub fn device_alignment_offset(&self) -> u64 {
-        let offset = unsafe { libfdisk::fdisk_get_alignment_offset(self.inner) };
-        log::debug!("Fdisk::device_alignment_offset value: {:?}", offset);
-
-        offset
-    }
-
-    /// Returns the underlying file descriptor associated with the assigned device.
+    /// Partitions keep their starting and ending sectors, and are numbered by starting sector.
+    /// When there are more than four of them, the first three become primary partitions, and the
+    /// others become logical partitions inside an extended partition (number `3`) spanning from
+    /// the sector before the first logical partition to the end of the last one. Each Extended
+    /// Boot Record is stored in the sector preceding its logical partition.
     ///
-    /// # Safety
+    /// Partition types are converted to the matching `MBR` type (see [`Guid::to_code`]), and
+    /// primary partitions with the [`GptAttributes::LEGACY_BIOS_BOOTABLE`] attribute are marked
+    /// bootable. The `GPT` headers left on the device are erased when the new partition table is
+    /// written.
     ///
-    /// You must guarantee that for the duration of this `Fdisk`'s lifetime, nobody will close the
-    /// file descriptor returned by this method.
-    pub unsafe fn device_borrow_fd(&self) -> BorrowedFd {
-        log::debug!("Fdisk::device_borrow_fd borrowing assigned device's file descriptor");
-
-        let raw_fd = unsafe { libfdisk::fdisk_get_devfd(self.inner) };
-
-        unsafe { BorrowedFd::borrow_raw(raw_fd) }
-    }
-
-    /// Returns a device's model.
-    pub fn device_model(&self) -> Option<&str> {
-        log::debug!("Fdisk::device_model getting device model");
+    /// The conversion is refused if [`Fdisk::partition_table_check_dos_conversion`] finds any
+    /// blocker, and the in-memory partition table is left untouched if it fails midway. The
+    /// location of the first logical sector and the grain size are restored on exit. As with
+    /// other edits, call [`Fdisk::partition_table_write_to_disk`] to save the new partition table
+    /// to the assigned device.
+    pub fn partition_table_convert_to_dos(&mut self) -> Result<Vec<(usize, usize)>, FdiskError> {
+        log::debug!("Fdisk::partition_table_convert_to_dos converting partition table to DOS");
+
+        let blockers = self.partition_table_check_dos_conversion()?;
+
+        if !blockers.is_empty() {
+            let reasons: Vec<String> = blockers.iter().map(|b| b.to_string()).collect();
+            let err_msg = format!(
+                "can not convert partition table to DOS: {}",
+                reasons.join(", ")
+            );
+            log::debug!("Fdisk::partition_table_convert_to_dos {}", err_msg);
 
-        let mut ptr = MaybeUninit::<*const libc::c_char>::zeroed();
-        unsafe {
-            ptr.write(libfdisk::fdisk_get_devmodel(self.inner));
+            return Err(FdiskError::Conversion(err_msg));
         }
 
-        match unsafe { ptr.assume_init() } {
-            model_ptr if model_ptr.is_null() => {
-                log::debug!("Fdisk::device_model got no device model. libfdisk::fdisk_get_devmodel returned a NULL pointer");
+        let partitions = self.gpt_data_partitions()?;
+        let needs_extended = partitions.len() > 4;
+        let extended_end = partitions.last().map(|&(_, _, end, ..)| end).unwrap_or(0);
+
+        let first_lba = self.device_first_lba();
+        let grain = self.device_grain_size();
+
+        let result = self.partition_table_transaction(|disk| {
+            disk.partition_table_create(PartitionTableKind::DOS)?;
+            // Store each Extended Boot Record right before its logical partition.
+            disk.device_set_first_lba(1)?;
+
+            let mut numbers = Vec::with_capacity(partitions.len());
+
+            for (i, &(number, start, end, guid, bootable)) in partitions.iter().enumerate() {
+                let is_logical = needs_extended && i >= 3;
+
+                if needs_extended && i == 3 {
+                    let extended = Self::converted_partition(
+                        3,
+                        start - 1,
+                        extended_end,
+                        PartitionKind::builder()
+                            .code(Code::ExtendedPartition)
+                            .build(),
+                    )?;
+                    disk.partition_add(extended)?;
+                }
 
-                None
-            }
-            model_ptr => {
-                let dev_model = ffi_utils::const_char_array_to_str_ref(model_ptr).ok();
-                log::debug!("Fdisk::device_model got device model: {:?}", dev_model);
+                // Unknown GPT types are converted to Linux partitions.
+                let code = guid.and_then(|guid| guid.to_code()).unwrap_or(Code::Linux);
+                let dos_number = if is_logical { i + 1 } else { i };
+                let partition = Self::converted_partition(
+                    dos_number,
+                    start,
+                    end,
+                    PartitionKind::builder().code(code).build(),
+                )?;
+                let dos_number = disk.partition_add(partition)?;
+
+                if bootable && !is_logical {
+                    disk.partition_table_toggle_partition_flag(
+                        dos_number,
+                        BitFlag::DOS(DOSFlag::Boot),
+                    )?;
+                }
 
-                dev_model
+                numbers.push((number, dos_number));
             }
-        }
-    }
-
-    /// Returns the assigned device's name.
-    pub fn device_name(&self) -> Option<&Path> {
-        log::debug!("Fdisk::device_name getting assigned device's name");
 
-        let mut ptr = MaybeUninit::<*const libc::c_char>::zeroed();
-        unsafe {
-            ptr.write(libfdisk::fdisk_get_devname(self.inner));
-        }
+            // Make sure libfdisk did not move any partition.
+            for (&(number, start, end, ..), &(_, dos_number)) in partitions.iter().zip(numbers.iter())
+            {
+                let position = disk
+                    .partition_by_number(dos_number)
+                    .map(|p| (p.starting_sector(), p.ending_sector()));
 
-        match unsafe { ptr.assume_init() } {
-            ptr if ptr.is_null() => {
-                let err_msg = "failed to get assigned device's name".to_owned();
-                log::debug!(
-                    "Fdisk::device_name {}. libfdisk::fdisk_get_devname returned a NULL pointer",
-                    err_msg
-                );
+                if position != Some((Some(start), Some(end))) {
+                    let err_msg = format!(
+                        "failed to convert partition {}. Could not keep its position: sectors {} to {}",
+                        number, start, end
+                    );
+                    log::debug!("Fdisk::partition_table_convert_to_dos {}", err_msg);
 
-                None
+                    return Err(FdiskError::Conversion(err_msg));
+                }
             }
-            dev_name_ptr => {
-                let dev_name = ffi_utils::const_c_char_array_to_path(dev_name_ptr);
-                log::debug!(
-                    "Fdisk::device_name got assigned device name: {:?}",
-                    dev_name
-                );
 
-                Some(dev_name)
-            }
-        }
-    }
+            Ok::<_, FdiskError>(numbers)
+        });
 
-    /// Returns the assigned device's identification number (`0` for an image file).
-    pub fn device_number(&self) -> u64 {
-        let dev_num = unsafe { libfdisk::fdisk_get_devno(self.inner) };
-        log::debug!("Fdisk::device_number value: {:?}", dev_num);
+        // Restore the alignment settings, whether the conversion succeeded or not.
+        let restored = self.restore_lba_alignment(first_lba, grain);
+        let numbers = result?;
+        restored?;
 
-        dev_num
-    }
+        self.enable_metadata_wipe()?;
 
-    /// Returns the location of the first logical block available for creating a new partition on
-    /// the assigned device.
-    pub fn device_first_lba(&self) -> u64 {
-        let first_lba = unsafe { libfdisk::fdisk_get_first_lba(self.inner) };
         log::debug!(
-            "Fdisk::device_first_lba address of first logical block: {:?}",
-            first_lba
+            "Fdisk::partition_table_convert_to_dos converted partitions: {:?}",
+            numbers
         );
 
-        first_lba
+        Ok(numbers)
     }
 
-    /// Returns the location of the last logical block available on the assigned device.
-    pub fn device_last_lba(&self) -> u64 {
-        let last_lba = unsafe { libfdisk::fdisk_get_last_lba(self.inner) };
-        log::debug!(
-            "Fdisk::device_last_lba address of last logical block: {:?}",
-            last_lba
-        );
-
-        last_lba
-    }
+    /// Compares the partition table on the assigned device with the one in memory, and lists the
+    /// changes [`Fdisk::partition_table_write_to_disk`] would apply.
+    ///
+    /// The partition table on disk is read anew, through a separate read-only `Fdisk`.
+    pub fn partition_table_diff(&self) -> Result<PartitionTableDiff, FdiskError> {
+        log::debug!("Fdisk::partition_table_diff comparing in-memory and on-disk partition tables");
 
-    /// Returns the number of cylinder subdivisions of the assigned device.
-    pub fn device_count_cylinders(&self) -> u64 {
-        let cylinders = unsafe { libfdisk::fdisk_get_geom_cylinders(self.inner) };
-        log::debug!(
-            "Fdisk::device_count_cylinders number of cylinders: {:?}",
-            cylinders
-        );
+        let device_name = self.device_name().ok_or_else(|| {
+            let err_msg = "no device assigned to read the partition table from".to_owned();
+            log::debug!("Fdisk::partition_table_diff {}", err_msg);
 
-        cylinders
-    }
+            FdiskError::Config(err_msg)
+        })?;
 
-    /// Returns the number of read-and-write heads (i.e. tracks per cylinder) of the assigned
-    /// device.
-    pub fn device_count_heads(&self) -> u64 {
-        let heads = unsafe { libfdisk::fdisk_get_geom_heads(self.inner) };
-        log::debug!("Fdisk::device_count_heads number of heads: {:?}", heads);
+        let on_disk = Fdisk::builder()
+            .assign_device(device_name)
+            .build()
+            .map_err(|e| {
+                let err_msg = format!("failed to read partition table on disk. {}", e);
+                log::debug!("Fdisk::partition_table_diff {}", err_msg);
 
-        heads as u64
-    }
+                FdiskError::AssignDevice(err_msg)
+            })?;
 
-    /// Returns the number of sectors per track of the assigned device.
-    pub fn device_count_sectors(&self) -> u64 {
-        let sectors = unsafe { libfdisk::fdisk_get_geom_sectors(self.inner) };
+        let diff = PartitionTableDiff::compute(&on_disk, self);
         log::debug!(
-            "Fdisk::device_count_sectors number of sectors per track: {:?}",
-            sectors
+            "Fdisk::partition_table_diff found {:?} change(s)",
+            diff.len()
         );
 
-        sectors
+        Ok(diff)
     }
 
-    /// Returns the device's grain size in bytes (usually `1 MiB`).
-    pub fn device_grain_size(&self) -> u64 {
-        let size = unsafe { libfdisk::fdisk_get_grain_size(self.inner) };
-        log::debug!("Fdisk::device_grain_size value: {:?}", size);
+    /// Saves a copy of the in-memory partition table, to restore with [`Savepoint::rollback`] if
+    /// a later edit fails.
+    ///
+    /// The returned [`Savepoint`] borrows this `Fdisk` until it is dropped; edit the partition
+    /// table through it in the meantime.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use rsfdisk::fdisk::Fdisk;
+    ///
+    /// fn main() -> rsfdisk::Result<()> {
+    ///     let mut disk = Fdisk::builder()
+    ///         .assign_device("/dev/vda")
+    ///         .enable_read_write()
+    ///         .build()?;
+    ///
+    ///     let mut savepoint = disk.partition_table_savepoint()?;
+    ///
+    ///     savepoint.partition_delete(0)?;
+    ///
+    ///     if savepoint.partition_delete(1).is_err() {
+    ///         // Restore partition 0.
+    ///         savepoint.rollback()?;
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn partition_table_savepoint(&mut self) -> Result<Savepoint<'_, 'a>, FdiskError> {
+        log::debug!("Fdisk::partition_table_savepoint saving in-memory partition table");
 
-        size
-    }
+        if !self.device_has_partition_table() {
+            let err_msg = "no partition table to save".to_owned();
+            log::debug!("Fdisk::partition_table_savepoint {}", err_msg);
 
-    /// Returns the preferred minimum number of bytes for random Input/Output on the assigned
-    /// device.
-    pub fn device_minimum_io_size(&self) -> u64 {
-        let min_io_size = unsafe { libfdisk::fdisk_get_minimal_iosize(self.inner) };
-        log::debug!(
-            "Fdisk::device_minimum_io_size minimal I/O size: {:?}",
-            min_io_size
-        );
+            return Err(FdiskError::Save(err_msg));
+        }
 
-        min_io_size
-    }
+        // The script lives no longer than the `Savepoint`, which holds an exclusive borrow of
+        // `self`.
+        let mut script = Script::from_ptr(self.new_script()?);
 
-    /// Returns the preferred optimal number of bytes for streaming Input/Output on the assigned
-    /// device.
-    pub fn device_optimal_io_size(&self) -> u64 {
-        let opt_io_size = unsafe { libfdisk::fdisk_get_minimal_iosize(self.inner) };
-        log::debug!(
-            "Fdisk::device_optimal_io_size optimal I/O size: {:?}",
-            opt_io_size
-        );
+        script.compose_script().map_err(|e| {
+            let err_msg = format!("failed to save in-memory partition table. {}", e);
+            log::debug!("Fdisk::partition_table_savepoint {}", err_msg);
 
-        opt_io_size
-    }
+            FdiskError::Save(err_msg)
+        })?;
 
-    /// Returns the size of the assigned device in bytes.
-    pub fn device_size_in_bytes(&self) -> u64 {
-        let size = self.device_size_in_sectors() * self.device_bytes_per_logical_sector();
-        log::debug!("Fdisk::device_size_in_bytes size (bytes): {:?}", size);
+        let partitions = self.list_partitions().ok_or_else(|| {
+            let err_msg = "failed to list partitions".to_owned();
+            log::debug!("Fdisk::partition_table_savepoint {}", err_msg);
 
-        size
-    }
+            FdiskError::Save(err_msg)
+        })?;
 
-    /// Returns the size of the assigned device in logical sectors.
-    pub fn device_size_in_sectors(&self) -> u64 {
-        let size = unsafe { libfdisk::fdisk_get_nsectors(self.inner) };
-        log::debug!("Fdisk::device_size_in_sectors size (sectors): {:?}", size);
+        log::debug!("Fdisk::partition_table_savepoint saved in-memory partition table");
 
-        size
+        Ok(Savepoint::new(self, script, partitions))
     }
 
-    /// Returns the size in bytes of a logical sector.
-    pub fn device_bytes_per_logical_sector(&self) -> u64 {
-        let size = unsafe { libfdisk::fdisk_get_sector_size(self.inner) };
-        log::debug!(
-            "Fdisk::device_bytes_per_logical_sector bytes per sector: {:?}",
-            size
-        );
+    /// Runs a multi-step edit of the in-memory partition table, rolling back all its changes if
+    /// `edit` returns an error.
+    ///
+    /// If rolling back also fails, returns the error produced by `edit` wrapped with the rollback
+    /// error in a [`FdiskError::Rollback`].
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use rsfdisk::core::partition::Partition;
+    /// use rsfdisk::fdisk::Fdisk;
+    ///
+    /// fn main() -> rsfdisk::Result<()> {
+    ///     let mut disk = Fdisk::builder()
+    ///         .assign_device("/dev/vda")
+    ///         .enable_read_write()
+    ///         .build()?;
+    ///
+    ///     // Either both partitions are replaced, or none is.
+    ///     disk.partition_table_transaction(|disk| -> rsfdisk::Result<()> {
+    ///         disk.partition_delete(0)?;
+    ///         disk.partition_delete(1)?;
+    ///
+    ///         let partition = Partition::builder().build()?;
+    ///         disk.partition_add(partition)?;
+    ///
+    ///         Ok(())
+    ///     })?;
+    ///
+    ///     disk.partition_table_write_to_disk()?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn partition_table_transaction<F, T, E>(&mut self, edit: F) -> Result<T, E>
+    where
+        F: FnOnce(&mut Self) -> Result<T, E>,
+        E: From<FdiskError> + std::error::Error + Send + Sync + 'static,
+    {
+        log::debug!("Fdisk::partition_table_transaction starting transaction");
 
-        size
-    }
+        let mut savepoint = self.partition_table_savepoint()?;
 
-    /// Returns the size in bytes of a physical sector.
-    pub fn device_bytes_per_physical_sector(&self) -> u64 {
-        let size = unsafe { libfdisk::fdisk_get_physector_size(self.inner) };
-        log::debug!(
-            "Fdisk::device_bytes_per_physical_sector bytes per sector: {:?}",
-            size
-        );
+        match edit(&mut savepoint) {
+            Ok(value) => {
+                log::debug!("Fdisk::partition_table_transaction committed transaction");
 
-        size
+                Ok(value)
+            }
+            Err(e) => {
+                log::debug!("Fdisk::partition_table_transaction rolling back transaction");
+
+                match savepoint.rollback() {
+                    Ok(()) => Err(e),
+                    Err(rollback) => {
+                        log::debug!("Fdisk::partition_table_transaction failed to roll back transaction after error: {}. {}", e, rollback);
+
+                        let err = FdiskError::Rollback {
+                            source: Box::new(e),
+                            rollback: Box::new(rollback),
+                        };
+
+                        Err(E::from(err))
+                    }
+                }
+            }
+        }
     }
 
-    /// Returns `true` if the caller answers `yes` to the `question`.
-    pub fn ask_yes_no_question<T>(&self, question: T) -> Result<bool, FdiskError>
+    /// Copies the raw sectors of the partition table on the assigned device to the file at
+    /// `file_path`, which is created or overwritten. Returns the content of the file.
+    ///
+    /// The file holds every section of the partition table (see
+    /// [`Fdisk::partition_table_section`]), in the format described by [`PartitionTableBackup`].
+    ///
+    /// **Note:** this method saves the partition table **on disk**, in-memory changes not yet
+    /// written by [`Fdisk::partition_table_write_to_disk`] are not part of the backup.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use rsfdisk::fdisk::Fdisk;
+    ///
+    /// fn main() -> rsfdisk::Result<()> {
+    ///     let mut disk = Fdisk::builder()
+    ///         .assign_device("/dev/vda")
+    ///         .enable_read_write()
+    ///         .build()?;
+    ///
+    ///     disk.partition_table_backup("/root/vda.bak")?;
+    ///
+    ///     // Edit the partition table...
+    ///
+    ///     // ...then revert all changes.
+    ///     disk.partition_table_restore("/root/vda.bak")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn partition_table_backup<T>(
+        &self,
+        file_path: T,
+    ) -> Result<PartitionTableBackup, FdiskError>
     where
-        T: AsRef<str>,
+        T: AsRef<Path>,
     {
-        let question = question.as_ref();
-        let question_cstr = ffi_utils::as_ref_str_to_c_string(question)?;
+        let file_path = file_path.as_ref();
         log::debug!(
-            "Fdisk::ask_yes_no_question asking yes/no question: {:?}",
-            question
+            "Fdisk::partition_table_backup backing up partition table to file: {:?}",
+            file_path
         );
 
-        let mut answer = MaybeUninit::<libc::c_int>::zeroed();
+        let (device, kind) = self
+            .device_name()
+            .zip(self.partition_table_current().map(|table| table.kind()))
+            .ok_or_else(|| {
+                let err_msg = "no partition table to back up".to_owned();
+                log::debug!("Fdisk::partition_table_backup {}", err_msg);
 
-        let result = unsafe {
-            libfdisk::fdisk_ask_yesno(self.inner, question_cstr.as_ptr(), answer.as_mut_ptr())
-        };
+                FdiskError::Save(err_msg)
+            })?;
 
-        match result {
-            0 => {
-                let answer = unsafe { answer.assume_init() };
-                let answer_str = if answer == 1 {
-                    "yes".to_owned()
-                } else {
-                    "no".to_owned()
-                };
-                log::debug!("Fdisk::ask_yes_no_question got answer: {:?}", answer_str);
+        let mut backup = PartitionTableBackup::new(
+            self.device_bytes_per_logical_sector(),
+            self.device_size_in_sectors(),
+            kind,
+        );
 
-                Ok(answer == 1)
-            }
-            code => {
-                let err_msg = format!("failed to get answer to question: {:?}", question);
-                log::debug!("Fdisk::ask_yes_no_question {}. libfdisk::fdisk_ask_yesno returned error code: {:?}", err_msg, code);
+        let mut device = File::open(device)?;
 
-                Err(FdiskError::Prompt(err_msg))
-            }
+        for section in (0..).map_while(|nth| self.partition_table_section(nth)) {
+            let mut content = vec![0u8; section.size()];
+            device.seek(SeekFrom::Start(section.starting_offset()))?;
+            device.read_exact(&mut content)?;
+
+            backup.push_section(section, content);
         }
-    }
 
-    #[doc(hidden)]
-    /// Asks the user for a partition number on the console.
-    fn request_partition_number(
-        ptr: *mut libfdisk::fdisk_context,
-        want_new: libc::c_int,
-    ) -> Result<usize, FdiskError> {
-        let op_str = if want_new == 0 {
-            "used".to_owned()
-        } else {
-            "unused".to_owned()
+        // Write to a temporary file next to the backup first, to keep any previous backup intact
+        // on failure.
+        let mut tmp_path = file_path.as_os_str().to_owned();
+        tmp_path.push(".tmp");
+        let tmp_path = PathBuf::from(tmp_path);
+
+        let persist = || -> std::io::Result<()> {
+            let mut tmp_file = File::create(&tmp_path)?;
+            tmp_file.write_all(&backup.to_bytes())?;
+            tmp_file.sync_all()?;
+
+            std::fs::rename(&tmp_path, file_path)
         };
+
+        if let Err(e) = persist() {
+            let _ = std::fs::remove_file(&tmp_path);
+            let err_msg = format!("failed to save backup to file: {:?}. {}", file_path, e);
+            log::debug!("Fdisk::partition_table_backup {}", err_msg);
+
+            return Err(FdiskError::Save(err_msg));
+        }
+
         log::debug!(
-            "Fdisk::request_partition_number requesting {} partition number",
-            op_str
+            "Fdisk::partition_table_backup backed up partition table to file: {:?}",
+            file_path
         );
 
-        let mut partition_number = MaybeUninit::<usize>::zeroed();
+        Ok(backup)
+    }
 
-        let result =
-            unsafe { libfdisk::fdisk_ask_partnum(ptr, partition_number.as_mut_ptr(), want_new) };
+    #[doc(hidden)]
+    /// Checks that each section in `backup` holds as many bytes as its recorded size, starts on a
+    /// sector boundary, fits on the assigned device, and does not overlap another section. For a
+    /// `GPT`, also checks that each header is at the location it records for itself, and each
+    /// Partition Entry Array at the location recorded by its header.
+    fn check_backup_sections(&self, backup: &PartitionTableBackup) -> Result<(), FdiskError> {
+        let reject = |section: &TableSection, reason: &str| {
+            let err_msg = format!(
+                "can not restore section {:?} at offset {}: {}",
+                section.name(),
+                section.starting_offset(),
+                reason
+            );
+            log::debug!("Fdisk::partition_table_restore {}", err_msg);
 
-        match result {
-            0 => {
-                let partition_number = unsafe { partition_number.assume_init() };
-                log::debug!(
-                    "Fdisk::request_partition_number got {} partition number: {:?}",
-                    op_str,
-                    partition_number
-                );
+            FdiskError::Restore(err_msg)
+        };
 
-                Ok(partition_number)
+        let sector_size = self.device_bytes_per_logical_sector().max(1);
+        let device_size = self.device_size_in_sectors().saturating_mul(sector_size);
+
+        // (starting offset, ending offset) of the sections checked so far.
+        let mut extents: Vec<(u64, u64)> = Vec::new();
+
+        for (section, content) in backup.sections() {
+            let start = section.starting_offset();
+
+            if section.size() == 0 || content.len() != section.size() {
+                return Err(reject(section, "content does not match recorded size"));
             }
-            1 => {
-                let err_msg = format!("no {} partition number available", op_str);
-                log::debug!("Fdisk::request_partition_number {}", err_msg);
 
-                Err(FdiskError::Prompt(err_msg))
+            if start % sector_size != 0 {
+                return Err(reject(section, "not on a sector boundary"));
             }
-            code => {
-                let err_msg = match -code {
-                    libc::ENOMEM => "out of memory".to_owned(),
-                    _ => format!("failed to request {} partition number", op_str),
-                };
 
-                log::debug!("Fdisk::request_partition_number {}. libfdisk::fdisk_ask_partnum returned error code: {:?}", err_msg, code);
+            let end = match start.checked_add(section.size() as u64) {
+                Some(end) if end <= device_size => end,
+                _ => return Err(reject(section, "outside of the device")),
+            };
 
-                Err(FdiskError::Prompt(err_msg))
+            if extents
+                .iter()
+                .any(|&(other_start, other_end)| start < other_end && other_start < end)
+            {
+                return Err(reject(section, "overlaps another section"));
             }
+
+            extents.push((start, end));
         }
-    }
 
-    /// Asks the caller for a used partition number.
-    pub fn ask_partition_number_used(&self) -> Result<usize, FdiskError> {
-        Self::request_partition_number(self.inner, 0)
-    }
+        if backup.partition_table_kind() == PartitionTableKind::GPT {
+            let le_u64 = |bytes: &[u8], offset: usize| {
+                bytes
+                    .get(offset..offset + 8)
+                    .and_then(|bytes| bytes.try_into().ok())
+                    .map(u64::from_le_bytes)
+            };
 
-    /// Asks the caller for an unused partition number.
-    pub fn ask_partition_number_unused(&self) -> Result<usize, FdiskError> {
-        Self::request_partition_number(self.inner, 1)
+            // (header section, entries section)
+            for (header_name, entries_name) in [
+                ("GPT Header", "GPT Entries"),
+                ("GPT Backup Header", "GPT Backup Entries"),
+            ] {
+                let header = backup
+                    .sections()
+                    .find(|(section, _)| section.name() == header_name);
+
+                if let Some((section, content)) = header {
+                    let my_lba = le_u64(content, 24);
+
+                    if !content.starts_with(b"EFI PART")
+                        || my_lba.and_then(|lba| lba.checked_mul(sector_size))
+                            != Some(section.starting_offset())
+                    {
+                        return Err(reject(section, "not where the GPT header says it is"));
+                    }
+                }
+
+                for (section, _) in backup
+                    .sections()
+                    .filter(|(section, _)| section.name() == entries_name)
+                {
+                    let entries_offset = header
+                        .and_then(|(_, content)| le_u64(content, 72))
+                        .and_then(|lba| lba.checked_mul(sector_size));
+
+                    if entries_offset != Some(section.starting_offset()) {
+                        return Err(reject(section, "not where its GPT header says it is"));
+                    }
+                }
+            }
+        }
+
+        Ok(())
     }
 
-    /// Prompts the caller for a numerical value.
-    pub fn ask_number_in_range<T>(
-        &self,
-        question: T,
-        default_value: u64,
-        lower_bound: u64,
-        upper_bound: u64,
-    ) -> Result<libfdisk::uintmax_t, FdiskError>
+    /// Overwrites the partition table on the assigned device with the one saved in the file at
+    /// `file_path` by [`Fdisk::partition_table_backup`], then reloads it, discarding any in-memory
+    /// change.
+    ///
+    /// The backup is only restored if it is intact, was taken from a device with the same size
+    /// and sector size as the assigned device, and each of its sections is consistent with the
+    /// locations and sizes recorded in the backup. The content of every section is staged and
+    /// checked before the first write. Once written, sections are flushed to the device, read
+    /// back, and compared with the backup; on a mismatch or a write failure, sections are
+    /// reverted to their previous content.
+    ///
+    /// **Warning:** sections are written one after the other. A power loss, or a crash, while
+    /// restoring can leave the device with a mix of old and restored sections.
+    pub fn partition_table_restore<T>(&mut self, file_path: T) -> Result<(), FdiskError>
     where
-        T: AsRef<str>,
+        T: AsRef<Path>,
     {
+        let file_path = file_path.as_ref();
         log::debug!(
-            "Fdisk::ask_number_in_range requesting value in range [{:?}, {:?}] (default: {:?})",
-            lower_bound,
-            upper_bound,
-            default_value
+            "Fdisk::partition_table_restore restoring partition table from file: {:?}",
+            file_path
         );
-        let question = question.as_ref();
-        let question_cstr = ffi_utils::as_ref_str_to_c_string(question)?;
 
-        let mut ptr = MaybeUninit::<libfdisk::uintmax_t>::zeroed();
+        let device = self.device_name().map(Path::to_path_buf).ok_or_else(|| {
+            let err_msg = "no device assigned".to_owned();
+            log::debug!("Fdisk::partition_table_restore {}", err_msg);
 
-        let result = unsafe {
-            libfdisk::fdisk_ask_number(
-                self.inner,
-                lower_bound,
-                default_value,
-                upper_bound,
-                question_cstr.as_ptr(),
-                ptr.as_mut_ptr(),
-            )
-        };
+            FdiskError::Restore(err_msg)
+        })?;
 
-        match result {
-            0 => {
-                let obtained = unsafe { ptr.assume_init() };
-                log::debug!("Fdisk::ask_number_in_range got value: {:?}", obtained);
+        if self.device_is_read_only() {
+            let err_msg = format!(
+                "can not restore partition table on read-only device: {:?}",
+                device
+            );
+            log::debug!("Fdisk::partition_table_restore {}", err_msg);
 
-                Ok(obtained)
-            }
-            code => {
-                let err_msg = match -code {
-                    libc::ENOMEM => "out of memory".to_owned(),
-                    _ => format!(
-                        "error while requesting value in range [{:?}, {:?}] (default: {:?})",
-                        lower_bound, upper_bound, default_value
-                    ),
-                };
+            return Err(FdiskError::Restore(err_msg));
+        }
 
-                log::debug!("Fdisk::ask_number_in_range {}. libfdisk::fdisk_ask_number returned error code: {:?}", err_msg, code);
+        let backup = std::fs::read(file_path)
+            .ok()
+            .and_then(|bytes| PartitionTableBackup::from_bytes(&bytes))
+            .ok_or_else(|| {
+                let err_msg = format!("invalid or corrupt backup file: {:?}", file_path);
+                log::debug!("Fdisk::partition_table_restore {}", err_msg);
 
-                Err(FdiskError::Prompt(err_msg))
-            }
+                FdiskError::Restore(err_msg)
+            })?;
+
+        let sector_size = self.device_bytes_per_logical_sector();
+        let device_size = self.device_size_in_sectors();
+
+        if backup.sector_size() != sector_size || backup.device_size_in_sectors() != device_size {
+            let err_msg = format!(
+                "backup of a device with {} sectors of {} bytes, can not restore it on a device with {} sectors of {} bytes",
+                backup.device_size_in_sectors(),
+                backup.sector_size(),
+                device_size,
+                sector_size
+            );
+            log::debug!("Fdisk::partition_table_restore {}", err_msg);
+
+            return Err(FdiskError::Restore(err_msg));
         }
-    }
 
-    /// Prompts the caller for a string value.
-    pub fn ask_string_value<T>(&self, question: T) -> Result<String, FdiskError>
-    where
-        T: AsRef<str>,
-    {
-        log::debug!("Fdisk::ask_string_value requesting string value");
-        let question = question.as_ref();
-        let question_cstr = ffi_utils::as_ref_str_to_c_string(question)?;
+        self.check_backup_sections(&backup)?;
 
-        let mut ptr = MaybeUninit::<*mut libc::c_char>::zeroed();
+        // Stage the sections to write, and save the current content of each one to revert a
+        // partial restoration.
+        let staged: Vec<(u64, &[u8])> = backup
+            .sections()
+            .map(|(section, content)| (section.starting_offset(), content))
+            .collect();
 
-        let result = unsafe {
-            libfdisk::fdisk_ask_string(self.inner, question_cstr.as_ptr(), ptr.as_mut_ptr())
-        };
+        let mut file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&device)?;
 
-        match result {
-            0 => {
-                let answer_ptr = unsafe { ptr.assume_init() };
-                let answer = ffi_to_string_or_empty!(answer_ptr);
+        let mut previous = Vec::with_capacity(staged.len());
+        for &(offset, content) in staged.iter() {
+            let mut buffer = vec![0u8; content.len()];
+            file.seek(SeekFrom::Start(offset))?;
+            file.read_exact(&mut buffer)?;
 
-                log::debug!("Fdisk::ask_string_value got string value: {:?}", answer);
+            previous.push((offset, buffer));
+        }
 
-                Ok(answer)
+        fn write_sections<'s, I>(file: &mut File, sections: I) -> std::io::Result<()>
+        where
+            I: IntoIterator<Item = (u64, &'s [u8])> + Clone,
+        {
+            for (offset, content) in sections.clone() {
+                file.seek(SeekFrom::Start(offset))?;
+                file.write_all(content)?;
             }
-            code => {
-                let err_msg = match -code {
-                    libc::ENOMEM => "out of memory".to_owned(),
-                    _ => "failed to request a string value".to_owned(),
-                };
 
-                log::debug!("Fdisk::ask_string_value {}. libfdisk::fdisk_ask_string returned error code: {:?}", err_msg, code);
+            file.sync_all()?;
 
-                Err(FdiskError::Prompt(err_msg))
+            // Read back what was written.
+            for (offset, content) in sections {
+                let mut buffer = vec![0u8; content.len()];
+                file.seek(SeekFrom::Start(offset))?;
+                file.read_exact(&mut buffer)?;
+
+                if buffer != content {
+                    let err_msg = format!("content read back at offset {} differs", offset);
+
+                    return Err(std::io::Error::new(std::io::ErrorKind::Other, err_msg));
+                }
             }
-        }
-    }
 
-    /// Returns the name of the already existing file system or partition table detected.
-    pub fn device_describe_collisions(&self) -> Option<&str> {
-        log::debug!("Fdisk::device_describe_collisions describing metadata collisions");
-        let mut ptr = MaybeUninit::<*const libc::c_char>::zeroed();
-        unsafe {
-            ptr.write(libfdisk::fdisk_get_collision(self.inner));
+            Ok(())
         }
 
-        match unsafe { ptr.assume_init() } {
-            ptr if ptr.is_null() => {
-                log::debug!("Fdisk::device_describe_collisions found no metadata conflict. libfdisk::fdisk_get_collision returned a NULL pointer");
+        if let Err(e) = write_sections(&mut file, staged.iter().copied()) {
+            let sections = previous
+                .iter()
+                .map(|(offset, content)| (*offset, content.as_slice()));
+            let reverted = write_sections(&mut file, sections).is_ok();
 
-                None
-            }
-            desc_ptr => {
-                let collisions = ffi_utils::const_char_array_to_str_ref(desc_ptr).ok();
-                log::debug!(
-                    "Fdisk::device_describe_collisions found collisions for: {:?}",
-                    collisions
-                );
+            let err_msg = format!(
+                "failed to restore partition table ({}). {}",
+                if reverted {
+                    "device left unchanged"
+                } else {
+                    "could not revert changes"
+                },
+                e
+            );
+            log::debug!("Fdisk::partition_table_restore {}", err_msg);
 
-                collisions
-            }
+            return Err(FdiskError::Restore(err_msg));
         }
-    }
 
-    /// Returns the format in which a `Fdisk` displays partition sizes.
-    pub fn partition_size_format(&self) -> SizeFormat {
-        let code = unsafe { libfdisk::fdisk_get_size_unit(self.inner) };
-        let size_format = SizeFormat::try_from(code as u32).unwrap();
-        log::debug!("Fdisk::partition_size_format value: {:?}", size_format);
-
-        size_format
-    }
+        self.discard_changes()?;
 
-    /// Returns the assigned device's name.
-    /// Returns the content of a [`Partition`]'s field in string form.
-    pub fn partition_field_to_string(
-        &self,
-        field: Field,
-        partition: &Partition,
-    ) -> Result<String, FdiskError> {
         log::debug!(
-            "Fdisk::partition_field_to_string convert content of partition field {:?} to string",
-            field
+            "Fdisk::partition_table_restore restored partition table from file: {:?}",
+            file_path
         );
-        let cfield = field as u32 as i32;
-
-        let mut content_ptr = MaybeUninit::<*mut libc::c_char>::zeroed();
-
-        let result = unsafe {
-            libfdisk::fdisk_partition_to_string(
-                partition.inner,
-                self.inner,
-                cfield,
-                content_ptr.as_mut_ptr(),
-            )
-        };
-
-        match result {
-            0 => {
-                let ptr = unsafe { content_ptr.assume_init() };
-                let field_content = ffi_to_string_or_empty!(ptr);
-                log::debug!("Fdisk::partition_field_to_string converted content of partition field {:?} to {:?}", field, field_content);
-
-                Ok(field_content)
-            }
-            code => {
-                let err_msg = format!("failed to convert content of partition field {:?}", field);
-                log::debug!("Fdisk::partition_field_to_string {}. libfdisk::fdisk_partition_to_string returned error code: {:?}", err_msg, code);
 
-                match -code {
-                    libc::ENOMEM => Err(FdiskError::OutOfMemory(err_msg)),
-                    _ => Err(FdiskError::Unexpected(err_msg)),
-                }
-            }
-        }
+        Ok(())
     }
 
-    #[doc(hidden)]
-    /// Gets a partition by its index number in a partition array.
-    fn get_partition_by_number(
-        fdisk: &Self,
-        partition_number: usize,
-    ) -> Option<*mut libfdisk::fdisk_partition> {
-        let mut partition_ptr = MaybeUninit::<*mut libfdisk::fdisk_partition>::zeroed();
+    /// Writes the in-memory partition table to disk.
+    pub fn partition_table_write_to_disk(&mut self) -> Result<(), FdiskError> {
+        log::debug!("Fdisk::partition_table_write_to_disk writing partition table to disk");
 
-        let result = unsafe {
-            libfdisk::fdisk_get_partition(fdisk.inner, partition_number, partition_ptr.as_mut_ptr())
-        };
+        let result = unsafe { libfdisk::fdisk_write_disklabel(self.inner) };
 
         match result {
             0 => {
-                log::debug!(
-                    "Fdisk::get_partition_by_number got partition with identification number: {:?}",
-                    partition_number
-                );
-
-                let partition = unsafe { partition_ptr.assume_init() };
+                log::debug!("Fdisk::partition_table_write_to_disk wrote partition table to disk");
 
-                Some(partition)
+                Ok(())
             }
             code => {
-                let err_msg = format!(
-                    "no partition with identification number: {:?}",
-                    partition_number
-                );
-                log::debug!("Fdisk::get_partition_by_number {}. libfdisk::fdisk_get_partition returned error code: {:?}", err_msg, code);
+                let err_msg = "failed to write partition table to disk".to_owned();
+                log::debug!("Fdisk::partition_table_write_to_disk {}. libfdisk::fdisk_write_disklabel returned error code: {:?}", err_msg, code);
 
-                None
+                Err(FdiskError::Save(err_msg))
             }
         }
     }
 
-    /// Returns a reference to a [`Partition`] from its identification number.
-    pub fn partition_by_number(&self, partition_number: usize) -> Option<&Partition> {
+    /// Returns `true` when the partition table on the assigned disk matches the given type.
+    pub fn partition_table_is_of_type(&self, kind: PartitionTableKind) -> bool {
+        let kind_u32: u32 = kind.into();
+        let state = unsafe { libfdisk::fdisk_is_labeltype(self.inner, kind_u32) == 1 };
         log::debug!(
-            "Partition::partition_by_number getting partition with identification number: {:?}",
-            partition_number
+            "Fdisk::partition_table_is_of_type is partition table of type {:?}? {:?}",
+            kind,
+            state
         );
 
-        Self::get_partition_by_number(self, partition_number)
-            .map(|ptr| owning_ref_from_ptr!(self, Partition, ptr))
+        state
     }
 
-    /// Returns a mutable reference to a [`Partition`] from its identification number.
-    pub fn partition_by_number_mut(&mut self, partition_number: usize) -> Option<&mut Partition> {
-        log::debug!(
-            "Partition::partition_by_number_mut getting partition with identification number: {:?}",
-            partition_number
-        );
-
-        Self::get_partition_by_number(self, partition_number)
-            .map(|ptr| owning_mut_from_ptr!(self, Partition, ptr))
-    }
-
-    /// Returns a list of unallocated spaces on the assigned device as a collection of
-    /// [`Partition`]s, or `None` if the device has no partition table.
-    ///
-    /// **Note:** this method will ignore free space smaller than the assigned device's grain
-    /// size (see [`Fdisk::device_grain_size`])
-    pub fn list_empty_spaces(&self) -> Option<PartitionList> {
-        log::debug!("Fdisk::list_empty_spaces listing unallocated spaces");
-
-        let mut ptr = MaybeUninit::<*mut libfdisk::fdisk_table>::zeroed();
-
-        let result = unsafe { libfdisk::fdisk_get_freespaces(self.inner, ptr.as_mut_ptr()) };
+    #[doc(hidden)]
+    /// Creates a new `libfdisk::fdisk_script` associated with this `Fdisk`.
+    fn new_script(&self) -> Result<*mut libfdisk::fdisk_script, FdiskError> {
+        let mut ptr = MaybeUninit::<*mut libfdisk::fdisk_script>::zeroed();
+        unsafe {
+            ptr.write(libfdisk::fdisk_new_script(self.inner));
+        }
 
-        match result {
-            0 => {
-                log::debug!("Fdisk::list_empty_spaces listed unallocated spaces");
-                let ptr = unsafe { ptr.assume_init() };
-                let list = PartitionList::from_ptr(ptr);
+        match unsafe { ptr.assume_init() } {
+            ptr if ptr.is_null() => {
+                let err_msg = "failed to create a new `Script` instance".to_owned();
+                log::debug!(
+                    "Fdisk::new_script {}. libfdisk::fdisk_new_script returned a NULL pointer",
+                    err_msg
+                );
 
-                Some(list)
+                Err(FdiskError::Script(err_msg))
             }
-            code => {
-                let err_msg = "failed to list unallocated spaces on assigned device".to_owned();
-                log::debug!("Fdisk::list_empty_spaces {}. libfdisk::fdisk_get_freespaces returned error code: {:?}", err_msg, code);
+            ptr => {
+                log::debug!("Fdisk::new_script created a new `Script` instance");
 
-                None
+                Ok(ptr)
             }
         }
     }
 
-    /// Returns a list of the [`Partition`]s in this `Fdisk`.
-    pub fn list_partitions(&self) -> Option<PartitionList> {
-        log::debug!("Fdisk::list_partitions extracting partitions from partition table");
+    /// Creates an `sfdisk`-compatible [`Script`] associated with this `Fdisk`.
+    // Each call allocates a new `libfdisk::fdisk_script`, so the returned references never alias.
+    #[allow(clippy::mut_from_ref)]
+    pub fn script_new(&self) -> Result<&mut Script<'_>, FdiskError> {
+        log::debug!("Fdisk::script_new creating a new `Script` instance");
 
-        let mut ptr = MaybeUninit::<*mut libfdisk::fdisk_table>::zeroed();
+        let ptr = self.new_script()?;
+        let script = owning_mut_from_ptr!(self, Script, ptr);
 
-        let result = unsafe { libfdisk::fdisk_get_partitions(self.inner, ptr.as_mut_ptr()) };
+        Ok(script)
+    }
 
-        match result {
-            0 => {
-                log::debug!("Fdisk::list_partitions extracted partitions from partition table");
-                let ptr = unsafe { ptr.assume_init() };
-                let list = PartitionList::from_ptr(ptr);
+    /// Creates a new `sfdisk`-compatible [`Script`] associated with this `Fdisk` importing its
+    /// content from a file.
+    // Each call allocates a new `libfdisk::fdisk_script`, so the returned references never alias.
+    #[allow(clippy::mut_from_ref)]
+    pub fn script_new_from_file<T>(&self, file_path: T) -> Result<&mut Script<'_>, FdiskError>
+    where
+        T: AsRef<Path>,
+    {
+        let file_path = file_path.as_ref();
+        let path = ffi_utils::as_ref_path_to_c_string(file_path)?;
+        log::debug!(
+            "Fdisk::script_new_from_file creating a new `Script` instance from: {:?}",
+            file_path
+        );
 
-                Some(list)
-            }
-            code => {
-                let err_msg = match -code {
-                    libc::EINVAL => "no partition table on device".to_owned(),
-                    libc::ENOSYS => "no partition in partition table".to_owned(),
-                    libc::ENOMEM => "out of memory".to_owned(),
-                    _ => "failed to list partitions in partition table".to_owned(),
-                };
+        let mut ptr = MaybeUninit::<*mut libfdisk::fdisk_script>::zeroed();
+        unsafe {
+            ptr.write(libfdisk::fdisk_new_script_from_file(
+                self.inner,
+                path.as_ptr(),
+            ));
+        }
 
-                log::debug!("Fdisk::list_partitions {}. libfdisk::fdisk_get_partitions returned error code: {:?}", err_msg, code);
+        match unsafe { ptr.assume_init() } {
+            ptr if ptr.is_null() => {
+                let err_msg = format!(
+                    "failed to create a new `Script` instance from: {:?}",
+                    file_path
+                );
+                log::debug!("Fdisk::script_new_from_file {}. libfdisk::fdisk_new_script_from_file returned a NULL pointer", err_msg);
 
-                None
+                Err(FdiskError::Script(err_msg))
+            }
+            ptr => {
+                log::debug!(
+                    "Fdisk::script_new_from_file created a new `Script` instance from: {:?}",
+                    file_path
+                );
+                let script = owning_mut_from_ptr!(self, Script, ptr);
+
+                Ok(script)
             }
         }
     }
 
-    /// Returns the content of an entry in the partition table header.
-    pub fn partition_table_header_entry(
-        &self,
-        header_entry: HeaderEntry,
-    ) -> Option<HeaderEntryContent> {
-        log::debug!(
-            "Fdisk::partition_table_header_entry getting content of partition table header entry {:?}",
-            header_entry
-        );
-        let c_header_entry = header_entry.to_original_u32() as i32;
-        let content = HeaderEntryContent::new().ok()?;
+    /// Applies the provided [`Script`] to this `Fdisk` to create the in-memory partition
+    /// table, and partitions its content specifies.
+    ///
+    /// To write the changes to disk call [`Fdisk::partition_table_write_to_disk`].
+    pub fn script_apply(&mut self, script: &Script) -> Result<(), FdiskError> {
+        log::debug!("Fdisk::script_apply applying script");
 
-        let result = unsafe {
-            libfdisk::fdisk_get_disklabel_item(self.inner, c_header_entry, content.inner)
-        };
+        // Internally `fdisk_apply_script` calls `fdisk_apply_script_headers` on `script.inner`
+        // see https://github.com/util-linux/util-linux/blob/8aa25617467a1249669cff7240ca31973bf9a127/libfdisk/src/script.c#L1621
+        //
+        // > which in turn will call `fdisk_set_script`
+        // see https://github.com/util-linux/util-linux/blob/8aa25617467a1249669cff7240ca31973bf9a127/libfdisk/src/script.c#L1541
+        //
+        // >> `set_script` will set `script.inner` as the current associated Script
+        // https://github.com/util-linux/util-linux/blob/8aa25617467a1249669cff7240ca31973bf9a127/libfdisk/src/script.c#L1500
+        //
+        // then `fdisk_apply_script` will restore the old script as the current one by calling
+        // `set_script` which will incidentally decrement the reference counter of `script.inner'
+        // and leave us with a dangling pointer.
+        // https://github.com/util-linux/util-linux/blob/8aa25617467a1249669cff7240ca31973bf9a127/libfdisk/src/script.c#L1496`
+        //
+        // To prevent this outcome, we increment the reference counter of `script.inner` before using it as a countermeasure.
+        unsafe {
+            libfdisk::fdisk_ref_script(script.inner);
+        }
+
+        let result = unsafe { libfdisk::fdisk_apply_script(self.inner, script.inner) };
 
         match result {
             0 => {
-                log::debug!(
-                    "Partition::partition_table_header_entry got content of partition table header entry {:?}",
-                    header_entry
-                );
+                log::debug!("Fdisk::script_apply applied script");
 
-                Some(content)
+                Ok(())
             }
             code => {
-                let err_msg = format!(
-                    "failed to get content of partition table header entry {:?}",
-                    header_entry
-                );
-                log::debug!("Fdisk::partition_table_header_entry {}. libfdisk::fdisk_get_disklabel_item returned error code: {:?}", err_msg, code);
+                let err_msg = "failed to apply script".to_owned();
+                log::debug!("Fdisk::script_apply {}. libfdisk::fdisk_apply_script returned error code: {:?}", err_msg, code);
 
-                None
+                Err(FdiskError::Config(err_msg))
             }
         }
     }
 
-    /// Returns the partition table's ID in string form.
-    pub fn partition_table_id(&self) -> Option<String> {
-        log::debug!("Fdisk::partition_table_id getting partition table's ID");
+    // FIXME a script retains a pointer to the context it was created from
+    // https://github.com/util-linux/util-linux/blob/8aa25617467a1249669cff7240ca31973bf9a127/libfdisk/src/script.c#L103
+    // a new assignment here does not update this reference. Furthermore, calling this function
+    // can create a shared ownership of the same script between `fdisk_context` structures. How does this impact Rust ownership
+    // constraints?
+    //
+    // - Each value in Rust has an owner.
+    // - There can only be one owner at a time.
+    // - When the owner goes out of scope, the value will be dropped.
 
-        let mut id_ptr = MaybeUninit::<*mut libc::c_char>::zeroed();
+    /// Associates a [`Script`] to this `Fdisk`, then applies it to create the in-memory partition
+    /// table it specifies.
+    ///
+    /// FIXME as is, this function is unusable since there is currently no way to pass it a fully
+    /// owned [`Script`]. More information from developers upstream is needed for me to come up
+    /// with a better API design. To be clear, it is **MY** lack of knowledge about the code produced
+    /// upstream that is at fault here, **NOT** the developers of `libfdisk`. In the meantime... ¯\\\_(ツ)\_/¯
+    pub fn script_apply_headers(&mut self, mut script: Script) -> Result<(), FdiskError> {
+        log::debug!("Fdisk::script_apply_headers applying script headers");
 
-        let result = unsafe { libfdisk::fdisk_get_disklabel_id(self.inner, id_ptr.as_mut_ptr()) };
+        // We are virtually ceding ownership of this instance to the C-side of the library;
+        // instance that will be automatically deallocated once it is out of scope, incrementing
+        // its reference counter protects it from being freed prematurely.
+        script.incr_ref_counter();
+
+        let result = unsafe { libfdisk::fdisk_apply_script_headers(self.inner, script.inner) };
 
         match result {
             0 => {
-                match unsafe { id_ptr.assume_init() } {
-                    ptr if ptr.is_null() => {
-                        let err_msg = "no partition table ID".to_owned();
-                        log::debug!("Fdisk::partition_table_id {}. libfdisk::fdisk_get_disklabel_id returned a NULL pointer", err_msg);
-
-                        None
-                    }
-                    ptr => {
-                        let id = ffi_utils::c_char_array_to_string(ptr);
-                        // We took ownership of the allocated char array returned by `fdisk_get_disklabel_id`,
-                        // we free it here to avoid a memory leak.
-                        unsafe {
-                            libc::free(ptr as *mut _);
-                        }
-
-                        log::debug!(
-                            "Fdisk::partition_table_id got partition table's ID: {:?}",
-                            id
-                        );
+                log::debug!("Fdisk::script_apply_headers applied script headers");
 
-                        Some(id)
-                    }
-                }
+                Ok(())
             }
             code => {
-                let err_msg = "failed to get partition table's ID".to_owned();
-                log::debug!("Fdisk::partition_table_id {}. libfdisk::fdisk_get_disklabel_id returned error code: {:?}", err_msg, code);
+                let err_msg = "failed to apply script headers".to_owned();
+                log::debug!("Fdisk::script_apply_headers {}. libfdisk::fdisk_apply_script_headers returned error code: {:?}", err_msg, code);
 
-                None
+                Err(FdiskError::Config(err_msg))
             }
         }
     }
 
     #[doc(hidden)]
-    /// Returns the partition table associated with this `Fdisk`.
-    fn get_partition_table(
-        ptr: &Self,
-        name: *const libc::c_char,
-    ) -> Option<*mut libfdisk::fdisk_label> {
-        let mut part_table_ptr = MaybeUninit::<*mut libfdisk::fdisk_label>::zeroed();
-        unsafe {
-            part_table_ptr.write(libfdisk::fdisk_get_label(ptr.inner, name));
-        }
+    /// Sets/unsets which [`Script`] a `Fdisk` is associated with.
+    fn set_script(ptr: &mut Self, script: *mut libfdisk::fdisk_script) -> Result<(), FdiskError> {
+        let result = unsafe { libfdisk::fdisk_set_script(ptr.inner, script) };
 
-        match unsafe { part_table_ptr.assume_init() } {
-            ptr if ptr.is_null() => {
-                log::debug!("Fdisk::get_partition_table found no partition table. libfdisk::fdisk_get_label returned a NULL pointer");
+        match result {
+            0 => {
+                log::debug!("Fdisk::set_script script set");
 
-                None
+                Ok(())
             }
-            ptr => {
-                log::debug!("Fdisk::get_partition_table found requested partition table");
+            code => {
+                let err_msg = "failed to set script".to_owned();
+                log::debug!(
+                    "Fdisk::set_script {}. libfdisk::fdisk_set_script returned error code: {:?}",
+                    err_msg,
+                    code
+                );
 
-                Some(ptr)
+                Err(FdiskError::Config(err_msg))
             }
         }
     }
 
-    /// Returns a reference to the current partition table associated with this `Fdisk`.
-    pub fn partition_table_current(&self) -> Option<&PartitionTable> {
-        log::debug!("Fdisk::partition_table_current getting current partition table");
+    /// Removes the [`Script`] currently associated with this `Fdisk`.
+    pub fn script_dissociate(&mut self) -> Result<(), FdiskError> {
+        log::debug!("Fdisk::script_dissociate dissociating script");
 
-        Self::get_partition_table(self, std::ptr::null())
-            .map(|ptr| owning_ref_from_ptr!(self, PartitionTable, ptr))
+        Self::set_script(self, std::ptr::null_mut())
     }
 
-    /// Returns a mutable reference to the current partition table associated with this `Fdisk`.
-    pub fn partition_table_current_mut(&mut self) -> Option<&mut PartitionTable> {
-        log::debug!("Fdisk::partition_table_current_mut getting current partition table");
+    //---- END mutators
 
-        Self::get_partition_table(self, std::ptr::null())
-            .map(|ptr| owning_mut_from_ptr!(self, PartitionTable, ptr))
-    }
+    //---- BEGIN getters
 
-    /// Returns a reference to the current table associated with this `Fdisk` matching the given type.
-    pub fn partition_table_by_type(&self, kind: PartitionTableKind) -> Option<&PartitionTable> {
-        log::debug!(
-            "Fdisk::partition_table_by_type getting partition table of type: {:?}",
-            kind
-        );
-        let name_cstr = kind.to_c_string();
+    #[doc(hidden)]
+    /// Gets the string representation of the unit in which numerical metadata is displayed.
+    fn unit_str<'b>(context: *mut libfdisk::fdisk_context, multiplicity: i32) -> &'b str {
+        log::debug!("Fdisk::unit_str getting display unit");
 
-        Self::get_partition_table(self, name_cstr.as_ptr())
-            .map(|ptr| owning_ref_from_ptr!(self, PartitionTable, ptr))
+        let mut ptr = MaybeUninit::<*const libc::c_char>::zeroed();
+        unsafe {
+            ptr.write(libfdisk::fdisk_get_unit(context, multiplicity));
+        }
+
+        let unit_ptr = unsafe { ptr.assume_init() };
+        ffi_utils::const_char_array_to_str_ref(unit_ptr).unwrap()
     }
 
-    /// Returns a mutable reference to the current table associated with this `Fdisk` matching the given type.
-    pub fn partition_table_by_type_mut(
-        &mut self,
-        kind: PartitionTableKind,
-    ) -> Option<&mut PartitionTable> {
-        log::debug!(
-            "Fdisk::partition_table_by_type_mut getting partition table of type: {:?}",
-            kind
-        );
-        let name_cstr = kind.to_c_string();
+    /// Returns the string representation in singular form of the unit in which numerical metadata
+    /// is displayed.
+    pub fn displayed_unit_singular(&self) -> &str {
+        let unit = Self::unit_str(self.inner, libfdisk::FDISK_SINGULAR);
+        log::debug!("Fdisk::displayed_unit_singular value: {:?}", unit);
 
-        Self::get_partition_table(self, name_cstr.as_ptr())
-            .map(|ptr| owning_mut_from_ptr!(self, PartitionTable, ptr))
+        unit
     }
 
-    /// Returns the number of supported partition table types.
-    pub fn partition_table_count_types(&self) -> usize {
-        let count = unsafe { libfdisk::fdisk_get_nlabels(self.inner) };
-        log::debug!(
-            "Fdisk::partition_table_count_types number of supported partition table types: {:?}",
-            count
-        );
+    /// Returns the string representation in plural form of the unit in which numerical metadata
+    /// is displayed.
+    pub fn displayed_unit_plural(&self) -> &str {
+        let unit = Self::unit_str(self.inner, libfdisk::FDISK_PLURAL);
+        log::debug!("Fdisk::displayed_unit_plural value: {:?}", unit);
 
-        count
+        unit
     }
 
-    /// Returns the maximum number of partitions the partition table can hold.
+    /// Returns a copy of all information and warning messages collected by this `Fdisk`, in the
+    /// order they were emitted.
     ///
-    /// **Note:** this function will always return `4` for `MBR` partitions, value which
-    /// corresponds to the maximum number of logical partitions. Use an extended partition to lift
-    /// this limit.
-    pub fn partition_table_max_partitions(&self) -> usize {
-        let max = unsafe { libfdisk::fdisk_get_npartitions(self.inner) };
-        log::debug!(
-            "Fdisk::partition_table_max_partitions maximum number of partitions: {:?}",
-            max
-        );
+    /// `libfdisk` reports, among other things, the problems found by
+    /// [`Fdisk::partition_table_check`] through such messages. A nested `Fdisk` shares its messages
+    /// with its parent.
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        let diagnostics = unsafe { self.prompt_dispatcher.as_ref() }
+            .map(|dispatcher| dispatcher.diagnostics())
+            .unwrap_or_default();
+        log::debug!("Fdisk::diagnostics value: {:?}", diagnostics);
 
-        max
+        diagnostics
     }
 
-    #[cfg_attr(doc,
-         cfg_attr(all(),
-        doc = ::embed_doc_image::embed_image!( "fig-01", "third-party/vendor/wikipedia/GUID_Partition_Table_Scheme.svg"),
-        ))]
-    /// Returns the name, location, and size of the `nth` section of an in-memory DOS or GPT partition table.
-    ///
-    /// For example, a primary GPT partition table has three sections:
-    /// - a Protective MBR (`nth=0`),
-    /// - a Partition Table Header(`nth=1`),
-    /// - and a Partition Entry Array (`nth=2`),
-    ///
-    /// as illustrated on the diagram below.
-    ///
-    /// ![Diagram illustrating the layout of the GUID Partition Table (GPT) scheme. Each logical
-    /// block (LBA) is 512 bytes in size. LBA addresses that are negative indicate position from
-    /// the end of the volume, with −1 being the last addressable block.][fig-01]
+    /// Returns a reference to the parent partitioner of this `Fdisk` when it is a nested
+    /// partitioner.
     ///
-    /// Source: <cite>The original uploader was Kbolino at [English
-    /// Wikipedia.](https://commons.wikimedia.org/wiki/File:GUID_Partition_Table_Scheme.svg), [CC
-    /// BY-SA
-    /// 2.5](https://creativecommons.org/licenses/by-sa/2.5), via Wikimedia Commons</cite>
+    /// # Panics
     ///
-    /// **Note:**
-    /// - `nth=3`, and `nth=4` allow access to respectively, the backup Partition Entry Array, and
-    /// the backup Partition Table Header (i.e. the secondary GPT in the diagram above).
-    /// - the values returned by this method are from a copy of the assigned device's partition
-    /// table kept in memory; which might differ from the one on disk.<br>
-    /// To have the most up-to-date values, it is recommended to invoke this method after
-    /// synchronising states by calling [`Fdisk::partition_table_write_to_disk`].
-    pub fn partition_table_section(&self, nth: i32) -> Option<TableSection> {
-        log::debug!(
-            "Fdisk::partition_table_section locating partition table section: {:?}",
-            nth
-        );
-
-        let mut name_ptr = MaybeUninit::<*const libc::c_char>::zeroed();
-        let mut offset_ptr = MaybeUninit::<u64>::zeroed();
-        let mut size_ptr = MaybeUninit::<libc::size_t>::zeroed();
-
-        let result = unsafe {
-            libfdisk::fdisk_locate_disklabel(
-                self.inner,
-                nth,
-                name_ptr.as_mut_ptr(),
-                offset_ptr.as_mut_ptr(),
-                size_ptr.as_mut_ptr(),
-            )
-        };
-
-        match result {
-            0 => {
-                let name_cstr = unsafe { name_ptr.assume_init() };
-                let offset = unsafe { offset_ptr.assume_init() };
-                let size = unsafe { size_ptr.assume_init() };
-                let name = ffi_to_string_or_empty!(name_cstr);
+    /// May panic if the underlying `libfdisk` parent C pointer differs from the cached parent Rust
+    /// reference.
+    pub fn parent_partitioner(&self) -> Option<&Fdisk<'_>> {
+        log::debug!("Fdisk::parent_partitioner getting reference to parent `Fdisk`");
 
-                let section = TableSection::new(name, offset, size);
-                log::debug!(
-                    "Fdisk::partition_table_section located partition table section: {:?}",
-                    section
-                );
+        let mut ptr = MaybeUninit::<*mut libfdisk::fdisk_context>::zeroed();
+        unsafe {
+            ptr.write(libfdisk::fdisk_get_parent(self.inner));
+        }
 
-                Some(section)
+        match unsafe { ptr.assume_init() } {
+            ptr if ptr.is_null() => {
+                log::debug!("Fdisk::parent_partitioner got no parent partitioner. libfdisk::fdisk_get_parent returned a NULL pointer");
+                None
             }
-            code => {
-                let err_msg = format!("failed to locate partition table section: {:?}", nth);
-                log::debug!("Fdisk::partition_table_section {}. libfdisk::fdisk_locate_disklabel returned error code: {:?}", err_msg, code);
+            parent_ptr => {
+                log::debug!("Fdisk::parent_partitioner got parent partitioner");
+                self._parent.map(|parent| {
+                    if parent.inner == parent_ptr {
+                        log::debug!("Fdisk::parent_partitioner parent partitioner matches cached value");
 
-                None
+                        parent
+                    } else {
+                        log::debug!(
+                            "Fdisk::parent_partitioner parent partitioner does NOT match cached value"
+                        );
+
+                        panic!("Fdisk::parent_partitioner parent partitioner does NOT match cached value");
+                    }
+                })
             }
         }
     }
 
-    /// Returns a list of the default fields of a partition entry in a partition table.
+    /// Returns the number of sectors per cylinder when this `Fdisk` is set to display data in
+    /// cylinder units, or `1` if device addressing is set to [`DeviceAddressing::Sector`] (see:
+    /// [`FdiskBuilder::device_addressing`]).
+    pub fn sectors_per_cylinder(&self) -> u64 {
+        let sectors = unsafe { libfdisk::fdisk_get_units_per_sector(self.inner) };
+        log::debug!("Fdisk::sectors_per_cylinder value: {:?}", sectors);
+
+        sectors as u64
+    }
+
+    /// Returns the offset in bytes between logical and physical sectors.
     ///
-    /// **Note**: the list of default fields depends on
-    /// [`FdiskBuilder::display_partition_details`] being set or not during this `Fdisk`'s
-    /// creation.
-    pub fn partition_table_collect_partition_fields(
-        &self,
-        table: &PartitionTable,
-    ) -> Result<Vec<Field>, FdiskError> {
-        log::debug!("Fdisk::partition_table_collect_partition_fields collecting identifier for partition table fields");
+    /// For backward compatibility, the first logical sector on 4K sector devices does not have to
+    /// start on a naturally aligned physical sector boundary.
+    pub fn device_alignment_offset(&self) -> u64 {
+        let offset = unsafe { libfdisk::fdisk_get_alignment_offset(self.inner) };
+        log::debug!("Fdisk::device_alignment_offset value: {:?}", offset);
 
-        let mut array_ptr = MaybeUninit::<*mut libc::c_int>::zeroed();
-        let mut len_ptr = MaybeUninit::<libc::size_t>::zeroed();
+        offset
+    }
 
-        let result = unsafe {
-            libfdisk::fdisk_label_get_fields_ids(
-                table.inner,
-                self.inner,
-                array_ptr.as_mut_ptr(),
-                len_ptr.as_mut_ptr(),
-            )
-        };
-        match result {
-            0 => {
-                let id_array_ptr = unsafe { array_ptr.assume_init() };
-                let len = unsafe { len_ptr.assume_init() };
-                let id_array = unsafe { slice::from_raw_parts(id_array_ptr, len) };
+    /// Returns the underlying file descriptor associated with the assigned device.
+    ///
+    /// # Safety
+    ///
+    /// You must guarantee that for the duration of this `Fdisk`'s lifetime, nobody will close the
+    /// file descriptor returned by this method.
+    pub unsafe fn device_borrow_fd(&self) -> BorrowedFd<'_> {
+        log::debug!("Fdisk::device_borrow_fd borrowing assigned device's file descriptor");
 
-                let fields = id_array
-                    .iter()
-                    .map(|&id| Field::try_from(id as u32))
-                    .collect::<Result<Vec<Field>, _>>()
-                    .unwrap_or(vec![]);
+        let raw_fd = unsafe { libfdisk::fdisk_get_devfd(self.inner) };
 
-                // Freeing `libfdisk`-allocated array.
-                unsafe {
-                    libc::free(id_array_ptr as *mut _);
-                }
+        unsafe { BorrowedFd::borrow_raw(raw_fd) }
+    }
 
-                log::debug!("Fdisk::partition_table_collect_partition_fields collected partition table field IDs: {:?}", fields);
+    /// Returns a device's model.
+    pub fn device_model(&self) -> Option<&str> {
+        log::debug!("Fdisk::device_model getting device model");
 
-                Ok(fields)
+        let mut ptr = MaybeUninit::<*const libc::c_char>::zeroed();
+        unsafe {
+            ptr.write(libfdisk::fdisk_get_devmodel(self.inner));
+        }
+
+        match unsafe { ptr.assume_init() } {
+            model_ptr if model_ptr.is_null() => {
+                log::debug!("Fdisk::device_model got no device model. libfdisk::fdisk_get_devmodel returned a NULL pointer");
+
+                None
             }
-            code => {
-                let err_msg = "failed to collect identifiers for partition table fields".to_owned();
-                log::debug!("Fdisk::partition_table_collect_partition_fields {}. libfdisk::fdisk_label_get_fields_ids returned error code: {:?}", err_msg, code);
+            model_ptr => {
+                let dev_model = ffi_utils::const_char_array_to_str_ref(model_ptr).ok();
+                log::debug!("Fdisk::device_model got device model: {:?}", dev_model);
 
-                Err(FdiskError::Unexpected(err_msg))
+                dev_model
             }
         }
     }
 
-    /// Returns a list of [`FieldFormat`]s for the default fields of a partition entry in a
-    /// partition table.
-    ///
-    /// **Note**: the list of default fields depends on
-    /// [`FdiskBuilder::display_partition_details`] being set or not during this `Fdisk`'s
-    /// creation.
-    pub fn partition_table_collect_partition_field_formats(
-        &self,
-        table: &PartitionTable,
-    ) -> Result<Vec<FieldFormat>, FdiskError> {
-        let formats = self
-            .partition_table_collect_partition_fields(table)?
-            .iter()
-            // Partition field formats depends on static `libfdisk::fdisk_field`s defined for all
-            // partition table types supported by the library. It should be safe to unwrap below.
-            // If it is not, then libfdisk is broken!
-            .map(|&field| table.partition_field_format(field).unwrap())
-            .collect::<Vec<_>>();
+    /// Returns the assigned device's name.
+    pub fn device_name(&self) -> Option<&Path> {
+        log::debug!("Fdisk::device_name getting assigned device's name");
 
-        Ok(formats)
+        let mut ptr = MaybeUninit::<*const libc::c_char>::zeroed();
+        unsafe {
+            ptr.write(libfdisk::fdisk_get_devname(self.inner));
+        }
+
+        match unsafe { ptr.assume_init() } {
+            ptr if ptr.is_null() => {
+                let err_msg = "failed to get assigned device's name".to_owned();
+                log::debug!(
+                    "Fdisk::device_name {}. libfdisk::fdisk_get_devname returned a NULL pointer",
+                    err_msg
+                );
+
+                None
+            }
+            dev_name_ptr => {
+                let dev_name = ffi_utils::const_c_char_array_to_path(dev_name_ptr);
+                log::debug!(
+                    "Fdisk::device_name got assigned device name: {:?}",
+                    dev_name
+                );
+
+                Some(dev_name)
+            }
+        }
     }
 
-    /// Returns a list of all the fields of a partition entry in a partition table.
-    pub fn partition_table_collect_all_partition_fields(
-        &self,
-        table: &PartitionTable,
-    ) -> Result<Vec<Field>, FdiskError> {
-        log::debug!("Fdisk::partition_table_collect_all_partition_fields collecting identifier for partition table fields");
+    /// Returns the assigned device's identification number (`0` for an image file).
+    pub fn device_number(&self) -> u64 {
+        let dev_num = unsafe { libfdisk::fdisk_get_devno(self.inner) };
+        log::debug!("Fdisk::device_number value: {:?}", dev_num);
 
-        let mut array_ptr = MaybeUninit::<*mut libc::c_int>::zeroed();
-        let mut len_ptr = MaybeUninit::<libc::size_t>::zeroed();
+        dev_num
+    }
+
+    /// Returns the location of the first logical block available for creating a new partition on
+    /// the assigned device.
+    pub fn device_first_lba(&self) -> u64 {
+        let first_lba = unsafe { libfdisk::fdisk_get_first_lba(self.inner) };
+        log::debug!(
+            "Fdisk::device_first_lba address of first logical block: {:?}",
+            first_lba
+        );
+
+        first_lba
+    }
+
+    /// Returns the location of the last logical block available on the assigned device.
+    pub fn device_last_lba(&self) -> u64 {
+        let last_lba = unsafe { libfdisk::fdisk_get_last_lba(self.inner) };
+        log::debug!(
+            "Fdisk::device_last_lba address of last logical block: {:?}",
+            last_lba
+        );
+
+        last_lba
+    }
+
+    /// Returns the number of cylinder subdivisions of the assigned device.
+    pub fn device_count_cylinders(&self) -> u64 {
+        let cylinders = unsafe { libfdisk::fdisk_get_geom_cylinders(self.inner) };
+        log::debug!(
+            "Fdisk::device_count_cylinders number of cylinders: {:?}",
+            cylinders
+        );
+
+        cylinders
+    }
+
+    /// Returns the number of read-and-write heads (i.e. tracks per cylinder) of the assigned
+    /// device.
+    pub fn device_count_heads(&self) -> u64 {
+        let heads = unsafe { libfdisk::fdisk_get_geom_heads(self.inner) };
+        log::debug!("Fdisk::device_count_heads number of heads: {:?}", heads);
+
+        heads as u64
+    }
+
+    /// Returns the number of sectors per track of the assigned device.
+    pub fn device_count_sectors(&self) -> u64 {
+        let sectors = unsafe { libfdisk::fdisk_get_geom_sectors(self.inner) };
+        log::debug!(
+            "Fdisk::device_count_sectors number of sectors per track: {:?}",
+            sectors
+        );
+
+        sectors
+    }
+
+    /// Returns the device's grain size in bytes (usually `1 MiB`).
+    pub fn device_grain_size(&self) -> u64 {
+        let size = unsafe { libfdisk::fdisk_get_grain_size(self.inner) };
+        log::debug!("Fdisk::device_grain_size value: {:?}", size);
+
+        size
+    }
+
+    /// Returns the preferred minimum number of bytes for random Input/Output on the assigned
+    /// device.
+    pub fn device_minimum_io_size(&self) -> u64 {
+        let min_io_size = unsafe { libfdisk::fdisk_get_minimal_iosize(self.inner) };
+        log::debug!(
+            "Fdisk::device_minimum_io_size minimal I/O size: {:?}",
+            min_io_size
+        );
+
+        min_io_size
+    }
+
+    /// Returns the preferred optimal number of bytes for streaming Input/Output on the assigned
+    /// device.
+    pub fn device_optimal_io_size(&self) -> u64 {
+        let opt_io_size = unsafe { libfdisk::fdisk_get_minimal_iosize(self.inner) };
+        log::debug!(
+            "Fdisk::device_optimal_io_size optimal I/O size: {:?}",
+            opt_io_size
+        );
+
+        opt_io_size
+    }
+
+    /// Returns a summary of the assigned device's characteristics, similar to the header printed
+    /// by `fdisk -l`.
+    pub fn device_report(&self) -> DeviceReport {
+        log::debug!("Fdisk::device_report collecting device characteristics");
+
+        DeviceReport::new(self)
+    }
+
+    /// Returns the size of the assigned device in bytes.
+    pub fn device_size_in_bytes(&self) -> u64 {
+        let size = self.device_size_in_sectors() * self.device_bytes_per_logical_sector();
+        log::debug!("Fdisk::device_size_in_bytes size (bytes): {:?}", size);
+
+        size
+    }
+
+    /// Returns the size of the assigned device in logical sectors.
+    pub fn device_size_in_sectors(&self) -> u64 {
+        let size = unsafe { libfdisk::fdisk_get_nsectors(self.inner) };
+        log::debug!("Fdisk::device_size_in_sectors size (sectors): {:?}", size);
+
+        size
+    }
+
+    /// Returns the size in bytes of a logical sector.
+    pub fn device_bytes_per_logical_sector(&self) -> u64 {
+        let size = unsafe { libfdisk::fdisk_get_sector_size(self.inner) };
+        log::debug!(
+            "Fdisk::device_bytes_per_logical_sector bytes per sector: {:?}",
+            size
+        );
+
+        size
+    }
+
+    /// Returns the size in bytes of a physical sector.
+    pub fn device_bytes_per_physical_sector(&self) -> u64 {
+        let size = unsafe { libfdisk::fdisk_get_physector_size(self.inner) };
+        log::debug!(
+            "Fdisk::device_bytes_per_physical_sector bytes per sector: {:?}",
+            size
+        );
+
+        size
+    }
+
+    /// Returns `true` if the caller answers `yes` to the `question`.
+    pub fn ask_yes_no_question<T>(&self, question: T) -> Result<bool, FdiskError>
+    where
+        T: AsRef<str>,
+    {
+        let question = question.as_ref();
+        let question_cstr = ffi_utils::as_ref_str_to_c_string(question)?;
+        log::debug!(
+            "Fdisk::ask_yes_no_question asking yes/no question: {:?}",
+            question
+        );
+
+        let mut answer = MaybeUninit::<libc::c_int>::zeroed();
 
         let result = unsafe {
-            libfdisk::fdisk_label_get_fields_ids_all(
-                table.inner,
-                self.inner,
-                array_ptr.as_mut_ptr(),
-                len_ptr.as_mut_ptr(),
-            )
+            libfdisk::fdisk_ask_yesno(self.inner, question_cstr.as_ptr(), answer.as_mut_ptr())
         };
 
         match result {
             0 => {
-                let id_array_ptr = unsafe { array_ptr.assume_init() };
-                let len = unsafe { len_ptr.assume_init() };
-                let id_array = unsafe { slice::from_raw_parts(id_array_ptr, len) };
-
-                let fields = id_array
-                    .iter()
-                    .map(|&id| Field::try_from(id as u32))
-                    .collect::<Result<Vec<Field>, _>>()
-                    .unwrap_or(vec![]);
-
-                // Freeing `libfdisk`-allocated array.
-                unsafe {
-                    libc::free(id_array_ptr as *mut _);
-                }
-
-                log::debug!("Fdisk::partition_table_collect_all_partition_fields collected partition table field IDs: {:?}", fields);
+                let answer = unsafe { answer.assume_init() };
+                let answer_str = if answer == 1 {
+                    "yes".to_owned()
+                } else {
+                    "no".to_owned()
+                };
+                log::debug!("Fdisk::ask_yes_no_question got answer: {:?}", answer_str);
 
-                Ok(fields)
+                Ok(answer == 1)
             }
             code => {
-                let err_msg = "failed to collect identifiers for partition table fiels".to_owned();
-                log::debug!("Fdisk::partition_table_collect_all_partition_fields {}. libfdisk::fdisk_label_get_fields_ids_all returned error code: {:?}", err_msg, code);
+                let err_msg = format!("failed to get answer to question: {:?}", question);
+                log::debug!("Fdisk::ask_yes_no_question {}. libfdisk::fdisk_ask_yesno returned error code: {:?}", err_msg, code);
 
-                Err(FdiskError::Unexpected(err_msg))
+                Err(FdiskError::Prompt(err_msg))
             }
         }
     }
 
-    /// Returns a list of [`FieldFormat`]s for all the fields of a partition entry in a
-    /// partition table.
-    pub fn partition_table_collect_all_partition_field_formats(
-        &self,
-        table: &PartitionTable,
-    ) -> Result<Vec<FieldFormat>, FdiskError> {
-        let formats = self
-            .partition_table_collect_all_partition_fields(table)?
-            .iter()
-            // Partition field formats depends on static `libfdisk::fdisk_field`s defined for all
-            // partition table types supported by the library. It should be safe to unwrap below.
-            // If it is not, then libfdisk is broken!
-            .map(|&field| table.partition_field_format(field).unwrap())
-            .collect::<Vec<_>>();
+    #[doc(hidden)]
+    /// Asks the user for a partition number on the console.
+    fn request_partition_number(
+        ptr: *mut libfdisk::fdisk_context,
+        want_new: libc::c_int,
+    ) -> Result<usize, FdiskError> {
+        let op_str = if want_new == 0 {
+            "used".to_owned()
+        } else {
+            "unused".to_owned()
+        };
+        log::debug!(
+            "Fdisk::request_partition_number requesting {} partition number",
+            op_str
+        );
+
+        let mut partition_number = MaybeUninit::<usize>::zeroed();
+
+        let result =
+            unsafe { libfdisk::fdisk_ask_partnum(ptr, partition_number.as_mut_ptr(), want_new) };
+
+        match result {
+            0 => {
+                let partition_number = unsafe { partition_number.assume_init() };
+                log::debug!(
+                    "Fdisk::request_partition_number got {} partition number: {:?}",
+                    op_str,
+                    partition_number
+                );
+
+                Ok(partition_number)
+            }
+            1 => {
+                let err_msg = format!("no {} partition number available", op_str);
+                log::debug!("Fdisk::request_partition_number {}", err_msg);
+
+                Err(FdiskError::Prompt(err_msg))
+            }
+            code => {
+                let err_msg = match -code {
+                    libc::ENOMEM => "out of memory".to_owned(),
+                    _ => format!("failed to request {} partition number", op_str),
+                };
+
+                log::debug!("Fdisk::request_partition_number {}. libfdisk::fdisk_ask_partnum returned error code: {:?}", err_msg, code);
+
+                Err(FdiskError::Prompt(err_msg))
+            }
+        }
+    }
+
+    /// Asks the caller for a used partition number.
+    pub fn ask_partition_number_used(&self) -> Result<usize, FdiskError> {
+        Self::request_partition_number(self.inner, 0)
+    }
+
+    /// Asks the caller for an unused partition number.
+    pub fn ask_partition_number_unused(&self) -> Result<usize, FdiskError> {
+        Self::request_partition_number(self.inner, 1)
+    }
+
+    /// Prompts the caller for a numerical value.
+    pub fn ask_number_in_range<T>(
+        &self,
+        question: T,
+        default_value: u64,
+        lower_bound: u64,
+        upper_bound: u64,
+    ) -> Result<libfdisk::uintmax_t, FdiskError>
+    where
+        T: AsRef<str>,
+    {
+        log::debug!(
+            "Fdisk::ask_number_in_range requesting value in range [{:?}, {:?}] (default: {:?})",
+            lower_bound,
+            upper_bound,
+            default_value
+        );
+        let question = question.as_ref();
+        let question_cstr = ffi_utils::as_ref_str_to_c_string(question)?;
+
+        let mut ptr = MaybeUninit::<libfdisk::uintmax_t>::zeroed();
+
+        let result = unsafe {
+            libfdisk::fdisk_ask_number(
+                self.inner,
+                lower_bound,
+                default_value,
+                upper_bound,
+                question_cstr.as_ptr(),
+                ptr.as_mut_ptr(),
+            )
+        };
+
+        match result {
+            0 => {
+                let obtained = unsafe { ptr.assume_init() };
+                log::debug!("Fdisk::ask_number_in_range got value: {:?}", obtained);
+
+                Ok(obtained)
+            }
+            code => {
+                let err_msg = match -code {
+                    libc::ENOMEM => "out of memory".to_owned(),
+                    _ => format!(
+                        "error while requesting value in range [{:?}, {:?}] (default: {:?})",
+                        lower_bound, upper_bound, default_value
+                    ),
+                };
+
+                log::debug!("Fdisk::ask_number_in_range {}. libfdisk::fdisk_ask_number returned error code: {:?}", err_msg, code);
+
+                Err(FdiskError::Prompt(err_msg))
+            }
+        }
+    }
+
+    /// Prompts the caller for a string value.
+    pub fn ask_string_value<T>(&self, question: T) -> Result<String, FdiskError>
+    where
+        T: AsRef<str>,
+    {
+        log::debug!("Fdisk::ask_string_value requesting string value");
+        let question = question.as_ref();
+        let question_cstr = ffi_utils::as_ref_str_to_c_string(question)?;
+
+        let mut ptr = MaybeUninit::<*mut libc::c_char>::zeroed();
+
+        let result = unsafe {
+            libfdisk::fdisk_ask_string(self.inner, question_cstr.as_ptr(), ptr.as_mut_ptr())
+        };
+
+        match result {
+            0 => {
+                let answer_ptr = unsafe { ptr.assume_init() };
+                let answer = ffi_to_string_or_empty!(answer_ptr);
+
+                log::debug!("Fdisk::ask_string_value got string value: {:?}", answer);
+
+                Ok(answer)
+            }
+            code => {
+                let err_msg = match -code {
+                    libc::ENOMEM => "out of memory".to_owned(),
+                    _ => "failed to request a string value".to_owned(),
+                };
+
+                log::debug!("Fdisk::ask_string_value {}. libfdisk::fdisk_ask_string returned error code: {:?}", err_msg, code);
+
+                Err(FdiskError::Prompt(err_msg))
+            }
+        }
+    }
+
+    /// Returns the name of the already existing file system or partition table detected.
+    pub fn device_describe_collisions(&self) -> Option<&str> {
+        log::debug!("Fdisk::device_describe_collisions describing metadata collisions");
+        let mut ptr = MaybeUninit::<*const libc::c_char>::zeroed();
+        unsafe {
+            ptr.write(libfdisk::fdisk_get_collision(self.inner));
+        }
+
+        match unsafe { ptr.assume_init() } {
+            ptr if ptr.is_null() => {
+                log::debug!("Fdisk::device_describe_collisions found no metadata conflict. libfdisk::fdisk_get_collision returned a NULL pointer");
+
+                None
+            }
+            desc_ptr => {
+                let collisions = ffi_utils::const_char_array_to_str_ref(desc_ptr).ok();
+                log::debug!(
+                    "Fdisk::device_describe_collisions found collisions for: {:?}",
+                    collisions
+                );
+
+                collisions
+            }
+        }
+    }
+
+    /// Returns the format in which a `Fdisk` displays partition sizes.
+    pub fn partition_size_format(&self) -> SizeFormat {
+        let code = unsafe { libfdisk::fdisk_get_size_unit(self.inner) };
+        let size_format = SizeFormat::try_from(code as u32).unwrap();
+        log::debug!("Fdisk::partition_size_format value: {:?}", size_format);
+
+        size_format
+    }
+
+    /// Returns the assigned device's name.
+    /// Returns the content of a [`Partition`]'s field in string form.
+    pub fn partition_field_to_string(
+        &self,
+        field: Field,
+        partition: &Partition,
+    ) -> Result<String, FdiskError> {
+        log::debug!(
+            "Fdisk::partition_field_to_string convert content of partition field {:?} to string",
+            field
+        );
+        let cfield = field as u32 as i32;
+
+        let mut content_ptr = MaybeUninit::<*mut libc::c_char>::zeroed();
+
+        let result = unsafe {
+            libfdisk::fdisk_partition_to_string(
+                partition.inner,
+                self.inner,
+                cfield,
+                content_ptr.as_mut_ptr(),
+            )
+        };
+
+        match result {
+            0 => {
+                let ptr = unsafe { content_ptr.assume_init() };
+                let field_content = ffi_to_string_or_empty!(ptr);
+                log::debug!("Fdisk::partition_field_to_string converted content of partition field {:?} to {:?}", field, field_content);
+
+                Ok(field_content)
+            }
+            code => {
+                let err_msg = format!("failed to convert content of partition field {:?}", field);
+                log::debug!("Fdisk::partition_field_to_string {}. libfdisk::fdisk_partition_to_string returned error code: {:?}", err_msg, code);
+
+                match -code {
+                    libc::ENOMEM => Err(FdiskError::OutOfMemory(err_msg)),
+                    _ => Err(FdiskError::Unexpected(err_msg)),
+                }
+            }
+        }
+    }
+
+    #[doc(hidden)]
+    /// Gets a partition by its index number in a partition array.
+    fn get_partition_by_number(
+        fdisk: &Self,
+        partition_number: usize,
+    ) -> Option<*mut libfdisk::fdisk_partition> {
+        let mut partition_ptr = MaybeUninit::<*mut libfdisk::fdisk_partition>::zeroed();
+
+        let result = unsafe {
+            libfdisk::fdisk_get_partition(fdisk.inner, partition_number, partition_ptr.as_mut_ptr())
+        };
+
+        match result {
+            0 => {
+                log::debug!(
+                    "Fdisk::get_partition_by_number got partition with identification number: {:?}",
+                    partition_number
+                );
+
+                let partition = unsafe { partition_ptr.assume_init() };
+
+                Some(partition)
+            }
+            code => {
+                let err_msg = format!(
+                    "no partition with identification number: {:?}",
+                    partition_number
+                );
+                log::debug!("Fdisk::get_partition_by_number {}. libfdisk::fdisk_get_partition returned error code: {:?}", err_msg, code);
+
+                None
+            }
+        }
+    }
+
+    /// Returns a reference to a [`Partition`] from its identification number.
+    pub fn partition_by_number(&self, partition_number: usize) -> Option<&Partition> {
+        log::debug!(
+            "Partition::partition_by_number getting partition with identification number: {:?}",
+            partition_number
+        );
+
+        Self::get_partition_by_number(self, partition_number)
+            .map(|ptr| owning_ref_from_ptr!(self, Partition, ptr))
+    }
+
+    /// Returns a mutable reference to a [`Partition`] from its identification number.
+    pub fn partition_by_number_mut(&mut self, partition_number: usize) -> Option<&mut Partition> {
+        log::debug!(
+            "Partition::partition_by_number_mut getting partition with identification number: {:?}",
+            partition_number
+        );
+
+        Self::get_partition_by_number(self, partition_number)
+            .map(|ptr| owning_mut_from_ptr!(self, Partition, ptr))
+    }
+
+    /// Returns a list of unallocated spaces on the assigned device as a collection of
+    /// [`Partition`]s, or `None` if the device has no partition table.
+    ///
+    /// **Note:** this method will ignore free space smaller than the assigned device's grain
+    /// size (see [`Fdisk::device_grain_size`])
+    pub fn list_empty_spaces(&self) -> Option<PartitionList> {
+        log::debug!("Fdisk::list_empty_spaces listing unallocated spaces");
+
+        let mut ptr = MaybeUninit::<*mut libfdisk::fdisk_table>::zeroed();
+
+        let result = unsafe { libfdisk::fdisk_get_freespaces(self.inner, ptr.as_mut_ptr()) };
+
+        match result {
+            0 => {
+                log::debug!("Fdisk::list_empty_spaces listed unallocated spaces");
+                let ptr = unsafe { ptr.assume_init() };
+                let list = PartitionList::from_ptr(ptr);
+
+                Some(list)
+            }
+            code => {
+                let err_msg = "failed to list unallocated spaces on assigned device".to_owned();
+                log::debug!("Fdisk::list_empty_spaces {}. libfdisk::fdisk_get_freespaces returned error code: {:?}", err_msg, code);
+
+                None
+            }
+        }
+    }
+
+    /// Returns a list of the [`Partition`]s in this `Fdisk`.
+    pub fn list_partitions(&self) -> Option<PartitionList> {
+        log::debug!("Fdisk::list_partitions extracting partitions from partition table");
+
+        let mut ptr = MaybeUninit::<*mut libfdisk::fdisk_table>::zeroed();
+
+        let result = unsafe { libfdisk::fdisk_get_partitions(self.inner, ptr.as_mut_ptr()) };
+
+        match result {
+            0 => {
+                log::debug!("Fdisk::list_partitions extracted partitions from partition table");
+                let ptr = unsafe { ptr.assume_init() };
+                let list = PartitionList::from_ptr(ptr);
+
+                Some(list)
+            }
+            code => {
+                let err_msg = match -code {
+                    libc::EINVAL => "no partition table on device".to_owned(),
+                    libc::ENOSYS => "no partition in partition table".to_owned(),
+                    libc::ENOMEM => "out of memory".to_owned(),
+                    _ => "failed to list partitions in partition table".to_owned(),
+                };
+
+                log::debug!("Fdisk::list_partitions {}. libfdisk::fdisk_get_partitions returned error code: {:?}", err_msg, code);
+
+                None
+            }
+        }
+    }
+
+    /// Returns the content of an entry in the partition table header.
+    pub fn partition_table_header_entry(
+        &self,
+        header_entry: HeaderEntry,
+    ) -> Option<HeaderEntryContent> {
+        log::debug!(
+            "Fdisk::partition_table_header_entry getting content of partition table header entry {:?}",
+            header_entry
+        );
+        let c_header_entry = header_entry.to_original_u32() as i32;
+        let content = HeaderEntryContent::new().ok()?;
+
+        let result = unsafe {
+            libfdisk::fdisk_get_disklabel_item(self.inner, c_header_entry, content.inner)
+        };
+
+        match result {
+            0 => {
+                log::debug!(
+                    "Partition::partition_table_header_entry got content of partition table header entry {:?}",
+                    header_entry
+                );
+
+                Some(content)
+            }
+            code => {
+                let err_msg = format!(
+                    "failed to get content of partition table header entry {:?}",
+                    header_entry
+                );
+                log::debug!("Fdisk::partition_table_header_entry {}. libfdisk::fdisk_get_disklabel_item returned error code: {:?}", err_msg, code);
+
+                None
+            }
+        }
+    }
+
+    /// Returns the partition table's ID in string form.
+    pub fn partition_table_id(&self) -> Option<String> {
+        log::debug!("Fdisk::partition_table_id getting partition table's ID");
+
+        let mut id_ptr = MaybeUninit::<*mut libc::c_char>::zeroed();
+
+        let result = unsafe { libfdisk::fdisk_get_disklabel_id(self.inner, id_ptr.as_mut_ptr()) };
+
+        match result {
+            0 => {
+                match unsafe { id_ptr.assume_init() } {
+                    ptr if ptr.is_null() => {
+                        let err_msg = "no partition table ID".to_owned();
+                        log::debug!("Fdisk::partition_table_id {}. libfdisk::fdisk_get_disklabel_id returned a NULL pointer", err_msg);
+
+                        None
+                    }
+                    ptr => {
+                        let id = ffi_utils::c_char_array_to_string(ptr);
+                        // We took ownership of the allocated char array returned by `fdisk_get_disklabel_id`,
+                        // we free it here to avoid a memory leak.
+                        unsafe {
+                            libc::free(ptr as *mut _);
+                        }
+
+                        log::debug!(
+                            "Fdisk::partition_table_id got partition table's ID: {:?}",
+                            id
+                        );
+
+                        Some(id)
+                    }
+                }
+            }
+            code => {
+                let err_msg = "failed to get partition table's ID".to_owned();
+                log::debug!("Fdisk::partition_table_id {}. libfdisk::fdisk_get_disklabel_id returned error code: {:?}", err_msg, code);
+
+                None
+            }
+        }
+    }
+
+    #[doc(hidden)]
+    /// Returns the partition table associated with this `Fdisk`.
+    fn get_partition_table(
+        ptr: &Self,
+        name: *const libc::c_char,
+    ) -> Option<*mut libfdisk::fdisk_label> {
+        let mut part_table_ptr = MaybeUninit::<*mut libfdisk::fdisk_label>::zeroed();
+        unsafe {
+            part_table_ptr.write(libfdisk::fdisk_get_label(ptr.inner, name));
+        }
+
+        match unsafe { part_table_ptr.assume_init() } {
+            ptr if ptr.is_null() => {
+                log::debug!("Fdisk::get_partition_table found no partition table. libfdisk::fdisk_get_label returned a NULL pointer");
+
+                None
+            }
+            ptr => {
+                log::debug!("Fdisk::get_partition_table found requested partition table");
+
+                Some(ptr)
+            }
+        }
+    }
+
+    /// Returns a reference to the current partition table associated with this `Fdisk`.
+    pub fn partition_table_current(&self) -> Option<&PartitionTable> {
+        log::debug!("Fdisk::partition_table_current getting current partition table");
+
+        Self::get_partition_table(self, std::ptr::null())
+            .map(|ptr| owning_ref_from_ptr!(self, PartitionTable, ptr))
+    }
+
+    /// Returns a mutable reference to the current partition table associated with this `Fdisk`.
+    pub fn partition_table_current_mut(&mut self) -> Option<&mut PartitionTable> {
+        log::debug!("Fdisk::partition_table_current_mut getting current partition table");
+
+        Self::get_partition_table(self, std::ptr::null())
+            .map(|ptr| owning_mut_from_ptr!(self, PartitionTable, ptr))
+    }
+
+    /// Returns a reference to the current table associated with this `Fdisk` matching the given type.
+    pub fn partition_table_by_type(&self, kind: PartitionTableKind) -> Option<&PartitionTable> {
+        log::debug!(
+            "Fdisk::partition_table_by_type getting partition table of type: {:?}",
+            kind
+        );
+        let name_cstr = kind.to_c_string();
+
+        Self::get_partition_table(self, name_cstr.as_ptr())
+            .map(|ptr| owning_ref_from_ptr!(self, PartitionTable, ptr))
+    }
+
+    /// Returns a mutable reference to the current table associated with this `Fdisk` matching the given type.
+    pub fn partition_table_by_type_mut(
+        &mut self,
+        kind: PartitionTableKind,
+    ) -> Option<&mut PartitionTable> {
+        log::debug!(
+            "Fdisk::partition_table_by_type_mut getting partition table of type: {:?}",
+            kind
+        );
+        let name_cstr = kind.to_c_string();
+
+        Self::get_partition_table(self, name_cstr.as_ptr())
+            .map(|ptr| owning_mut_from_ptr!(self, PartitionTable, ptr))
+    }
+
+    /// Returns the number of supported partition table types.
+    pub fn partition_table_count_types(&self) -> usize {
+        let count = unsafe { libfdisk::fdisk_get_nlabels(self.inner) };
+        log::debug!(
+            "Fdisk::partition_table_count_types number of supported partition table types: {:?}",
+            count
+        );
+
+        count
+    }
+
+    /// Returns the maximum number of partitions the partition table can hold.
+    ///
+    /// **Note:** this function will always return `4` for `MBR` partitions, value which
+    /// corresponds to the maximum number of logical partitions. Use an extended partition to lift
+    /// this limit.
+    pub fn partition_table_max_partitions(&self) -> usize {
+        let max = unsafe { libfdisk::fdisk_get_npartitions(self.inner) };
+        log::debug!(
+            "Fdisk::partition_table_max_partitions maximum number of partitions: {:?}",
+            max
+        );
+
+        max
+    }
+
+    #[cfg_attr(doc,
+         cfg_attr(all(),
+        doc = ::embed_doc_image::embed_image!( "fig-01", "third-party/vendor/wikipedia/GUID_Partition_Table_Scheme.svg"),
+        ))]
+    /// Returns the name, location, and size of the `nth` section of an in-memory DOS or GPT partition table.
+    ///
+    /// For example, a primary GPT partition table has three sections:
+    /// - a Protective MBR (`nth=0`),
+    /// - a Partition Table Header(`nth=1`),
+    /// - and a Partition Entry Array (`nth=2`),
+    ///
+    /// as illustrated on the diagram below.
+    ///
+    /// ![Diagram illustrating the layout of the GUID Partition Table (GPT) scheme. Each logical
+    /// block (LBA) is 512 bytes in size. LBA addresses that are negative indicate position from
+    /// the end of the volume, with −1 being the last addressable block.][fig-01]
+    ///
+    /// Source: <cite>The original uploader was Kbolino at [English
+    /// Wikipedia.](https://commons.wikimedia.org/wiki/File:GUID_Partition_Table_Scheme.svg), [CC
+    /// BY-SA
+    /// 2.5](https://creativecommons.org/licenses/by-sa/2.5), via Wikimedia Commons</cite>
+    ///
+    /// **Note:**
+    /// - `nth=3`, and `nth=4` allow access to respectively, the backup Partition Entry Array, and
+    ///   the backup Partition Table Header (i.e. the secondary GPT in the diagram above).
+    /// - the values returned by this method are from a copy of the assigned device's partition
+    ///   table kept in memory; which might differ from the one on disk.<br>
+    ///   To have the most up-to-date values, it is recommended to invoke this method after
+    ///   synchronising states by calling [`Fdisk::partition_table_write_to_disk`].
+    pub fn partition_table_section(&self, nth: i32) -> Option<TableSection> {
+        log::debug!(
+            "Fdisk::partition_table_section locating partition table section: {:?}",
+            nth
+        );
+
+        let mut name_ptr = MaybeUninit::<*const libc::c_char>::zeroed();
+        let mut offset_ptr = MaybeUninit::<u64>::zeroed();
+        let mut size_ptr = MaybeUninit::<libc::size_t>::zeroed();
+
+        let result = unsafe {
+            libfdisk::fdisk_locate_disklabel(
+                self.inner,
+                nth,
+                name_ptr.as_mut_ptr(),
+                offset_ptr.as_mut_ptr(),
+                size_ptr.as_mut_ptr(),
+            )
+        };
+
+        match result {
+            0 => {
+                let name_cstr = unsafe { name_ptr.assume_init() };
+                let offset = unsafe { offset_ptr.assume_init() };
+                let size = unsafe { size_ptr.assume_init() };
+                let name = ffi_to_string_or_empty!(name_cstr);
+
+                let section = TableSection::new(name, offset, size);
+                log::debug!(
+                    "Fdisk::partition_table_section located partition table section: {:?}",
+                    section
+                );
+
+                Some(section)
+            }
+            code => {
+                let err_msg = format!("failed to locate partition table section: {:?}", nth);
+                log::debug!("Fdisk::partition_table_section {}. libfdisk::fdisk_locate_disklabel returned error code: {:?}", err_msg, code);
+
+                None
+            }
+        }
+    }
+
+    /// Returns a list of the default fields of a partition entry in a partition table.
+    ///
+    /// **Note**: the list of default fields depends on
+    /// [`FdiskBuilder::display_partition_details`] being set or not during this `Fdisk`'s
+    /// creation.
+    pub fn partition_table_collect_partition_fields(
+        &self,
+        table: &PartitionTable,
+    ) -> Result<Vec<Field>, FdiskError> {
+        log::debug!("Fdisk::partition_table_collect_partition_fields collecting identifier for partition table fields");
+
+        let mut array_ptr = MaybeUninit::<*mut libc::c_int>::zeroed();
+        let mut len_ptr = MaybeUninit::<libc::size_t>::zeroed();
+
+        let result = unsafe {
+            libfdisk::fdisk_label_get_fields_ids(
+                table.inner,
+                self.inner,
+                array_ptr.as_mut_ptr(),
+                len_ptr.as_mut_ptr(),
+            )
+        };
+        match result {
+            0 => {
+                let id_array_ptr = unsafe { array_ptr.assume_init() };
+                let len = unsafe { len_ptr.assume_init() };
+                let id_array = unsafe { slice::from_raw_parts(id_array_ptr, len) };
+
+                let fields = id_array
+                    .iter()
+                    .map(|&id| Field::try_from(id as u32))
+                    .collect::<Result<Vec<Field>, _>>()
+                    .unwrap_or(vec![]);
+
+                // Freeing `libfdisk`-allocated array.
+                unsafe {
+                    libc::free(id_array_ptr as *mut _);
+                }
+
+                log::debug!("Fdisk::partition_table_collect_partition_fields collected partition table field IDs: {:?}", fields);
+
+                Ok(fields)
+            }
+            code => {
+                let err_msg = "failed to collect identifiers for partition table fields".to_owned();
+                log::debug!("Fdisk::partition_table_collect_partition_fields {}. libfdisk::fdisk_label_get_fields_ids returned error code: {:?}", err_msg, code);
+
+                Err(FdiskError::Unexpected(err_msg))
+            }
+        }
+    }
+
+    /// Returns a list of [`FieldFormat`]s for the default fields of a partition entry in a
+    /// partition table.
+    ///
+    /// **Note**: the list of default fields depends on
+    /// [`FdiskBuilder::display_partition_details`] being set or not during this `Fdisk`'s
+    /// creation.
+    pub fn partition_table_collect_partition_field_formats(
+        &self,
+        table: &PartitionTable,
+    ) -> Result<Vec<FieldFormat>, FdiskError> {
+        let formats = self
+            .partition_table_collect_partition_fields(table)?
+            .iter()
+            // Partition field formats depends on static `libfdisk::fdisk_field`s defined for all
+            // partition table types supported by the library. It should be safe to unwrap below.
+            // If it is not, then libfdisk is broken!
+            .map(|&field| table.partition_field_format(field).unwrap())
+            .collect::<Vec<_>>();
+
+        Ok(formats)
+    }
+
+    /// Returns a list of all the fields of a partition entry in a partition table.
+    pub fn partition_table_collect_all_partition_fields(
+        &self,
+        table: &PartitionTable,
+    ) -> Result<Vec<Field>, FdiskError> {
+        log::debug!("Fdisk::partition_table_collect_all_partition_fields collecting identifier for partition table fields");
+
+        let mut array_ptr = MaybeUninit::<*mut libc::c_int>::zeroed();
+        let mut len_ptr = MaybeUninit::<libc::size_t>::zeroed();
+
+        let result = unsafe {
+            libfdisk::fdisk_label_get_fields_ids_all(
+                table.inner,
+                self.inner,
+                array_ptr.as_mut_ptr(),
+                len_ptr.as_mut_ptr(),
+            )
+        };
+
+        match result {
+            0 => {
+                let id_array_ptr = unsafe { array_ptr.assume_init() };
+                let len = unsafe { len_ptr.assume_init() };
+                let id_array = unsafe { slice::from_raw_parts(id_array_ptr, len) };
+
+                let fields = id_array
+                    .iter()
+                    .map(|&id| Field::try_from(id as u32))
+                    .collect::<Result<Vec<Field>, _>>()
+                    .unwrap_or(vec![]);
+
+                // Freeing `libfdisk`-allocated array.
+                unsafe {
+                    libc::free(id_array_ptr as *mut _);
+                }
+
+                log::debug!("Fdisk::partition_table_collect_all_partition_fields collected partition table field IDs: {:?}", fields);
+
+                Ok(fields)
+            }
+            code => {
+                let err_msg = "failed to collect identifiers for partition table fiels".to_owned();
+                log::debug!("Fdisk::partition_table_collect_all_partition_fields {}. libfdisk::fdisk_label_get_fields_ids_all returned error code: {:?}", err_msg, code);
+
+                Err(FdiskError::Unexpected(err_msg))
+            }
+        }
+    }
+
+    /// Returns a list of [`FieldFormat`]s for all the fields of a partition entry in a
+    /// partition table.
+    pub fn partition_table_collect_all_partition_field_formats(
+        &self,
+        table: &PartitionTable,
+    ) -> Result<Vec<FieldFormat>, FdiskError> {
+        let formats = self
+            .partition_table_collect_all_partition_fields(table)?
+            .iter()
+            // Partition field formats depends on static `libfdisk::fdisk_field`s defined for all
+            // partition table types supported by the library. It should be safe to unwrap below.
+            // If it is not, then libfdisk is broken!
+            .map(|&field| table.partition_field_format(field).unwrap())
+            .collect::<Vec<_>>();
+
+        Ok(formats)
+    }
+
+    /// Returns a reference to the [`Script`] currently associated with this `Fdisk`.
+    pub fn script(&self) -> Option<&Script<'_>> {
+        log::debug!("Fdisk::script getting currently linked `Script` instance");
+
+        let mut ptr = MaybeUninit::<*mut libfdisk::fdisk_script>::zeroed();
+        unsafe {
+            ptr.write(libfdisk::fdisk_get_script(self.inner));
+        }
+
+        match unsafe { ptr.assume_init() } {
+            ptr if ptr.is_null() => {
+                let err_msg = "got no currently linked script".to_owned();
+                log::debug!(
+                    "Fdisk::script {}. libfdisk::fdisk_get_script returned a NULL pointer",
+                    err_msg
+                );
+
+                None
+            }
+            ptr => {
+                log::debug!("Fdisk::script got currently linked `Script`");
+                let script = owning_ref_from_ptr!(self, Script, ptr);
+
+                Some(script)
+            }
+        }
+    }
+
+    /// Returns a mutable reference to the [`Script`] currently associated with this `Fdisk`.
+    pub fn script_mut(&mut self) -> Option<&mut Script<'_>> {
+        log::debug!("Fdisk::script_mut getting currently linked `Script` instance");
+
+        let mut ptr = MaybeUninit::<*mut libfdisk::fdisk_script>::zeroed();
+        unsafe {
+            ptr.write(libfdisk::fdisk_get_script(self.inner));
+        }
+
+        match unsafe { ptr.assume_init() } {
+            ptr if ptr.is_null() => {
+                let err_msg = "got no currently linked script".to_owned();
+                log::debug!(
+                    "Fdisk::script_mut {}. libfdisk::fdisk_get_script returned a NULL pointer",
+                    err_msg
+                );
+
+                None
+            }
+            ptr => {
+                log::debug!("Fdisk::script_mut got currently linked `Script`");
+                let script = owning_mut_from_ptr!(self, Script, ptr);
+
+                Some(script)
+            }
+        }
+    }
+
+    //---- END getters
+
+    //---- BEGIN iterators
+
+    /// Returns an iterator over [`PartitionTable`]s on the assigned device.
+    pub fn iter(&self) -> PartitionTableIter<'_> {
+        log::debug!("Fdisk::iter creating a new `PartitionTableIter`");
+
+        PartitionTableIter::new(self)
+    }
+
+    /// Returns a mutable iterator over [`PartitionTable`]s on the assigned device.
+    pub fn iter_mut(&'a mut self) -> PartitionTableIterMut<'a> {
+        log::debug!("Fdisk::iter creating a new `PartitionTableIterMut`");
+
+        PartitionTableIterMut::new(self)
+    }
+
+    //---- END iterators
+
+    //---- BEGIN predicates
+
+    /// Returns `true` when the given `partition_number` is being used.
+    ///
+    /// **Note:** the first partition has identification number `0`.
+    pub fn partition_is_number_in_use(&self, partition_number: usize) -> bool {
+        let state = unsafe { libfdisk::fdisk_is_partition_used(self.inner, partition_number) == 1 };
+        log::debug!("Fdisk::partition_is_number_in_use value: {:?}", state);
+
+        state
+    }
+
+    /// Returns `true` when all metadata on the device area specified by the [`Partition`] will be
+    /// wiped when the partition table is written to disk.
+    pub fn is_partition_wipe_active(&self, partition: &Partition) -> bool {
+        let state = unsafe { libfdisk::fdisk_partition_has_wipe(self.inner, partition.inner) == 1 };
+        log::debug!("Fdisk::is_partition_wipe_active value: {:?}", state);
+
+        state
+    }
+
+    /// Returns `true` if the user has overridden some device properties.
+    pub fn has_overriden_device_properties(&self) -> bool {
+        let state = unsafe { libfdisk::fdisk_has_user_device_properties(self.inner) == 1 };
+        log::debug!("Fdisk::has_overriden_device_properties value: {:?}", state);
+
+        state
+    }
+
+    /// Returns `true` the `LBA` is aligned to a physical sector boundary.
+    pub fn is_lba_physically_aligned(&self, lba: u64) -> bool {
+        let state = unsafe { libfdisk::fdisk_lba_is_phy_aligned(self.inner, lba) == 1 };
+        log::debug!("Fdisk::is_lba_physically_aligned value: {:?}", state);
+
+        state
+    }
+
+    /// Returns `true` when this `Fdisk` is set to display each partition's detailed metadata when
+    /// printing on the console.
+    pub fn displays_partition_details(&self) -> bool {
+        let state = unsafe { libfdisk::fdisk_is_details(self.inner) == 1 };
+        log::debug!("Fdisk::displays_partition_details value:{:?}", state);
+
+        state
+    }
+
+    /// Returns `true` when this `Fdisk` is set to only display a partitions list **without**
+    /// detailed metadata when printing on the console.
+    pub fn displays_partition_list_only(&self) -> bool {
+        let state = unsafe { libfdisk::fdisk_is_listonly(self.inner) == 1 };
+        log::debug!("Fdisk::displays_partition_list_only value: {:?}", state);
+
+        state
+    }
+
+    /// Returns `true` when this `Fdisk` is set to display partition metadata in cylinder
+    /// units.
+    pub fn displays_metadata_in_cylinders(&self) -> bool {
+        let state = unsafe { libfdisk::fdisk_use_cylinders(self.inner) == 1 };
+        log::debug!("Fdisk::displays_metadata_in_cylinders value: {:?}", state);
+
+        state
+    }
+
+    /// Returns `true` when device partitioning is dialog-driven.
+    pub fn is_partitioning_interactive(&self) -> bool {
+        let state = unsafe { libfdisk::fdisk_has_dialogs(self.inner) == 1 };
+        log::debug!("Fdisk::is_partitioning_interactive value: {:?}", state);
+
+        state
+    }
+
+    /// Returns `true` if this `Fdisk` is set to protect the master boot record of its assigned
+    /// device when creating a new partition table.
+    pub fn protects_master_boot_record(&self) -> bool {
+        let state = unsafe { libfdisk::fdisk_has_protected_bootbits(self.inner) == 1 };
+        log::debug!("Fdisk::protects_master_boot_record value: {:?}", state);
+
+        state
+    }
+
+    /// Returns `true` when this `Fdisk` erases all device metadata before writing a new
+    /// partition table.
+    pub fn wipes_device_metadata(&self) -> bool {
+        let state = unsafe { libfdisk::fdisk_has_wipe(self.inner) == 1 };
+        log::debug!("Fdisk::wipes_device_metadata value: {:?}", state);
+
+        state
+    }
+
+    /// Returns `true` if `libblkid` detects an already existing file system or partition table on
+    /// the assigned device.
+    ///
+    /// **Note:** `libblkid` does not support all types of partition table, which can lead to some
+    /// inconsistencies where [`Fdisk::device_has_partition_table`] returns `false` while this
+    /// method returns `true`.
+    pub fn device_has_collisions(&self) -> bool {
+        let state = unsafe { libfdisk::fdisk_is_ptcollision(self.inner) == 1 };
+        log::debug!("Fdisk::device_has_collisions value: {:?}", state);
+
+        state
+    }
+
+    /// Returns `true` when there is a partition table on the assigned device.
+    pub fn device_has_partition_table(&self) -> bool {
+        let state = unsafe { libfdisk::fdisk_has_label(self.inner) == 1 };
+        log::debug!("Fdisk::device_has_partition_table value: {:?}", state);
+
+        state
+    }
+
+    /// Returns `true` when the assigned device is currently in use by the Operating System.
+    ///
+    /// **Warning:** always returns `false` if the device was assigned by file, or it is opened in read-only mode.
+    pub fn device_is_in_use(&self) -> bool {
+        let state = unsafe { libfdisk::fdisk_device_is_used(self.inner) == 1 };
+        log::debug!("Fdisk::device_is_in_use value: {:?}", state);
+
+        state
+    }
+
+    /// Returns `true` when the assigned device is open in read-only mode.
+    pub fn device_is_read_only(&self) -> bool {
+        let state = unsafe { libfdisk::fdisk_is_readonly(self.inner) == 1 };
+        log::debug!("Fdisk::device_is_read_only value: {:?}", state);
+
+        state
+    }
+
+    /// Returns `true` when the assigned device is an image file rather than a physical block device.
+    pub fn device_is_image_file(&self) -> bool {
+        let state = unsafe { libfdisk::fdisk_is_regfile(self.inner) == 1 };
+        log::debug!("Fdisk::device_is_image_file value: {:?}", state);
+
+        state
+    }
+
+    //---- END predicates
+}
+
+impl<'a> AsRef<Fdisk<'a>> for Fdisk<'a> {
+    #[inline]
+    fn as_ref(&self) -> &Fdisk<'a> {
+        self
+    }
+}
+
+impl<'a> Drop for Fdisk<'a> {
+    fn drop(&mut self) {
+        log::debug!("Fdisk::drop deallocating `Fdisk` instance");
+
+        // Stop `libfdisk` from calling back into a dispatcher we are about to free.
+        if self.owns_prompt_dispatcher {
+            unsafe {
+                libfdisk::fdisk_set_ask(self.inner, None, std::ptr::null_mut());
+            }
+        }
+
+        unsafe { libfdisk::fdisk_unref_context(self.inner) }
+
+        // Release heap allocated PartitionTable references.
+        while let Some(gc_item) = self.gc.pop() {
+            gc_item.destroy();
+        }
+
+        if self.owns_prompt_dispatcher {
+            drop(unsafe { Box::from_raw(self.prompt_dispatcher) });
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use pretty_assertions::{assert_eq, assert_ne};
+    use tempfile::NamedTempFile;
+    use terminal_size::{terminal_size, Height, Width};
+
+    use std::io::Read;
+    use std::io::Write;
+    use std::path::Path;
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::core::partition::Guid;
+    use crate::core::partition::Partition;
+    use crate::core::partition::PartitionKind;
+    use crate::core::partition::PartitionList;
+    use crate::core::partition_table::HeaderEntry;
+    use crate::core::partition_table::MaxColWidth;
+    use crate::core::partition_table::PartitionTableKind;
+    use crate::core::script::ScriptDocument;
+    use crate::fdisk::DeviceAddressing;
+    use crate::fdisk::SizeFormat;
+
+    //---- Helper functions
+
+    static BASE_DIR_TEST_IMG_FILES: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/third-party/vendor/util-linux/blkid/images"
+    );
+
+    static PATH_BLANK_IMG_FILE: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/images/blank-10MB.img.xz"
+    );
+
+    fn decode_into<P, W>(xz_file_path: P, writer: &mut W) -> std::io::Result<u64>
+    where
+        P: AsRef<Path>,
+        W: Write + ?Sized,
+    {
+        let xz_file_path = xz_file_path.as_ref();
+
+        // Copy decompressed image to temporary file
+        let compressed_image_file = std::fs::File::open(xz_file_path)?;
+        let mut decompressed = xz2::read::XzDecoder::new(compressed_image_file);
+
+        std::io::copy(&mut decompressed, writer)
+    }
+
+    /// Creates a named temporary image file with one of the supported file systems from the
+    /// compressed samples.
+    fn disk_image_with_fs(fs_type: &str) -> NamedTempFile {
+        let img_path = format!("{BASE_DIR_TEST_IMG_FILES}/filesystems/{fs_type}.img.xz");
+        let mut named_file = NamedTempFile::new().expect("failed to get new NamedTempFile");
+
+        decode_into(img_path, named_file.as_file_mut()).expect("failed to create named disk image");
+        named_file
+    }
+
+    /// Creates a temporary image file with one of the supported partition tables from the
+    /// compressed samples.
+    fn disk_image_with_pt(pt_type: &str) -> NamedTempFile {
+        let img_path = format!("{BASE_DIR_TEST_IMG_FILES}/partition_tables/{pt_type}.img.xz");
+        let mut named_file = NamedTempFile::new().expect("failed to get new NamedTempFile");
+
+        decode_into(img_path, named_file.as_file_mut()).expect("failed to create named disk image");
+        named_file
+    }
+
+    // Create a temporary 50MB blank image file.
+    fn blank_image_file() -> NamedTempFile {
+        let mut named_file = NamedTempFile::new().expect("failed to get new NamedTempFile");
+
+        decode_into(PATH_BLANK_IMG_FILE, named_file.as_file_mut())
+            .expect("failed to create named disk image");
+        named_file
+    }
+
+    // Adds partitions at exactly the positions given as (partition number, type, starting
+    // sector, size in sectors).
+    fn add_partitions<I>(disk: &mut Fdisk, layout: I) -> crate::Result<()>
+    where
+        I: IntoIterator<Item = (usize, PartitionKind, u64, u64)>,
+    {
+        for (number, kind, start, size) in layout {
+            let partition = Partition::builder()
+                .ask_size_interactive()
+                .number(number)
+                .partition_type(kind)
+                .starting_sector(start)
+                .size_in_sectors(size)
+                .build()?;
+            disk.partition_add(partition)?;
+        }
+
+        Ok(())
+    }
+
+    fn dos_kind(code: Code) -> PartitionKind {
+        PartitionKind::builder()
+            .code(code)
+            .build()
+            .expect("failed to build DOS partition type")
+    }
+
+    fn gpt_kind(guid: Guid) -> PartitionKind {
+        PartitionKind::builder()
+            .guid(guid)
+            .build()
+            .expect("failed to build GPT partition type")
+    }
+
+    //-------------------------------------------------------------------------
+
+    #[test]
+    #[should_panic(
+        expected = "one of the methods `assign_device` or `assign_device_by_file` must be called"
+    )]
+    fn fdisk_must_assign_a_device() {
+        let _ = Fdisk::builder().build().unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to assign read-only device")]
+    fn fdisk_can_not_assign_a_regular_file_by_pathname() {
+        let regular_file = NamedTempFile::new().unwrap();
+        let _ = Fdisk::builder()
+            .assign_device(regular_file.path())
+            .build()
+            .unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to assign read-only device")]
+    fn fdisk_can_not_assign_a_regular_file_by_file_stream() {
+        let regular_file = NamedTempFile::new().unwrap();
+        let (file, temp_path) = regular_file.into_parts();
+        let _ = Fdisk::builder()
+            .assign_device_by_file(file, temp_path.as_os_str())
+            .build()
+            .unwrap();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "methods `display_partition_details` and `display_partition_list_only` can not be called at the same time"
+    )]
+    fn fdisk_can_not_both_display_details_and_list_only() {
+        let tmp_image = disk_image_with_pt("gpt");
+        let _ = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .display_partition_details()
+            .display_partition_list_only()
+            .build()
+            .unwrap();
+    }
+
+    #[test]
+    fn fdisk_can_assign_a_device_by_pathname_in_read_only_mode() -> crate::Result<()> {
+        let tmp_image = disk_image_with_pt("gpt");
+
+        let disk = Fdisk::builder().assign_device(tmp_image.path()).build()?;
+
+        let actual = disk.device_is_read_only();
+        let expected = true;
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_assign_a_device_by_pathname_in_read_write_mode() -> crate::Result<()> {
+        let tmp_image = disk_image_with_fs("ext4");
+
+        let disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
+
+        let actual = disk.device_is_read_only();
+        let expected = false;
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_set_device_addressing() -> crate::Result<()> {
+        let tmp_image = disk_image_with_fs("ext4");
+
+        // Default:  DeviceAddressing::Sector
+        let disk = Fdisk::builder().assign_device(tmp_image.path()).build()?;
+
+        let actual = disk.sectors_per_cylinder();
+        let expected = 1;
+        assert_eq!(actual, expected);
+
+        let addressing = DeviceAddressing::Cylinder;
+        let disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .device_addressing(addressing)
+            .build()?;
+
+        let actual = disk.sectors_per_cylinder();
+        assert!(actual > 1);
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_enable_interactive_partitioning() -> crate::Result<()> {
+        let tmp_image = disk_image_with_pt("gpt");
+
+        // Default
+        let disk = Fdisk::builder().assign_device(tmp_image.path()).build()?;
+
+        let actual = disk.is_partitioning_interactive();
+        let expected = false;
+        assert_eq!(actual, expected);
+
+        // Enabled
+        let disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_interactive()
+            .build()?;
+
+        let actual = disk.is_partitioning_interactive();
+        let expected = true;
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_answer_prompts_with_a_prompt_handler() -> crate::Result<()> {
+        use crate::core::prompt::PromptKind;
+
+        let tmp_image = disk_image_with_pt("gpt");
+
+        let disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_interactive()
+            .prompt_handler(|prompt: &mut Prompt| match prompt.kind() {
+                PromptKind::YesNo => prompt.yes_no_set_answer(true),
+                PromptKind::Number => {
+                    let answer = prompt.number_upper_bound();
+                    prompt.number_set_answer(answer)
+                }
+                PromptKind::String => prompt.string_set_answer("answer"),
+                _ => Ok(()),
+            })
+            .build()?;
+
+        let actual = disk.ask_yes_no_question("Continue?")?;
+        let expected = true;
+        assert_eq!(actual, expected);
+
+        let actual = disk.ask_number_in_range("Pick a number", 5, 1, 10)?;
+        let expected = 10;
+        assert_eq!(actual, expected);
+
+        let actual = disk.ask_string_value("Name?")?;
+        let expected = "answer";
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_not_answer_prompts_without_a_prompt_handler() -> crate::Result<()> {
+        let tmp_image = disk_image_with_pt("gpt");
+
+        let disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_interactive()
+            .build()?;
+
+        let actual = disk.ask_yes_no_question("Continue?");
+        assert!(actual.is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_reports_prompt_handler_errors() -> crate::Result<()> {
+        use crate::core::errors::PromptError;
+
+        let tmp_image = disk_image_with_pt("gpt");
+
+        let disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_interactive()
+            .prompt_handler(|_: &mut Prompt| Err(PromptError::Config("no answer".to_owned())))
+            .build()?;
+
+        let actual = disk.ask_yes_no_question("Continue?");
+        assert!(actual.is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_add_a_partition_interactively_with_an_answer_queue() -> crate::Result<()> {
+        use crate::core::prompt::Answer;
+        use crate::core::prompt::AnswerQueue;
+        use crate::core::prompt::PromptKind;
+        use crate::core::prompt::QueryMatcher;
+        use regex::Regex;
+
+        let answers = AnswerQueue::new()
+            .expect(
+                PromptKind::Number,
+                QueryMatcher::Exact("Partition number".to_owned()),
+                Answer::Number(3),
+            )
+            .expect(
+                PromptKind::Number,
+                QueryMatcher::Contains("First sector".to_owned()),
+                Answer::Default,
+            )
+            .expect(
+                PromptKind::Offset,
+                QueryMatcher::Regex(Regex::new(r"^Last sector").unwrap()),
+                Answer::Number(4_095),
+            );
+
+        let tmp_image = blank_image_file();
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .enable_interactive()
+            .prompt_handler(answers.clone())
+            .build()?;
+
+        disk.partition_table_create(PartitionTableKind::GPT)?;
+
+        let actual = disk.partition_add_interactive()?;
+        let expected = 2;
+        assert_eq!(actual, expected);
+
+        answers.verify()?;
+
+        let partitions = disk.list_partitions().unwrap();
+        let actual = partitions[0].ending_sector();
+        let expected = Some(4_095);
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_use_a_parent_after_dropping_its_nested_partitioner() -> crate::Result<()> {
+        let tmp_image = disk_image_with_pt("dos_bsd");
+        let mut disk = Fdisk::builder().assign_device(tmp_image.path()).build()?;
+
+        let bsd = disk.create_nested_partitioner_with_name("bsd")?;
+
+        let actual = bsd.partition_table_is_of_type(PartitionTableKind::BSD);
+        let expected = true;
+        assert_eq!(actual, expected);
+
+        drop(bsd);
+
+        let actual = disk.partition_table_is_of_type(PartitionTableKind::DOS);
+        let expected = true;
+        assert_eq!(actual, expected);
+
+        let nested = disk.create_nested_partitioner()?;
+
+        let actual = nested.parent_partitioner().is_some();
+        let expected = true;
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_set_bsd_disk_label_fields() -> crate::Result<()> {
+        use crate::core::partition_table::BsdDiskLabelFields;
+        use crate::fdisk::FdiskBSDExt;
+
+        let tmp_image = disk_image_with_pt("dos_bsd");
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
+
+        let mut bsd = disk.create_nested_partitioner_with_name("bsd")?;
+
+        let fields = BsdDiskLabelFields::new()
+            .rpm(7200)
+            .interleave(2)
+            .track_skew(3);
+        bsd.bsd_set_disk_label_fields(fields)?;
+        bsd.partition_table_write_to_disk()?;
+        drop(bsd);
+        drop(disk);
+
+        let mut disk = Fdisk::builder().assign_device(tmp_image.path()).build()?;
+        let bsd = disk.create_nested_partitioner_with_name("bsd")?;
+
+        let actual = bsd
+            .partition_table_header_entry(HeaderEntry::BsdRpm)
+            .and_then(|entry| entry.data_u64());
+        let expected = Some(7200);
+        assert_eq!(actual, expected);
+
+        let actual = bsd
+            .partition_table_header_entry(HeaderEntry::BsdInterlave)
+            .and_then(|entry| entry.data_u64());
+        let expected = Some(2);
+        assert_eq!(actual, expected);
+
+        let actual = bsd
+            .partition_table_header_entry(HeaderEntry::BsdTrackSkew)
+            .and_then(|entry| entry.data_u64());
+        let expected = Some(3);
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_not_set_bsd_disk_label_fields_out_of_range() -> crate::Result<()> {
+        use crate::core::partition_table::BsdDiskLabelFields;
+        use crate::fdisk::FdiskBSDExt;
+
+        let tmp_image = disk_image_with_pt("dos_bsd");
+        let mut disk = Fdisk::builder().assign_device(tmp_image.path()).build()?;
+        let mut bsd = disk.create_nested_partitioner_with_name("bsd")?;
+
+        let fields = BsdDiskLabelFields::new().sectors_per_cylinder(u32::MAX);
+        let actual = bsd.bsd_set_disk_label_fields(fields);
+        assert!(actual.is_err());
+
+        let fields = BsdDiskLabelFields::new().rpm(0);
+        let actual = bsd.bsd_set_disk_label_fields(fields);
+        assert!(actual.is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_set_bsd_drive_type_pack_name_and_sector_size() -> crate::Result<()> {
+        use crate::core::partition_table::BsdDiskLabelFields;
+        use crate::core::partition_table::BsdDriveType;
+        use crate::fdisk::FdiskBSDExt;
+
+        let tmp_image = disk_image_with_pt("dos_bsd");
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
+
+        let mut bsd = disk.create_nested_partitioner_with_name("bsd")?;
+
+        let fields = BsdDiskLabelFields::new()
+            .drive_type(BsdDriveType::Floppy)
+            .pack_name("rsfdisk")
+            .sector_size(1024)
+            .rpm(7200);
+        bsd.bsd_set_disk_label_fields(fields)?;
+
+        let actual = bsd
+            .partition_table_header_entry(HeaderEntry::BsdPackName)
+            .and_then(|entry| entry.data_string().map(|name| name.to_owned()));
+        let expected = Some("rsfdisk".to_owned());
+        assert_eq!(actual, expected);
+
+        drop(bsd);
+        drop(disk);
+
+        let mut disk = Fdisk::builder().assign_device(tmp_image.path()).build()?;
+
+        let actual = disk.list_partitions().map(|partitions| partitions.len());
+        let expected = Some(2);
+        assert_eq!(actual, expected);
+
+        let bsd = disk.create_nested_partitioner_with_name("bsd")?;
+
+        let actual = bsd
+            .partition_table_header_entry(HeaderEntry::BsdType)
+            .and_then(|entry| entry.data_string().map(|name| name.to_owned()));
+        let expected = Some("floppy".to_owned());
+        assert_eq!(actual, expected);
+
+        let actual = bsd
+            .partition_table_header_entry(HeaderEntry::BsdPackName)
+            .and_then(|entry| entry.data_string().map(|name| name.to_owned()));
+        let expected = Some("rsfdisk".to_owned());
+        assert_eq!(actual, expected);
+
+        let actual = bsd
+            .partition_table_header_entry(HeaderEntry::BsdSectorSize)
+            .and_then(|entry| entry.data_u64());
+        let expected = Some(1024);
+        assert_eq!(actual, expected);
+
+        let actual = bsd
+            .partition_table_header_entry(HeaderEntry::BsdRpm)
+            .and_then(|entry| entry.data_u64());
+        let expected = Some(7200);
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_not_set_an_invalid_bsd_pack_name_or_sector_size() -> crate::Result<()> {
+        use crate::core::partition_table::BsdDiskLabelFields;
+        use crate::fdisk::FdiskBSDExt;
+
+        let tmp_image = disk_image_with_pt("dos_bsd");
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
+        let mut bsd = disk.create_nested_partitioner_with_name("bsd")?;
+
+        let fields = BsdDiskLabelFields::new().pack_name("a pack name longer than 16 bytes");
+        let actual = bsd.bsd_set_disk_label_fields(fields);
+        assert!(actual.is_err());
+
+        let fields = BsdDiskLabelFields::new().sector_size(1000);
+        let actual = bsd.bsd_set_disk_label_fields(fields);
+        assert!(actual.is_err());
+
+        let actual = bsd
+            .partition_table_header_entry(HeaderEntry::BsdSectorSize)
+            .and_then(|entry| entry.data_u64());
+        let expected = Some(512);
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_link_a_bsd_partition_to_a_dos_partition() -> crate::Result<()> {
+        use crate::fdisk::FdiskBSDExt;
+
+        let tmp_image = disk_image_with_pt("dos_bsd");
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
+
+        let dos_partition = disk.partition_by_number(1).unwrap();
+        let dos_start = dos_partition.starting_sector();
+        let dos_size = dos_partition.size_in_sectors();
+
+        let mut bsd = disk.create_nested_partitioner_with_name("bsd")?;
+
+        let actual = bsd.partition_is_number_in_use(3);
+        let expected = false;
+        assert_eq!(actual, expected);
+
+        bsd.bsd_link_dos_partition(1, 3)?;
+
+        let partition = bsd.partition_by_number(3).unwrap();
+
+        let actual = partition.starting_sector();
+        let expected = dos_start;
+        assert_eq!(actual, expected);
+
+        let actual = partition.size_in_sectors();
+        let expected = dos_size;
+        assert_eq!(actual, expected);
+
+        let actual = bsd.bsd_link_dos_partition(1, 16);
+        assert!(actual.is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_set_sun_label_geometry() -> crate::Result<()> {
+        use crate::fdisk::FdiskSUNExt;
+
+        let tmp_image = disk_image_with_pt("sun");
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
+
+        disk.sun_set_alternate_cylinder_count_to(4)?;
+        disk.sun_set_interleave_factor_to(2)?;
+        disk.sun_set_physical_cylinder_count_to(1_000)?;
+        disk.sun_set_rotation_per_minute_to(7_200)?;
+        disk.sun_set_extra_sectors_per_cylinder_to(1)?;
+
+        let entries = [
+            (HeaderEntry::SunAlternateCylinders, 4),
+            (HeaderEntry::SunInterleave, 2),
+            (HeaderEntry::SunPhysicalCylinders, 1_000),
+            (HeaderEntry::SunRpm, 7_200),
+            (HeaderEntry::SunExtraSectorsPerCylinder, 1),
+        ];
+
+        for (entry, value) in entries {
+            let actual = disk
+                .partition_table_header_entry(entry)
+                .and_then(|entry| entry.data_u64());
+            let expected = Some(value);
+            assert_eq!(actual, expected);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_not_set_sun_label_geometry_out_of_range() -> crate::Result<()> {
+        use crate::fdisk::FdiskSUNExt;
+
+        let tmp_image = disk_image_with_pt("sun");
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
+
+        let actual = disk.sun_set_interleave_factor_to(0);
+        assert!(actual.is_err());
+
+        let actual = disk.sun_set_interleave_factor_to(33);
+        assert!(actual.is_err());
+
+        let actual = disk.sun_set_rotation_per_minute_to(0);
+        assert!(actual.is_err());
+
+        // The test image has 2 sectors per track.
+        let actual = disk.sun_set_extra_sectors_per_cylinder_to(3);
+        assert!(actual.is_err());
+
+        let actual = disk
+            .partition_table_header_entry(HeaderEntry::SunInterleave)
+            .and_then(|entry| entry.data_u64());
+        let expected = Some(1);
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_set_sun_volume_id() -> crate::Result<()> {
+        use crate::fdisk::FdiskSUNExt;
+
+        let tmp_image = disk_image_with_pt("sun");
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
+
+        let actual = disk.sun_set_volume_id("too long name");
+        assert!(actual.is_err());
+
+        // Pending changes are saved along with the volume ID.
+        disk.sun_set_rotation_per_minute_to(7_200)?;
+        disk.sun_set_volume_id("sparc")?;
+
+        let actual = disk
+            .partition_table_header_entry(HeaderEntry::SunVolumeId)
+            .and_then(|entry| entry.data_string().map(String::from));
+        let expected = Some("sparc".to_owned());
+        assert_eq!(actual, expected);
+        drop(disk);
+
+        let disk = Fdisk::builder().assign_device(tmp_image.path()).build()?;
+
+        let actual = disk
+            .partition_table_header_entry(HeaderEntry::SunVolumeId)
+            .and_then(|entry| entry.data_string().map(String::from));
+        let expected = Some("sparc".to_owned());
+        assert_eq!(actual, expected);
+
+        let actual = disk
+            .partition_table_header_entry(HeaderEntry::SunRpm)
+            .and_then(|entry| entry.data_u64());
+        let expected = Some(7_200);
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_set_sgi_boot_file() -> crate::Result<()> {
+        use crate::fdisk::FdiskSGIExt;
+
+        let tmp_image = disk_image_with_pt("sgi");
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
+
+        let actual = disk.sgi_set_boot_file_to("unix");
+        assert!(actual.is_err());
+
+        let actual = disk.sgi_set_boot_file_to("/unix/is/too/long");
+        assert!(actual.is_err());
+
+        // Setting the current boot file is a no-op.
+        let actual = disk.sgi_set_boot_file_to("/unix");
+        assert!(actual.is_ok());
+
+        disk.sgi_set_boot_file_to("/unix.save")?;
+        disk.partition_table_write_to_disk()?;
+        drop(disk);
+
+        let disk = Fdisk::builder().assign_device(tmp_image.path()).build()?;
+
+        let actual = disk.sgi_boot_file();
+        let expected = Some("/unix.save".to_owned());
+        assert_eq!(actual, expected);
+
+        let actual = disk.sgi_interleave();
+        let expected = Some(1);
+        assert_eq!(actual, expected);
+
+        let actual = disk.sgi_physical_cylinder_count();
+        let expected = Some(0);
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_edit_sgi_volume_directory() -> crate::Result<()> {
+        use crate::core::partition_table::SgiVolumeEntry;
+        use crate::fdisk::FdiskSGIExt;
+
+        let tmp_image = disk_image_with_pt("sgi");
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
+
+        let initial = disk.sgi_volume_directory()?;
+
+        let sash = SgiVolumeEntry::new("sash", 64, 4096);
+        disk.sgi_add_volume_entry(sash.clone())?;
+
+        let actual = disk.sgi_add_volume_entry(SgiVolumeEntry::new("sash", 128, 512));
+        assert!(actual.is_err());
+
+        let actual = disk.sgi_add_volume_entry(SgiVolumeEntry::new("ide", 70, 512));
+        assert!(actual.is_err());
+
+        let actual = disk.sgi_add_volume_entry(SgiVolumeEntry::new("long_name", 128, 512));
+        assert!(actual.is_err());
+
+        let actual = disk.sgi_add_volume_entry(SgiVolumeEntry::new("ide", 0, 512));
+        assert!(actual.is_err());
+
+        drop(disk);
+
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
+
+        let actual = disk.sgi_volume_directory()?;
+        let mut expected = initial.clone();
+        expected.push(sash);
+        assert_eq!(actual, expected);
+
+        disk.sgi_remove_volume_entry("sash")?;
+
+        let actual = disk.sgi_remove_volume_entry("sash");
+        assert!(actual.is_err());
+
+        let actual = disk.sgi_volume_directory()?;
+        let expected = initial;
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_does_not_write_a_sgi_volume_header_if_its_volume_directory_patch_fails(
+    ) -> crate::Result<()> {
+        use crate::fdisk::FdiskSGIExt;
+
+        let tmp_image = disk_image_with_pt("sgi");
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
+
+        let partitions = disk.list_partitions().unwrap().len();
+        let number = disk.list_partitions().unwrap()[0].number().unwrap();
+        disk.partition_delete(number)?;
+
+        let actual = disk.sgi_remove_volume_entry("missing");
+        assert!(actual.is_err());
+
+        drop(disk);
+
+        let disk = Fdisk::builder().assign_device(tmp_image.path()).build()?;
+
+        let actual = disk.list_partitions().unwrap().len();
+        let expected = partitions;
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_convert_a_dos_partition_table_to_gpt() -> crate::Result<()> {
+        use crate::core::partition::Code;
+        use crate::core::partition::DOSFlag;
+        use crate::core::partition::GptAttributes;
+        use crate::core::partition::Guid;
+        use crate::fdisk::FdiskGPTExt;
+
+        let tmp_image = blank_image_file();
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
+
+        disk.partition_table_create(PartitionTableKind::DOS)?;
+
+        // (partition number, type, starting sector, size in sectors)
+        let layout = [
+            (0, dos_kind(Code::W95FAT32LBA), 2048, 4096),
+            (1, dos_kind(Code::ExtendedPartition), 6144, 12288),
+            (4, dos_kind(Code::LinuxSwap), 8192, 4096),
+            (5, dos_kind(Code::Linux), 14336, 4096),
+        ];
+        add_partitions(&mut disk, layout)?;
+
+        disk.partition_table_toggle_partition_flag(0, BitFlag::DOS(DOSFlag::Boot))?;
+
+        let actual = disk.partition_table_check_gpt_conversion()?;
+        let expected = vec![];
+        assert_eq!(actual, expected);
+
+        let actual = disk.partition_table_convert_to_gpt()?;
+        let expected = vec![0, 4, 5];
+        assert_eq!(actual, expected);
+
+        disk.partition_table_write_to_disk()?;
+        drop(disk);
+
+        let disk = Fdisk::builder().assign_device(tmp_image.path()).build()?;
+
+        let actual = disk.partition_table_is_of_type(PartitionTableKind::GPT);
+        let expected = true;
+        assert_eq!(actual, expected);
+
+        let partitions = disk.list_partitions().unwrap();
+        let actual: Vec<_> = partitions
+            .iter()
+            .map(|p| {
+                (
+                    p.number(),
+                    p.starting_sector(),
+                    p.size_in_sectors(),
+                    p.partition_type().and_then(|t| t.guid().map(String::from)),
+                )
+            })
+            .collect();
+        let expected: Vec<_> = [
+            (0, 2048, 4096, Guid::WindowsBasicData),
+            (4, 8192, 4096, Guid::LinuxSwap),
+            (5, 14336, 4096, Guid::LinuxData),
+        ]
+        .into_iter()
+        .map(|(number, start, size, guid)| {
+            (
+                Some(number),
+                Some(start),
+                Some(size),
+                Some(guid.as_str().to_uppercase()),
+            )
+        })
+        .collect();
+        assert_eq!(actual, expected);
+
+        let actual = disk.gpt_attributes(0);
+        let expected = Some(GptAttributes::LEGACY_BIOS_BOOTABLE);
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_not_convert_a_dos_partition_table_without_room_for_the_backup_gpt(
+    ) -> crate::Result<()> {
+        let tmp_image = blank_image_file();
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
+
+        disk.partition_table_create(PartitionTableKind::DOS)?;
+
+        let last_lba = disk.device_last_lba();
+        let partition = Partition::builder()
+            .starting_sector(2048)
+            .size_in_sectors(last_lba - 2048 + 1)
+            .build()?;
+        disk.partition_add(partition)?;
+
+        let actual = disk.partition_table_check_gpt_conversion()?;
+        let expected = vec![ConversionBlocker::NoRoomForBackupGpt {
+            partition_number: 0,
+            ending_sector: last_lba,
+            last_usable_lba: last_lba - 33,
+        }];
+        assert_eq!(actual, expected);
+
+        let actual = disk.partition_table_convert_to_gpt();
+        assert!(actual.is_err());
+
+        let actual = disk.partition_table_is_of_type(PartitionTableKind::DOS);
+        let expected = true;
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_convert_a_gpt_partition_table_to_dos() -> crate::Result<()> {
+        use crate::core::partition::Code;
+        use crate::core::partition::GptAttributes;
+        use crate::core::partition::Guid;
+        use crate::fdisk::FdiskGPTExt;
+        use std::io::{Read, Seek, SeekFrom};
+
+        let tmp_image = blank_image_file();
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
+
+        disk.partition_table_create(PartitionTableKind::GPT)?;
+
+        // (partition number, type, starting sector, size in sectors)
+        let layout = [
+            (0, gpt_kind(Guid::EfiSystem), 2048, 2048),
+            (1, gpt_kind(Guid::LinuxSwap), 4096, 2048),
+            (2, gpt_kind(Guid::LinuxData), 6144, 2048),
+            (3, gpt_kind(Guid::WindowsBasicData), 8194, 2046),
+            (4, gpt_kind(Guid::LinuxHome), 10241, 2047),
+        ];
+        add_partitions(&mut disk, layout)?;
+
+        disk.gpt_set_attributes(0, GptAttributes::LEGACY_BIOS_BOOTABLE)?;
+
+        let actual = disk.partition_table_check_dos_conversion()?;
+        let expected = vec![];
+        assert_eq!(actual, expected);
+
+        let first_lba = disk.device_first_lba();
+        let grain = disk.device_grain_size();
+
+        let actual = disk.partition_table_convert_to_dos()?;
+        let expected = vec![(0, 0), (1, 1), (2, 2), (3, 4), (4, 5)];
+        assert_eq!(actual, expected);
+
+        // The conversion restores the alignment settings it changes.
+        let actual = (disk.device_first_lba(), disk.device_grain_size());
+        let expected = (first_lba, grain);
+        assert_eq!(actual, expected);
+
+        disk.partition_table_write_to_disk()?;
+        drop(disk);
+
+        let disk = Fdisk::builder().assign_device(tmp_image.path()).build()?;
+
+        let actual = disk.partition_table_is_of_type(PartitionTableKind::DOS);
+        let expected = true;
+        assert_eq!(actual, expected);
+
+        let partitions = disk.list_partitions().unwrap();
+        let actual: Vec<_> = partitions
+            .iter()
+            .map(|p| {
+                (
+                    p.number(),
+                    p.starting_sector(),
+                    p.size_in_sectors(),
+                    p.partition_type().and_then(|t| t.code()),
+                    p.is_bootable(),
+                )
+            })
+            .collect();
+        let expected: Vec<_> = [
+            (0, 2048, 2048, Code::EfiSystem, true),
+            (1, 4096, 2048, Code::LinuxSwap, false),
+            (2, 6144, 2048, Code::Linux, false),
+            (3, 8193, 4095, Code::ExtendedPartition, false),
+            (4, 8194, 2046, Code::HPFSNTFSExfat, false),
+            (5, 10241, 2047, Code::Linux, false),
+        ]
+        .into_iter()
+        .map(|(number, start, size, code, bootable)| {
+            (
+                Some(number),
+                Some(start),
+                Some(size),
+                Some(code.to_u32()),
+                bootable,
+            )
+        })
+        .collect();
+        assert_eq!(actual, expected);
+
+        // The primary GPT header was erased.
+        let mut file = File::open(tmp_image.path()).expect("failed to open disk image");
+        let mut signature = [0u8; 8];
+        file.seek(SeekFrom::Start(512))
+            .expect("failed to seek GPT header");
+        file.read_exact(&mut signature)
+            .expect("failed to read GPT header signature");
+
+        let actual = &signature;
+        let expected = &[0u8; 8];
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_not_convert_a_gpt_partition_table_without_room_for_extended_boot_records(
+    ) -> crate::Result<()> {
+        let tmp_image = blank_image_file();
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
+
+        disk.partition_table_create(PartitionTableKind::GPT)?;
+
+        for number in 0..5 {
+            let partition = Partition::builder()
+                .number(number)
+                .starting_sector(2048 + number as u64 * 2048)
+                .size_in_sectors(2048)
+                .build()?;
+            disk.partition_add(partition)?;
+        }
+
+        let actual = disk.partition_table_check_dos_conversion()?;
+        let expected = vec![
+            ConversionBlocker::NoRoomForEbr {
+                partition_number: 3,
+                starting_sector: 8192,
+            },
+            ConversionBlocker::NoRoomForEbr {
+                partition_number: 4,
+                starting_sector: 10240,
+            },
+        ];
+        assert_eq!(actual, expected);
+
+        let actual = disk.partition_table_convert_to_dos();
+        assert!(actual.is_err());
+
+        let actual = disk.partition_table_is_of_type(PartitionTableKind::GPT);
+        let expected = true;
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_write_a_hybrid_mbr() -> crate::Result<()> {
+        use crate::core::partition::Code;
+        use crate::core::partition::Guid;
+        use crate::core::partition_table::HybridMbr;
+        use crate::core::partition_table::HybridMbrEntry;
+        use crate::core::partition_table::ProtectiveEntryPosition;
+        use crate::fdisk::FdiskGPTExt;
+
+        let tmp_image = blank_image_file();
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
+
+        disk.partition_table_create(PartitionTableKind::GPT)?;
+
+        // (partition number, type, starting sector, size in sectors)
+        let layout = [
+            (0, gpt_kind(Guid::EfiSystem), 2048, 2048),
+            (1, gpt_kind(Guid::LinuxSwap), 4096, 2048),
+            (2, gpt_kind(Guid::LinuxData), 6144, 4096),
+        ];
+        add_partitions(&mut disk, layout)?;
+
+        disk.partition_table_write_to_disk()?;
+
+        let hybrid = HybridMbr::new()
+            .entry(HybridMbrEntry::new(2).code(Code::W95FAT32LBA).bootable())
+            .entry(HybridMbrEntry::new(0))
+            .protective_entry_position(ProtectiveEntryPosition::Last);
+
+        disk.gpt_write_hybrid_mbr(&hybrid)?;
+        drop(disk);
+
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
+
+        let actual = disk.gpt_is_hybrid();
+        let expected = true;
+        assert_eq!(actual, expected);
+
+        let mbr = disk.create_nested_partitioner_with_name("dos")?;
+        let partitions = mbr.list_partitions().unwrap();
+        let actual: Vec<_> = partitions
+            .iter()
+            .map(|p| {
+                (
+                    p.number(),
+                    p.starting_sector(),
+                    p.size_in_sectors(),
+                    p.partition_type().and_then(|t| t.code()),
+                    p.is_bootable(),
+                )
+            })
+            .collect();
+        let expected: Vec<_> = [
+            (0, 6144, 4096, Code::W95FAT32LBA, true),
+            (1, 2048, 2048, Code::EfiSystem, false),
+            (2, 1, 2047, Code::GPTProtectiveMBR, false),
+        ]
+        .into_iter()
+        .map(|(number, start, size, code, bootable)| {
+            (
+                Some(number),
+                Some(start),
+                Some(size),
+                Some(code.to_u32()),
+                bootable,
+            )
+        })
+        .collect();
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_not_write_a_hybrid_mbr_mirroring_more_than_three_partitions() -> crate::Result<()>
+    {
+        use crate::core::partition_table::HybridMbr;
+        use crate::core::partition_table::HybridMbrEntry;
+        use crate::fdisk::FdiskGPTExt;
+
+        let tmp_image = disk_image_with_pt("gpt");
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
+
+        let hybrid = (0..4).fold(HybridMbr::new(), |hybrid, number| {
+            hybrid.entry(HybridMbrEntry::new(number))
+        });
+
+        let actual = disk.gpt_write_hybrid_mbr(&hybrid);
+        assert!(actual.is_err());
+
+        let actual = disk.gpt_is_hybrid();
+        let expected = false;
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_relocate_a_misplaced_gpt_backup_header() -> crate::Result<()> {
+        use crate::core::partition_table::GptRepair;
+        use crate::fdisk::FdiskGPTExt;
+
+        let tmp_image = blank_image_file();
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
+
+        disk.partition_table_create(PartitionTableKind::GPT)?;
+        disk.partition_table_write_to_disk()?;
+        drop(disk);
+
+        // Simulate copying the disk image to a device twice its size.
+        tmp_image
+            .as_file()
+            .set_len(20 * 1024 * 1024)
+            .expect("failed to resize disk image");
+
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
+
+        let actual = disk.gpt_repair()?;
+        let expected = vec![GptRepair::BackupHeaderRelocated {
+            from: 20479,
+            to: 40959,
+        }];
+        assert_eq!(actual, expected);
+
+        let actual = disk.gpt_repair()?;
+        let expected = vec![];
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_rebuild_a_corrupt_gpt_primary_header() -> crate::Result<()> {
+        use crate::core::partition_table::GptRepair;
+        use crate::fdisk::FdiskGPTExt;
+        use std::io::{Read, Seek, SeekFrom};
+
+        let tmp_image = blank_image_file();
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
+
+        disk.partition_table_create(PartitionTableKind::GPT)?;
+        disk.partition_table_write_to_disk()?;
+        drop(disk);
+
+        // Erase the primary GPT header.
+        let mut image = tmp_image.as_file();
+        image
+            .seek(SeekFrom::Start(512))
+            .and_then(|_| image.write_all(&[0u8; 512]))
+            .expect("failed to erase primary GPT header");
+
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
+
+        let actual = disk.gpt_repair()?;
+        let expected = vec![GptRepair::PrimaryHeaderRebuilt];
+        assert_eq!(actual, expected);
+
+        let mut signature = [0u8; 8];
+        image
+            .seek(SeekFrom::Start(512))
+            .and_then(|_| image.read_exact(&mut signature))
+            .expect("failed to read primary GPT header");
+
+        let actual = &signature;
+        let expected = b"EFI PART";
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_grow_the_last_partition_to_fill_the_device() -> crate::Result<()> {
+        use crate::core::partition::Guid;
+
+        let tmp_image = blank_image_file();
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
+
+        disk.partition_table_create(PartitionTableKind::GPT)?;
+
+        let layout = [
+            (0, gpt_kind(Guid::LinuxData), 2048, 2048),
+            (1, gpt_kind(Guid::LinuxData), 4096, 4096),
+        ];
+        add_partitions(&mut disk, layout)?;
+
+        disk.partition_table_write_to_disk()?;
+        drop(disk);
+
+        // Simulate resizing the disk image to twice its size.
+        tmp_image
+            .as_file()
+            .set_len(20 * 1024 * 1024)
+            .expect("failed to resize disk image");
+
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
+
+        // No room to grow between two partitions.
+        let actual = disk.partition_check_grow(0)?.new_size();
+        let expected = 2048;
+        assert_eq!(actual, expected);
+
+        let actual = disk.partition_check_grow(2);
+        assert!(actual.is_err());
+
+        // Last usable sector: 40926, aligned down to a 1 MiB boundary.
+        let actual = disk.partition_check_grow(1)?;
+        let expected = PartitionGrowth::new(4096, 34816, None);
+        assert_eq!(actual, expected);
+
+        let actual = disk.partition_grow(1)?;
+        let expected = 34816;
+        assert_eq!(actual, expected);
+
+        disk.partition_table_write_to_disk()?;
+        drop(disk);
+
+        let disk = Fdisk::builder().assign_device(tmp_image.path()).build()?;
+
+        let actual = disk
+            .partition_by_number(1)
+            .and_then(|p| p.size_in_sectors());
+        let expected = Some(34816);
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_grow_the_last_partition_past_a_misplaced_backup_gpt_header() -> crate::Result<()> {
+        use crate::core::partition::Guid;
+        use crate::core::partition_table::PartitionTableGPTExt;
+
+        let tmp_image = blank_image_file();
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
+
+        disk.partition_table_create(PartitionTableKind::GPT)?;
+        add_partitions(&mut disk, [(0, gpt_kind(Guid::LinuxData), 2048, 4096)])?;
+        disk.partition_table_write_to_disk()?;
+        drop(disk);
+
+        // Simulate copying the disk image to a device twice its size, leaving the backup GPT
+        // header in the middle of the device.
+        tmp_image
+            .as_file()
+            .set_len(20 * 1024 * 1024)
+            .expect("failed to resize disk image");
+
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
+
+        // Keep the backup GPT header where it is on disk.
+        if let Some(table) = disk.partition_table_current_mut() {
+            table.gpt_disable_backup_header_relocation();
+        }
+        disk.discard_changes()?;
+
+        let actual = disk.partition_check_grow(0)?;
+        let expected = PartitionGrowth::new(
+            4096,
+            36864,
+            Some(GptRepair::BackupHeaderRelocated {
+                from: 20479,
+                to: 40959,
+            }),
+        );
+        assert_eq!(actual, expected);
+
+        let actual = disk.partition_grow(0)?;
+        let expected = 36864;
+        assert_eq!(actual, expected);
+
+        disk.partition_table_write_to_disk()?;
+        drop(disk);
+
+        let disk = Fdisk::builder().assign_device(tmp_image.path()).build()?;
+
+        let actual = disk.gpt_backup_header_offset();
+        let expected = 0;
+        assert_eq!(actual, expected);
+
+        let actual = disk
+            .partition_by_number(0)
+            .and_then(|p| p.size_in_sectors());
+        let expected = Some(36864);
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_does_not_grow_a_dos_logical_partition_over_the_next_ebr() -> crate::Result<()> {
+        let tmp_image = blank_image_file();
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
+
+        disk.partition_table_create(PartitionTableKind::DOS)?;
+
+        let layout = [
+            (0, dos_kind(Code::ExtendedPartition), 2048, 16384),
+            (4, dos_kind(Code::Linux), 4096, 2048),
+            (5, dos_kind(Code::Linux), 10240, 2048),
+        ];
+        add_partitions(&mut disk, layout)?;
+
+        // The EBR of partition 5 sits at sector 8192, between the two logical partitions.
+        let actual = disk.partition_grow(4)?;
+        let expected = 2048;
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_resize_and_move_partitions() -> crate::Result<()> {
+        use crate::core::partition::Guid;
+        use crate::fdisk::LBAAlign;
+
+        let tmp_image = blank_image_file();
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
+
+        disk.partition_table_create(PartitionTableKind::GPT)?;
+
+        let layout = [
+            (0, gpt_kind(Guid::LinuxData), 2048, 2048),
+            (1, gpt_kind(Guid::LinuxData), 6144, 2048),
+        ];
+        add_partitions(&mut disk, layout)?;
+
+        let actual = disk.partition_resize(0, 3000, LBAAlign::Up)?;
+        let expected = 4096;
+        assert_eq!(actual, expected);
+
+        // Would overlap partition 1.
+        let actual = disk.partition_resize(0, 5000, LBAAlign::Up);
+        assert!(actual.is_err());
+
+        let actual = disk.partition_move(1, 9000, LBAAlign::Down)?;
+        let expected = 8192;
+        assert_eq!(actual, expected);
+
+        // Would overlap the GPT header, or the backup GPT.
+        let actual = disk.partition_move(0, 0, LBAAlign::Down);
+        assert!(actual.is_err());
+
+        let actual = disk.partition_move(1, 20000, LBAAlign::Nearest);
+        assert!(actual.is_err());
+
+        let partitions = disk.list_partitions().unwrap();
+        let actual: Vec<_> = partitions
+            .iter()
+            .map(|p| (p.number(), p.starting_sector(), p.size_in_sectors()))
+            .collect();
+        let expected = vec![
+            (Some(0), Some(2048), Some(4096)),
+            (Some(1), Some(8192), Some(2048)),
+        ];
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_place_a_dos_logical_partition_next_to_its_containers_ebr() -> crate::Result<()> {
+        let tmp_image = blank_image_file();
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
+
+        disk.partition_table_create(PartitionTableKind::DOS)?;
+
+        let layout = [
+            (0, dos_kind(Code::ExtendedPartition), 2048, 16384),
+            (4, dos_kind(Code::Linux), 4096, 2048),
+        ];
+        add_partitions(&mut disk, layout)?;
+
+        // The first sector of the extended partition holds the EBR of partition 4.
+        let actual = disk.check_partition_placement(4, 2048, 4095, "test");
+        assert!(actual.is_err());
+
+        let actual = disk.check_partition_placement(4, 2049, 4096, "test");
+        assert!(actual.is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_not_move_a_partition_onto_the_partition_table() -> crate::Result<()> {
+        use crate::core::partition::Guid;
+        use crate::fdisk::LBAAlign;
+
+        for (kind, partition_kind) in [
+            (PartitionTableKind::DOS, dos_kind(Code::Linux)),
+            (PartitionTableKind::GPT, gpt_kind(Guid::LinuxData)),
+        ] {
+            let tmp_image = blank_image_file();
+            let mut disk = Fdisk::builder()
+                .assign_device(tmp_image.path())
+                .enable_read_write()
+                .build()?;
+
+            disk.partition_table_create(kind)?;
+            add_partitions(&mut disk, [(0, partition_kind, 2048, 2048)])?;
+
+            // Lift the alignment constraint keeping partitions away from the partition table.
+            disk.device_set_first_lba(0)?;
+
+            // Sector 0 holds the MBR, or the protective MBR of a GPT.
+            let actual = disk.partition_move(0, 0, LBAAlign::Down);
+            assert!(actual.is_err());
+
+            // Sector 1 holds the primary GPT header.
+            let actual = disk.check_partition_placement(0, 1, 2048, "test");
+            let expected = kind == PartitionTableKind::DOS;
+            assert_eq!(actual.is_ok(), expected);
+
+            let actual = disk
+                .partition_by_number(0)
+                .and_then(|p| p.starting_sector());
+            let expected = Some(2048);
+            assert_eq!(actual, expected);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_backup_and_restore_a_partition_table() -> crate::Result<()> {
+        let tmp_image = disk_image_with_pt("gpt");
+        let backup_file = NamedTempFile::new().expect("failed to get new NamedTempFile");
+
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
+
+        let positions = |disk: &Fdisk| -> Vec<_> {
+            disk.list_partitions()
+                .map(|partitions| {
+                    partitions
+                        .iter()
+                        .map(|p| (p.number(), p.starting_sector(), p.size_in_sectors()))
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        let expected = positions(&disk);
+
+        let backup = disk.partition_table_backup(backup_file.path())?;
+
+        let actual: Vec<_> = backup
+            .sections()
+            .map(|(section, _)| section.name())
+            .collect();
+        let expected_sections = vec![
+            "PMBR",
+            "GPT Header",
+            "GPT Entries",
+            "GPT Backup Entries",
+            "GPT Backup Header",
+        ];
+        assert_eq!(actual, expected_sections);
+
+        disk.partition_delete_all()?;
+        disk.partition_table_write_to_disk()?;
+
+        let actual = positions(&disk);
+        assert_ne!(actual, expected);
+
+        disk.partition_table_restore(backup_file.path())?;
+
+        let actual = positions(&disk);
+        assert_eq!(actual, expected);
+
+        // Refuse to restore a backup on a device of a different size.
+        tmp_image
+            .as_file()
+            .set_len(20 * 1024 * 1024)
+            .expect("failed to resize disk image");
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
+
+        let actual = disk.partition_table_restore(backup_file.path());
+        assert!(matches!(actual, Err(FdiskError::Restore(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_restore_a_gpt_with_a_custom_partition_entry_array_size() -> crate::Result<()> {
+        use crate::core::partition::Guid;
+
+        let tmp_image = blank_image_file();
+        let backup_file = NamedTempFile::new().expect("failed to get new NamedTempFile");
+
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
+
+        disk.partition_table_create(PartitionTableKind::GPT)?;
+        disk.gpt_set_partition_entry_array_size(32)?;
+        add_partitions(&mut disk, [(0, gpt_kind(Guid::LinuxData), 2048, 2048)])?;
+        disk.partition_table_write_to_disk()?;
+
+        let backup = disk.partition_table_backup(backup_file.path())?;
+
+        let actual = backup
+            .sections()
+            .find(|(section, _)| section.name() == "GPT Entries")
+            .map(|(section, _)| section.size());
+        let expected = Some(32 * 128);
+        assert_eq!(actual, expected);
+
+        disk.partition_delete_all()?;
+        disk.partition_table_write_to_disk()?;
+
+        disk.partition_table_restore(backup_file.path())?;
+
+        let actual = disk
+            .partition_by_number(0)
+            .and_then(|p| p.starting_sector());
+        let expected = Some(2048);
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_not_restore_a_backup_with_misplaced_sections() -> crate::Result<()> {
+        let tmp_image = disk_image_with_pt("gpt");
+        let backup_file = NamedTempFile::new().expect("failed to get new NamedTempFile");
+
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
+
+        let backup = disk.partition_table_backup(backup_file.path())?;
+
+        let tampered = |rename: Option<&str>, offset: u64| {
+            let mut tampered = PartitionTableBackup::new(
+                backup.sector_size(),
+                backup.device_size_in_sectors(),
+                backup.partition_table_kind(),
+            );
+
+            for (nth, (section, content)) in backup.sections().enumerate() {
+                let section = match nth {
+                    1 => TableSection::new(
+                        rename.unwrap_or(section.name()).to_owned(),
+                        offset,
+                        content.len(),
+                    ),
+                    _ => TableSection::new(
+                        section.name().to_owned(),
+                        section.starting_offset(),
+                        section.size(),
+                    ),
+                };
+                tampered.push_section(section, content.to_vec());
+            }
+
+            std::fs::write(backup_file.path(), tampered.to_bytes())
+                .expect("failed to write tampered backup");
+        };
+
+        // Section past the end of the device.
+        tampered(None, 10 * 1024 * 1024);
+        let actual = disk.partition_table_restore(backup_file.path());
+        assert!(matches!(actual, Err(FdiskError::Restore(_))));
+
+        // Section not on a sector boundary.
+        tampered(None, 513);
+        let actual = disk.partition_table_restore(backup_file.path());
+        assert!(matches!(actual, Err(FdiskError::Restore(_))));
+
+        // Section overlapping the protective MBR.
+        tampered(None, 0);
+        let actual = disk.partition_table_restore(backup_file.path());
+        assert!(matches!(actual, Err(FdiskError::Restore(_))));
+
+        // GPT header not at the location it records.
+        tampered(None, 4096);
+        let actual = disk.partition_table_restore(backup_file.path());
+        assert!(matches!(actual, Err(FdiskError::Restore(_))));
+
+        // Partition Entry Array not at the location recorded by its header.
+        tampered(Some("GPT Entries"), 512);
+        let actual = disk.partition_table_restore(backup_file.path());
+        assert!(matches!(actual, Err(FdiskError::Restore(_))));
+
+        // Untampered sections.
+        tampered(None, 512);
+        disk.partition_table_restore(backup_file.path())?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_render_a_partition_table() -> crate::Result<()> {
+        use crate::core::partition_table::Field;
+        use crate::fdisk::TableFormat;
+        use crate::fdisk::TableRenderer;
+
+        let tmp_image = disk_image_with_pt("gpt");
+        let disk = Fdisk::builder().assign_device(tmp_image.path()).build()?;
+        let partitions = disk.list_partitions().unwrap();
+        let fields = vec![
+            Field::StartingLba,
+            Field::EndingLba,
+            Field::Size,
+            Field::Name,
+        ];
+
+        let actual = TableRenderer::new(&disk)
+            .fields(fields.clone())
+            .size_format(SizeFormat::Bytes)
+            .format(TableFormat::Csv)
+            .render(&partitions)?;
+        let expected = "Start,End,Size,Name\n\
+                        34,2047,1031168,ThisIsName\n\
+                        2048,4095,1048576,ThisIsOtherName\n\
+                        4096,6143,1048576,primary\n\
+                        6144,8191,1048576,primary\n\
+                        8192,10239,1048576,primary\n";
+        assert_eq!(actual, expected);
+
+        let actual = TableRenderer::new(&disk)
+            .fields(fields.clone())
+            .size_format(SizeFormat::HumanReadable)
+            .render(&partitions)?;
+        let expected = "Start   End  Size Name\n   \
+                           34  2047 1007K ThisIsName\n \
+                         2048  4095    1M ThisIsOtherName\n \
+                         4096  6143    1M primary\n \
+                         6144  8191    1M primary\n \
+                         8192 10239    1M primary\n";
+        assert_eq!(actual, expected);
+
+        // The partition size format of `disk` is left untouched.
+        let actual = disk.partition_size_format();
+        let expected = SizeFormat::Bytes;
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn fdisk_can_render_a_partition_table_as_json() -> crate::Result<()> {
+        use crate::core::partition_table::Field;
+        use crate::fdisk::TableFormat;
+        use crate::fdisk::TableRenderer;
+
+        let tmp_image = disk_image_with_pt("gpt");
+        let disk = Fdisk::builder().assign_device(tmp_image.path()).build()?;
+        let partitions = disk.list_partitions().unwrap();
+        let fields = vec![Field::StartingLba, Field::EndingLba];
+
+        let actual = TableRenderer::new(&disk)
+            .fields(fields)
+            .format(TableFormat::Json)
+            .render(&partitions)?;
+        let expected = r#"{
+   "partitions": [
+      {"start":34, "end":2047},
+      {"start":2048, "end":4095},
+      {"start":4096, "end":6143},
+      {"start":6144, "end":8191},
+      {"start":8192, "end":10239}
+   ]
+}
+"#;
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_report_device_characteristics() -> crate::Result<()> {
+        let tmp_image = disk_image_with_pt("gpt");
+        let disk = Fdisk::builder().assign_device(tmp_image.path()).build()?;
+
+        let report = disk.device_report();
+
+        let actual = report.name();
+        let expected = Some(tmp_image.path());
+        assert_eq!(actual, expected);
+
+        let actual = report.partition_table_kind();
+        let expected = Some(PartitionTableKind::GPT);
+        assert_eq!(actual, expected);
+
+        let actual = report.to_string();
+        let expected = format!(
+            "Disk {}: 10 MiB, 10485760 bytes, 20480 sectors\n\
+             Units: sectors of 1 * 512 = 512 bytes\n\
+             Sector size (logical/physical): 512 bytes / 512 bytes\n\
+             I/O size (minimum/optimal): 512 bytes / 512 bytes\n\
+             Disklabel type: gpt\n\
+             Disk identifier: DD27F98D-7519-4C9E-8041-F2BFA7B1EF61",
+            tmp_image.path().display()
+        );
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn device_report_can_be_serialized_to_json_and_back() -> crate::Result<()> {
+        let tmp_image = disk_image_with_pt("gpt");
+        let disk = Fdisk::builder().assign_device(tmp_image.path()).build()?;
+        let report = disk.device_report();
+
+        let json = serde_json::to_string(&report).unwrap();
+        let actual: DeviceReport = serde_json::from_str(&json).unwrap();
+        let expected = report;
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_apply_a_partition_layout() -> crate::Result<()> {
+        use crate::core::layout::LayoutPartition;
+        use crate::core::layout::LayoutSize;
+        use crate::core::partition::Guid;
+
+        let tmp_image = blank_image_file();
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
+
+        disk.partition_table_create(PartitionTableKind::GPT)?;
+
+        let esp = LayoutPartition::builder()
+            .partition_type(PartitionKind::builder().guid(Guid::EfiSystem).build()?)
+            .name("EFI System")
+            .size(LayoutSize::Bytes(2 << 20))
+            .build()?;
+        let swap = LayoutPartition::builder()
+            .partition_type(PartitionKind::builder().guid(Guid::LinuxSwap).build()?)
+            .size(LayoutSize::Percentage(20))
+            .build()?;
+        let root = LayoutPartition::builder()
+            .name("System")
+            .size(LayoutSize::Remaining)
+            .build()?;
+        let layout = Layout::new().partition(esp).partition(swap).partition(root);
+
+        let planned = layout.resolve(&disk)?;
+
+        let actual = disk.partitions_apply_layout(&layout)?;
+        let expected = vec![0, 1, 2];
+        assert_eq!(actual, expected);
+
+        let partitions = disk.list_partitions().unwrap();
+        let actual: Vec<_> = partitions
+            .iter()
+            .map(|p| (p.starting_sector(), p.size_in_sectors()))
+            .collect();
+        let expected: Vec<_> = planned
+            .iter()
+            .map(|p| (Some(p.starting_sector()), Some(p.size_in_sectors())))
+            .collect();
+        assert_eq!(actual, expected);
+
+        let actual = partitions[0].name();
+        let expected = Some("EFI System");
+        assert_eq!(actual, expected);
+
+        let actual = partitions[2].ending_sector();
+        let expected = Some(disk.device_last_lba());
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_leaves_the_partition_table_unchanged_if_a_layout_fails_to_apply() -> crate::Result<()>
+    {
+        use crate::core::layout::LayoutPartition;
+        use crate::core::layout::LayoutSize;
+
+        let tmp_image = blank_image_file();
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
+
+        disk.partition_table_create(PartitionTableKind::DOS)?;
+
+        // A DOS partition table holds at most four primary partitions.
+        let mut layout = Layout::new();
+        for _ in 0..5 {
+            let partition = LayoutPartition::builder()
+                .partition_type(PartitionKind::builder().code(Code::Linux).build()?)
+                .size(LayoutSize::Bytes(1 << 20))
+                .build()?;
+            layout = layout.partition(partition);
+        }
+
+        let actual = disk.partitions_apply_layout(&layout);
+        assert!(actual.is_err());
+
+        let actual = disk.list_partitions().map(|partitions| partitions.len());
+        let expected = Some(0);
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_not_apply_a_partition_layout_larger_than_the_device() -> crate::Result<()> {
+        use crate::core::layout::LayoutPartition;
+        use crate::core::layout::LayoutSize;
+
+        let tmp_image = blank_image_file();
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
+
+        disk.partition_table_create(PartitionTableKind::GPT)?;
+
+        let huge = LayoutPartition::builder()
+            .size(LayoutSize::Bytes(1 << 40))
+            .build()?;
+        let layout = Layout::new().partition(huge);
 
-        Ok(formats)
+        let actual = disk.partitions_apply_layout(&layout);
+        assert!(matches!(actual, Err(FdiskError::Layout(_))));
+
+        Ok(())
     }
 
-    /// Returns a reference to the [`Script`] currently associated with this `Fdisk`.
-    pub fn script(&self) -> Option<&Script> {
-        log::debug!("Fdisk::script getting currently linked `Script` instance");
+    #[test]
+    fn fdisk_reports_no_changes_for_an_unmodified_partition_table() -> crate::Result<()> {
+        let tmp_image = disk_image_with_pt("gpt");
+        let disk = Fdisk::builder().assign_device(tmp_image.path()).build()?;
 
-        let mut ptr = MaybeUninit::<*mut libfdisk::fdisk_script>::zeroed();
-        unsafe {
-            ptr.write(libfdisk::fdisk_get_script(self.inner));
-        }
+        let diff = disk.partition_table_diff()?;
 
-        match unsafe { ptr.assume_init() } {
-            ptr if ptr.is_null() => {
-                let err_msg = "got no currently linked script".to_owned();
-                log::debug!(
-                    "Fdisk::script {}. libfdisk::fdisk_get_script returned a NULL pointer",
-                    err_msg
-                );
+        let actual = diff.is_empty();
+        let expected = true;
+        assert_eq!(actual, expected);
 
-                None
-            }
-            ptr => {
-                log::debug!("Fdisk::script got currently linked `Script`");
-                let script = owning_ref_from_ptr!(self, Script, ptr);
+        let actual = diff.to_string();
+        let expected = "no changes";
+        assert_eq!(actual, expected);
 
-                Some(script)
-            }
-        }
+        Ok(())
     }
 
-    /// Returns a mutable reference to the [`Script`] currently associated with this `Fdisk`.
-    pub fn script_mut(&mut self) -> Option<&mut Script> {
-        log::debug!("Fdisk::script_mut getting currently linked `Script` instance");
+    #[test]
+    fn fdisk_can_list_changes_to_a_partition_table() -> crate::Result<()> {
+        use crate::core::partition_table::PartitionTableChange;
 
-        let mut ptr = MaybeUninit::<*mut libfdisk::fdisk_script>::zeroed();
-        unsafe {
-            ptr.write(libfdisk::fdisk_get_script(self.inner));
-        }
+        let tmp_image = disk_image_with_pt("gpt");
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
 
-        match unsafe { ptr.assume_init() } {
-            ptr if ptr.is_null() => {
-                let err_msg = "got no currently linked script".to_owned();
-                log::debug!(
-                    "Fdisk::script_mut {}. libfdisk::fdisk_get_script returned a NULL pointer",
-                    err_msg
-                );
+        let id = disk.partition_table_id();
+        disk.partition_table_set_string_id("01234567-89AB-CDEF-0123-456789ABCDEF")?;
+
+        let template = Partition::builder().name("Renamed").build()?;
+        disk.partition_override_settings(0, &template)?;
+
+        disk.partition_delete(1)?;
+
+        let template = Partition::builder().size_in_sectors(1024).build()?;
+        disk.partition_override_settings(2, &template)?;
+
+        let diff = disk.partition_table_diff()?;
+
+        let actual = diff.changes();
+        let expected = vec![
+            PartitionTableChange::IdChanged {
+                before: id,
+                after: Some("01234567-89AB-CDEF-0123-456789ABCDEF".to_owned()),
+            },
+            PartitionTableChange::PartitionRenamed {
+                partition_number: 0,
+                before: Some("ThisIsName".to_owned()),
+                after: Some("Renamed".to_owned()),
+            },
+            PartitionTableChange::PartitionDeleted {
+                partition_number: 1,
+                starting_sector: 2048,
+                size_in_sectors: 2048,
+                partition_type: Some("EBD0A0A2-B9E5-4433-87C0-68B6B72699C7".to_owned()),
+                name: Some("ThisIsOtherName".to_owned()),
+            },
+            PartitionTableChange::PartitionResized {
+                partition_number: 2,
+                before: 2048,
+                after: 1024,
+            },
+        ];
+        assert_eq!(actual, expected);
+
+        let actual = diff.changes()[2].to_string();
+        let expected = "- partition 1: sectors 2048-4095 (2048 sectors), type EBD0A0A2-B9E5-4433-87C0-68B6B72699C7, name \"ThisIsOtherName\"";
+        assert_eq!(actual, expected);
 
-                None
-            }
-            ptr => {
-                log::debug!("Fdisk::script_mut got currently linked `Script`");
-                let script = owning_mut_from_ptr!(self, Script, ptr);
+        Ok(())
+    }
 
-                Some(script)
-            }
-        }
+    #[test]
+    fn fdisk_can_not_save_a_device_without_a_partition_table() -> crate::Result<()> {
+        let tmp_image = blank_image_file();
+        let mut disk = Fdisk::builder().assign_device(tmp_image.path()).build()?;
+
+        let actual = disk.partition_table_savepoint();
+        assert!(actual.is_err());
+
+        Ok(())
     }
 
-    //---- END getters
+    #[test]
+    fn fdisk_can_roll_back_to_a_savepoint() -> crate::Result<()> {
+        let tmp_image = disk_image_with_pt("gpt");
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
 
-    //---- BEGIN iterators
+        let mut savepoint = disk.partition_table_savepoint()?;
 
-    /// Returns an iterator over [`PartitionTable`]s on the assigned device.
-    pub fn iter(&self) -> PartitionTableIter {
-        log::debug!("Fdisk::iter creating a new `PartitionTableIter`");
+        let actual = savepoint.partitions().len();
+        let expected = 5;
+        assert_eq!(actual, expected);
 
-        PartitionTableIter::new(self)
-    }
+        savepoint.partition_delete(1)?;
+        savepoint.partition_delete(2)?;
+        savepoint.rollback()?;
 
-    /// Returns a mutable iterator over [`PartitionTable`]s on the assigned device.
-    pub fn iter_mut(&'a mut self) -> PartitionTableIterMut {
-        log::debug!("Fdisk::iter creating a new `PartitionTableIterMut`");
+        let actual = savepoint.partition_table_diff()?.is_empty();
+        let expected = true;
+        assert_eq!(actual, expected);
 
-        PartitionTableIterMut::new(self)
+        // A savepoint can be reused.
+        savepoint.partition_delete_all()?;
+        savepoint.rollback()?;
+
+        let actual = savepoint.partition_table_diff()?.is_empty();
+        let expected = true;
+        assert_eq!(actual, expected);
+
+        Ok(())
     }
 
-    //---- END iterators
+    #[test]
+    fn fdisk_can_roll_back_a_failed_transaction() -> crate::Result<()> {
+        let tmp_image = disk_image_with_pt("gpt");
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
 
-    //---- BEGIN predicates
+        let actual = disk.partition_table_transaction(|disk| {
+            disk.partition_delete(0)?;
+            // No partition numbered 42.
+            disk.partition_delete(42)
+        });
+        assert!(actual.is_err());
 
-    /// Returns `true` when the given `partition_number` is being used.
-    ///
-    /// **Note:** the first partition has identification number `0`.
-    pub fn partition_is_number_in_use(&self, partition_number: usize) -> bool {
-        let state = unsafe { libfdisk::fdisk_is_partition_used(self.inner, partition_number) == 1 };
-        log::debug!("Fdisk::partition_is_number_in_use value: {:?}", state);
+        let actual = disk.partition_table_diff()?.is_empty();
+        let expected = true;
+        assert_eq!(actual, expected);
 
-        state
+        Ok(())
     }
 
-    /// Returns `true` when all metadata on the device area specified by the [`Partition`] will be
-    /// wiped when the partition table is written to disk.
-    pub fn is_partition_wipe_active(&self, partition: &Partition) -> bool {
-        let state = unsafe { libfdisk::fdisk_partition_has_wipe(self.inner, partition.inner) == 1 };
-        log::debug!("Fdisk::is_partition_wipe_active value: {:?}", state);
+    #[test]
+    fn fdisk_can_commit_a_transaction() -> crate::Result<()> {
+        let tmp_image = disk_image_with_pt("gpt");
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
 
-        state
-    }
+        disk.partition_table_transaction(|disk| -> crate::Result<()> {
+            disk.partition_delete(0)?;
+            disk.partition_delete(1)?;
 
-    /// Returns `true` if the user has overridden some device properties.
-    pub fn has_overriden_device_properties(&self) -> bool {
-        let state = unsafe { libfdisk::fdisk_has_user_device_properties(self.inner) == 1 };
-        log::debug!("Fdisk::has_overriden_device_properties value: {:?}", state);
+            Ok(())
+        })?;
 
-        state
+        let actual = disk.list_partitions().unwrap().len();
+        let expected = 3;
+        assert_eq!(actual, expected);
+
+        Ok(())
     }
 
-    /// Returns `true` the `LBA` is aligned to a physical sector boundary.
-    pub fn is_lba_physically_aligned(&self, lba: u64) -> bool {
-        let state = unsafe { libfdisk::fdisk_lba_is_phy_aligned(self.inner, lba) == 1 };
-        log::debug!("Fdisk::is_lba_physically_aligned value: {:?}", state);
+    #[cfg(feature = "serde")]
+    #[test]
+    fn fdisk_can_import_a_json_script_exported_by_libfdisk() -> crate::Result<()> {
+        let tmp_image = disk_image_with_pt("gpt");
+        let disk = Fdisk::builder().assign_device(tmp_image.path()).build()?;
 
-        state
-    }
+        let json_file = NamedTempFile::new().unwrap();
+        let script = disk.script_new()?;
+        script.compose_script()?;
+        script.enable_json_output()?;
+        script.export_to_file(json_file.path())?;
 
-    /// Returns `true` when this `Fdisk` is set to display each partition's detailed metadata when
-    /// printing on the console.
-    pub fn displays_partition_details(&self) -> bool {
-        let state = unsafe { libfdisk::fdisk_is_details(self.inner) == 1 };
-        log::debug!("Fdisk::displays_partition_details value:{:?}", state);
+        let mut json = String::new();
+        json_file.as_file().read_to_string(&mut json).unwrap();
 
-        state
-    }
+        let copy = disk.script_new()?;
+        copy.import_json_file(json_file.path())?;
 
-    /// Returns `true` when this `Fdisk` is set to only display a partitions list **without**
-    /// detailed metadata when printing on the console.
-    pub fn displays_partition_list_only(&self) -> bool {
-        let state = unsafe { libfdisk::fdisk_is_listonly(self.inner) == 1 };
-        log::debug!("Fdisk::displays_partition_list_only value: {:?}", state);
+        let actual = copy.header_value("label");
+        let expected = Some("gpt");
+        assert_eq!(actual, expected);
 
-        state
-    }
+        let actual = copy.header_value("label-id");
+        let expected = script.header_value("label-id");
+        assert_eq!(actual, expected);
 
-    /// Returns `true` when this `Fdisk` is set to display partition metadata in cylinder
-    /// units.
-    pub fn displays_metadata_in_cylinders(&self) -> bool {
-        let state = unsafe { libfdisk::fdisk_use_cylinders(self.inner) == 1 };
-        log::debug!("Fdisk::displays_metadata_in_cylinders value: {:?}", state);
+        let actual = copy.partition_table_entries().map(|entries| entries.len());
+        let expected = Some(5);
+        assert_eq!(actual, expected);
 
-        state
-    }
+        // Exporting the imported script yields the same JSON document.
+        let copy_file = NamedTempFile::new().unwrap();
+        copy.enable_json_output()?;
+        copy.export_to_file(copy_file.path())?;
 
-    /// Returns `true` when device partitioning is dialog-driven.
-    pub fn is_partitioning_interactive(&self) -> bool {
-        let state = unsafe { libfdisk::fdisk_has_dialogs(self.inner) == 1 };
-        log::debug!("Fdisk::is_partitioning_interactive value: {:?}", state);
+        let mut actual = String::new();
+        copy_file.as_file().read_to_string(&mut actual).unwrap();
+        let expected = json;
+        assert_eq!(actual, expected);
 
-        state
+        Ok(())
     }
 
-    /// Returns `true` if this `Fdisk` is set to protect the master boot record of its assigned
-    /// device when creating a new partition table.
-    pub fn protects_master_boot_record(&self) -> bool {
-        let state = unsafe { libfdisk::fdisk_has_protected_bootbits(self.inner) == 1 };
-        log::debug!("Fdisk::protects_master_boot_record value: {:?}", state);
+    #[cfg(feature = "serde")]
+    #[test]
+    fn fdisk_can_import_a_json_script() -> crate::Result<()> {
+        let tmp_image = blank_image_file();
+        let disk = Fdisk::builder().assign_device(tmp_image.path()).build()?;
 
-        state
-    }
+        let script = disk.script_new()?;
+        script.import_json(
+            r#"{
+               "partitiontable": {
+                  "label": "dos",
+                  "id": "0x12345678",
+                  "unit": "sectors",
+                  "partitions": [
+                     {"node": "/dev/vda1", "start": 2048, "size": 4096, "type": "83", "bootable": true},
+                     {"node": "/dev/vda2", "start": 6144, "size": 2048, "type": "82"}
+                  ]
+               }
+            }"#,
+        )?;
 
-    /// Returns `true` when this `Fdisk` erases all device metadata before writing a new
-    /// partition table.
-    pub fn wipes_device_metadata(&self) -> bool {
-        let state = unsafe { libfdisk::fdisk_has_wipe(self.inner) == 1 };
-        log::debug!("Fdisk::wipes_device_metadata value: {:?}", state);
+        let actual = script.header_value("label-id");
+        let expected = Some("0x12345678");
+        assert_eq!(actual, expected);
 
-        state
-    }
+        let entries = script.partition_table_entries().unwrap();
 
-    /// Returns `true` if `libblkid` detects an already existing file system or partition table on
-    /// the assigned device.
-    ///
-    /// **Note:** `libblkid` does not support all types of partition table, which can lead to some
-    /// inconsistencies where [`Fdisk::device_has_partition_table`] returns `false` while this
-    /// method returns `true`.
-    pub fn device_has_collisions(&self) -> bool {
-        let state = unsafe { libfdisk::fdisk_is_ptcollision(self.inner) == 1 };
-        log::debug!("Fdisk::device_has_collisions value: {:?}", state);
+        let actual = entries.len();
+        let expected = 2;
+        assert_eq!(actual, expected);
 
-        state
-    }
+        let actual = entries[0].is_bootable();
+        let expected = true;
+        assert_eq!(actual, expected);
 
-    /// Returns `true` when there is a partition table on the assigned device.
-    pub fn device_has_partition_table(&self) -> bool {
-        let state = unsafe { libfdisk::fdisk_has_label(self.inner) == 1 };
-        log::debug!("Fdisk::device_has_partition_table value: {:?}", state);
+        let actual = entries[1].number();
+        let expected = Some(1);
+        assert_eq!(actual, expected);
 
-        state
-    }
+        let actual = entries[1].size_in_sectors();
+        let expected = Some(2048);
+        assert_eq!(actual, expected);
 
-    /// Returns `true` when the assigned device is currently in use by the Operating System.
-    ///
-    /// **Warning:** always returns `false` if the device was assigned by file, or it is opened in read-only mode.
-    pub fn device_is_in_use(&self) -> bool {
-        let state = unsafe { libfdisk::fdisk_device_is_used(self.inner) == 1 };
-        log::debug!("Fdisk::device_is_in_use value: {:?}", state);
+        let actual = entries[1].partition_type().and_then(|kind| kind.code());
+        let expected = Some(0x82);
+        assert_eq!(actual, expected);
 
-        state
+        Ok(())
     }
 
-    /// Returns `true` when the assigned device is open in read-only mode.
-    pub fn device_is_read_only(&self) -> bool {
-        let state = unsafe { libfdisk::fdisk_is_readonly(self.inner) == 1 };
-        log::debug!("Fdisk::device_is_read_only value: {:?}", state);
+    #[test]
+    fn fdisk_can_convert_a_script_to_and_from_a_script_document() -> crate::Result<()> {
+        let tmp_image = disk_image_with_pt("gpt");
+        let disk = Fdisk::builder().assign_device(tmp_image.path()).build()?;
 
-        state
-    }
+        let script = disk.script_new()?;
+        script.compose_script()?;
 
-    /// Returns `true` when the assigned device is an image file rather than a physical block device.
-    pub fn device_is_image_file(&self) -> bool {
-        let state = unsafe { libfdisk::fdisk_is_regfile(self.inner) == 1 };
-        log::debug!("Fdisk::device_is_image_file value: {:?}", state);
+        let document = ScriptDocument::try_from(&*script)?;
 
-        state
+        let actual = document.label;
+        let expected = Some(PartitionTableKind::GPT);
+        assert_eq!(actual, expected);
+
+        let actual = document.entries.len();
+        let expected = 5;
+        assert_eq!(actual, expected);
+
+        let copy = disk.script_new()?;
+        copy.import_document(&document)?;
+
+        let actual = ScriptDocument::try_from(&*copy)?;
+        let expected = document;
+        assert_eq!(actual, expected);
+
+        Ok(())
     }
 
-    //---- END predicates
-}
+    #[test]
+    fn fdisk_can_not_import_an_invalid_script_document() -> crate::Result<()> {
+        let tmp_image = disk_image_with_pt("gpt");
+        let disk = Fdisk::builder().assign_device(tmp_image.path()).build()?;
 
-impl<'a> AsRef<Fdisk<'a>> for Fdisk<'a> {
-    #[inline]
-    fn as_ref(&self) -> &Fdisk<'a> {
-        self
+        let script = disk.script_new()?;
+        script.compose_script()?;
+
+        let mut document = ScriptDocument::try_from(&*script)?;
+        document.entries[1].starting_sector = document.entries[0].starting_sector;
+
+        let copy = disk.script_new()?;
+        let actual = copy.import_document(&document);
+        assert!(actual.is_err());
+
+        Ok(())
     }
-}
 
-impl<'a> Drop for Fdisk<'a> {
-    fn drop(&mut self) {
-        log::debug!("Fdisk::drop deallocating `Fdisk` instance");
+    #[test]
+    fn fdisk_can_set_gpt_partition_attributes() -> crate::Result<()> {
+        use crate::core::partition::GptAttributes;
+        use crate::fdisk::FdiskGPTExt;
 
-        unsafe { libfdisk::fdisk_unref_context(self.inner) }
+        let tmp_image = disk_image_with_pt("gpt");
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .build()?;
 
-        // Release heap allocated PartitionTable references.
-        while let Some(gc_item) = self.gc.pop() {
-            gc_item.destroy();
-        }
-    }
-}
+        let mut attributes = GptAttributes::LEGACY_BIOS_BOOTABLE;
+        attributes.set_chromeos_priority(3)?;
+        attributes.set_microsoft_read_only(true);
 
-#[cfg(test)]
-#[allow(unused_imports)]
-mod tests {
-    use pretty_assertions::{assert_eq, assert_ne};
-    use tempfile::NamedTempFile;
-    use terminal_size::{terminal_size, Height, Width};
+        disk.gpt_set_attributes(1, attributes)?;
 
-    use std::io::Read;
-    use std::io::Write;
-    use std::path::Path;
-    use std::path::PathBuf;
+        let actual = disk.gpt_attributes(1);
+        let expected = Some(attributes);
+        assert_eq!(actual, expected);
 
-    use super::*;
-    use crate::core::partition::Guid;
-    use crate::core::partition::Partition;
-    use crate::core::partition::PartitionKind;
-    use crate::core::partition::PartitionList;
-    use crate::core::partition_table::HeaderEntry;
-    use crate::core::partition_table::MaxColWidth;
-    use crate::core::partition_table::PartitionTableKind;
-    use crate::fdisk::DeviceAddressing;
-    use crate::fdisk::SizeFormat;
+        let actual = disk
+            .gpt_attributes(1)
+            .map(|attrs| attrs.chromeos_priority());
+        let expected = Some(3);
+        assert_eq!(actual, expected);
 
-    //---- Helper functions
+        let actual = disk.gpt_set_attributes(1, GptAttributes::from_bits_retain(1 << 47));
+        assert!(actual.is_err());
 
-    static BASE_DIR_TEST_IMG_FILES: &str = concat!(
-        env!("CARGO_MANIFEST_DIR"),
-        "/third-party/vendor/util-linux/blkid/images"
-    );
+        Ok(())
+    }
 
-    static PATH_BLANK_IMG_FILE: &str = concat!(
-        env!("CARGO_MANIFEST_DIR"),
-        "/tests/images/blank-10MB.img.xz"
-    );
+    #[cfg(feature = "serde")]
+    #[test]
+    fn fdisk_can_not_import_an_invalid_json_script() -> crate::Result<()> {
+        let tmp_image = disk_image_with_pt("gpt");
+        let disk = Fdisk::builder().assign_device(tmp_image.path()).build()?;
 
-    fn decode_into<P, W>(xz_file_path: P, writer: &mut W) -> std::io::Result<u64>
-    where
-        P: AsRef<Path>,
-        W: Write + ?Sized,
-    {
-        let xz_file_path = xz_file_path.as_ref();
+        let script = disk.script_new()?;
 
-        // Copy decompressed image to temporary file
-        let compressed_image_file = std::fs::File::open(xz_file_path)?;
-        let mut decompressed = xz2::read::XzDecoder::new(compressed_image_file);
+        let actual = script.import_json(r#"{"partitiontable": {"label": "gpt""#);
+        assert!(actual.is_err());
 
-        std::io::copy(&mut decompressed, writer)
-    }
+        let actual = script.import_json(r#"{"label": "gpt"}"#);
+        assert!(actual.is_err());
 
-    /// Creates a named temporary image file with one of the supported file systems from the
-    /// compressed samples.
-    fn disk_image_with_fs(fs_type: &str) -> NamedTempFile {
-        let img_path = format!("{BASE_DIR_TEST_IMG_FILES}/filesystems/{fs_type}.img.xz");
-        let mut named_file = NamedTempFile::new().expect("failed to get new NamedTempFile");
+        let actual = script.import_json(
+            r#"{"partitiontable": {"label": "gpt", "partitions": [{"start": "2048, size=1"}]}}"#,
+        );
+        assert!(actual.is_err());
 
-        decode_into(img_path, named_file.as_file_mut()).expect("failed to create named disk image");
-        named_file
+        Ok(())
     }
 
-    /// Creates a temporary image file with one of the supported partition tables from the
-    /// compressed samples.
-    fn disk_image_with_pt(pt_type: &str) -> NamedTempFile {
-        let img_path = format!("{BASE_DIR_TEST_IMG_FILES}/partition_tables/{pt_type}.img.xz");
-        let mut named_file = NamedTempFile::new().expect("failed to get new NamedTempFile");
+    #[test]
+    fn fdisk_answer_queue_rejects_unexpected_prompts() -> crate::Result<()> {
+        use crate::core::prompt::Answer;
+        use crate::core::prompt::AnswerQueue;
+        use crate::core::prompt::PromptKind;
+        use crate::core::prompt::QueryMatcher;
 
-        decode_into(img_path, named_file.as_file_mut()).expect("failed to create named disk image");
-        named_file
-    }
+        let answers =
+            AnswerQueue::new().expect(PromptKind::YesNo, QueryMatcher::Any, Answer::YesNo(true));
 
-    // Create a temporary 50MB blank image file.
-    fn blank_image_file() -> NamedTempFile {
-        let mut named_file = NamedTempFile::new().expect("failed to get new NamedTempFile");
+        let tmp_image = blank_image_file();
+        let mut disk = Fdisk::builder()
+            .assign_device(tmp_image.path())
+            .enable_read_write()
+            .enable_interactive()
+            .prompt_handler(answers.clone())
+            .build()?;
 
-        decode_into(PATH_BLANK_IMG_FILE, named_file.as_file_mut())
-            .expect("failed to create named disk image");
-        named_file
-    }
+        disk.partition_table_create(PartitionTableKind::GPT)?;
 
-    //-------------------------------------------------------------------------
+        let actual = disk.partition_add_interactive();
+        assert!(actual.is_err());
 
-    #[test]
-    #[should_panic(
-        expected = "one of the methods `assign_device` or `assign_device_by_file` must be called"
-    )]
-    fn fdisk_must_assign_a_device() {
-        let _ = Fdisk::builder().build().unwrap();
-    }
+        let actual = answers.verify();
+        assert!(matches!(actual, Err(FdiskError::Prompt(msg)) if msg.contains("Partition number")));
 
-    #[test]
-    #[should_panic(expected = "failed to assign read-only device")]
-    fn fdisk_can_not_assign_a_regular_file_by_pathname() {
-        let regular_file = NamedTempFile::new().unwrap();
-        let _ = Fdisk::builder()
-            .assign_device(regular_file.path())
-            .build()
-            .unwrap();
+        Ok(())
     }
 
     #[test]
-    #[should_panic(expected = "failed to assign read-only device")]
-    fn fdisk_can_not_assign_a_regular_file_by_file_stream() {
-        let regular_file = NamedTempFile::new().unwrap();
-        let (file, temp_path) = regular_file.into_parts();
-        let _ = Fdisk::builder()
-            .assign_device_by_file(file, temp_path.as_os_str())
-            .build()
-            .unwrap();
-    }
+    fn fdisk_answer_queue_reports_unanswered_prompts() -> crate::Result<()> {
+        use crate::core::prompt::Answer;
+        use crate::core::prompt::AnswerQueue;
+        use crate::core::prompt::PromptKind;
+        use crate::core::prompt::QueryMatcher;
+
+        let answers = AnswerQueue::new()
+            .expect(PromptKind::YesNo, QueryMatcher::Any, Answer::YesNo(true))
+            .expect(
+                PromptKind::String,
+                QueryMatcher::Exact("Name?".to_owned()),
+                Answer::String("answer".to_owned()),
+            );
 
-    #[test]
-    #[should_panic(
-        expected = "methods `display_partition_details` and `display_partition_list_only` can not be called at the same time"
-    )]
-    fn fdisk_can_not_both_display_details_and_list_only() {
         let tmp_image = disk_image_with_pt("gpt");
-        let _ = Fdisk::builder()
+        let disk = Fdisk::builder()
             .assign_device(tmp_image.path())
-            .display_partition_details()
-            .display_partition_list_only()
-            .build()
-            .unwrap();
+            .enable_interactive()
+            .prompt_handler(answers.clone())
+            .build()?;
+
+        let actual = disk.ask_yes_no_question("Continue?")?;
+        let expected = true;
+        assert_eq!(actual, expected);
+
+        let actual = answers.remaining();
+        let expected = 1;
+        assert_eq!(actual, expected);
+
+        let actual = answers.verify();
+        assert!(matches!(actual, Err(FdiskError::Prompt(_))));
+
+        Ok(())
     }
 
     #[test]
-    fn fdisk_can_assign_a_device_by_pathname_in_read_only_mode() -> crate::Result<()> {
+    fn fdisk_collects_information_and_warning_messages() -> crate::Result<()> {
+        use crate::core::prompt::PromptKind;
+
         let tmp_image = disk_image_with_pt("gpt");
+        let mut disk = Fdisk::builder().assign_device(tmp_image.path()).build()?;
 
-        let disk = Fdisk::builder().assign_device(tmp_image.path()).build()?;
+        disk.log_info("info message")?;
+        disk.log_warn("warning message")?;
+        disk.log_warn_set_errno("warning message with errno", libc::EIO)?;
 
-        let actual = disk.device_is_read_only();
-        let expected = true;
+        let diagnostics = disk.diagnostics();
+
+        let actual = diagnostics.len();
+        let expected = 3;
+        assert_eq!(actual, expected);
+
+        let actual = diagnostics[0].kind();
+        let expected = PromptKind::Info;
+        assert_eq!(actual, expected);
+
+        let actual = diagnostics[0].message();
+        let expected = "info message";
+        assert_eq!(actual, expected);
+
+        let actual = diagnostics[1].kind();
+        let expected = PromptKind::WarnX;
+        assert_eq!(actual, expected);
+
+        let actual = diagnostics[2].kind();
+        let expected = PromptKind::Warn;
+        assert_eq!(actual, expected);
+
+        let actual = diagnostics[2].message();
+        let expected = "warning message with errno";
         assert_eq!(actual, expected);
 
         Ok(())
     }
 
     #[test]
-    fn fdisk_can_assign_a_device_by_pathname_in_read_write_mode() -> crate::Result<()> {
-        let tmp_image = disk_image_with_fs("ext4");
+    fn fdisk_collects_messages_when_answering_prompts() -> crate::Result<()> {
+        use crate::core::prompt::PromptKind;
 
-        let disk = Fdisk::builder()
+        let tmp_image = blank_image_file();
+        let mut disk = Fdisk::builder()
             .assign_device(tmp_image.path())
             .enable_read_write()
+            .enable_interactive()
+            .prompt_handler(|_: &mut Prompt| Ok(()))
+            .log_diagnostics()
             .build()?;
 
-        let actual = disk.device_is_read_only();
-        let expected = false;
+        disk.partition_table_create(PartitionTableKind::GPT)?;
+
+        let diagnostics = disk.diagnostics();
+
+        let actual = diagnostics.len();
+        let expected = 1;
+        assert_eq!(actual, expected);
+
+        let actual = diagnostics[0].kind();
+        let expected = PromptKind::Info;
         assert_eq!(actual, expected);
 
+        assert!(diagnostics[0]
+            .message()
+            .starts_with("Created a new GPT disklabel"));
+
         Ok(())
     }
 
     #[test]
-    fn fdisk_can_set_device_addressing() -> crate::Result<()> {
-        let tmp_image = disk_image_with_fs("ext4");
+    fn fdisk_can_take_collected_diagnostics() -> crate::Result<()> {
+        let tmp_image = disk_image_with_pt("gpt");
+        let mut disk = Fdisk::builder().assign_device(tmp_image.path()).build()?;
 
-        // Default:  DeviceAddressing::Sector
-        let disk = Fdisk::builder().assign_device(tmp_image.path()).build()?;
+        disk.log_info("info message")?;
 
-        let actual = disk.sectors_per_cylinder();
+        let actual = disk.diagnostics_take().len();
         let expected = 1;
         assert_eq!(actual, expected);
 
-        let addressing = DeviceAddressing::Cylinder;
-        let disk = Fdisk::builder()
+        let actual = disk.diagnostics().is_empty();
+        let expected = true;
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_not_verify_a_device_without_a_partition_table() -> crate::Result<()> {
+        let tmp_image = blank_image_file();
+        let disk = Fdisk::builder().assign_device(tmp_image.path()).build()?;
+
+        let actual = disk.partition_table_verify();
+        assert!(actual.is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_verify_a_well_formed_partition_table() -> crate::Result<()> {
+        let tmp_image = blank_image_file();
+        let mut disk = Fdisk::builder()
             .assign_device(tmp_image.path())
-            .device_addressing(addressing)
+            .enable_read_write()
             .build()?;
 
-        let actual = disk.sectors_per_cylinder();
-        assert!(actual > 1);
+        disk.partition_table_create(PartitionTableKind::GPT)?;
+
+        let mut partitions = PartitionList::new()?;
+        for _ in 0..2 {
+            let partition_type = PartitionKind::builder().guid(Guid::LinuxData).build()?;
+            let partition = Partition::builder()
+                .partition_type(partition_type)
+                .size_in_sectors(4_096)
+                .build()?;
+
+            partitions.push(partition)?;
+        }
+
+        disk.partitions_append(partitions)?;
+
+        let actual = disk.partition_table_verify()?;
+        let expected: Vec<VerificationFinding> = vec![];
+        assert_eq!(actual, expected);
+
         Ok(())
     }
 
     #[test]
-    fn fdisk_can_enable_interactive_partitioning() -> crate::Result<()> {
+    fn fdisk_can_report_misaligned_partitions() -> crate::Result<()> {
         let tmp_image = disk_image_with_pt("gpt");
-
-        // Default
         let disk = Fdisk::builder().assign_device(tmp_image.path()).build()?;
 
-        let actual = disk.is_partitioning_interactive();
-        let expected = false;
+        let actual = disk.partition_table_verify()?;
+        let expected = vec![VerificationFinding::MisalignedStart {
+            partition_number: 0,
+            starting_sector: 34,
+            grain_size: 1_048_576,
+        }];
         assert_eq!(actual, expected);
 
-        // Enabled
-        let disk = Fdisk::builder()
-            .assign_device(tmp_image.path())
-            .enable_interactive()
-            .build()?;
+        Ok(())
+    }
 
-        let actual = disk.is_partitioning_interactive();
+    #[test]
+    fn fdisk_verifies_a_partition_table_without_using_earlier_diagnostics() -> crate::Result<()> {
+        use std::io::{Seek, SeekFrom};
+
+        let mut tmp_image = disk_image_with_pt("gpt");
+
+        // Overwrite the primary GPT header's CRC32 checksum.
+        let file = tmp_image.as_file_mut();
+        file.seek(SeekFrom::Start(512 + 16))
+            .expect("failed to seek GPT header");
+        file.write_all(&[0xde, 0xad, 0xbe, 0xef])
+            .expect("failed to overwrite GPT header checksum");
+        file.sync_all().expect("failed to sync disk image");
+
+        let mut disk = Fdisk::builder().assign_device(tmp_image.path()).build()?;
+
+        let findings = disk.partition_table_verify()?;
+
+        let actual = findings
+            .iter()
+            .any(|finding| matches!(finding, VerificationFinding::BadChecksum { .. }));
         let expected = true;
         assert_eq!(actual, expected);
 
+        // Findings do not depend on earlier messages.
+        disk.diagnostics_take();
+
+        let actual = disk.partition_table_verify()?;
+        let expected = findings;
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fdisk_can_report_inconsistent_chs_addresses() -> crate::Result<()> {
+        use std::io::{Seek, SeekFrom};
+
+        let mut tmp_image = blank_image_file();
+        {
+            let mut disk = Fdisk::builder()
+                .assign_device(tmp_image.path())
+                .enable_read_write()
+                .build()?;
+
+            disk.partition_table_create(PartitionTableKind::DOS)?;
+            add_partitions(&mut disk, [(0, dos_kind(Code::Linux), 2_048, 4_096)])?;
+            disk.partition_table_write_to_disk()?;
+        }
+
+        // Overwrite the first partition's starting CHS address.
+        let file = tmp_image.as_file_mut();
+        file.seek(SeekFrom::Start(446 + 1))
+            .expect("failed to seek partition entry");
+        file.write_all(&[0xfe, 0xff, 0xff])
+            .expect("failed to overwrite CHS address");
+        file.sync_all().expect("failed to sync disk image");
+
+        let disk = Fdisk::builder().assign_device(tmp_image.path()).build()?;
+
+        let actual: Vec<_> = disk
+            .partition_table_verify()?
+            .into_iter()
+            .filter_map(|finding| match finding {
+                VerificationFinding::ChsInconsistency {
+                    partition_number, ..
+                } => Some(partition_number),
+                _ => None,
+            })
+            .collect();
+        let expected = vec![Some(0)];
+        assert_eq!(actual, expected);
+
         Ok(())
     }
 
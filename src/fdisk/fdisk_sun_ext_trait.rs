@@ -4,12 +4,43 @@
 // From dependency library
 
 // From standard library
+use std::fs::OpenOptions;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
 
 // From this library
+use crate::core::partition_table::PartitionTableKind;
 use crate::core::private::Sealed;
+use crate::core::prompt::Answer;
+use crate::core::prompt::AnswerQueue;
+use crate::core::prompt::PromptKind;
+use crate::core::prompt::QueryMatcher;
 use crate::fdisk::Fdisk;
 use crate::fdisk::FdiskError;
 
+/// Size of a `SUN` disklabel in bytes.
+const SUN_LABEL_SIZE: usize = 512;
+
+/// Offset of the volume ID in a `SUN` disklabel.
+const SUN_VOLUME_ID_OFFSET: usize = 132;
+
+/// Maximum length of a `SUN` volume ID.
+const SUN_VOLUME_ID_MAX_LEN: usize = 8;
+
+/// Offset of the number of physical cylinders in a `SUN` disklabel.
+const SUN_PCYL_OFFSET: usize = 422;
+
+/// Offset of the magic number in a `SUN` disklabel.
+const SUN_MAGIC_OFFSET: usize = 508;
+
+/// `SUN` disklabel magic number, stored in big-endian order.
+const SUN_LABEL_MAGIC: [u8; 2] = [0xda, 0xbe];
+
+/// Offset of the checksum in a `SUN` disklabel.
+const SUN_CHECKSUM_OFFSET: usize = 510;
+
 /// `SUN` specific functions for a [`Fdisk`].
 ///
 /// This trait is sealed and can not be implemented for types outside of `rsfdisk`.
@@ -33,6 +64,151 @@ pub trait FdiskSUNExt: Sealed {
     /// Sets the assigned device's number of extra sectors per cylinder by
     /// [`Prompt`](crate::core::prompt::Prompt)ing the user for a value.
     fn sun_set_extra_sectors_per_cylinder(&mut self) -> Result<(), FdiskError>;
+
+    /// Sets the assigned device's number of alternate cylinders to `count`.
+    fn sun_set_alternate_cylinder_count_to(&mut self, count: u16) -> Result<(), FdiskError>;
+
+    /// Sets the assigned device's interleave factor to `factor`, a value between `1` and `32`.
+    fn sun_set_interleave_factor_to(&mut self, factor: u16) -> Result<(), FdiskError>;
+
+    /// Sets the assigned device's number of physical cylinders to `count`.
+    ///
+    /// `libfdisk` ignores the value given to
+    /// [`FdiskSUNExt::sun_set_physical_cylinder_count`]. Like
+    /// [`FdiskSUNExt::sun_set_volume_id`], this method writes the disklabel, with all pending
+    /// changes, to the assigned device before updating the number of physical cylinders in place.
+    fn sun_set_physical_cylinder_count_to(&mut self, count: u16) -> Result<(), FdiskError>;
+
+    /// Sets the assigned device's rotation speed to `rpm`, a value greater than `0`.
+    fn sun_set_rotation_per_minute_to(&mut self, rpm: u16) -> Result<(), FdiskError>;
+
+    /// Sets the assigned device's number of extra sectors per cylinder to `count`, a value
+    /// between `0` and the number of sectors per track.
+    fn sun_set_extra_sectors_per_cylinder_to(&mut self, count: u16) -> Result<(), FdiskError>;
+
+    /// Sets the volume ID of the `SUN` disklabel on the assigned device to `volume_id`, a string
+    /// of at most 8 printable ASCII characters. An empty string clears the volume ID.
+    ///
+    /// `libfdisk` does not provide a way to change a volume ID. This method writes the disklabel,
+    /// with all pending changes, to the assigned device, then updates the volume ID in place and
+    /// reloads the disklabel.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use rsfdisk::core::partition_table::PartitionTableKind;
+    /// use rsfdisk::fdisk::Fdisk;
+    /// use rsfdisk::fdisk::FdiskSUNExt;
+    ///
+    /// fn main() -> rsfdisk::Result<()> {
+    ///     let mut disk = Fdisk::builder()
+    ///         .assign_device("boot.img")
+    ///         .enable_read_write()
+    ///         .build()?;
+    ///
+    ///     disk.partition_table_create(PartitionTableKind::SUN)?;
+    ///     disk.sun_set_rotation_per_minute_to(7200)?;
+    ///     disk.sun_set_interleave_factor_to(1)?;
+    ///
+    ///     // Saves the disklabel on `boot.img`.
+    ///     disk.sun_set_volume_id("sparc")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    fn sun_set_volume_id<T>(&mut self, volume_id: T) -> Result<(), FdiskError>
+    where
+        T: AsRef<str>;
+}
+
+#[doc(hidden)]
+/// Answers the single question asked by the `libfdisk` function called in `operation` with
+/// `value`, after checking it is between `lower` and `upper`.
+fn sun_answer_with<F>(
+    disk: &mut Fdisk,
+    query: &str,
+    value: u16,
+    lower: u16,
+    upper: u16,
+    operation: F,
+) -> Result<(), FdiskError>
+where
+    F: FnOnce(&mut Fdisk) -> Result<(), FdiskError>,
+{
+    if !(lower..=upper).contains(&value) {
+        let err_msg = format!(
+            "{} out of range: {:?}, must be between {:?} and {:?}",
+            query.to_lowercase(),
+            value,
+            lower,
+            upper
+        );
+        log::debug!("Fdisk::sun_answer_with {}", err_msg);
+
+        return Err(FdiskError::Config(err_msg));
+    }
+
+    let answers = AnswerQueue::new().expect(
+        PromptKind::Number,
+        QueryMatcher::Exact(query.to_owned()),
+        Answer::Number(u64::from(value)),
+    );
+
+    disk.answer_dialogs_with(answers, operation)
+}
+
+#[doc(hidden)]
+/// Writes the `SUN` disklabel of `disk` to its assigned device, modifies it in place with `patch`,
+/// updates its checksum, then reloads it.
+fn sun_patch_disk_label<F>(disk: &mut Fdisk, patch: F) -> Result<(), FdiskError>
+where
+    F: FnOnce(&mut [u8; SUN_LABEL_SIZE]),
+{
+    if !disk.partition_table_is_of_type(PartitionTableKind::SUN) {
+        let err_msg = "no SUN disklabel on assigned device".to_owned();
+        log::debug!("Fdisk::sun_patch_disk_label {}", err_msg);
+
+        return Err(FdiskError::Config(err_msg));
+    }
+
+    let device = match disk.device_name() {
+        Some(device) => device.to_path_buf(),
+        None => {
+            let err_msg = "no device assigned".to_owned();
+            log::debug!("Fdisk::sun_patch_disk_label {}", err_msg);
+
+            return Err(FdiskError::Config(err_msg));
+        }
+    };
+
+    disk.partition_table_write_to_disk()?;
+
+    let mut file = OpenOptions::new().read(true).write(true).open(&device)?;
+
+    let mut label = [0u8; SUN_LABEL_SIZE];
+    file.read_exact(&mut label)?;
+
+    if label[SUN_MAGIC_OFFSET..SUN_MAGIC_OFFSET + 2] != SUN_LABEL_MAGIC {
+        let err_msg = format!("no SUN disklabel found on device: {:?}", device);
+        log::debug!("Fdisk::sun_patch_disk_label {}", err_msg);
+
+        return Err(FdiskError::Config(err_msg));
+    }
+
+    patch(&mut label);
+
+    // The XOR of all 16-bit words in a valid disklabel, checksum included, is zero.
+    let mut checksum = [0u8; 2];
+    for (i, byte) in label[..SUN_CHECKSUM_OFFSET].iter().enumerate() {
+        checksum[i % 2] ^= byte;
+    }
+    label[SUN_CHECKSUM_OFFSET..].copy_from_slice(&checksum);
+
+    file.seek(SeekFrom::Start(0))?;
+    file.write_all(&label)?;
+    file.sync_all()?;
+
+    disk.discard_changes()
 }
 
 impl<'a> FdiskSUNExt for Fdisk<'a> {
@@ -145,4 +321,115 @@ fn sun_set_extra_sectors_per_cylinder(&mut self) -> Result<(), FdiskError> {
             }
         }
     }
+
+    fn sun_set_alternate_cylinder_count_to(&mut self, count: u16) -> Result<(), FdiskError> {
+        log::debug!(
+            "Fdisk::sun_set_alternate_cylinder_count_to setting SUN number of alternate cylinders to: {:?}",
+            count
+        );
+
+        sun_answer_with(
+            self,
+            "Number of alternate cylinders",
+            count,
+            0,
+            u16::MAX,
+            |disk| disk.sun_set_alternate_cylinder_count(),
+        )
+    }
+
+    fn sun_set_interleave_factor_to(&mut self, factor: u16) -> Result<(), FdiskError> {
+        log::debug!(
+            "Fdisk::sun_set_interleave_factor_to setting SUN device interleave factor to: {:?}",
+            factor
+        );
+
+        sun_answer_with(self, "Interleave factor", factor, 1, 32, |disk| {
+            disk.sun_set_interleave_factor()
+        })
+    }
+
+    fn sun_set_physical_cylinder_count_to(&mut self, count: u16) -> Result<(), FdiskError> {
+        log::debug!(
+            "Fdisk::sun_set_physical_cylinder_count_to setting SUN number of physical cylinders to: {:?}",
+            count
+        );
+
+        sun_patch_disk_label(self, |label| {
+            label[SUN_PCYL_OFFSET..SUN_PCYL_OFFSET + 2].copy_from_slice(&count.to_be_bytes());
+        })?;
+
+        log::debug!(
+            "Fdisk::sun_set_physical_cylinder_count_to set SUN number of physical cylinders to: {:?}",
+            count
+        );
+
+        Ok(())
+    }
+
+    fn sun_set_rotation_per_minute_to(&mut self, rpm: u16) -> Result<(), FdiskError> {
+        log::debug!(
+            "Fdisk::sun_set_rotation_per_minute_to setting SUN device rotation speed to: {:?}",
+            rpm
+        );
+
+        sun_answer_with(self, "Rotation speed (rpm)", rpm, 1, u16::MAX, |disk| {
+            disk.sun_set_rotation_per_minute()
+        })
+    }
+
+    fn sun_set_extra_sectors_per_cylinder_to(&mut self, count: u16) -> Result<(), FdiskError> {
+        log::debug!(
+            "Fdisk::sun_set_extra_sectors_per_cylinder_to setting SUN number of extra sectors per cylinder to: {:?}",
+            count
+        );
+
+        let sectors_per_track = u16::try_from(self.device_count_sectors()).unwrap_or(u16::MAX);
+
+        sun_answer_with(
+            self,
+            "Extra sectors per cylinder",
+            count,
+            0,
+            sectors_per_track,
+            |disk| disk.sun_set_extra_sectors_per_cylinder(),
+        )
+    }
+
+    fn sun_set_volume_id<T>(&mut self, volume_id: T) -> Result<(), FdiskError>
+    where
+        T: AsRef<str>,
+    {
+        let volume_id = volume_id.as_ref();
+        log::debug!(
+            "Fdisk::sun_set_volume_id setting SUN volume ID to: {:?}",
+            volume_id
+        );
+
+        if volume_id.len() > SUN_VOLUME_ID_MAX_LEN
+            || !volume_id.bytes().all(|b| b.is_ascii_graphic() || b == b' ')
+        {
+            let err_msg = format!(
+                "invalid SUN volume ID: {:?}, expected at most {} printable ASCII characters",
+                volume_id, SUN_VOLUME_ID_MAX_LEN
+            );
+            log::debug!("Fdisk::sun_set_volume_id {}", err_msg);
+
+            return Err(FdiskError::Config(err_msg));
+        }
+
+        sun_patch_disk_label(self, |label| {
+            let field =
+                &mut label[SUN_VOLUME_ID_OFFSET..SUN_VOLUME_ID_OFFSET + SUN_VOLUME_ID_MAX_LEN];
+            field.fill(0);
+            field[..volume_id.len()].copy_from_slice(volume_id.as_bytes());
+        })?;
+
+        log::debug!(
+            "Fdisk::sun_set_volume_id set SUN volume ID to: {:?}",
+            volume_id
+        );
+
+        Ok(())
+    }
 }
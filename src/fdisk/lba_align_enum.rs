@@ -13,9 +13,12 @@
 #[derive(Clone, Copy, Debug, Eq, PartialEq, IntoPrimitive)]
 #[repr(i32)]
 #[non_exhaustive]
-pub(crate) enum LBAAlign {
+pub enum LBAAlign {
+    /// Align to the previous grain boundary.
     Down = libfdisk::FDISK_ALIGN_DOWN,
+    /// Align to the nearest grain boundary.
     Nearest = libfdisk::FDISK_ALIGN_NEAREST,
+    /// Align to the next grain boundary.
     Up = libfdisk::FDISK_ALIGN_UP,
 }
 
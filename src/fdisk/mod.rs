@@ -84,10 +84,9 @@
 //!
 //! ```ignore
 //! use terminal_size::{terminal_size, Width};
-//! use rsfdisk::core::partition_table::MaxColWidth;
 //! use rsfdisk::fdisk::Fdisk;
 //! use rsfdisk::fdisk::SizeFormat;
-//!
+//! use rsfdisk::fdisk::TableRenderer;
 //!
 //! fn main() -> rsfdisk::Result<()> {
 //!     let disk = Fdisk::builder()
@@ -97,61 +96,21 @@
 //!         .partition_size_format(SizeFormat::HumanReadable)
 //!         .build()?;
 //!
-//!     let table = disk.partition_table_current().unwrap();
-//!     let formats = disk.partition_table_collect_partition_field_formats(table)?;
-//!
-//!     // Collect column headers.
-//!     let headers: Vec<_> = formats
-//!         .iter()
-//!         .enumerate()
-//!         .map(|(i, f)| {
-//!             let col_name = f.col_name().unwrap();
-//!             format!("({}) {}", i + 1, col_name)
-//!         })
-//!         .collect();
-//!
-//!     let mut rows: Vec<String> = vec![];
-//!
-//!     // Collect and format data about each partition.
+//!     let (Width(w), _) = terminal_size().unwrap();
 //!     let partitions = disk.list_partitions().unwrap();
 //!
-//!     for partition in partitions.iter() {
-//!         let mut columns: Vec<String> = vec![];
-//!
-//!         for field_format in formats.iter() {
-//!             let (Width(w), _) = terminal_size::terminal_size().unwrap();
-//!             let mut value = disk.partition_field_to_string(field_format.field(), partition)?;
-//!
-//!             match field_format.width().unwrap() {
-//!                 MaxColWidth::Length(l) => {
-//!                     let max_width = l as usize;
-//!                     value.truncate(max_width);
+//!     // Display the default columns of the partition table, aligned to fit the terminal.
+//!     let table = TableRenderer::new(&disk)
+//!         .terminal_width(w as usize)
+//!         .render(&partitions)?;
 //!
-//!                     let cell = format!("{:>max_width$} ", value);
-//!                     columns.push(cell);
-//!                 }
-//!                 MaxColWidth::Percentage(p) => {
-//!                     let max_width = (w * p / 100) as usize;
-//!                     value.truncate(max_width);
-//!
-//!                     let cell = format!("{:max_width$} ", value);
-//!                     columns.push(cell);
-//!                 }
-//!             }
-//!         }
-//!
-//!         rows.push(columns.join(" "));
-//!     }
-//!
-//!     println!("Columns: {}\n", headers.join(" "));
-//!     println!("{}", rows.join("\n"));
+//!     print!("{}", table);
 //!
 //!     // Example output
 //!     //
-//!     // Columns: (1) Device (2) Boot (3) Start (4) End (5) Sectors (6) Size (7) Id (8) Type
-//!     //
-//!     // /dev/vda1  *    32   7679   7648   3.7M  83  Linux
-//!     // /dev/vda2     7680  16383   8704   4.3M  a5  FreeBSD
+//!     // Device    Boot Start   End Sectors Size Id Type
+//!     // /dev/vda1 *       32  7679    7648 3.7M 83 Linux
+//!     // /dev/vda2       7680 16383    8704 4.3M a5 FreeBSD
 //!
 //!     Ok(())
 //! }
@@ -242,6 +201,8 @@
 // From this library
 
 pub use device_addressing_enum::DeviceAddressing;
+pub use device_report_struct::DeviceReport;
+pub use diagnostic_struct::Diagnostic;
 pub use fdisk_bsd_ext_trait::FdiskBSDExt;
 pub use fdisk_builder_error_enum::FdiskBuilderError;
 pub(crate) use fdisk_builder_struct::CtxBuilder;
@@ -253,12 +214,19 @@
 pub use fdisk_struct::Fdisk;
 pub use fdisk_sun_ext_trait::FdiskSUNExt;
 pub(crate) use gc_item_enum::GcItem;
-pub(crate) use lba_align_enum::LBAAlign;
+pub use lba_align_enum::LBAAlign;
+pub use partition_growth_struct::PartitionGrowth;
 pub use partition_table_iter_mut_struct::PartitionTableIterMut;
 pub use partition_table_iter_struct::PartitionTableIter;
+pub(crate) use prompt_dispatcher_struct::PromptDispatcher;
+pub use savepoint_struct::Savepoint;
 pub use size_format_enum::SizeFormat;
+pub use table_format_enum::TableFormat;
+pub use table_renderer_struct::TableRenderer;
 
 mod device_addressing_enum;
+mod device_report_struct;
+mod diagnostic_struct;
 mod fdisk_bsd_ext_trait;
 mod fdisk_builder_error_enum;
 mod fdisk_builder_struct;
@@ -270,6 +238,11 @@
 mod fdisk_sun_ext_trait;
 mod gc_item_enum;
 mod lba_align_enum;
+mod partition_growth_struct;
 mod partition_table_iter_mut_struct;
 mod partition_table_iter_struct;
+mod prompt_dispatcher_struct;
+mod savepoint_struct;
 mod size_format_enum;
+mod table_format_enum;
+mod table_renderer_struct;
@@ -0,0 +1,46 @@
+// Copyright (c) 2023 Nick Piaddo
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// From dependency library
+
+// From standard library
+
+// From this library
+use crate::core::partition_table::GptRepair;
+
+/// Outcome of growing a partition, as previewed by
+/// [`Fdisk::partition_check_grow`](crate::fdisk::Fdisk::partition_check_grow).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PartitionGrowth {
+    size: u64,
+    new_size: u64,
+    relocation: Option<GptRepair>,
+}
+
+impl PartitionGrowth {
+    #[doc(hidden)]
+    /// Creates a new `PartitionGrowth`.
+    pub(crate) fn new(size: u64, new_size: u64, relocation: Option<GptRepair>) -> PartitionGrowth {
+        Self {
+            size,
+            new_size,
+            relocation,
+        }
+    }
+
+    /// Returns the current size of the partition, in sectors.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Returns the size of the partition once grown, in sectors.
+    pub fn new_size(&self) -> u64 {
+        self.new_size
+    }
+
+    /// Returns the move of the backup `GPT` header to the end of the device that growing the
+    /// partition requires, if any.
+    pub fn backup_header_relocation(&self) -> Option<GptRepair> {
+        self.relocation
+    }
+}
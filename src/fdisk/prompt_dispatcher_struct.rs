@@ -0,0 +1,126 @@
+// Copyright (c) 2023 Nick Piaddo
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// From dependency library
+
+// From standard library
+use std::cell::RefCell;
+
+// From this library
+use crate::core::errors::PromptError;
+use crate::core::prompt::Prompt;
+use crate::core::prompt::PromptHandler;
+use crate::core::prompt::PromptKind;
+use crate::fdisk::Diagnostic;
+
+/// Receives all [`Prompt`]s issued by an [`Fdisk`](crate::fdisk::Fdisk), records information and
+/// warning messages, and forwards everything to the user-provided [`PromptHandler`].
+///
+/// `libfdisk` calls the dispatcher back while running a function invoked from a shared reference
+/// to its `Fdisk`, so the dispatcher only ever hands out shared references, and keeps its mutable
+/// state in `RefCell`s. None of the borrows outlives a method call. A handler issuing a new
+/// prompt on the same context while answering one would re-enter [`PromptDispatcher::dispatch`];
+/// that nested prompt finds the handler already borrowed, and is rejected instead of aliasing it.
+#[derive(Debug, Default)]
+pub(crate) struct PromptDispatcher {
+    handler: RefCell<Option<Box<dyn PromptHandler>>>,
+    diagnostics: RefCell<Vec<Diagnostic>>,
+    log_diagnostics: bool,
+}
+
+impl PromptDispatcher {
+    #[doc(hidden)]
+    /// Creates a new `PromptDispatcher`.
+    pub(crate) fn new() -> PromptDispatcher {
+        Self::default()
+    }
+
+    #[doc(hidden)]
+    /// Sets the handler answering questions.
+    pub(crate) fn set_handler(&mut self, handler: Box<dyn PromptHandler>) {
+        *self.handler.get_mut() = Some(handler);
+    }
+
+    #[doc(hidden)]
+    /// Replaces the handler answering questions, and returns the previous one.
+    pub(crate) fn replace_handler(
+        &mut self,
+        handler: Option<Box<dyn PromptHandler>>,
+    ) -> Option<Box<dyn PromptHandler>> {
+        std::mem::replace(self.handler.get_mut(), handler)
+    }
+
+    #[doc(hidden)]
+    /// Enables/disables forwarding information and warning messages to the `log` crate.
+    pub(crate) fn set_log_diagnostics(&mut self, enable: bool) {
+        self.log_diagnostics = enable;
+    }
+
+    #[doc(hidden)]
+    /// Returns a copy of the recorded information and warning messages.
+    pub(crate) fn diagnostics(&self) -> Vec<Diagnostic> {
+        self.diagnostics.borrow().clone()
+    }
+
+    #[doc(hidden)]
+    /// Removes and returns all recorded information and warning messages.
+    pub(crate) fn take_diagnostics(&self) -> Vec<Diagnostic> {
+        self.diagnostics.take()
+    }
+
+    #[doc(hidden)]
+    /// Returns `true` if the `kind` of prompt carries an information or warning message.
+    fn is_message(kind: PromptKind) -> bool {
+        matches!(
+            kind,
+            PromptKind::Info | PromptKind::Warn | PromptKind::WarnX
+        )
+    }
+
+    #[doc(hidden)]
+    /// Records an information or warning message.
+    fn record(&self, kind: PromptKind, prompt: &Prompt) {
+        let message = prompt.error_message().unwrap_or_default().to_owned();
+        let error_number = prompt.error_number();
+        let diagnostic = Diagnostic::new(kind, message, error_number);
+
+        if self.log_diagnostics {
+            match kind {
+                PromptKind::Info => log::info!("{}", diagnostic),
+                _ => log::warn!("{}", diagnostic),
+            }
+        }
+
+        self.diagnostics.borrow_mut().push(diagnostic);
+    }
+
+    #[doc(hidden)]
+    /// Records information and warning messages, then forwards the `Prompt` to the registered
+    /// handler.
+    pub(crate) fn dispatch(&self, prompt: &mut Prompt) -> Result<(), PromptError> {
+        let kind = prompt.kind();
+
+        if Self::is_message(kind) {
+            self.record(kind, prompt);
+        }
+
+        let mut handler = self.handler.try_borrow_mut().map_err(|_| {
+            let err_msg = format!(
+                "can not answer {:?} prompt issued while answering another one",
+                kind
+            );
+
+            PromptError::Unexpected(err_msg)
+        })?;
+
+        match handler.as_mut() {
+            Some(handler) => handler.answer(prompt),
+            None if Self::is_message(kind) => Ok(()),
+            None => {
+                let err_msg = format!("no prompt handler to answer {:?} prompt", kind);
+
+                Err(PromptError::Config(err_msg))
+            }
+        }
+    }
+}
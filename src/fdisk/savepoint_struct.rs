@@ -0,0 +1,83 @@
+// Copyright (c) 2023 Nick Piaddo
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// From dependency library
+
+// From standard library
+use std::ops::{Deref, DerefMut};
+
+// From this library
+use crate::core::partition::PartitionList;
+use crate::core::script::Script;
+use crate::fdisk::Fdisk;
+use crate::fdisk::FdiskError;
+
+/// Copy of the in-memory partition table of an [`Fdisk`](crate::fdisk::Fdisk), to roll back to
+/// if a later edit fails.
+///
+/// A `Savepoint` holds an exclusive borrow of the `Fdisk` it was created from, and gives access
+/// to it by dereferencing, so it can not outlive the context its copy describes.
+///
+/// For usage, see [`Fdisk::partition_table_savepoint`](crate::fdisk::Fdisk::partition_table_savepoint).
+#[derive(Debug)]
+pub struct Savepoint<'disk, 'a> {
+    disk: &'disk mut Fdisk<'a>,
+    script: Script<'disk>,
+    partitions: PartitionList,
+}
+
+impl<'disk, 'a> Savepoint<'disk, 'a> {
+    #[doc(hidden)]
+    /// Creates a new `Savepoint` from a script describing the partition table of `disk`, and the
+    /// list of its partitions.
+    pub(crate) fn new(
+        disk: &'disk mut Fdisk<'a>,
+        script: Script<'disk>,
+        partitions: PartitionList,
+    ) -> Savepoint<'disk, 'a> {
+        log::debug!("Savepoint::new creating a new `Savepoint` instance");
+
+        Self {
+            disk,
+            script,
+            partitions,
+        }
+    }
+
+    /// Returns the partitions in the partition table at the time this `Savepoint` was created.
+    pub fn partitions(&self) -> &PartitionList {
+        &self.partitions
+    }
+
+    /// Replaces the in-memory partition table with the copy saved in this `Savepoint`.
+    ///
+    /// The same `Savepoint` can be used for multiple rollbacks.
+    pub fn rollback(&mut self) -> Result<(), FdiskError> {
+        log::debug!("Savepoint::rollback restoring in-memory partition table");
+
+        self.disk.script_apply(&self.script).map_err(|e| {
+            let err_msg = format!("failed to restore in-memory partition table. {}", e);
+            log::debug!("Savepoint::rollback {}", err_msg);
+
+            FdiskError::Restore(err_msg)
+        })?;
+
+        log::debug!("Savepoint::rollback restored in-memory partition table");
+
+        Ok(())
+    }
+}
+
+impl<'disk, 'a> Deref for Savepoint<'disk, 'a> {
+    type Target = Fdisk<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        self.disk
+    }
+}
+
+impl<'disk, 'a> DerefMut for Savepoint<'disk, 'a> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.disk
+    }
+}
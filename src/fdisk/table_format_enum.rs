@@ -0,0 +1,26 @@
+// Copyright (c) 2023 Nick Piaddo
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// From dependency library
+
+// From standard library
+
+// From this library
+
+/// Output formats of a [`TableRenderer`](crate::fdisk::TableRenderer).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum TableFormat {
+    /// Columns aligned with spaces, like `fdisk -l`.
+    #[default]
+    Plain,
+    /// Values separated by a single space, with whitespace and special characters escaped as
+    /// `\xHH` (`lsblk --raw`).
+    Raw,
+    /// Comma-separated values.
+    Csv,
+    #[cfg(feature = "serde")]
+    /// A JSON object holding an array of partitions (`lsblk --json`). Column names, in lowercase,
+    /// are used as keys. Requires the `serde` feature.
+    Json,
+}
@@ -0,0 +1,378 @@
+// Copyright (c) 2023 Nick Piaddo
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// From dependency library
+
+// From standard library
+
+// From this library
+use crate::core::partition::PartitionList;
+use crate::core::partition_table::Field;
+use crate::core::partition_table::MaxColWidth;
+use crate::fdisk::Fdisk;
+use crate::fdisk::FdiskError;
+use crate::fdisk::SizeFormat;
+use crate::fdisk::TableFormat;
+
+/// Renders partition entries as a table, one row per partition, one column per [`Field`].
+///
+/// By default, a `TableRenderer` displays the default fields of the current partition table (see
+/// [`Fdisk::partition_table_collect_partition_fields`]) as [`TableFormat::Plain`] text, with
+/// partition sizes in the format set on its [`Fdisk`]. Numerical columns are right-aligned, the
+/// other ones left-aligned. A column's [`MaxColWidth::Length`] sets its minimum width, while a
+/// [`MaxColWidth::Percentage`] of the terminal's width caps the length of its values when
+/// [`TableRenderer::terminal_width`] is set.
+///
+/// Rendering partitions as JSON (`TableFormat::Json`) requires the `serde` feature.
+///
+/// # Examples
+///
+/// ```ignore
+/// use rsfdisk::core::partition_table::Field;
+/// use rsfdisk::fdisk::Fdisk;
+/// use rsfdisk::fdisk::SizeFormat;
+/// use rsfdisk::fdisk::TableFormat;
+/// use rsfdisk::fdisk::TableRenderer;
+///
+/// fn main() -> rsfdisk::Result<()> {
+///     let disk = Fdisk::builder().assign_device("/dev/vda").build()?;
+///
+///     if let Some(partitions) = disk.list_partitions() {
+///         let table = TableRenderer::new(&disk)
+///             .fields(vec![Field::Device, Field::StartingLba, Field::Size, Field::Type])
+///             .size_format(SizeFormat::HumanReadable)
+///             .format(TableFormat::Json)
+///             .render(&partitions)?;
+///
+///         print!("{}", table);
+///     }
+///
+///     // Example output
+///     //
+///     // {
+///     //    "partitions": [
+///     //       {"device":"/dev/vda1", "start":2048, "size":"4M", "type":"Linux filesystem"},
+///     //       {"device":"/dev/vda2", "start":10240, "size":"14M", "type":"Linux swap"}
+///     //    ]
+///     // }
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct TableRenderer<'a> {
+    partitioner: &'a Fdisk<'a>,
+    fields: Option<Vec<Field>>,
+    format: TableFormat,
+    size_format: Option<SizeFormat>,
+    terminal_width: Option<usize>,
+}
+
+impl<'a> TableRenderer<'a> {
+    /// Creates a new `TableRenderer` for the partitions of the current partition table on
+    /// `partitioner`.
+    pub fn new(partitioner: &'a Fdisk) -> TableRenderer<'a> {
+        log::debug!("TableRenderer::new creating a new `TableRenderer` instance");
+
+        Self {
+            partitioner,
+            fields: None,
+            format: TableFormat::default(),
+            size_format: None,
+            terminal_width: None,
+        }
+    }
+
+    /// Sets the columns to display, in order.
+    pub fn fields(mut self, fields: Vec<Field>) -> TableRenderer<'a> {
+        self.fields = Some(fields);
+        self
+    }
+
+    /// Sets the output format (default [`TableFormat::Plain`]).
+    pub fn format(mut self, format: TableFormat) -> TableRenderer<'a> {
+        self.format = format;
+        self
+    }
+
+    /// Sets the format of partition sizes, overriding the one set on the [`Fdisk`].
+    pub fn size_format(mut self, format: SizeFormat) -> TableRenderer<'a> {
+        self.size_format = Some(format);
+        self
+    }
+
+    /// Sets the width of the terminal, in characters, used to compute the maximum width of
+    /// [`TableFormat::Plain`] columns sized with a [`MaxColWidth::Percentage`].
+    pub fn terminal_width(mut self, width: usize) -> TableRenderer<'a> {
+        self.terminal_width = Some(width);
+        self
+    }
+
+    /// Renders `partitions` as a table. Each line, including the last, ends with a newline
+    /// character.
+    pub fn render(&self, partitions: &PartitionList) -> Result<String, FdiskError> {
+        log::debug!(
+            "TableRenderer::render rendering partitions as {:?}",
+            self.format
+        );
+
+        let table = self.partitioner.partition_table_current().ok_or_else(|| {
+            let err_msg = "no partition table to get column formats from".to_owned();
+            log::debug!("TableRenderer::render {}", err_msg);
+
+            FdiskError::Config(err_msg)
+        })?;
+
+        let fields = match self.fields.as_ref() {
+            Some(fields) => fields.clone(),
+            None => self
+                .partitioner
+                .partition_table_collect_partition_fields(table)?,
+        };
+
+        let columns = fields
+            .iter()
+            .map(|&field| {
+                table
+                    .partition_field_format(field)
+                    .map(|format| Column {
+                        name: format.col_name().unwrap_or_default().to_owned(),
+                        is_numeric: format.is_numeric(),
+                        width: format.width(),
+                    })
+                    .ok_or_else(|| {
+                        let err_msg = format!(
+                            "field {:?} not supported by {} partition tables",
+                            field,
+                            table.kind()
+                        );
+                        log::debug!("TableRenderer::render {}", err_msg);
+
+                        FdiskError::Config(err_msg)
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let size_format = self
+            .size_format
+            .unwrap_or_else(|| self.partitioner.partition_size_format());
+
+        let rows = self
+            .partitioner
+            .with_partition_size_format(size_format, |partitioner| {
+                partitions
+                    .iter()
+                    .map(|partition| {
+                        fields
+                            .iter()
+                            .map(|&field| partitioner.partition_field_to_string(field, partition))
+                            .collect::<Result<Vec<_>, _>>()
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })?;
+
+        let output = match self.format {
+            TableFormat::Plain => self.render_plain(&columns, &rows),
+            TableFormat::Raw => Self::render_separated(&columns, &rows, " ", raw_escape),
+            TableFormat::Csv => Self::render_separated(&columns, &rows, ",", csv_escape),
+            #[cfg(feature = "serde")]
+            TableFormat::Json => Self::render_json(&columns, &rows),
+        };
+        log::debug!("TableRenderer::render rendered {} partitions", rows.len());
+
+        Ok(output)
+    }
+
+    #[doc(hidden)]
+    /// Renders rows as aligned columns.
+    fn render_plain(&self, columns: &[Column], rows: &[Vec<String>]) -> String {
+        let widths: Vec<usize> = columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                let name_width = column.name.chars().count();
+                let longest = rows
+                    .iter()
+                    .map(|row| row[i].chars().count())
+                    .fold(name_width, usize::max);
+
+                match (column.width, self.terminal_width) {
+                    (Some(MaxColWidth::Length(min)), _) => longest.max(min as usize),
+                    (Some(MaxColWidth::Percentage(percent)), Some(terminal_width)) => {
+                        let max = terminal_width * percent as usize / 100;
+                        longest.min(max.max(name_width))
+                    }
+                    _ => longest,
+                }
+            })
+            .collect();
+
+        let header: Vec<String> = columns.iter().map(|column| column.name.clone()).collect();
+
+        std::iter::once(&header)
+            .chain(rows.iter())
+            .map(|row| {
+                let cells: Vec<String> = row
+                    .iter()
+                    .zip(columns.iter().zip(widths.iter()))
+                    .map(|(value, (column, &width))| {
+                        let value: String = value.chars().take(width).collect();
+
+                        if column.is_numeric {
+                            format!("{:>width$}", value)
+                        } else {
+                            format!("{:<width$}", value)
+                        }
+                    })
+                    .collect();
+
+                format!("{}\n", cells.join(" ").trim_end())
+            })
+            .collect()
+    }
+
+    #[doc(hidden)]
+    /// Renders rows as escaped values separated by `separator`.
+    fn render_separated(
+        columns: &[Column],
+        rows: &[Vec<String>],
+        separator: &str,
+        escape: fn(&str) -> String,
+    ) -> String {
+        let header: Vec<String> = columns.iter().map(|column| column.name.clone()).collect();
+
+        std::iter::once(&header)
+            .chain(rows.iter())
+            .map(|row| {
+                let values: Vec<String> = row.iter().map(|value| escape(value)).collect();
+
+                format!("{}\n", values.join(separator))
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "serde")]
+    #[doc(hidden)]
+    /// Renders rows as a JSON object.
+    fn render_json(columns: &[Column], rows: &[Vec<String>]) -> String {
+        let objects: Vec<String> = rows
+            .iter()
+            .map(|row| {
+                let members: Vec<String> = row
+                    .iter()
+                    .zip(columns.iter())
+                    .map(|(value, column)| {
+                        let key = serde_json::Value::from(column.name.to_lowercase());
+                        let value = match value.as_str() {
+                            "" => serde_json::Value::Null,
+                            string => match string.parse::<u64>() {
+                                Ok(number) if column.is_numeric => serde_json::Value::from(number),
+                                _ => serde_json::Value::from(string),
+                            },
+                        };
+
+                        format!("{}:{}", key, value)
+                    })
+                    .collect();
+
+                format!("      {{{}}}", members.join(", "))
+            })
+            .collect();
+
+        format!(
+            "{{\n   \"partitions\": [\n{}{}   ]\n}}\n",
+            objects.join(",\n"),
+            if objects.is_empty() { "" } else { "\n" }
+        )
+    }
+}
+
+#[doc(hidden)]
+/// Formatting parameters of a column.
+#[derive(Debug)]
+struct Column {
+    name: String,
+    is_numeric: bool,
+    width: Option<MaxColWidth>,
+}
+
+#[doc(hidden)]
+/// Escapes whitespace, control characters and backslashes as `\xHH` sequences.
+fn raw_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        if c == '\\' || c.is_whitespace() || c.is_control() {
+            let mut buffer = [0u8; 4];
+            for byte in c.encode_utf8(&mut buffer).bytes() {
+                escaped.push_str(&format!("\\x{:02x}", byte));
+            }
+        } else {
+            escaped.push(c);
+        }
+    }
+
+    escaped
+}
+
+#[doc(hidden)]
+/// Quotes a value containing a separator, a double quote or a line break (RFC 4180).
+fn csv_escape(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_owned()
+    }
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+mod tests {
+    use super::*;
+    use pretty_assertions::{assert_eq, assert_ne};
+
+    #[test]
+    fn table_renderer_escapes_raw_values() {
+        let actual = raw_escape("Linux swap\\x");
+        let expected = "Linux\\x20swap\\x5cx";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn table_renderer_escapes_csv_values() {
+        let actual = csv_escape("W95 FAT32 (LBA)");
+        let expected = "W95 FAT32 (LBA)";
+        assert_eq!(actual, expected);
+
+        let actual = csv_escape("a \"quoted\", name");
+        let expected = "\"a \"\"quoted\"\", name\"";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn table_renderer_escapes_json_values() {
+        let columns = [
+            Column {
+                name: "Start".to_owned(),
+                is_numeric: true,
+                width: None,
+            },
+            Column {
+                name: "Type".to_owned(),
+                is_numeric: false,
+                width: None,
+            },
+        ];
+        let rows = [vec![
+            "".to_owned(),
+            "OS/2 \"Boot\"\tManager\u{1}".to_owned(),
+        ]];
+
+        let actual = TableRenderer::render_json(&columns, &rows);
+        let expected = "{\n   \"partitions\": [\n      \
+                        {\"start\":null, \"type\":\"OS/2 \\\"Boot\\\"\\tManager\\u0001\"}\n   \
+                        ]\n}\n";
+        assert_eq!(actual, expected);
+    }
+}
@@ -92,7 +92,7 @@
 //!
 //! `rsfdisk`'s API is roughly divided into two parts:
 //! - [`fdisk`]: the main library module holding the [`Fdisk`](crate::fdisk::Fdisk) struct to create/edit/modify partition
-//! tables,
+//!   tables,
 //! - [`core`]: the module holding specialised objects used and/or returned by [`Fdisk`](crate::fdisk::Fdisk).
 //!
 //! Finally, look to the [`debug`] module if you need diagnostics during development.
@@ -226,7 +226,7 @@
 //! | [`fdisk_info`][57]                        | [`Fdisk::log_info`](crate::fdisk::Fdisk::log_info)                                                                                                                                   |
 //! | [`fdisk_warn`][58]                        | [`Fdisk::log_warn_set_errno`](crate::fdisk::Fdisk::log_warn_set_errno)                                                                                                               |
 //! | [`fdisk_warnx`][59]                       | [`Fdisk::log_warn`](crate::fdisk::Fdisk::log_warn)                                                                                                                                   |
-//! | [`fdisk_set_ask`][60]                     | TBD                                                                                                                                                                                  |
+//! | [`fdisk_set_ask`][60]                     | [`FdiskBuilder::prompt_handler`](crate::fdisk::FdiskBuilder::prompt_handler)                                                                                                         |
 //! | [`fdisk_is_ask`][61]                      | [`Prompt::is_of_kind`](crate::core::prompt::Prompt::is_of_kind)                                                                                                                      |
 //! | [`fdisk_ask_get_query`][62]               | [`Prompt::query`](crate::core::prompt::Prompt::query)                                                                                                                                |
 //! | [`fdisk_ask_get_type`][63]                | [`Prompt::kind`](crate::core::prompt::Prompt::kind)                                                                                                                                  |
@@ -392,7 +392,7 @@
 //! | [`fdisk_set_disklabel_id_from_string`][133]   | [`Fdisk::partition_table_set_string_id`](crate::fdisk::Fdisk::partition_table_set_string_id)                                                                                                                                                                                                                                                                 |
 //! | [`fdisk_set_partition_type`][134]             | [`Fdisk::partition_table_set_partition_type`](crate::fdisk::Fdisk::partition_table_set_partition_type)                                                                                                                                                                                                                                                       |
 //! | [`fdisk_toggle_partition_flag`][135]          | [`Fdisk::partition_table_toggle_partition_flag`](crate::fdisk::Fdisk::partition_table_toggle_partition_flag)                                                                                                                                                                                                                                                 |
-//! | [`fdisk_verify_disklabel`][136]               | [`Fdisk::partition_table_check`](crate::fdisk::Fdisk::partition_table_check)                                                                                                                                                                                                                                                                                 |
+//! | [`fdisk_verify_disklabel`][136]               | [`Fdisk::partition_table_check`](crate::fdisk::Fdisk::partition_table_check)<br>[`Fdisk::partition_table_verify`](crate::fdisk::Fdisk::partition_table_verify)                                                                                                                                                                                               |
 //! | [`fdisk_write_disklabel`][137]                | [`Fdisk::partition_table_write_to_disk`](crate::fdisk::Fdisk::partition_table_write_to_disk)                                                                                                                                                                                                                                                                 |
 //! | [`fdisk_get_disklabel_id`][138]               | [`Fdisk::partition_table_id`](crate::fdisk::Fdisk::partition_table_id)                                                                                                                                                                                                                                                                                       |
 //! | [`fdisk_get_label`][139]                      | [`Fdisk::partition_table_current`](crate::fdisk::Fdisk::partition_table_current)<br>[`Fdisk::partition_table_current_mut`](crate::fdisk::Fdisk::partition_table_current_mut)<br>[`Fdisk::partition_table_by_type`](crate::fdisk::Fdisk::partition_table_by_type)<br>[`Fdisk::partition_table_by_type_mut`](crate::fdisk::Fdisk::partition_table_by_type_mut) |
@@ -732,8 +732,8 @@
 //! | [`GPT_FLAG_LEGACYBOOT`][280]           | [`GPTFlag::LegacyBiosBootable`](crate::core::partition::GPTFlag::LegacyBiosBootable)                                                                                                                                                                                                                               |
 //! | [`GPT_FLAG_GUIDSPECIFIC`][281]         | [`GPTFlag::GuidSpecific`](crate::core::partition::GPTFlag::GuidSpecific)                                                                                                                                                                                                                                           |
 //! | [`fdisk_gpt_is_hybrid`][282]           | [`FdiskGPTExt::gpt_is_hybrid`](crate::fdisk::FdiskGPTExt::gpt_is_hybrid)                                                                                                                                                                                                                                           |
-//! | [`fdisk_gpt_get_partition_attrs`][283] | [`FdiskGPTExt::gpt_attribute_bits`](crate::fdisk::FdiskGPTExt::gpt_attribute_bits)                                                                                                                                                                                                                                 |
-//! | [`fdisk_gpt_set_partition_attrs`][284] | [`FdiskGPTExt::gpt_set_attribute_bits`](crate::fdisk::FdiskGPTExt::gpt_set_attribute_bits)                                                                                                                                                                                                                         |
+//! | [`fdisk_gpt_get_partition_attrs`][283] | [`FdiskGPTExt::gpt_attribute_bits`](crate::fdisk::FdiskGPTExt::gpt_attribute_bits)<br>[`FdiskGPTExt::gpt_attributes`](crate::fdisk::FdiskGPTExt::gpt_attributes)                                                                                                                                                                                                                                 |
+//! | [`fdisk_gpt_set_partition_attrs`][284] | [`FdiskGPTExt::gpt_set_attribute_bits`](crate::fdisk::FdiskGPTExt::gpt_set_attribute_bits)<br>[`FdiskGPTExt::gpt_set_attributes`](crate::fdisk::FdiskGPTExt::gpt_set_attributes)                                                                                                                                                                                                                         |
 //! | [`fdisk_gpt_set_npartitions`][285]     | [`FdiskGPTExt::gpt_set_partition_entry_array_size`](crate::fdisk::FdiskGPTExt::gpt_set_partition_entry_array_size)                                                                                                                                                                                                 |
 //! | [`fdisk_gpt_disable_relocation`][286]  | [`PartitionTableGPTExt::gpt_enable_backup_header_relocation`](crate::core::partition_table::PartitionTableGPTExt::gpt_enable_backup_header_relocation)<br>[`PartitionTableGPTExt::gpt_disable_backup_header_relocation`](crate::core::partition_table::PartitionTableGPTExt::gpt_disable_backup_header_relocation) |
 //! | [`fdisk_gpt_enable_minimize`][287]     | [`PartitionTableGPTExt::gpt_enable_minimize_footprint`](crate::core::partition_table::PartitionTableGPTExt::gpt_enable_minimize_footprint)<br>[`PartitionTableGPTExt::gpt_disable_minimize_footprint`](crate::core::partition_table::PartitionTableGPTExt::gpt_disable_minimize_footprint)                         |
@@ -753,11 +753,11 @@
 //!
 //! | `libfdisk`                       | `rsfdisk`                                                                                                          |
 //! | ------------------               | ---------                                                                                                          |
-//! | [`fdisk_sun_set_alt_cyl`][288]   | [`FdiskSUNExt::sun_set_alternate_cylinder_count`](crate::fdisk::FdiskSUNExt::sun_set_alternate_cylinder_count)     |
-//! | [`fdisk_sun_set_ilfact`][289]    | [`FdiskSUNExt::sun_set_interleave_factor`](crate::fdisk::FdiskSUNExt::sun_set_interleave_factor)                   |
-//! | [`fdisk_sun_set_pcylcount`][290] | [`FdiskSUNExt::sun_set_physical_cylinder_count`](crate::fdisk::FdiskSUNExt::sun_set_physical_cylinder_count)       |
-//! | [`fdisk_sun_set_rspeed`][291]    | [`FdiskSUNExt::sun_set_rotation_per_minute`](crate::fdisk::FdiskSUNExt::sun_set_rotation_per_minute)               |
-//! | [`fdisk_sun_set_xcyl`][292]      | [`FdiskSUNExt::sun_set_extra_sectors_per_cylinder`](crate::fdisk::FdiskSUNExt::sun_set_extra_sectors_per_cylinder) |
+//! | [`fdisk_sun_set_alt_cyl`][288]   | [`FdiskSUNExt::sun_set_alternate_cylinder_count`](crate::fdisk::FdiskSUNExt::sun_set_alternate_cylinder_count)<br>[`FdiskSUNExt::sun_set_alternate_cylinder_count_to`](crate::fdisk::FdiskSUNExt::sun_set_alternate_cylinder_count_to) |
+//! | [`fdisk_sun_set_ilfact`][289]    | [`FdiskSUNExt::sun_set_interleave_factor`](crate::fdisk::FdiskSUNExt::sun_set_interleave_factor)<br>[`FdiskSUNExt::sun_set_interleave_factor_to`](crate::fdisk::FdiskSUNExt::sun_set_interleave_factor_to) |
+//! | [`fdisk_sun_set_pcylcount`][290] | [`FdiskSUNExt::sun_set_physical_cylinder_count`](crate::fdisk::FdiskSUNExt::sun_set_physical_cylinder_count)<br>[`FdiskSUNExt::sun_set_physical_cylinder_count_to`](crate::fdisk::FdiskSUNExt::sun_set_physical_cylinder_count_to) |
+//! | [`fdisk_sun_set_rspeed`][291]    | [`FdiskSUNExt::sun_set_rotation_per_minute`](crate::fdisk::FdiskSUNExt::sun_set_rotation_per_minute)<br>[`FdiskSUNExt::sun_set_rotation_per_minute_to`](crate::fdisk::FdiskSUNExt::sun_set_rotation_per_minute_to) |
+//! | [`fdisk_sun_set_xcyl`][292]      | [`FdiskSUNExt::sun_set_extra_sectors_per_cylinder`](crate::fdisk::FdiskSUNExt::sun_set_extra_sectors_per_cylinder)<br>[`FdiskSUNExt::sun_set_extra_sectors_per_cylinder_to`](crate::fdisk::FdiskSUNExt::sun_set_extra_sectors_per_cylinder_to) |
 //!
 //! [288]: https://mirrors.edge.kernel.org/pub/linux/utils/util-linux/v2.39/libfdisk-docs/libfdisk-SUN.html#fdisk-sun-set-alt-cyl
 //! [289]: https://mirrors.edge.kernel.org/pub/linux/utils/util-linux/v2.39/libfdisk-docs/libfdisk-SUN.html#fdisk-sun-set-ilfact
@@ -772,7 +772,7 @@
 //! | [`SGI_FLAG_BOOT`][293]          | [`SGIFlag::Boot`](crate::core::partition::SGIFlag::Boot)                         |
 //! | [`SGI_FLAG_SWAP`][294]          | [`SGIFlag::Swap`](crate::core::partition::SGIFlag::Swap)                         |
 //! | [`fdisk_sgi_create_info`][295]  | [`FdiskSGIExt::sgi_add_hint`](crate::fdisk::FdiskSGIExt::sgi_add_hint)           |
-//! | [`fdisk_sgi_set_bootfile`][296] | [`FdiskSGIExt::sgi_set_boot_file`](crate::fdisk::FdiskSGIExt::sgi_set_boot_file) |
+//! | [`fdisk_sgi_set_bootfile`][296] | [`FdiskSGIExt::sgi_set_boot_file`](crate::fdisk::FdiskSGIExt::sgi_set_boot_file)<br>[`FdiskSGIExt::sgi_set_boot_file_to`](crate::fdisk::FdiskSGIExt::sgi_set_boot_file_to) |
 //!
 //! [293]: https://mirrors.edge.kernel.org/pub/linux/utils/util-linux/v2.39/libfdisk-docs/libfdisk-SGI.html#SGI-FLAG-BOOT:CAPS
 //! [294]: https://mirrors.edge.kernel.org/pub/linux/utils/util-linux/v2.39/libfdisk-docs/libfdisk-SGI.html#SGI-FLAG-SWAP:CAPS
@@ -783,9 +783,9 @@
 //!
 //! | `libfdisk`                         | `rsfdisk`                                                                                              |
 //! | ------------------                 | ---------                                                                                              |
-//! | [`fdisk_bsd_edit_disklabel`][297]  | [`FdiskBSDExt::bsd_edit_disk_label`](crate::fdisk::FdiskBSDExt::bsd_edit_disk_label)                   |
-//! | [`fdisk_bsd_link_partition`][298]  | [`FdiskBSDExt::bsd_link_to_nested_partition`](crate::fdisk::FdiskBSDExt::bsd_link_to_nested_partition) |
-//! | [`fdisk_bsd_write_bootstrap`][299] | [`FdiskBSDExt::bsd_install_bootstrap_file`](crate::fdisk::FdiskBSDExt::bsd_install_bootstrap_file)     |
+//! | [`fdisk_bsd_edit_disklabel`][297]  | [`FdiskBSDExt::bsd_edit_disk_label`](crate::fdisk::FdiskBSDExt::bsd_edit_disk_label)<br>[`FdiskBSDExt::bsd_set_disk_label_fields`](crate::fdisk::FdiskBSDExt::bsd_set_disk_label_fields) |
+//! | [`fdisk_bsd_link_partition`][298]  | [`FdiskBSDExt::bsd_link_to_nested_partition`](crate::fdisk::FdiskBSDExt::bsd_link_to_nested_partition)<br>[`FdiskBSDExt::bsd_link_dos_partition`](crate::fdisk::FdiskBSDExt::bsd_link_dos_partition) |
+//! | [`fdisk_bsd_write_bootstrap`][299] | [`FdiskBSDExt::bsd_install_bootstrap_file`](crate::fdisk::FdiskBSDExt::bsd_install_bootstrap_file)<br>[`FdiskBSDExt::bsd_install_bootstrap`](crate::fdisk::FdiskBSDExt::bsd_install_bootstrap) |
 //!
 //! [297]: https://mirrors.edge.kernel.org/pub/linux/utils/util-linux/v2.39/libfdisk-docs/libfdisk-BSD.html#fdisk-bsd-edit-disklabel
 //! [298]: https://mirrors.edge.kernel.org/pub/linux/utils/util-linux/v2.39/libfdisk-docs/libfdisk-BSD.html#fdisk-bsd-link-partition